@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 응답 데이터의 디코딩 시간을 측정합니다.
+//!
+//! `parallel` 기능의 효과를 확인하려면 기능을 켜고 끈 결과를 비교합니다.
+//!
+//! ```sh
+//! cargo bench --bench decode
+//! cargo bench --bench decode --features parallel
+//! ```
+
+mod fixtures;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+
+    for fixture in fixtures::all() {
+        let raw_data = fixture.raw_data();
+        assert_eq!(raw_data.decode(&fixture.tr_layout).unwrap(), fixture.data);
+
+        group.bench_function(&fixture.name, |b| {
+            b.iter(|| raw_data.decode(&fixture.tr_layout).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 데이터의 인코딩 시간을 측정합니다.
+//!
+//! ```sh
+//! cargo bench --bench encode
+//! ```
+
+mod fixtures;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    let mut buf = Vec::new();
+
+    for fixture in fixtures::all() {
+        group.bench_function(&fixture.name, |b| {
+            b.iter(|| {
+                buf.clear();
+                fixture
+                    .data
+                    .encode_into(&fixture.tr_layout, &mut buf)
+                    .unwrap();
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
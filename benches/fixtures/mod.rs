@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 벤치마크에서 사용하는 레이아웃과 데이터
+//!
+//! 실제 RES 파일이 없어도 실행할 수 있도록 자주 사용하는 TR과 비슷한 크기의
+//! 레이아웃을 생성합니다.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use xingapi::data::{Block, Data, DataType, RawData};
+use xingapi::layout::TrLayout;
+
+pub struct Fixture {
+    pub name: String,
+    pub tr_layout: TrLayout,
+    pub data: Data,
+}
+
+impl Fixture {
+    /// 서버에서 수신하는 형태로 인코딩한 응답 데이터를 반환합니다.
+    pub fn raw_data(&self) -> RawData {
+        let mut buf = Vec::new();
+        self.data.encode_into(&self.tr_layout, &mut buf).unwrap();
+
+        if self.tr_layout.block_mode {
+            // 블록이 하나인 block mode 응답은 인코딩된 블록과 같습니다.
+            assert_eq!(self.data.blocks.len(), 1);
+            let name = self.data.blocks.keys().next().unwrap().clone();
+            RawData::Block([(name, buf)].into_iter().collect())
+        } else {
+            RawData::NonBlock(buf)
+        }
+    }
+}
+
+// (이름, 타입, 길이) 목록으로 블록 레이아웃을 생성합니다.
+fn block(name: &str, occurs: bool, fields: &[(String, &str, usize)]) -> String {
+    let mut text = format!(
+        "{},출력,output{};\nbegin\n",
+        name,
+        if occurs { ",occurs" } else { "" }
+    );
+    for (field, ty, len) in fields {
+        text += &format!("{0},{0},{0},{1},{2};\n", field, ty, len);
+    }
+    text + "end\n"
+}
+
+fn layout(code: &str, attrs: &str, blocks: &[String]) -> TrLayout {
+    format!(
+        "BEGIN_FUNCTION_MAP\n.Func,벤치마크,{},{};\nBEGIN_DATA_MAP\n{}END_DATA_MAP\nEND_FUNCTION_MAP\n",
+        code,
+        attrs,
+        blocks.concat()
+    )
+    .parse()
+    .unwrap()
+}
+
+fn row(fields: &[(String, &str, usize)], i: usize) -> HashMap<Arc<str>, String> {
+    fields
+        .iter()
+        .map(|(name, ty, len)| {
+            let value = match *ty {
+                "char" => format!("가{}", i).chars().take(len / 2).collect(),
+                _ => (i % 10000).to_string(),
+            };
+            (name.as_str().into(), value)
+        })
+        .collect()
+}
+
+fn fields(prefix: &str, count: usize) -> Vec<(String, &'static str, usize)> {
+    (0..count)
+        .map(|i| {
+            let ty = if i % 4 == 0 { "char" } else { "long" };
+            (
+                format!("{}{}", prefix, i),
+                ty,
+                if i % 4 == 0 { 20 } else { 12 },
+            )
+        })
+        .collect()
+}
+
+/// 주식 현재가 호가 조회와 같이 필드가 많은 단일 블록
+pub fn t1101() -> Fixture {
+    let out = fields("f", 64);
+    Fixture {
+        name: "t1101".into(),
+        tr_layout: layout("t1101", "attr", &[block("t1101OutBlock", false, &out)]),
+        data: Data {
+            tr_code: "t1101".into(),
+            data_type: DataType::Output,
+            blocks: [("t1101OutBlock".into(), Block::Block(row(&out, 1)))]
+                .into_iter()
+                .collect(),
+        },
+    }
+}
+
+/// 주식 잔고 조회와 같이 단일 블록과 작은 배열 블록
+pub fn t0424() -> Fixture {
+    let out = fields("s", 12);
+    let out1 = fields("j", 24);
+    Fixture {
+        name: "t0424".into(),
+        tr_layout: layout(
+            "t0424",
+            "attr",
+            &[
+                block("t0424OutBlock", false, &out),
+                block("t0424OutBlock1", true, &out1),
+            ],
+        ),
+        data: Data {
+            tr_code: "t0424".into(),
+            data_type: DataType::Output,
+            blocks: [
+                ("t0424OutBlock".into(), Block::Block(row(&out, 0))),
+                (
+                    "t0424OutBlock1".into(),
+                    Block::Array((0..30).map(|i| row(&out1, i)).collect()),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        },
+    }
+}
+
+/// 전체 종목 조회와 같이 행이 많은 배열 블록
+pub fn t8430(rows: usize) -> Fixture {
+    let out = fields("c", 10);
+    Fixture {
+        name: format!("t8430 ({} rows)", rows),
+        tr_layout: layout("t8430", "attr,block", &[block("t8430OutBlock", true, &out)]),
+        data: Data {
+            tr_code: "t8430".into(),
+            data_type: DataType::Output,
+            blocks: [(
+                "t8430OutBlock".into(),
+                Block::Array((0..rows).map(|i| row(&out, i)).collect()),
+            )]
+            .into_iter()
+            .collect(),
+        },
+    }
+}
+
+pub fn all() -> Vec<Fixture> {
+    vec![t1101(), t0424(), t8430(100), t8430(2500), t8430(10000)]
+}
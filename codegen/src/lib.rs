@@ -0,0 +1,420 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! RES 파일로부터 TR별 타입이 지정된 모듈을 생성하는 크레이트
+//!
+//! 빌드 스크립트에서 사용하여 프로젝트에서 사용하는 TR의 블록을 구조체로
+//! 생성합니다. 필드 이름이 잘못된 경우 컴파일 시간에 확인할 수 있습니다.
+//!
+//! ## 예제
+//! ```rust,ignore
+//! // build.rs
+//! fn main() {
+//!     let out_dir = std::env::var("OUT_DIR").unwrap();
+//!     xingapi_codegen::generate_dir(
+//!         "res",
+//!         &["t1101", "t0424"],
+//!         std::path::Path::new(&out_dir).join("tr.rs"),
+//!     )
+//!     .unwrap();
+//!     println!("cargo:rerun-if-changed=res");
+//! }
+//!
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/tr.rs"));
+//!
+//! let block = t1101::InBlock {
+//!     shcode: "096530".into(),
+//! };
+//! ```
+//!
+//! 생성되는 모듈은 TR 코드를 소문자로 바꾼 이름을 가지며 다음 항목을
+//! 포함합니다.
+//!
+//! - TR 코드 상수 `CODE`
+//! - 블록마다 필드를 가지는 구조체와 블록 이름 상수 `NAME`, 필드 이름 상수
+//! - 구조체와 `HashMap<Arc<str>, String>` 사이의 변환 구현
+//! - 단일 블록인 경우 [`xingapi::data::Block`]으로의 변환 구현
+//!
+//! 정수 필드는 `i64`, 실수 필드는 `f64`, 나머지 필드는 `String` 타입을
+//! 가집니다. 소수점 자릿수가 지정된 실수 필드의 값에 소수점이 없는 경우
+//! [`Data::apply_points()`](xingapi::data::Data::apply_points)와 같이
+//! `10^point`로 나눈 값으로 변환합니다.
+
+use xingapi::layout::{
+    self, error::LoadError, BlockLayout, FieldLayout, FieldType, LayoutRegistry, TrLayout,
+};
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// 코드 생성이 실패하여 발생하는 에러
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// 레이아웃을 불러오지 못했습니다.
+    Load(LoadError),
+    /// 요청한 TR 코드의 레이아웃이 없습니다.
+    MissingLayout(String),
+    /// 생성된 코드를 쓰지 못했습니다.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Load(err) => write!(f, "unable to load layouts; {}", err),
+            Self::MissingLayout(code) => write!(f, "missing layout; code: {}", code),
+            Self::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Load(err) => Some(err),
+            Self::MissingLayout(_) => None,
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(err: LoadError) -> Self {
+        Self::Load(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// 지정된 디렉터리의 RES 파일에서 모듈을 생성하여 파일로 저장합니다.
+///
+/// `codes`가 비어 있는 경우 디렉터리의 모든 TR에 대해 생성합니다.
+pub fn generate_dir<P: AsRef<Path>, Q: AsRef<Path>>(
+    res_dir: P,
+    codes: &[&str],
+    out_path: Q,
+) -> Result<(), Error> {
+    let registry = LayoutRegistry::from(if codes.is_empty() {
+        layout::load_dir(res_dir)?
+    } else {
+        layout::load_dir_filtered(res_dir, codes)?
+    });
+
+    std::fs::write(out_path, generate_registry(&registry, codes)?)?;
+    Ok(())
+}
+
+/// 레이아웃 테이블의 레이아웃으로 모듈을 생성하여 소스 코드를 반환합니다.
+///
+/// `codes`가 비어 있는 경우 테이블의 모든 TR에 대해 생성합니다.
+pub fn generate_registry(registry: &LayoutRegistry, codes: &[&str]) -> Result<String, Error> {
+    let layout_tbl = registry.snapshot();
+
+    let mut layouts: Vec<_> = if codes.is_empty() {
+        layout_tbl.values().collect()
+    } else {
+        codes
+            .iter()
+            .map(|code| {
+                layout_tbl
+                    .get(*code)
+                    .ok_or_else(|| Error::MissingLayout(code.to_string()))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    layouts.sort_unstable_by(|a, b| a.code.cmp(&b.code));
+    layouts.dedup_by(|a, b| a.code == b.code);
+
+    Ok(generate(layouts.into_iter().map(|tr_layout| &**tr_layout)))
+}
+
+/// 레이아웃마다 모듈을 생성하여 소스 코드를 반환합니다.
+pub fn generate<'a, I: IntoIterator<Item = &'a TrLayout>>(layouts: I) -> String {
+    let mut src = String::from("// 이 파일은 xingapi-codegen으로 생성되었습니다.\n");
+
+    for tr_layout in layouts {
+        write_tr(&mut src, tr_layout).unwrap();
+    }
+
+    src
+}
+
+fn write_tr(src: &mut String, tr_layout: &TrLayout) -> std::fmt::Result {
+    writeln!(src)?;
+    writeln!(src, "/// {}", tr_layout.desc)?;
+    writeln!(src, "#[allow(non_snake_case, unused)]")?;
+    writeln!(src, "pub mod {} {{", ident(&tr_layout.code.to_lowercase()))?;
+    writeln!(src, "    use std::collections::HashMap;")?;
+    writeln!(src, "    use std::sync::Arc;")?;
+    writeln!(src)?;
+    writeln!(src, "    /// TR 코드")?;
+    writeln!(src, "    pub const CODE: &str = {:?};", tr_layout.code)?;
+    writeln!(src)?;
+    writeln!(
+        src,
+        "    // 값에 소수점이 없는 경우 소수점 자릿수를 적용합니다."
+    )?;
+    writeln!(
+        src,
+        "    fn scale(value: ::xingapi::data::Decimal, point: u32) -> f64 {{"
+    )?;
+    writeln!(src, "        if value.scale == 0 {{")?;
+    writeln!(
+        src,
+        "            ::xingapi::data::Decimal {{ scale: point, ..value }}.to_f64()"
+    )?;
+    writeln!(src, "        }} else {{")?;
+    writeln!(src, "            value.to_f64()")?;
+    writeln!(src, "        }}")?;
+    writeln!(src, "    }}")?;
+
+    for block_layout in tr_layout.in_blocks.iter().chain(&tr_layout.out_blocks) {
+        write_block(src, tr_layout, block_layout)?;
+    }
+
+    writeln!(src, "}}")
+}
+
+fn write_block(
+    src: &mut String,
+    tr_layout: &TrLayout,
+    block_layout: &BlockLayout,
+) -> std::fmt::Result {
+    let name = block_layout
+        .name
+        .strip_prefix(&tr_layout.code)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(&block_layout.name);
+    let name = ident(name);
+
+    // 같은 이름의 필드가 여러 번 나오는 경우 디코딩된 블록에는 하나만
+    // 남으므로 처음 필드만 사용합니다.
+    let mut names = HashSet::new();
+    let fields: Vec<_> = block_layout
+        .fields
+        .iter()
+        .filter(|field| names.insert(&*field.name))
+        .map(|field| (field, ident(&field.name)))
+        .collect();
+
+    // 실수 필드가 있는 경우 `Eq`를 구현할 수 없습니다.
+    let derive_eq = fields
+        .iter()
+        .all(|(field, _)| field_kind(field) != FieldKind::Float);
+
+    writeln!(src)?;
+    writeln!(src, "    /// {}", block_layout.desc)?;
+    if derive_eq {
+        writeln!(src, "    #[derive(Clone, Debug, Default, PartialEq, Eq)]")?;
+    } else {
+        writeln!(src, "    #[derive(Clone, Debug, Default, PartialEq)]")?;
+    }
+    writeln!(src, "    pub struct {} {{", name)?;
+    for (field, field_ident) in &fields {
+        let field_type = match field_kind(field) {
+            FieldKind::Text => "String",
+            FieldKind::Int => "i64",
+            FieldKind::Float => "f64",
+        };
+
+        writeln!(src, "        /// {}", field.desc)?;
+        writeln!(src, "        pub {}: {},", field_ident, field_type)?;
+    }
+    writeln!(src, "    }}")?;
+
+    let mut consts = HashSet::new();
+
+    writeln!(src)?;
+    writeln!(src, "    impl {} {{", name)?;
+    writeln!(src, "        /// 블록 이름")?;
+    writeln!(
+        src,
+        "        pub const NAME: &'static str = {:?};",
+        block_layout.name
+    )?;
+    for (field, _) in &fields {
+        let const_name = const_ident(&field.name);
+        if const_name != "NAME" && consts.insert(const_name.clone()) {
+            writeln!(src, "        /// {} 필드 이름", field.desc)?;
+            writeln!(
+                src,
+                "        pub const {}: &'static str = {:?};",
+                const_name, field.name
+            )?;
+        }
+    }
+    writeln!(src, "    }}")?;
+
+    writeln!(src)?;
+    writeln!(
+        src,
+        "    impl TryFrom<&HashMap<Arc<str>, String>> for {} {{",
+        name
+    )?;
+    writeln!(src, "        type Error = ::xingapi::data::FieldError;")?;
+    writeln!(src)?;
+    writeln!(
+        src,
+        "        fn try_from(block: &HashMap<Arc<str>, String>) -> Result<Self, Self::Error> {{"
+    )?;
+    writeln!(src, "            use ::xingapi::data::FieldsExt;")?;
+    writeln!(src)?;
+    writeln!(src, "            Ok(Self {{")?;
+    for (field, field_ident) in &fields {
+        let value = match (field_kind(field), field.point) {
+            (FieldKind::Text, _) => format!("block.get_str({:?})?.to_owned()", field.name),
+            (FieldKind::Int, _) => format!("block.get_i64({:?})?", field.name),
+            (FieldKind::Float, Some(point)) if point > 0 => {
+                format!("scale(block.get_decimal({:?})?, {})", field.name, point)
+            }
+            (FieldKind::Float, _) => format!("block.get_f64({:?})?", field.name),
+        };
+
+        writeln!(src, "                {}: {},", field_ident, value)?;
+    }
+    writeln!(src, "            }})")?;
+    writeln!(src, "        }}")?;
+    writeln!(src, "    }}")?;
+
+    writeln!(src)?;
+    writeln!(
+        src,
+        "    impl From<{}> for HashMap<Arc<str>, String> {{",
+        name
+    )?;
+    writeln!(src, "        fn from(block: {}) -> Self {{", name)?;
+    writeln!(src, "            HashMap::from([")?;
+    for (field, field_ident) in &fields {
+        let value = match (field_kind(field), field.point) {
+            (FieldKind::Text, _) => format!("block.{}", field_ident),
+            (FieldKind::Float, Some(point)) if point > 0 => {
+                format!("format!(\"{{:.{}}}\", block.{})", point, field_ident)
+            }
+            _ => format!("block.{}.to_string()", field_ident),
+        };
+
+        writeln!(src, "                ({:?}.into(), {}),", field.name, value)?;
+    }
+    writeln!(src, "            ])")?;
+    writeln!(src, "        }}")?;
+    writeln!(src, "    }}")?;
+
+    if !block_layout.occurs {
+        writeln!(src)?;
+        writeln!(src, "    impl From<{}> for ::xingapi::data::Block {{", name)?;
+        writeln!(src, "        fn from(block: {}) -> Self {{", name)?;
+        writeln!(src, "            Self::Block(block.into())")?;
+        writeln!(src, "        }}")?;
+        writeln!(src, "    }}")?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Text,
+    Int,
+    Float,
+}
+
+fn field_kind(field: &FieldLayout) -> FieldKind {
+    match field.field_type {
+        FieldType::Int => FieldKind::Int,
+        FieldType::Float | FieldType::Double => FieldKind::Float,
+        FieldType::Char | FieldType::Date | FieldType::Unknown(_) => FieldKind::Text,
+    }
+}
+
+// 러스트 식별자로 사용할 수 있도록 이름을 변환합니다.
+fn ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    match ident.as_str() {
+        "_" | "self" | "Self" | "super" | "crate" => ident.push('_'),
+        _ if KEYWORDS.contains(&ident.as_str()) => ident.insert_str(0, "r#"),
+        _ => {}
+    }
+
+    ident
+}
+
+fn const_ident(name: &str) -> String {
+    ident(name).trim_start_matches("r#").to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, ident};
+    use xingapi::layout::TrLayout;
+
+    #[test]
+    fn test_generate() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,주식현재가,t1102,attr;
+                BEGIN_DATA_MAP
+                t1102InBlock,기본입력,input;
+                begin
+                    단축코드,shcode,shcode,char,6;
+                end
+                t1102OutBlock1,출력,output,occurs;
+                begin
+                    구분,type,type,char,1;
+                    현재가,price,price,long,8;
+                    현재가,price,price,long,8;
+                    등락율,diff,diff,float,6.2;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let src = generate([&tr_layout]);
+        assert!(src.contains("pub mod t1102 {"));
+        assert!(src.contains("    pub struct InBlock {"));
+        assert!(src.contains("        pub r#type: String,"));
+        assert!(src.contains("        pub const TYPE: &'static str = \"type\";"));
+        assert!(src.contains("impl From<InBlock> for ::xingapi::data::Block"));
+        assert!(!src.contains("impl From<OutBlock1> for ::xingapi::data::Block"));
+        assert_eq!(src.matches("pub price: i64").count(), 1);
+        assert!(src.contains("        pub diff: f64,"));
+        assert!(src.contains("diff: scale(block.get_decimal(\"diff\")?, 2),"));
+        assert!(src.contains(
+            "    #[derive(Clone, Debug, Default, PartialEq, Eq)]\n    pub struct InBlock"
+        ));
+        assert!(src
+            .contains("    #[derive(Clone, Debug, Default, PartialEq)]\n    pub struct OutBlock1"));
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("shcode"), "shcode");
+        assert_eq!(ident("type"), "r#type");
+        assert_eq!(ident("self"), "self_");
+        assert_eq!(ident("1st-day"), "_1st_day");
+        assert_eq!(ident("H1_"), "H1_");
+    }
+}
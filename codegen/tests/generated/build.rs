@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MPL-2.0
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    xingapi_codegen::generate_dir("res", &[], std::path::Path::new(&out_dir).join("tr.rs"))
+        .unwrap();
+    println!("cargo:rerun-if-changed=res");
+}
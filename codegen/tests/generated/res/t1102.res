@@ -0,0 +1,17 @@
+BEGIN_FUNCTION_MAP
+	.Func,주식현재가(시세)조회,t1102,attr,block,headtype=A;
+	BEGIN_DATA_MAP
+	t1102InBlock,기본입력,input;
+	begin
+		단축코드,shcode,shcode,char,6;
+	end
+	t1102OutBlock,출력,output;
+	begin
+		한글명,hname,hname,char,20;
+		현재가,price,price,long,8;
+		등락율,diff,diff,float,6.2;
+		거래대금,value,value,double,12;
+		상장일,listdate,listdate,date,8;
+	end
+	END_DATA_MAP
+END_FUNCTION_MAP
@@ -0,0 +1,15 @@
+BEGIN_FUNCTION_MAP
+	.Func,주식종목조회,t8430,block,headtype=A;
+	BEGIN_DATA_MAP
+	t8430InBlock,기본입력,input;
+	begin
+		구분(0:전체1:코스피2:코스닥),gubun,gubun,char,1;
+	end
+	t8430OutBlock,출력,output,occurs;
+	begin
+		종목명,hname,hname,char,20;
+		단축코드,shcode,shcode,char,6;
+		기준가,recprice,recprice,long,8;
+	end
+	END_DATA_MAP
+END_FUNCTION_MAP
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! `xingapi-codegen`으로 생성된 모듈을 컴파일하여 검사하는 크레이트
+
+include!(concat!(env!("OUT_DIR"), "/tr.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::{t1102, t8430};
+    use xingapi::data::{Block, FieldError};
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn block(fields: &[(&str, &str)]) -> HashMap<Arc<str>, String> {
+        fields
+            .iter()
+            .map(|(name, value)| ((*name).into(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_try_from() {
+        let out_block = t1102::OutBlock::try_from(&block(&[
+            ("hname", "삼성전자"),
+            ("price", "00091000"),
+            ("diff", "-00125"),
+            ("value", "1234.5"),
+            ("listdate", "19750611"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            out_block,
+            t1102::OutBlock {
+                hname: "삼성전자".into(),
+                price: 91000,
+                diff: -1.25,
+                value: 1234.5,
+                listdate: "19750611".into(),
+            }
+        );
+
+        let fields = HashMap::from(out_block);
+        assert_eq!(fields["price"], "91000");
+        assert_eq!(fields["diff"], "-1.25");
+        assert_eq!(fields["value"], "1234.5");
+
+        assert_eq!(t1102::OutBlock::try_from(&fields).unwrap().diff, -1.25);
+    }
+
+    #[test]
+    fn test_try_from_error() {
+        assert_eq!(
+            t8430::OutBlock::try_from(&block(&[("hname", "삼성전자"), ("shcode", "005930")])),
+            Err(FieldError::Missing("recprice".into()))
+        );
+
+        assert!(matches!(
+            t8430::OutBlock::try_from(&block(&[
+                ("hname", "삼성전자"),
+                ("shcode", "005930"),
+                ("recprice", "abc"),
+            ])),
+            Err(FieldError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(t1102::CODE, "t1102");
+        assert_eq!(t1102::InBlock::NAME, "t1102InBlock");
+        assert_eq!(t1102::InBlock::SHCODE, "shcode");
+        assert_eq!(t8430::OutBlock::NAME, "t8430OutBlock");
+
+        let in_block: Block = t8430::InBlock { gubun: "0".into() }.into();
+        assert_eq!(in_block, Block::Block(block(&[("gubun", "0")])));
+    }
+}
@@ -6,66 +6,11 @@ use clap::{App, Arg};
 use lazy_static::lazy_static;
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::{collections::HashMap, sync::RwLock, time::Duration};
+use std::time::Duration;
 
-use xingapi::data::{Block, Data, DataType};
-use xingapi::layout::TrLayout;
-use xingapi::{hashmap, QueryResponse, RealEvent, Response};
-
-lazy_static! {
-    static ref LAYOUT_TBL: RwLock<HashMap<String, TrLayout>> = RwLock::new(HashMap::new());
-}
-
-enum Market {
-    Kospi,
-    Kosdaq,
-}
-
-impl Market {
-    fn request_t8430(&self) -> Result<QueryResponse, xingapi::Error> {
-        xingapi::request(
-            &Data {
-                tr_code: "t8430".into(),
-                data_type: DataType::Input,
-                blocks: hashmap! {
-                    "t8430InBlock" => Block::Block(hashmap! {
-                        "gubun" => match self {
-                            Self::Kospi => "1",
-                            Self::Kosdaq => "2",
-                        },
-                    }),
-                },
-            },
-            LAYOUT_TBL.read().unwrap().get("t8430").unwrap(),
-            None,
-            Duration::from_secs(10),
-        )
-    }
-
-    #[allow(dead_code)]
-    pub fn symbols(&self) -> Vec<String> {
-        let res = self.request_t8430().unwrap();
-        let data = res.data().unwrap();
-
-        data.blocks["t8430OutBlock"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|block| block["shcode"].to_owned())
-            .collect()
-    }
-
-    pub fn is_listed(&self, ticker: &str) -> bool {
-        let res = self.request_t8430().unwrap();
-        let data = res.data().unwrap();
-
-        data.blocks["t8430OutBlock"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .any(|block| block["shcode"] == ticker)
-    }
-}
+use xingapi::layout::LayoutRegistry;
+use xingapi::symbols::{Market, SymbolMaster};
+use xingapi::{RealEvent, Response};
 
 fn main() {
     lazy_static! {
@@ -106,12 +51,9 @@ fn main() {
     let cert_pw = matches.value_of("cert-pw").unwrap_or("");
     let ticker_symbol = matches.value_of("stock").unwrap_or("005930");
 
-    *LAYOUT_TBL.write().unwrap() = xingapi::layout::load().unwrap();
+    let registry = LayoutRegistry::from(xingapi::layout::load().unwrap());
 
-    assert!(
-        LAYOUT_TBL.read().unwrap().contains_key("t8430"),
-        "t8430 layout is missing"
-    );
+    assert!(registry.contains("t8430"), "t8430 layout is missing");
 
     xingapi::loader::load().unwrap();
     println!("xingapi loaded");
@@ -126,20 +68,19 @@ fn main() {
         panic!("login failed: {:?}", res);
     }
 
-    let (tr_code, market) = {
-        if Market::Kospi.is_listed(ticker_symbol) {
-            ("S3_", "KOSPI")
-        } else if Market::Kosdaq.is_listed(ticker_symbol) {
-            ("K3_", "KOSDAQ")
-        } else {
+    let symbols =
+        SymbolMaster::request(&registry.get("t8430").unwrap(), Duration::from_secs(10)).unwrap();
+
+    let (tr_code, market) = match symbols.get(ticker_symbol).map(|s| s.market) {
+        Some(Market::Kospi) => ("S3_", "KOSPI"),
+        Some(Market::Kosdaq) => ("K3_", "KOSDAQ"),
+        None => {
             eprintln!("unknown ticker: {}", ticker_symbol);
             return;
         }
     };
 
-    let real = RealEvent::new().unwrap();
-
-    real.insert_layout(LAYOUT_TBL.read().unwrap().get(tr_code).unwrap().to_owned());
+    let real = RealEvent::with_registry(registry).unwrap();
     real.subscribe(tr_code, &[ticker_symbol]);
 
     println!(
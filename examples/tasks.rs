@@ -5,8 +5,7 @@
 use clap::{App, Arg};
 use std::time::Duration;
 
-use xingapi::data::{Block, Data, DataType};
-use xingapi::{hashmap, Error, Response};
+use xingapi::{data, ErrorKind, Response};
 
 fn main() {
     let matches = App::new("login")
@@ -61,23 +60,22 @@ fn main() {
     println!("t1764 limit_per_sec: {}", t1764_limit_per_sec);
     println!("t1764 limit_per_ten_min: {:?}", t1764_limit_per_ten_min);
 
-    let t1101_layout = layout_tbl.get("t1101").unwrap().to_owned();
+    let t1101_layout = layout_tbl["t1101"].clone();
 
     let t1101_loop = std::thread::spawn(move || {
-        let req_data = Data {
-            tr_code: "t1101".into(),
-            data_type: DataType::Input,
-            blocks: hashmap! {
-                "t1101InBlock" => Block::Block(hashmap! {
-                    "shcode" => "078020",
-                }),
-            },
-        };
+        let req_data = data!("t1101", input {
+            t1101InBlock { shcode: "078020" },
+        });
 
         for i in 0..20 * t1101_limit_per_sec {
             let res = loop {
-                match xingapi::request(&req_data, &t1101_layout, None, Duration::from_secs(30)) {
-                    Err(Error::XingApi { code: -21, .. }) => {
+                match xingapi::request(
+                    &req_data,
+                    t1101_layout.clone(),
+                    None,
+                    Duration::from_secs(30),
+                ) {
+                    Err(err) if err.kind().is_some_and(ErrorKind::is_retryable) => {
                         println!("t1101: limit reached");
                         std::thread::sleep(Duration::from_millis(1));
                         continue;
@@ -99,24 +97,22 @@ fn main() {
         }
     });
 
-    let t1764_layout = layout_tbl.get("t1764").unwrap().to_owned();
+    let t1764_layout = layout_tbl["t1764"].clone();
 
     let t1764_loop = std::thread::spawn(move || {
-        let req_data = Data {
-            tr_code: "t1764".into(),
-            data_type: DataType::Input,
-            blocks: hashmap! {
-                "t1764InBlock" => Block::Block(hashmap! {
-                    "shcode" => "096530",
-                    "gubun1" => "0",
-                }),
-            },
-        };
+        let req_data = data!("t1764", input {
+            t1764InBlock { shcode: "096530", gubun1: "0" },
+        });
 
         for i in 0..=20 * t1764_limit_per_sec {
             let res = loop {
-                match xingapi::request(&req_data, &t1764_layout, None, Duration::from_secs(30)) {
-                    Err(Error::XingApi { code: -21, .. }) => {
+                match xingapi::request(
+                    &req_data,
+                    t1764_layout.clone(),
+                    None,
+                    Duration::from_secs(30),
+                ) {
+                    Err(err) if err.kind().is_some_and(ErrorKind::is_retryable) => {
                         println!("t1764: limit reached");
                         std::thread::sleep(Duration::from_millis(1));
                         continue;
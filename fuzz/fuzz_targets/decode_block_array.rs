@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xingapi::data::fuzzing;
+use xingapi::layout::TrLayout;
+
+// 첫 번째 0 바이트를 기준으로 RES 파일 내용과 디코딩할 데이터로 나눕니다.
+fuzz_target!(|data: &[u8]| {
+    let Some(pos) = data.iter().position(|&b| b == 0) else {
+        return;
+    };
+    let Ok(text) = std::str::from_utf8(&data[..pos]) else {
+        return;
+    };
+    let Ok(tr_layout) = text.parse::<TrLayout>() else {
+        return;
+    };
+
+    let _ = fuzzing::decode_block_array(&tr_layout, &data[pos + 1..]);
+});
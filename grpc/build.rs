@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MPL-2.0
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::compile_protos("../proto/xingapi.proto")?;
+    Ok(())
+}
@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! xingapi-rs gRPC 서버
+//!
+//! `proto/xingapi.proto`에 정의된 서비스를 제공합니다. 서버 주소와 계정 정보는
+//! 환경 변수로 지정합니다.
+//!
+//! - `XINGAPI_ID`, `XINGAPI_PW`, `XINGAPI_CERT_PW`: 로그인 정보
+//! - `XINGAPI_ADDR`: XingAPI 서버 주소 (기본값: `demo.ebestsec.co.kr`)
+//! - `GRPC_ADDR`: gRPC 서버 주소 (기본값: `127.0.0.1:50051`)
+
+// `tonic`의 핸들러는 `Status`를 에러로 반환해야 합니다.
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+
+use xingapi::data::{self, DataType};
+use xingapi::layout::{self, TrLayout};
+use xingapi::{Error, RealEvent, RealResponse, Response as _, SubscribeBatch};
+
+mod pb {
+    tonic::include_proto!("xingapi");
+}
+
+use pb::block::Kind;
+use pb::xing_api_server::{XingApi, XingApiServer};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn to_status(err: Error) -> Status {
+    match err {
+        Error::Encode(_) | Error::Field(_) => Status::invalid_argument(err.to_string()),
+        Error::Rejected { code, message } => {
+            Status::failed_precondition(format!("[{}] {}", code, message))
+        }
+        Error::TimedOut => Status::deadline_exceeded(err.to_string()),
+        Error::Busy => Status::resource_exhausted(err.to_string()),
+        Error::Decode(_) | Error::Internal(_) => Status::internal(err.to_string()),
+        err => Status::unavailable(err.to_string()),
+    }
+}
+
+fn to_pb_fields(fields: &HashMap<Arc<str>, String>) -> pb::Fields {
+    pb::Fields {
+        fields: fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+    }
+}
+
+fn from_pb_fields(fields: pb::Fields) -> HashMap<Arc<str>, String> {
+    fields
+        .fields
+        .into_iter()
+        .map(|(name, value)| (name.into(), value))
+        .collect()
+}
+
+fn to_pb_data(data: &data::Data) -> pb::Data {
+    let blocks = data
+        .blocks
+        .iter()
+        .map(|(name, block)| {
+            let kind = match block {
+                data::Block::Block(fields) => Kind::Single(to_pb_fields(fields)),
+                data::Block::Array(arr) => Kind::Array(pb::BlockArray {
+                    items: arr.iter().map(to_pb_fields).collect(),
+                }),
+            };
+
+            (name.clone(), pb::Block { kind: Some(kind) })
+        })
+        .collect();
+
+    pb::Data {
+        tr_code: data.tr_code.clone(),
+        blocks,
+    }
+}
+
+fn to_input(
+    tr_layout: &TrLayout,
+    blocks: HashMap<String, pb::Block>,
+) -> Result<data::Data, Status> {
+    // 지정하지 않은 단일 블록의 필드는 빈 문자열로 채웁니다.
+    let mut input = HashMap::new();
+    for block_layout in tr_layout.in_blocks.iter().filter(|b| !b.occurs) {
+        let fields = block_layout
+            .fields
+            .iter()
+            .map(|field_layout| (field_layout.name.clone(), String::new()))
+            .collect();
+        input.insert(block_layout.name.clone(), data::Block::Block(fields));
+    }
+
+    for (name, block) in blocks {
+        let block = match block.kind {
+            Some(Kind::Single(fields)) => match input.remove(&name) {
+                Some(data::Block::Block(mut defaults)) => {
+                    defaults.extend(from_pb_fields(fields));
+                    data::Block::Block(defaults)
+                }
+                _ => data::Block::Block(from_pb_fields(fields)),
+            },
+            Some(Kind::Array(arr)) => {
+                data::Block::Array(arr.items.into_iter().map(from_pb_fields).collect())
+            }
+            None => return Err(Status::invalid_argument(format!("empty block: {}", name))),
+        };
+
+        input.insert(name, block);
+    }
+
+    Ok(data::Data {
+        tr_code: tr_layout.code.clone(),
+        data_type: DataType::Input,
+        blocks: input,
+    })
+}
+
+fn to_pb_feed(res: &RealResponse) -> Result<pb::RealFeed, Status> {
+    let data = res
+        .data()
+        .map_err(|err| Status::internal(err.to_string()))?;
+    let received_at_ms = res
+        .received_at()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64);
+
+    Ok(pb::RealFeed {
+        tr_code: res.tr_code().to_owned(),
+        key: res.key().to_owned(),
+        data: Some(to_pb_data(data)),
+        received_at_ms,
+    })
+}
+
+struct Service {
+    layout_tbl: HashMap<String, Arc<TrLayout>>,
+}
+
+impl Service {
+    fn layout(&self, tr_code: &str) -> Result<Arc<TrLayout>, Status> {
+        self.layout_tbl
+            .get(tr_code)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("unknown tr code: {}", tr_code)))
+    }
+}
+
+#[tonic::async_trait]
+impl XingApi for Service {
+    async fn request(
+        &self,
+        req: Request<pb::QueryRequest>,
+    ) -> Result<Response<pb::QueryResponse>, Status> {
+        let req = req.into_inner();
+        let tr_layout = self.layout(&req.tr_code)?;
+        let data = to_input(&tr_layout, req.blocks)?;
+        let timeout = match req.timeout_ms {
+            0 => DEFAULT_TIMEOUT,
+            ms => Duration::from_millis(ms.into()),
+        };
+
+        // XingAPI 호출은 블로킹 방식이므로 별도의 스레드에서 실행합니다.
+        let res = tokio::task::spawn_blocking(move || {
+            xingapi::request(&data, tr_layout, req.next_key.as_deref(), timeout)
+        })
+        .await
+        .map_err(|err| Status::internal(err.to_string()))?
+        .map_err(to_status)?;
+
+        if !res.is_ok() {
+            return Err(to_status(Error::Rejected {
+                code: res.code().to_owned(),
+                message: res.message().to_owned(),
+            }));
+        }
+
+        let data = res
+            .data()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(pb::QueryResponse {
+            code: res.code().to_owned(),
+            message: res.message().to_owned(),
+            elapsed_ms: res.elapsed().as_millis() as u64,
+            next_key: res.next_key().map(str::to_owned),
+            data: Some(to_pb_data(data)),
+        }))
+    }
+
+    type SubscribeStream = ReceiverStream<Result<pb::RealFeed, Status>>;
+
+    async fn subscribe(
+        &self,
+        req: Request<pb::SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let req = req.into_inner();
+        let tr_layout = self.layout(&req.tr_code)?;
+
+        let (tx, rx) = mpsc::channel(1024);
+        let tx_closed = tx.clone();
+
+        // 실시간 TR 등록은 블로킹 방식이므로 별도의 스레드에서 실행합니다.
+        let real = tokio::task::spawn_blocking(move || -> Result<RealEvent, Status> {
+            let real = RealEvent::new().map_err(|err| Status::unavailable(err.to_string()))?;
+            real.insert_layout(tr_layout);
+
+            real.on(&req.tr_code, move |res| {
+                // 수신 스레드는 tokio 런타임 밖에서 실행되므로 블로킹 전송을 사용합니다.
+                let _ = tx.blocking_send(to_pb_feed(res));
+            });
+
+            // 등록에 실패한 키가 있는 경우 객체를 해제하여 모두 등록 해제합니다.
+            let failed: Vec<_> = real
+                .subscribe_batched(&req.tr_code, &req.keys, SubscribeBatch::default())
+                .into_iter()
+                .filter(|(_, ok)| !ok)
+                .map(|(key, _)| key)
+                .collect();
+            if !failed.is_empty() {
+                return Err(Status::unavailable(format!(
+                    "unable to subscribe; tr code: {}, keys: {}",
+                    req.tr_code,
+                    failed.join(",")
+                )));
+            }
+
+            Ok(real)
+        })
+        .await
+        .map_err(|err| Status::internal(err.to_string()))??;
+
+        // 클라이언트가 스트림을 닫으면 실시간 TR을 등록 해제합니다.
+        tokio::spawn(async move {
+            tx_closed.closed().await;
+            let _ = tokio::task::spawn_blocking(move || drop(real)).await;
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn accounts(
+        &self,
+        _req: Request<pb::AccountsRequest>,
+    ) -> Result<Response<pb::AccountsResponse>, Status> {
+        let accounts = xingapi::accounts()
+            .into_iter()
+            .map(|account| pb::Account {
+                code: account.code,
+                name: account.name,
+                detailed_name: account.detailed_name,
+                nickname: account.nickname,
+            })
+            .collect();
+
+        Ok(Response::new(pb::AccountsResponse { accounts }))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let id = env::var("XINGAPI_ID")?;
+    let pw = env::var("XINGAPI_PW")?;
+    let cert_pw = env::var("XINGAPI_CERT_PW").unwrap_or_default();
+    let xingapi_addr =
+        env::var("XINGAPI_ADDR").unwrap_or_else(|_| "demo.ebestsec.co.kr".to_owned());
+    let grpc_addr = env::var("GRPC_ADDR")
+        .unwrap_or_else(|_| "127.0.0.1:50051".to_owned())
+        .parse()?;
+
+    let layout_tbl = layout::load()?;
+    xingapi::loader::load()?;
+
+    tokio::task::spawn_blocking(
+        move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            xingapi::connect(&xingapi_addr, 20001, Duration::from_secs(10))?;
+            let res = xingapi::login(&id, &pw, &cert_pw, false)?;
+            if !res.is_ok() {
+                return Err(format!("[{}] {}", res.code(), res.message()).into());
+            }
+            Ok(())
+        },
+    )
+    .await?
+    .map_err(|err| err as Box<dyn std::error::Error>)?;
+
+    Server::builder()
+        .add_service(XingApiServer::new(Service { layout_tbl }))
+        .serve(grpc_addr)
+        .await?;
+
+    Ok(())
+}
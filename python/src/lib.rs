@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! xingapi-rs 파이썬 바인딩
+//!
+//! 조회 TR의 요청 및 응답 데이터는 블록 이름을 키로 하는 `dict`로 변환되며,
+//! 단일 블록은 `dict`, 배열 블록은 `dict`의 `list`입니다.
+
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyTimeoutError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+use xingapi_rs::data::{Block, Data, DataType};
+use xingapi_rs::layout::{self, TrLayout};
+use xingapi_rs::{Error, RealEvent, RealResponse, Response};
+
+static LAYOUT_TBL: LazyLock<RwLock<HashMap<String, Arc<TrLayout>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+fn to_py_err(err: Error) -> PyErr {
+    match err {
+        Error::TimedOut => PyTimeoutError::new_err(err.to_string()),
+        err => PyRuntimeError::new_err(err.to_string()),
+    }
+}
+
+fn get_layout(tr_code: &str) -> PyResult<Arc<TrLayout>> {
+    LAYOUT_TBL
+        .read()
+        .unwrap()
+        .get(tr_code)
+        .cloned()
+        .ok_or_else(|| PyKeyError::new_err(format!("unknown tr code: {}", tr_code)))
+}
+
+fn data_to_dict<'py>(py: Python<'py>, data: &Data) -> PyResult<Bound<'py, PyDict>> {
+    fn to_dict(fields: &HashMap<Arc<str>, String>) -> HashMap<&str, &str> {
+        fields.iter().map(|(k, v)| (&**k, v.as_str())).collect()
+    }
+
+    let dict = PyDict::new_bound(py);
+    for (name, block) in &data.blocks {
+        match block {
+            Block::Block(fields) => dict.set_item(name, to_dict(fields))?,
+            Block::Array(arr) => {
+                dict.set_item(name, arr.iter().map(to_dict).collect::<Vec<_>>())?
+            }
+        }
+    }
+
+    Ok(dict)
+}
+
+fn real_to_dict<'py>(py: Python<'py>, res: &RealResponse) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("tr_code", res.tr_code())?;
+    dict.set_item("key", res.key())?;
+    match res.data() {
+        Ok(data) => dict.set_item("data", data_to_dict(py, data)?)?,
+        Err(err) => return Err(PyRuntimeError::new_err(err.to_string())),
+    }
+
+    Ok(dict)
+}
+
+/// XingAPI DLL과 TR 레이아웃을 불러옵니다.
+///
+/// 경로를 지정하지 않은 경우 XingAPI SDK의 기본 설치 경로를 사용합니다.
+#[pyfunction]
+#[pyo3(signature = (dll_path = None, res_dir = None))]
+fn load(dll_path: Option<&str>, res_dir: Option<&str>) -> PyResult<()> {
+    let layout_tbl = match res_dir {
+        Some(res_dir) => layout::load_dir(res_dir),
+        None => layout::load(),
+    }
+    .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+    match dll_path {
+        Some(dll_path) => xingapi_rs::loader::load_with_path(&dll_path),
+        None => xingapi_rs::loader::load(),
+    }
+    .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+    *LAYOUT_TBL.write().unwrap() = layout_tbl;
+    Ok(())
+}
+
+/// XingAPI DLL을 언로드합니다.
+#[pyfunction]
+fn unload() {
+    xingapi_rs::loader::unload();
+}
+
+/// 서버에 연결합니다.
+#[pyfunction]
+#[pyo3(signature = (addr, port = 20001, timeout = 10.0))]
+fn connect(py: Python<'_>, addr: &str, port: u16, timeout: f64) -> PyResult<()> {
+    let timeout = Duration::from_secs_f64(timeout);
+    py.allow_threads(|| xingapi_rs::connect(addr, port, timeout))
+        .map_err(to_py_err)
+}
+
+/// 서버 연결 여부를 반환합니다.
+#[pyfunction]
+fn is_connected() -> bool {
+    xingapi_rs::is_connected()
+}
+
+/// 서버와의 연결을 종료합니다.
+#[pyfunction]
+fn disconnect() {
+    xingapi_rs::disconnect();
+}
+
+/// 서버에 로그인 요청을 합니다.
+///
+/// 서버가 로그인을 거부한 경우 `RuntimeError`가 발생합니다.
+#[pyfunction]
+#[pyo3(signature = (id, pw, cert_pw = ""))]
+fn login(py: Python<'_>, id: &str, pw: &str, cert_pw: &str) -> PyResult<()> {
+    let res = py
+        .allow_threads(|| xingapi_rs::login(id, pw, cert_pw, false))
+        .map_err(to_py_err)?;
+
+    if res.is_ok() {
+        Ok(())
+    } else {
+        Err(PyRuntimeError::new_err(format!(
+            "[{}] {}",
+            res.code(),
+            res.message()
+        )))
+    }
+}
+
+/// 계좌번호 목록을 반환합니다.
+#[pyfunction]
+fn accounts() -> Vec<String> {
+    xingapi_rs::accounts()
+        .into_iter()
+        .map(|account| account.code)
+        .collect()
+}
+
+/// 서버에 조회 TR 요청을 합니다.
+///
+/// `inputs`에 지정하지 않은 단일 블록의 필드는 빈 문자열로 요청합니다.
+/// `code`, `message`, `elapsed`(초), `next_key`, `data`를 키로 갖는 `dict`를
+/// 반환하며, 응답 데이터가 없는 경우 `data`는 `None`입니다.
+#[pyfunction]
+#[pyo3(signature = (tr_code, inputs, next_key = None, timeout = 30.0))]
+fn request(
+    py: Python<'_>,
+    tr_code: &str,
+    inputs: HashMap<String, Bound<'_, PyAny>>,
+    next_key: Option<&str>,
+    timeout: f64,
+) -> PyResult<PyObject> {
+    let tr_layout = get_layout(tr_code)?;
+
+    let mut blocks = HashMap::new();
+    for block_layout in tr_layout.in_blocks.iter().filter(|b| !b.occurs) {
+        let fields = block_layout
+            .fields
+            .iter()
+            .map(|field_layout| (field_layout.name.clone(), String::new()))
+            .collect();
+        blocks.insert(block_layout.name.clone(), Block::Block(fields));
+    }
+
+    let to_fields = |fields: HashMap<String, String>| -> HashMap<Arc<str>, String> {
+        fields.into_iter().map(|(k, v)| (k.into(), v)).collect()
+    };
+
+    for (block_name, value) in inputs {
+        let block = if let Ok(fields) = value.extract::<HashMap<String, String>>() {
+            let fields = to_fields(fields);
+            match blocks.remove(&block_name) {
+                Some(Block::Block(mut defaults)) => {
+                    defaults.extend(fields);
+                    Block::Block(defaults)
+                }
+                _ => Block::Block(fields),
+            }
+        } else if let Ok(arr) = value.extract::<Vec<HashMap<String, String>>>() {
+            Block::Array(arr.into_iter().map(to_fields).collect())
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "block must be a dict or a list of dicts: {}",
+                block_name
+            )));
+        };
+
+        blocks.insert(block_name, block);
+    }
+
+    let data = Data {
+        tr_code: tr_code.to_owned(),
+        data_type: DataType::Input,
+        blocks,
+    };
+
+    let timeout = Duration::from_secs_f64(timeout);
+    let res = py
+        .allow_threads(|| xingapi_rs::request(&data, tr_layout, next_key, timeout))
+        .map_err(to_py_err)?;
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item("code", res.code())?;
+    dict.set_item("message", res.message())?;
+    dict.set_item("elapsed", res.elapsed().as_secs_f64())?;
+    dict.set_item("next_key", res.next_key())?;
+    if res.is_ok() {
+        let data = res
+            .data()
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        dict.set_item("data", data_to_dict(py, data)?)?;
+    } else {
+        dict.set_item("data", py.None())?;
+    }
+
+    Ok(dict.into_any().unbind())
+}
+
+/// 실시간 TR을 등록하고 수신하는 객체
+///
+/// 반복자로 사용하면 응답을 수신할 때까지 기다리며, `tr_code`, `key`,
+/// `data`를 키로 갖는 `dict`를 반환합니다.
+#[pyclass]
+struct Real {
+    event: RealEvent,
+    tx_res: Sender<RealResponse>,
+    rx_res: Mutex<Receiver<RealResponse>>,
+    tr_codes: Mutex<HashSet<String>>,
+}
+
+impl Real {
+    fn recv_inner(&self, py: Python<'_>, timeout: Option<Duration>) -> PyResult<Option<PyObject>> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+        let rx_res = &self.rx_res;
+        let mut remaining = timeout;
+
+        loop {
+            let interval = remaining.map_or(POLL_INTERVAL, |r| r.min(POLL_INTERVAL));
+            match py.allow_threads(|| rx_res.lock().unwrap().recv_timeout(interval)) {
+                Ok(res) => return Ok(Some(real_to_dict(py, &res)?.into_any().unbind())),
+                Err(RecvTimeoutError::Disconnected) => return Ok(None),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+
+            // Ctrl+C 등의 시그널을 처리합니다.
+            py.check_signals()?;
+
+            if let Some(r) = remaining.as_mut() {
+                *r = r.saturating_sub(interval);
+                if r.is_zero() {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Real {
+    #[new]
+    fn new() -> PyResult<Self> {
+        let event = RealEvent::new().map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let (tx_res, rx_res) = mpsc::channel();
+
+        Ok(Self {
+            event,
+            tx_res,
+            rx_res: Mutex::new(rx_res),
+            tr_codes: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// 실시간 TR을 등록합니다.
+    fn subscribe(&self, tr_code: &str, keys: Vec<String>) -> PyResult<()> {
+        let mut tr_codes = self.tr_codes.lock().unwrap();
+        if !tr_codes.contains(tr_code) {
+            self.event.insert_layout(get_layout(tr_code)?);
+
+            let tx_res = self.tx_res.clone();
+            self.event.on(tr_code, move |res| {
+                let _ = tx_res.send(res.clone());
+            });
+
+            tr_codes.insert(tr_code.to_owned());
+        }
+
+        self.event.subscribe(tr_code, &keys);
+        Ok(())
+    }
+
+    /// 실시간 TR을 등록 해제합니다.
+    fn unsubscribe(&self, tr_code: &str, keys: Vec<String>) {
+        self.event.unsubscribe(tr_code, &keys);
+    }
+
+    /// 모든 실시간 TR을 등록 해제합니다.
+    fn unsubscribe_all(&self) {
+        self.event.unsubscribe_all();
+    }
+
+    /// 응답을 수신할 때까지 지정된 시간(초) 동안 기다립니다.
+    ///
+    /// 시간 내에 수신하지 못한 경우 `None`을 반환합니다.
+    #[pyo3(signature = (timeout = None))]
+    fn recv(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<Option<PyObject>> {
+        self.recv_inner(py, timeout.map(Duration::from_secs_f64))
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.recv_inner(py, None)
+    }
+}
+
+#[pymodule]
+fn xingapi(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(load, m)?)?;
+    m.add_function(wrap_pyfunction!(unload, m)?)?;
+    m.add_function(wrap_pyfunction!(connect, m)?)?;
+    m.add_function(wrap_pyfunction!(is_connected, m)?)?;
+    m.add_function(wrap_pyfunction!(disconnect, m)?)?;
+    m.add_function(wrap_pyfunction!(login, m)?)?;
+    m.add_function(wrap_pyfunction!(accounts, m)?)?;
+    m.add_function(wrap_pyfunction!(request, m)?)?;
+    m.add_class::<Real>()?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! xingapi 브로커 헬퍼 프로세스
+//!
+//! 32비트 타겟으로 빌드하여 64비트 애플리케이션에서
+//! `xingapi::broker::BrokerBackend`로 실행합니다.
+//!
+//! ```text
+//! xingapi-broker <pipe-name> [res-dir]
+//! ```
+
+#[cfg(windows)]
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let pipe_name = match args.next() {
+        Some(pipe_name) => pipe_name,
+        None => {
+            eprintln!("usage: xingapi-broker <pipe-name> [res-dir]");
+            std::process::exit(2);
+        }
+    };
+
+    let layout_tbl = match args.next() {
+        Some(res_dir) => xingapi::layout::load_dir(res_dir),
+        None => xingapi::layout::load(),
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    });
+
+    if let Err(err) = xingapi::loader::load() {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+
+    let result = xingapi::broker::serve(&pipe_name, &layout_tbl);
+
+    xingapi::loader::unload();
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("error: xingapi-broker is only supported on windows");
+    std::process::exit(1);
+}
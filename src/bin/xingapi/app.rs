@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+
+use std::collections::HashMap;
+use std::env;
+use std::process;
+use std::sync::Arc;
+use std::time::Duration;
+
+use xingapi::data::{Block, Data, DataType};
+use xingapi::layout::{docgen, BlockLayout, TrLayout};
+use xingapi::{RealEvent, Response};
+
+pub fn main() {
+    let matches = App::new("xingapi")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("addr")
+                .long("addr")
+                .takes_value(true)
+                .default_value("demo.ebestsec.co.kr"),
+        )
+        .arg(Arg::with_name("id").long("id").takes_value(true))
+        .arg(Arg::with_name("pw").long("pw").takes_value(true))
+        .arg(Arg::with_name("cert-pw").long("cert-pw").takes_value(true))
+        .arg(Arg::with_name("res-dir").long("res-dir").takes_value(true))
+        .arg(Arg::with_name("json").long("json").global(true))
+        .subcommand(
+            SubCommand::with_name("request")
+                .arg(Arg::with_name("tr_code").required(true))
+                .arg(
+                    Arg::with_name("in")
+                        .long("in")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("next-key")
+                        .long("next-key")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .takes_value(true)
+                        .default_value("30"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("subscribe")
+                .arg(Arg::with_name("tr_code").required(true))
+                .arg(Arg::with_name("keys").required(true).multiple(true)),
+        )
+        .subcommand(SubCommand::with_name("accounts"))
+        .subcommand(
+            SubCommand::with_name("docs")
+                .arg(Arg::with_name("html").long("html"))
+                .arg(Arg::with_name("tr_codes").multiple(true)),
+        )
+        .get_matches();
+
+    if let Err(err) = run(&matches) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn arg_or_env(matches: &ArgMatches, name: &str, key: &str) -> Option<String> {
+    matches
+        .value_of(name)
+        .map(str::to_owned)
+        .or_else(|| env::var(key).ok())
+}
+
+fn run(matches: &ArgMatches) -> Result<(), String> {
+    let layout_tbl = match matches.value_of("res-dir") {
+        Some(res_dir) => xingapi::layout::load_dir(res_dir),
+        None => xingapi::layout::load(),
+    }
+    .map_err(|err| err.to_string())?;
+
+    // 문서 생성은 서버에 접속하지 않습니다.
+    if let ("docs", Some(matches)) = matches.subcommand() {
+        return docs(&layout_tbl, matches);
+    }
+
+    let id = arg_or_env(matches, "id", "XINGAPI_ID").ok_or("--id is required")?;
+    let pw = arg_or_env(matches, "pw", "XINGAPI_PW").ok_or("--pw is required")?;
+    let cert_pw = arg_or_env(matches, "cert-pw", "XINGAPI_CERT_PW").unwrap_or_default();
+    let json = matches.is_present("json");
+
+    xingapi::loader::load().map_err(|err| err.to_string())?;
+
+    xingapi::connect(
+        matches.value_of("addr").unwrap(),
+        20001,
+        Duration::from_secs(10),
+    )
+    .map_err(|err| err.to_string())?;
+
+    let res = xingapi::login(&id, &pw, &cert_pw, false).map_err(|err| err.to_string())?;
+    if !res.is_ok() {
+        return Err(format!("login failed: [{}] {}", res.code(), res.message()));
+    }
+
+    let get_layout = |tr_code: &str| {
+        layout_tbl
+            .get(tr_code)
+            .ok_or_else(|| format!("unknown tr code: {}", tr_code))
+    };
+
+    let result = match matches.subcommand() {
+        ("request", Some(matches)) => {
+            let tr_layout = get_layout(matches.value_of("tr_code").unwrap())?;
+            request(tr_layout, matches, json)
+        }
+        ("subscribe", Some(matches)) => {
+            let tr_layout = get_layout(matches.value_of("tr_code").unwrap())?;
+            let keys: Vec<_> = matches.values_of("keys").unwrap().collect();
+            subscribe(tr_layout, &keys, json)
+        }
+        ("accounts", Some(_)) => {
+            accounts(json);
+            Ok(())
+        }
+        _ => unreachable!(),
+    };
+
+    xingapi::disconnect();
+    xingapi::loader::unload();
+
+    result
+}
+
+fn docs(layout_tbl: &HashMap<String, Arc<TrLayout>>, matches: &ArgMatches) -> Result<(), String> {
+    let layouts = match matches.values_of("tr_codes") {
+        Some(tr_codes) => tr_codes
+            .map(|tr_code| {
+                layout_tbl
+                    .get(tr_code)
+                    .map(|tr_layout| &**tr_layout)
+                    .ok_or_else(|| format!("unknown tr code: {}", tr_code))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => layout_tbl.values().map(|tr_layout| &**tr_layout).collect(),
+    };
+
+    if matches.is_present("html") {
+        print!("{}", docgen::to_html(layouts));
+    } else {
+        print!("{}", docgen::to_markdown(layouts));
+    }
+
+    Ok(())
+}
+
+fn request(tr_layout: &Arc<TrLayout>, matches: &ArgMatches, json: bool) -> Result<(), String> {
+    let inputs: Vec<_> = matches.values_of("in").into_iter().flatten().collect();
+    let data = parse_input(tr_layout, &inputs)?;
+    let timeout = matches
+        .value_of("timeout")
+        .unwrap()
+        .parse::<u64>()
+        .map_err(|_| "invalid timeout")?;
+
+    let res = xingapi::request(
+        &data,
+        tr_layout.clone(),
+        matches.value_of("next-key"),
+        Duration::from_secs(timeout),
+    )
+    .map_err(|err| err.to_string())?;
+
+    if !res.is_ok() {
+        return Err(format!("[{}] {}", res.code(), res.message()));
+    }
+
+    let data = res.data().map_err(|err| err.to_string())?;
+    if json {
+        println!("{}", data.to_json());
+    } else {
+        eprintln!("[{}] {} ({:?})", res.code(), res.message(), res.elapsed());
+        if let Some(next_key) = res.next_key() {
+            eprintln!("next key: {}", next_key);
+        }
+        print_data(&tr_layout.out_blocks, data);
+    }
+
+    Ok(())
+}
+
+// `field=value` 또는 `block.field=value` 형식의 요청 값을 파싱합니다.
+//
+// 블록 이름을 생략한 경우 첫 번째 단일 입력 블록으로 간주하며, 지정하지 않은
+// 필드는 빈 문자열로 요청합니다.
+fn parse_input(tr_layout: &TrLayout, inputs: &[&str]) -> Result<Data, String> {
+    let mut blocks: HashMap<_, _> = tr_layout
+        .in_blocks
+        .iter()
+        .filter(|block_layout| !block_layout.occurs)
+        .map(|block_layout| {
+            let fields = block_layout
+                .fields
+                .iter()
+                .map(|field_layout| (field_layout.name.clone(), String::new()))
+                .collect::<HashMap<_, _>>();
+            (block_layout.name.clone(), fields)
+        })
+        .collect();
+
+    let default_block = tr_layout
+        .in_blocks
+        .iter()
+        .find(|block_layout| !block_layout.occurs)
+        .map(|block_layout| block_layout.name.as_str());
+
+    for input in inputs {
+        let (key, value) = input
+            .split_once('=')
+            .ok_or_else(|| format!("invalid input: {}", input))?;
+
+        let (block_name, field_name) = match key.split_once('.') {
+            Some((block_name, field_name)) => (block_name, field_name),
+            None => (
+                default_block.ok_or_else(|| format!("no input block: {}", input))?,
+                key,
+            ),
+        };
+
+        let fields = blocks
+            .get_mut(block_name)
+            .ok_or_else(|| format!("unknown block: {}", block_name))?;
+        match fields.get_mut(field_name) {
+            Some(field) => *field = value.to_owned(),
+            None => return Err(format!("unknown field: {}", key)),
+        }
+    }
+
+    Ok(Data {
+        tr_code: tr_layout.code.clone(),
+        data_type: DataType::Input,
+        blocks: blocks
+            .into_iter()
+            .map(|(name, fields)| (name, Block::Block(fields)))
+            .collect(),
+    })
+}
+
+fn subscribe(tr_layout: &Arc<TrLayout>, keys: &[&str], json: bool) -> Result<(), String> {
+    let real = RealEvent::new().map_err(|err| err.to_string())?;
+    real.insert_layout(tr_layout.clone());
+    real.subscribe(&tr_layout.code, keys);
+
+    // Ctrl+C로 프로세스가 종료될 때까지 수신합니다.
+    loop {
+        let res = match real.recv_timeout(Duration::from_secs(1)) {
+            Some(res) => res,
+            None => continue,
+        };
+
+        match res.data() {
+            Ok(data) if json => println!("{}", data.to_json()),
+            Ok(data) => {
+                println!("[{}] {}", res.tr_code(), res.key());
+                print_data(&tr_layout.out_blocks, data);
+            }
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
+}
+
+fn accounts(json: bool) {
+    let accounts = xingapi::accounts();
+    if json {
+        let rows: Vec<_> = accounts
+            .iter()
+            .map(|account| {
+                format!(
+                    "{{\"code\":{},\"name\":{},\"detailed_name\":{},\"nickname\":{}}}",
+                    json_str(&account.code),
+                    json_str(&account.name),
+                    json_str(&account.detailed_name),
+                    json_str(&account.nickname)
+                )
+            })
+            .collect();
+        println!("[{}]", rows.join(","));
+    } else {
+        let rows: Vec<_> = accounts
+            .iter()
+            .map(|account| {
+                vec![
+                    account.code.as_str(),
+                    account.name.as_str(),
+                    account.detailed_name.as_str(),
+                    account.nickname.as_str(),
+                ]
+            })
+            .collect();
+        print_table(&["code", "name", "detailed_name", "nickname"], &rows);
+    }
+}
+
+fn json_str(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// 레이아웃의 필드 순서대로 블록을 출력합니다.
+fn print_data(block_layouts: &[BlockLayout], data: &Data) {
+    for block_layout in block_layouts {
+        let block = match data.blocks.get(&block_layout.name) {
+            Some(block) => block,
+            None => continue,
+        };
+
+        println!("{}", block_layout.name);
+
+        let names: Vec<_> = block_layout
+            .fields
+            .iter()
+            .map(|field_layout| &*field_layout.name)
+            .collect();
+
+        match block {
+            Block::Block(fields) => {
+                let rows: Vec<_> = names
+                    .iter()
+                    .map(|name| vec![*name, fields.get(*name).map_or("", String::as_str)])
+                    .collect();
+                print_table(&["field", "value"], &rows);
+            }
+            Block::Array(arr) => {
+                let rows: Vec<_> = arr
+                    .iter()
+                    .map(|fields| {
+                        names
+                            .iter()
+                            .map(|name| fields.get(*name).map_or("", String::as_str))
+                            .collect()
+                    })
+                    .collect();
+                print_table(&names, &rows);
+            }
+        }
+    }
+}
+
+// 터미널에 출력되는 문자열의 너비를 반환합니다. 한글 등 전각 문자는 2칸으로
+// 계산합니다.
+fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if c >= '\u{1100}' { 2 } else { 1 }).sum()
+}
+
+fn print_table(header: &[&str], rows: &[Vec<&str>]) {
+    let mut widths: Vec<_> = header.iter().map(|s| display_width(s)).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+
+    let print_row = |row: &[&str]| {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - display_width(cell))))
+            .collect();
+        println!("  {}", line.join("  ").trim_end());
+    };
+
+    print_row(header);
+    for row in rows {
+        print_row(row);
+    }
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! xingapi 명령줄 도구
+//!
+//! ```text
+//! xingapi --id ID --pw PW request t1101 --in shcode=078020
+//! xingapi --id ID --pw PW subscribe S3_ 005930
+//! xingapi --id ID --pw PW accounts
+//! xingapi --res-dir Res docs --html t1101 t1102 > tr.html
+//! ```
+//!
+//! 계정 정보는 `XINGAPI_ID`, `XINGAPI_PW`, `XINGAPI_CERT_PW` 환경 변수로도
+//! 지정할 수 있습니다.
+
+#[cfg(windows)]
+mod app;
+
+#[cfg(windows)]
+fn main() {
+    app::main();
+}
+
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("error: xingapi is only supported on windows");
+    std::process::exit(1);
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 이진 파일 형식에서 공통으로 사용하는 인코딩 함수
+//!
+//! 정수는 리틀 엔디언으로, 문자열은 `u32` 길이와 UTF-8 바이트로 저장합니다.
+//! 레이아웃 캐시와 실시간 데이터 기록 파일 등에서 사용합니다.
+
+use std::io;
+
+pub(crate) fn invalid_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid binary data")
+}
+
+pub(crate) fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn put_str(buf: &mut Vec<u8>, value: &str) {
+    put_u32(buf, value.len() as u32);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+// 버퍼의 앞에서 지정된 길이만큼 가져옵니다.
+pub(crate) fn take<'a>(buf: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(invalid_data());
+    }
+
+    let (head, tail) = buf.split_at(len);
+    *buf = tail;
+    Ok(head)
+}
+
+pub(crate) fn get_u8(buf: &mut &[u8]) -> io::Result<u8> {
+    Ok(take(buf, 1)?[0])
+}
+
+pub(crate) fn get_u32(buf: &mut &[u8]) -> io::Result<u32> {
+    Ok(u32::from_le_bytes(take(buf, 4)?.try_into().unwrap()))
+}
+
+pub(crate) fn get_u64(buf: &mut &[u8]) -> io::Result<u64> {
+    Ok(u64::from_le_bytes(take(buf, 8)?.try_into().unwrap()))
+}
+
+pub(crate) fn get_str(buf: &mut &[u8]) -> io::Result<String> {
+    let len = get_u32(buf)? as usize;
+    String::from_utf8(take(buf, len)?.to_owned()).map_err(|_| invalid_data())
+}
@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::Block;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 필드 값을 가져오지 못하여 발생하는 에러
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum FieldError {
+    /// 배열 블록에서 필드를 가져오려고 했습니다.
+    NotBlock,
+    /// 필드가 없습니다.
+    Missing(String),
+    /// 필드의 값을 지정된 타입으로 변환할 수 없습니다.
+    Invalid {
+        /// 필드 이름
+        field: String,
+        /// 필드 값
+        value: String,
+        /// 변환하려던 타입
+        expected: &'static str,
+    },
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotBlock => "expected a block but found an array".fmt(f),
+            Self::Missing(field) => write!(f, "missing {} field", field),
+            Self::Invalid {
+                field,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value of {} field; expected {}, found {:?}",
+                field, expected, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// 고정 소수점 실수
+///
+/// 가격 등 이진 실수로 정확하게 표현할 수 없는 값을 잃지 않고 다루기 위해
+/// 사용합니다. 값은 `mantissa / 10^scale`입니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    /// 소수점을 제거한 정수
+    pub mantissa: i64,
+    /// 소수점 이하 자릿수
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// 가장 가까운 `f64` 값으로 변환합니다.
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+
+        let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(());
+        }
+
+        let mut mantissa: i64 = 0;
+        for b in int.bytes().chain(frac.bytes()) {
+            if !b.is_ascii_digit() {
+                return Err(());
+            }
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add((b - b'0') as i64))
+                .ok_or(())?;
+        }
+
+        Ok(Self {
+            mantissa: if negative { -mantissa } else { mantissa },
+            scale: frac.len() as u32,
+        })
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, int, frac)
+    }
+}
+
+/// 필드 값을 타입에 맞게 가져오는 트레잇
+///
+/// 필드가 없거나 값을 변환할 수 없는 경우 패닉 대신 [`FieldError`]를
+/// 반환합니다. 단일 블록과 배열 블록의 각 행에 사용할 수 있습니다.
+pub trait FieldsExt {
+    /// 필드의 문자열 값을 반환합니다.
+    fn get_str(&self, name: &str) -> Result<&str, FieldError>;
+
+    /// 필드 값을 정수로 변환합니다.
+    fn get_i64(&self, name: &str) -> Result<i64, FieldError> {
+        parse(self.get_str(name)?, name, "integer")
+    }
+
+    /// 필드 값을 실수로 변환합니다.
+    fn get_f64(&self, name: &str) -> Result<f64, FieldError> {
+        parse(self.get_str(name)?, name, "float")
+    }
+
+    /// 필드 값을 고정 소수점 실수로 변환합니다.
+    fn get_decimal(&self, name: &str) -> Result<Decimal, FieldError> {
+        parse(self.get_str(name)?, name, "decimal")
+    }
+
+    /// `YYYYMMDD` 형식의 필드 값을 날짜로 변환합니다.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    fn get_date(&self, name: &str) -> Result<chrono::NaiveDate, FieldError> {
+        parse::<super::date::Date>(self.get_str(name)?, name, "date").map(Into::into)
+    }
+
+    /// `HHMMSS`나 `HHMMSSmm` 형식의 필드 값을 시각으로 변환합니다.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    fn get_time(&self, name: &str) -> Result<chrono::NaiveTime, FieldError> {
+        parse::<super::date::Time>(self.get_str(name)?, name, "time").map(Into::into)
+    }
+}
+
+fn parse<T: FromStr>(value: &str, name: &str, expected: &'static str) -> Result<T, FieldError> {
+    value.parse().map_err(|_| FieldError::Invalid {
+        field: name.to_owned(),
+        value: value.to_owned(),
+        expected,
+    })
+}
+
+impl FieldsExt for HashMap<Arc<str>, String> {
+    fn get_str(&self, name: &str) -> Result<&str, FieldError> {
+        self.get(name)
+            .map(String::as_str)
+            .ok_or_else(|| FieldError::Missing(name.to_owned()))
+    }
+}
+
+impl FieldsExt for Block {
+    fn get_str(&self, name: &str) -> Result<&str, FieldError> {
+        self.as_block().ok_or(FieldError::NotBlock)?.get_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, FieldError, FieldsExt};
+    use crate::data::Block;
+    use crate::hashmap;
+
+    #[test]
+    fn test_decimal() {
+        let parse = |s: &str| s.parse::<Decimal>();
+
+        assert_eq!(
+            parse("123.45"),
+            Ok(Decimal {
+                mantissa: 12345,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            parse("-0.05"),
+            Ok(Decimal {
+                mantissa: -5,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            parse("+7"),
+            Ok(Decimal {
+                mantissa: 7,
+                scale: 0
+            })
+        );
+        assert_eq!(
+            parse(".5"),
+            Ok(Decimal {
+                mantissa: 5,
+                scale: 1
+            })
+        );
+        assert!(parse("").is_err());
+        assert!(parse("-").is_err());
+        assert!(parse("1.2.3").is_err());
+        assert!(parse("99999999999999999999").is_err());
+
+        assert_eq!(parse("-0.05").unwrap().to_string(), "-0.05");
+        assert_eq!(parse("123.45").unwrap().to_string(), "123.45");
+        assert_eq!(parse("42").unwrap().to_string(), "42");
+        assert_eq!(parse("1.50").unwrap().to_f64(), 1.5);
+    }
+
+    #[test]
+    fn test_get() {
+        let block = Block::Block(hashmap! {
+            "hname" => "삼성전자",
+            "price" => "81000",
+            "diff" => "-1.25",
+        });
+
+        assert_eq!(block.get_str("hname"), Ok("삼성전자"));
+        assert_eq!(block.get_i64("price"), Ok(81000));
+        assert_eq!(block.get_f64("diff"), Ok(-1.25));
+        assert_eq!(block.get_decimal("diff").unwrap().to_string(), "-1.25");
+        assert_eq!(
+            block.get_i64("volume"),
+            Err(FieldError::Missing("volume".into()))
+        );
+        assert!(matches!(
+            block.get_i64("hname"),
+            Err(FieldError::Invalid {
+                expected: "integer",
+                ..
+            })
+        ));
+
+        let block = Block::Array(vec![hashmap! { "price" => "100" }]);
+        assert_eq!(block.get_str("price"), Err(FieldError::NotBlock));
+        assert_eq!(block.rows()[0].get_i64("price"), Ok(100));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_get_chrono() {
+        use chrono::{NaiveDate, NaiveTime};
+
+        let block = Block::Block(hashmap! {
+            "date" => "20210111",
+            "time" => "090015",
+            "time2" => "09001523",
+        });
+
+        assert_eq!(
+            block.get_date("date"),
+            Ok(NaiveDate::from_ymd_opt(2021, 1, 11).unwrap())
+        );
+        assert_eq!(
+            block.get_time("time"),
+            Ok(NaiveTime::from_hms_opt(9, 0, 15).unwrap())
+        );
+        assert_eq!(
+            block.get_time("time2"),
+            Ok(NaiveTime::from_hms_milli_opt(9, 0, 15, 230).unwrap())
+        );
+        assert!(block.get_date("time").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{block_array_fields, block_fields, non_block_fields};
+use super::{DecodeError, Fields, RawData};
+use crate::layout::{FieldLayout, TrLayout};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 블록 이름을 키로 하는 필드 속성 테이블
+///
+/// 레이아웃에 속성 바이트가 있는 경우 서버는 각 필드 뒤에 상태를 나타내는
+/// 1바이트를 함께 보냅니다. 디코딩된 [`Data`](super::Data)에는 포함되지 않으며
+/// 값의 의미는 TR마다 다릅니다.
+pub type Attrs = HashMap<String, AttrBlock>;
+
+/// 블록의 필드 속성
+///
+/// [`Block`](super::Block)과 같은 형태로 필드 이름을 키로 합니다.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttrBlock {
+    /// 단일 블록
+    Block(HashMap<Arc<str>, u8>),
+    /// 배열 블록
+    Array(Vec<HashMap<Arc<str>, u8>>),
+}
+
+impl AttrBlock {
+    /// 단일 블록인 경우 필드 속성 테이블을 반환합니다.
+    pub fn as_block(&self) -> Option<&HashMap<Arc<str>, u8>> {
+        match self {
+            Self::Block(attrs) => Some(attrs),
+            Self::Array(_) => None,
+        }
+    }
+
+    /// 배열 블록인 경우 행마다 필드 속성 테이블을 반환합니다.
+    pub fn as_array(&self) -> Option<&Vec<HashMap<Arc<str>, u8>>> {
+        match self {
+            Self::Block(_) => None,
+            Self::Array(arr) => Some(arr),
+        }
+    }
+}
+
+fn attr_field(
+    field_layout: &FieldLayout,
+    _raw_field: &[u8],
+    attr: Option<u8>,
+) -> Result<(Arc<str>, u8), DecodeError> {
+    Ok((field_layout.name.clone(), attr.unwrap_or_default()))
+}
+
+// 응답 데이터에서 필드 값 대신 속성 바이트를 디코딩합니다.
+//
+// 레이아웃에 속성 바이트가 없는 경우 빈 테이블을 반환합니다.
+pub(crate) fn decode_attrs(tr_layout: &TrLayout, raw_data: &RawData) -> Result<Attrs, DecodeError> {
+    if !tr_layout.attr_byte {
+        return Ok(Attrs::new());
+    }
+
+    match raw_data {
+        RawData::Block(raw_block_tbl) => {
+            assert!(tr_layout.block_mode);
+
+            let mut attrs = Attrs::new();
+
+            for (block_name, raw_block) in raw_block_tbl {
+                let block_layout = tr_layout
+                    .out_block(block_name)
+                    .ok_or_else(|| DecodeError::UnknownBlock(block_name.clone()))?;
+
+                let block = if block_layout.occurs {
+                    AttrBlock::Array(block_array_fields(
+                        tr_layout,
+                        block_layout,
+                        raw_block,
+                        &attr_field,
+                    )?)
+                } else {
+                    AttrBlock::Block(block_fields(
+                        tr_layout,
+                        block_layout,
+                        raw_block,
+                        &attr_field,
+                    )?)
+                };
+
+                attrs.insert(block_name.clone(), block);
+            }
+
+            Ok(attrs)
+        }
+        RawData::NonBlock(raw_data) => Ok(non_block_fields(tr_layout, raw_data, &attr_field)?
+            .into_iter()
+            .map(|(block_layout, block)| {
+                let block = match block {
+                    Fields::Block(attrs) => AttrBlock::Block(attrs),
+                    Fields::Array(arr) => AttrBlock::Array(arr),
+                };
+                (block_layout.name.clone(), block)
+            })
+            .collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_attrs, AttrBlock};
+    use crate::data::{decode, RawData};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_decode_attrs() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000,attr;
+                BEGIN_DATA_MAP
+                t0000OutBlock,출력,output;
+                begin
+                    코드,code,code,char,6;
+                end
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    가격,price,price,long,3;
+                    대비,sign,sign,char,1;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let raw_data = RawData::NonBlock(b"096530\x0000002012\x312\x32345\x00 \x00".to_vec());
+
+        let attrs = decode_attrs(&tr_layout, &raw_data).unwrap();
+        assert_eq!(
+            attrs["t0000OutBlock"],
+            AttrBlock::Block(hashmap! { "code" => 0u8 })
+        );
+        assert_eq!(
+            attrs["t0000OutBlock1"],
+            AttrBlock::Array(vec![
+                hashmap! { "price" => 0x31u8, "sign" => 0x32u8 },
+                hashmap! { "price" => 0u8, "sign" => 0u8 },
+            ])
+        );
+
+        let data = decode(&tr_layout, raw_data).unwrap();
+        let arr = data.blocks["t0000OutBlock1"].as_array().unwrap();
+        assert_eq!(arr[1]["price"], "345");
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{block_array_fields, block_fields, decode_cow, non_block_fields};
+use super::{Block, Data, DataType, DecodeError, Fields, RawData};
+use crate::layout::{FieldLayout, TrLayout};
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 원본 데이터를 빌려서 디코딩한 데이터
+///
+/// ASCII 문자로만 이루어진 필드는 복사하지 않고 원본 데이터를 빌리며, 필드와
+/// 블록의 이름은 레이아웃을 빌립니다. 응답을 대부분 버리거나 일부 필드만
+/// 읽는 경우 [`Data`]보다 할당이 적습니다.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataRef<'a> {
+    /// TR 코드
+    pub tr_code: &'a str,
+    /// 데이터 종류
+    pub data_type: DataType,
+    /// 블록 테이블
+    pub blocks: HashMap<&'a str, BlockRef<'a>>,
+}
+
+impl DataRef<'_> {
+    /// 빌린 값을 모두 복사하여 [`Data`]로 변환합니다.
+    pub fn into_owned(self) -> Data {
+        Data {
+            tr_code: self.tr_code.to_owned(),
+            data_type: self.data_type,
+            blocks: self
+                .blocks
+                .into_iter()
+                .map(|(name, block)| (name.to_owned(), block.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a Data> for DataRef<'a> {
+    fn from(data: &'a Data) -> Self {
+        Self {
+            tr_code: &data.tr_code,
+            data_type: data.data_type,
+            blocks: data
+                .blocks
+                .iter()
+                .map(|(name, block)| (name.as_str(), BlockRef::from(block)))
+                .collect(),
+        }
+    }
+}
+
+/// 원본 데이터를 빌려서 디코딩한 블록
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockRef<'a> {
+    /// 단일 블록
+    Block(HashMap<&'a str, Cow<'a, str>>),
+    /// 배열 블록
+    Array(Vec<HashMap<&'a str, Cow<'a, str>>>),
+}
+
+impl<'a> BlockRef<'a> {
+    /// 단일 블록인 경우 필드 테이블을 반환합니다.
+    pub fn as_block(&self) -> Option<&HashMap<&'a str, Cow<'a, str>>> {
+        match self {
+            Self::Block(block) => Some(block),
+            Self::Array(_) => None,
+        }
+    }
+
+    /// 배열 블록인 경우 필드 테이블의 배열을 반환합니다.
+    pub fn as_array(&self) -> Option<&Vec<HashMap<&'a str, Cow<'a, str>>>> {
+        match self {
+            Self::Block(_) => None,
+            Self::Array(arr) => Some(arr),
+        }
+    }
+
+    /// 빌린 값을 모두 복사하여 [`Block`]으로 변환합니다.
+    pub fn into_owned(self) -> Block {
+        fn owned(fields: HashMap<&str, Cow<'_, str>>) -> HashMap<Arc<str>, String> {
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into_owned()))
+                .collect()
+        }
+
+        match self {
+            Self::Block(block) => Block::Block(owned(block)),
+            Self::Array(arr) => Block::Array(arr.into_iter().map(owned).collect()),
+        }
+    }
+}
+
+impl<'a> From<&'a Block> for BlockRef<'a> {
+    fn from(block: &'a Block) -> Self {
+        fn borrowed(fields: &HashMap<Arc<str>, String>) -> HashMap<&str, Cow<'_, str>> {
+            fields
+                .iter()
+                .map(|(k, v)| (&**k, Cow::Borrowed(v.as_str())))
+                .collect()
+        }
+
+        match block {
+            Block::Block(block) => Self::Block(borrowed(block)),
+            Block::Array(arr) => Self::Array(arr.iter().map(borrowed).collect()),
+        }
+    }
+}
+
+fn borrowed_field<'a>(
+    field_layout: &'a FieldLayout,
+    raw_field: &'a [u8],
+    _attr: Option<u8>,
+) -> Result<(&'a str, Cow<'a, str>), DecodeError> {
+    Ok((&field_layout.name, decode_cow(raw_field)?))
+}
+
+// 응답 데이터를 복사하지 않고 디코딩합니다.
+pub(crate) fn decode_ref<'a>(
+    tr_layout: &'a TrLayout,
+    raw_data: &'a RawData,
+) -> Result<DataRef<'a>, DecodeError> {
+    let blocks = match raw_data {
+        RawData::Block(raw_block_tbl) => {
+            assert!(tr_layout.block_mode);
+
+            let mut blocks = HashMap::new();
+
+            for (block_name, raw_block) in raw_block_tbl {
+                let block_layout = tr_layout
+                    .out_block(block_name)
+                    .ok_or_else(|| DecodeError::UnknownBlock(block_name.clone()))?;
+
+                let block = if block_layout.occurs {
+                    BlockRef::Array(block_array_fields(
+                        tr_layout,
+                        block_layout,
+                        raw_block,
+                        &borrowed_field,
+                    )?)
+                } else {
+                    BlockRef::Block(block_fields(
+                        tr_layout,
+                        block_layout,
+                        raw_block,
+                        &borrowed_field,
+                    )?)
+                };
+
+                blocks.insert(block_layout.name.as_str(), block);
+            }
+
+            blocks
+        }
+        RawData::NonBlock(raw_data) => non_block_fields(tr_layout, raw_data, &borrowed_field)?
+            .into_iter()
+            .map(|(block_layout, block)| {
+                let block = match block {
+                    Fields::Block(fields) => BlockRef::Block(fields),
+                    Fields::Array(arr) => BlockRef::Array(arr),
+                };
+                (block_layout.name.as_str(), block)
+            })
+            .collect(),
+    };
+
+    Ok(DataRef {
+        tr_code: &tr_layout.code,
+        data_type: DataType::Output,
+        blocks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ref, BlockRef};
+    use crate::data::{decode, RawData};
+    use crate::layout::TrLayout;
+
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_decode_ref() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000OutBlock,출력,output;
+                begin
+                    코드,code,code,char,6;
+                    이름,name,name,char,4;
+                end
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    가격,price,price,long,3;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let mut raw = b"096530".to_vec();
+        raw.extend_from_slice(&[0xc7, 0xd1, 0xb1, 0xdb]);
+        raw.extend_from_slice(b"00002 12345");
+        let raw_data = RawData::NonBlock(raw);
+
+        let data = decode_ref(&tr_layout, &raw_data).unwrap();
+        let block = data.blocks["t0000OutBlock"].as_block().unwrap();
+        assert!(matches!(block["code"], Cow::Borrowed("096530")));
+        assert!(matches!(&block["name"], Cow::Owned(name) if name == "한글"));
+        assert_eq!(
+            data.blocks["t0000OutBlock1"],
+            BlockRef::Array(vec![
+                [("price", Cow::Borrowed("12"))].into_iter().collect(),
+                [("price", Cow::Borrowed("345"))].into_iter().collect(),
+            ])
+        );
+
+        assert_eq!(data.into_owned(), decode(&tr_layout, raw_data).unwrap());
+    }
+}
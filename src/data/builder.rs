@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{Block, Data, DataType, EncodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// [`Data`]를 단계적으로 구성하는 객체
+///
+/// [`Data::builder()`]로 생성하며, [`build()`](Self::build)에서 블록과 필드의
+/// 이름을 레이아웃과 비교하여 인코딩 전에 잘못된 이름을 찾습니다.
+///
+/// ## 예제
+/// ```rust
+/// use xingapi::data::Data;
+/// use xingapi::layout::TrLayout;
+///
+/// let tr_layout: TrLayout = "
+///     BEGIN_FUNCTION_MAP
+///         .Func,현재가,t1101,block;
+///         BEGIN_DATA_MAP
+///         t1101InBlock,기본입력,input;
+///         begin
+///             단축코드,shcode,shcode,char,6;
+///         end
+///         END_DATA_MAP
+///     END_FUNCTION_MAP
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// let data = Data::builder("t1101")
+///     .input()
+///     .block("t1101InBlock")
+///     .field("shcode", "078020")
+///     .build(&tr_layout)
+///     .unwrap();
+///
+/// assert_eq!(data.blocks["t1101InBlock"]["shcode"], *"078020");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataBuilder {
+    data: Data,
+    // 필드를 추가할 블록
+    current: Option<String>,
+    // 레이아웃의 필드 길이에 따라 형식을 정할 시각 필드 (블록, 행, 필드, 값)
+    #[cfg(feature = "chrono")]
+    times: Vec<(String, usize, Arc<str>, chrono::NaiveTime)>,
+}
+
+impl Data {
+    /// 데이터를 구성하는 객체를 생성합니다. 데이터 종류는 요청 데이터입니다.
+    pub fn builder<T: Into<String>>(tr_code: T) -> DataBuilder {
+        DataBuilder {
+            data: Data {
+                tr_code: tr_code.into(),
+                data_type: DataType::Input,
+                blocks: HashMap::new(),
+            },
+            current: None,
+            #[cfg(feature = "chrono")]
+            times: Vec::new(),
+        }
+    }
+}
+
+impl DataBuilder {
+    /// 요청 데이터로 지정합니다.
+    pub fn input(mut self) -> Self {
+        self.data.data_type = DataType::Input;
+        self
+    }
+
+    /// 응답 데이터로 지정합니다.
+    pub fn output(mut self) -> Self {
+        self.data.data_type = DataType::Output;
+        self
+    }
+
+    /// 단일 블록을 추가하고 이후의 필드를 이 블록에 추가합니다.
+    pub fn block<T: Into<String>>(mut self, name: T) -> Self {
+        let name = name.into();
+        self.data
+            .blocks
+            .insert(name.clone(), Block::Block(HashMap::new()));
+        self.current = Some(name);
+        self
+    }
+
+    /// 배열 블록을 추가하고 이후의 행과 필드를 이 블록에 추가합니다.
+    pub fn array<T: Into<String>>(mut self, name: T) -> Self {
+        let name = name.into();
+        self.data
+            .blocks
+            .insert(name.clone(), Block::Array(Vec::new()));
+        self.current = Some(name);
+        self
+    }
+
+    /// 현재 배열 블록에 새로운 행을 추가합니다.
+    ///
+    /// 배열 블록을 추가하지 않은 경우 패닉이 발생합니다.
+    pub fn row(mut self) -> Self {
+        self.current_block()
+            .as_array_mut()
+            .expect("row() requires an array block")
+            .push(HashMap::new());
+        self
+    }
+
+    /// 현재 블록에 필드를 추가합니다.
+    ///
+    /// 배열 블록인 경우 마지막 행에 추가하며, 행이 없으면 새로운 행을
+    /// 추가합니다. 블록을 추가하지 않은 경우 패닉이 발생합니다.
+    pub fn field<K: Into<Arc<str>>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        let fields = match self.current_block() {
+            Block::Block(fields) => fields,
+            Block::Array(arr) => {
+                if arr.is_empty() {
+                    arr.push(HashMap::new());
+                }
+                arr.last_mut().unwrap()
+            }
+        };
+        fields.insert(name.into(), value.into());
+        self
+    }
+
+    /// 현재 블록에 `YYYYMMDD` 형식의 날짜 필드를 추가합니다.
+    ///
+    /// 블록을 추가하지 않은 경우 패닉이 발생합니다.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    pub fn date_field<K: Into<Arc<str>>>(self, name: K, date: chrono::NaiveDate) -> Self {
+        self.field(name, super::date::Date::from(date).to_string())
+    }
+
+    /// 현재 블록에 시각 필드를 추가합니다.
+    ///
+    /// [`build()`](Self::build)에서 레이아웃의 필드 길이가 8바이트이면
+    /// `HHMMSSmm` 형식으로, 아니면 `HHMMSS` 형식으로 변환합니다. 블록을
+    /// 추가하지 않은 경우 패닉이 발생합니다.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    pub fn time_field<K: Into<Arc<str>>>(mut self, name: K, time: chrono::NaiveTime) -> Self {
+        let name = name.into();
+        self = self.field(name.clone(), super::date::Time::from(time).to_string());
+
+        let block = self.current.clone().unwrap();
+        let row = self.current_block().rows().len().saturating_sub(1);
+        self.times.push((block, row, name, time));
+        self
+    }
+
+    /// 레이아웃과 비교하여 검사한 후 데이터를 반환합니다.
+    ///
+    /// TR 코드, 블록 및 필드의 이름, 블록 타입이 레이아웃과 일치해야 하며
+    /// 레이아웃의 모든 필드가 있어야 합니다.
+    pub fn build(self, tr_layout: &TrLayout) -> Result<Data, EncodeError> {
+        #[allow(unused_mut)]
+        let mut data = self.data;
+
+        if data.tr_code != tr_layout.code {
+            return Err(EncodeError::MismatchLayout);
+        }
+
+        let block_layouts = match data.data_type {
+            DataType::Input => &tr_layout.in_blocks,
+            DataType::Output => &tr_layout.out_blocks,
+        };
+
+        for (name, block) in &data.blocks {
+            let block_layout = block_layouts
+                .iter()
+                .find(|b| &b.name == name)
+                .ok_or_else(|| EncodeError::UnknownBlock {
+                    block: name.clone(),
+                })?;
+
+            if block_layout.occurs != block.is_array() {
+                return Err(EncodeError::MismatchBlockType {
+                    block: name.clone(),
+                });
+            }
+
+            for fields in block.rows() {
+                for field in fields.keys() {
+                    if block_layout.field(field).is_none() {
+                        return Err(EncodeError::UnknownField {
+                            block: name.clone(),
+                            field: field.to_string(),
+                        });
+                    }
+                }
+
+                for field_layout in &block_layout.fields {
+                    if !fields.contains_key(&field_layout.name)
+                        && !fields.contains_key(field_layout.name_old.as_str())
+                    {
+                        return Err(EncodeError::MissingField {
+                            block: name.clone(),
+                            field: field_layout.name.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(block_layout) = block_layouts
+            .iter()
+            .find(|b| !data.blocks.contains_key(&b.name))
+        {
+            return Err(EncodeError::MissingBlock {
+                block: block_layout.name.clone(),
+            });
+        }
+
+        #[cfg(feature = "chrono")]
+        for (block, row, field, time) in self.times {
+            let len = block_layouts
+                .iter()
+                .find(|b| b.name == block)
+                .and_then(|b| b.field(&field))
+                .map(|f| f.len);
+            let fields = match data.blocks.get_mut(&block) {
+                Some(Block::Block(fields)) => Some(fields),
+                Some(Block::Array(arr)) => arr.get_mut(row),
+                None => None,
+            };
+
+            if let (Some(fields), Some(8)) = (fields, len) {
+                fields.insert(field, format!("{:#}", super::date::Time::from(time)));
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn current_block(&mut self) -> &mut Block {
+        let name = self
+            .current
+            .as_ref()
+            .expect("block() or array() must be called first");
+        self.data.blocks.get_mut(name).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{Block, Data, EncodeError};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+
+    fn tr_layout() -> TrLayout {
+        "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000InBlock,입력,input;
+                begin
+                    코드,code,code,char,6;
+                end
+                t0000InBlock1,입력1,input,occurs;
+                begin
+                    가격,price,price,long,8;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let tr_layout = tr_layout();
+
+        let data = Data::builder("t0000")
+            .block("t0000InBlock")
+            .field("code", "096530")
+            .array("t0000InBlock1")
+            .field("price", "100")
+            .row()
+            .field("price", "200")
+            .build(&tr_layout)
+            .unwrap();
+
+        assert_eq!(
+            data.blocks["t0000InBlock1"],
+            Block::Array(vec![
+                hashmap! { "price" => "100" },
+                hashmap! { "price" => "200" },
+            ])
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_build_chrono() {
+        use chrono::{NaiveDate, NaiveTime};
+
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000InBlock,입력,input;
+                begin
+                    일자,date,date,date,8;
+                    시각,time,time,char,6;
+                end
+                t0000InBlock1,입력1,input,occurs;
+                begin
+                    시각,time,time,char,8;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let time = NaiveTime::from_hms_milli_opt(9, 0, 15, 230).unwrap();
+        let data = Data::builder("t0000")
+            .block("t0000InBlock")
+            .date_field("date", NaiveDate::from_ymd_opt(2021, 1, 11).unwrap())
+            .time_field("time", time)
+            .array("t0000InBlock1")
+            .time_field("time", time)
+            .row()
+            .time_field("time", NaiveTime::from_hms_opt(15, 30, 0).unwrap())
+            .build(&tr_layout)
+            .unwrap();
+
+        assert_eq!(
+            data.blocks["t0000InBlock"],
+            Block::Block(hashmap! { "date" => "20210111", "time" => "090015" })
+        );
+        assert_eq!(
+            data.blocks["t0000InBlock1"],
+            Block::Array(vec![
+                hashmap! { "time" => "09001523" },
+                hashmap! { "time" => "15300000" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_build_error() {
+        let tr_layout = tr_layout();
+
+        let result = Data::builder("t0000")
+            .block("t0000InBlock")
+            .field("cdoe", "096530")
+            .array("t0000InBlock1")
+            .build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::UnknownField { field, .. }) if field == "cdoe"));
+
+        let result = Data::builder("t0000")
+            .block("t0000InBlock2")
+            .build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::UnknownBlock { .. })));
+
+        let result = Data::builder("t0000")
+            .array("t0000InBlock")
+            .array("t0000InBlock1")
+            .build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::MismatchBlockType { .. })));
+
+        let result = Data::builder("t0000")
+            .array("t0000InBlock1")
+            .build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::MissingBlock { .. })));
+
+        let result = Data::builder("t0000")
+            .block("t0000InBlock")
+            .field("code", "096530")
+            .array("t0000InBlock1")
+            .row()
+            .build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::MissingField { .. })));
+
+        let result = Data::builder("t1101").output().build(&tr_layout);
+        assert!(matches!(result, Err(EncodeError::MismatchLayout)));
+    }
+}
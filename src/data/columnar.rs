@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::date::Date;
+use super::{decode_policy, decode_str, encoding, Block, Data, DecodeError, DecodePolicy, RawData};
+use crate::layout::{BlockLayout, FieldType, TrLayout};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 필드 이름을 키로 하는 열 단위 블록
+///
+/// 각 열의 길이는 블록의 행 개수와 같습니다.
+pub type Columns = HashMap<Arc<str>, Vec<String>>;
+
+// 블록을 행마다 필드 테이블을 만들지 않고 열 단위로 디코딩합니다.
+//
+// 단일 블록은 길이가 1인 열로 디코딩합니다.
+pub(crate) fn decode_columns(
+    tr_layout: &TrLayout,
+    raw_data: &RawData,
+    block_name: &str,
+) -> Result<Columns, DecodeError> {
+    let block_layout = tr_layout
+        .out_block(block_name)
+        .ok_or_else(|| DecodeError::UnknownBlock(block_name.to_owned()))?;
+
+    let (raw_block, rows) = match raw_data {
+        RawData::Block(raw_block_tbl) => {
+            let raw_block = raw_block_tbl
+                .get(block_name)
+                .ok_or_else(|| DecodeError::MissingBlock(block_name.to_owned()))?;
+
+            let rows = match (
+                block_layout.occurs,
+                block_layout.occurs_count,
+                block_layout.len,
+            ) {
+                (false, _, len) if raw_block.len() == len => 1,
+                (true, Some(count), len) if len.checked_mul(count) == Some(raw_block.len()) => {
+                    count
+                }
+                (true, None, 0) if raw_block.is_empty() => 0,
+                (true, None, len) if len != 0 && raw_block.len() % len == 0 => {
+                    raw_block.len() / len
+                }
+                _ => return Err(DecodeError::MismatchDataLength),
+            };
+
+            (raw_block.as_slice(), rows)
+        }
+        RawData::NonBlock(raw_data) => locate_block(tr_layout, block_layout, raw_data)?,
+    };
+
+    let mut columns: Vec<Vec<String>> = block_layout
+        .fields
+        .iter()
+        .map(|_| Vec::with_capacity(rows))
+        .collect();
+
+    let attr_len = if tr_layout.attr_byte { 1 } else { 0 };
+    let mut offset = 0;
+
+    for _ in 0..rows {
+        for (field_layout, column) in block_layout.fields.iter().zip(&mut columns) {
+            column.push(
+                match decode_str(&raw_block[offset..offset + field_layout.len]) {
+                    Err(DecodeError::MalformedString)
+                        if decode_policy() == DecodePolicy::SkipField =>
+                    {
+                        String::new()
+                    }
+                    result => result?,
+                },
+            );
+            offset += field_layout.len + attr_len;
+        }
+    }
+
+    Ok(block_layout
+        .fields
+        .iter()
+        .map(|field_layout| field_layout.name.clone())
+        .zip(columns)
+        .collect())
+}
+
+// non-block mode인 데이터에서 블록의 위치와 행 개수를 찾습니다.
+fn locate_block<'a>(
+    tr_layout: &TrLayout,
+    target: &BlockLayout,
+    raw_data: &'a [u8],
+) -> Result<(&'a [u8], usize), DecodeError> {
+    let mut offset = 0;
+
+    for block_layout in &tr_layout.out_blocks {
+        let rows = if let Some(count) = block_layout.occurs_count {
+            count
+        } else if block_layout.occurs {
+            let raw_len = raw_data
+                .get(offset..offset + 5)
+                .ok_or(DecodeError::MismatchDataLength)?;
+
+            offset += 5;
+
+            encoding()
+                .decode(raw_len)
+                .and_then(|len| len.parse().ok())
+                .ok_or(DecodeError::InvalidArrayLength)?
+        } else {
+            1
+        };
+
+        let end = block_layout
+            .len
+            .checked_mul(rows)
+            .and_then(|len| len.checked_add(offset))
+            .filter(|end| *end <= raw_data.len())
+            .ok_or(DecodeError::MismatchDataLength)?;
+
+        if std::ptr::eq(block_layout, target) {
+            return Ok((&raw_data[offset..end], rows));
+        }
+
+        offset = end;
+    }
+
+    Err(DecodeError::UnknownBlock(target.name.clone()))
+}
+
+// 이미 디코딩된 데이터의 블록을 열 단위로 변환합니다.
+//
+// 레이아웃 없이 필드 이름으로 변환하므로 빈 배열 블록은 열이 없습니다.
+pub(crate) fn to_columns(data: &Data, block_name: &str) -> Result<Columns, DecodeError> {
+    let rows = data
+        .blocks
+        .get(block_name)
+        .ok_or_else(|| DecodeError::MissingBlock(block_name.to_owned()))?
+        .rows();
+
+    let mut columns = Columns::new();
+
+    for fields in rows {
+        for (name, value) in fields {
+            columns
+                .entry(name.clone())
+                .or_insert_with(|| Vec::with_capacity(rows.len()))
+                .push(value.clone());
+        }
+    }
+
+    Ok(columns)
+}
+
+/// 필드 타입에 따라 값을 변환한 열
+///
+/// Arrow의 `Utf8`, `Int64`, `Float64`, `Date32` 타입과 대응합니다. `arrow`
+/// 기능을 사용하면 `Block::to_record_batch()`로 Arrow `RecordBatch`를 바로
+/// 만들 수 있습니다. 빈 값은 `None`입니다.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedColumn {
+    /// 문자열 ([`FieldType::Char`], [`FieldType::Unknown`])
+    Utf8(Vec<Option<String>>),
+    /// 정수 ([`FieldType::Int`])
+    Int64(Vec<Option<i64>>),
+    /// 실수 ([`FieldType::Float`], [`FieldType::Double`])
+    Float64(Vec<Option<f64>>),
+    /// 1970-01-01부터의 일수 ([`FieldType::Date`])
+    Date32(Vec<Option<i32>>),
+}
+
+impl TypedColumn {
+    /// 열의 길이를 반환합니다.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Utf8(v) => v.len(),
+            Self::Int64(v) => v.len(),
+            Self::Float64(v) => v.len(),
+            Self::Date32(v) => v.len(),
+        }
+    }
+
+    /// 열이 비어 있는지 여부를 반환합니다.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Block {
+    /// 블록을 레이아웃의 필드 순서대로 필드 타입에 따라 변환한 열로
+    /// 반환합니다.
+    ///
+    /// 단일 블록은 길이가 1인 열로 반환합니다. 날짜 필드는 `YYYYMMDD`
+    /// 형식이어야 하며 `00000000`은 빈 값으로 취급합니다.
+    pub fn to_typed_columns(
+        &self,
+        block_layout: &BlockLayout,
+    ) -> Result<Vec<(Arc<str>, TypedColumn)>, DecodeError> {
+        let rows = self.rows();
+
+        block_layout
+            .fields
+            .iter()
+            .map(|field_layout| {
+                let invalid = || DecodeError::InvalidField {
+                    block: block_layout.name.clone(),
+                    field: field_layout.name.to_string(),
+                };
+
+                let values = rows.iter().map(|fields| {
+                    fields
+                        .get(&field_layout.name)
+                        .map(|value| Some(value.trim()).filter(|v| !v.is_empty()))
+                        .ok_or_else(|| DecodeError::MissingField {
+                            block: block_layout.name.clone(),
+                            field: field_layout.name.to_string(),
+                        })
+                });
+
+                let column = match field_layout.field_type {
+                    FieldType::Char | FieldType::Unknown(_) => TypedColumn::Utf8(
+                        values
+                            .map(|v| v.map(|v| v.map(str::to_owned)))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    FieldType::Int => TypedColumn::Int64(
+                        values
+                            .map(|v| v?.map(|v| v.parse().map_err(|_| invalid())).transpose())
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    FieldType::Float | FieldType::Double => TypedColumn::Float64(
+                        values
+                            .map(|v| v?.map(|v| v.parse().map_err(|_| invalid())).transpose())
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    FieldType::Date => TypedColumn::Date32(
+                        values
+                            .map(|v| match v? {
+                                None | Some("00000000") => Ok(None),
+                                Some(v) => parse_date32(v).map(Some).ok_or_else(invalid),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                };
+
+                Ok((field_layout.name.clone(), column))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "arrow")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "arrow")))]
+impl Block {
+    /// 블록을 Arrow `RecordBatch`로 변환합니다.
+    ///
+    /// [`to_typed_columns()`](Self::to_typed_columns)와 같은 규칙으로 필드
+    /// 타입을 Arrow의 `Utf8`, `Int64`, `Float64`, `Date32` 타입으로 변환하며,
+    /// 모든 열은 빈 값을 허용합니다.
+    pub fn to_record_batch(
+        &self,
+        block_layout: &BlockLayout,
+    ) -> Result<arrow_array::RecordBatch, DecodeError> {
+        use arrow_array::{ArrayRef, Date32Array, Float64Array, Int64Array, StringArray};
+        use arrow_array::{RecordBatch, RecordBatchOptions};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let (fields, arrays): (Vec<_>, Vec<_>) = self
+            .to_typed_columns(block_layout)?
+            .into_iter()
+            .map(|(name, column)| {
+                let (data_type, array): (_, ArrayRef) = match column {
+                    TypedColumn::Utf8(v) => (DataType::Utf8, Arc::new(StringArray::from(v))),
+                    TypedColumn::Int64(v) => (DataType::Int64, Arc::new(Int64Array::from(v))),
+                    TypedColumn::Float64(v) => (DataType::Float64, Arc::new(Float64Array::from(v))),
+                    TypedColumn::Date32(v) => (DataType::Date32, Arc::new(Date32Array::from(v))),
+                };
+                (Field::new(&*name, data_type, true), array)
+            })
+            .unzip();
+
+        // 열의 타입과 길이가 스키마와 일치하므로 실패하지 않습니다.
+        let options = RecordBatchOptions::new().with_row_count(Some(self.rows().len()));
+        Ok(
+            RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+                .unwrap(),
+        )
+    }
+}
+
+// `YYYYMMDD` 형식의 날짜를 1970-01-01부터의 일수로 변환합니다.
+fn parse_date32(text: &str) -> Option<i32> {
+    text.parse::<Date>().ok()?.days().try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_columns, parse_date32, to_columns, TypedColumn};
+    use crate::data::Block;
+    use crate::data::{decode, RawData};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_decode_columns() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000OutBlock,출력,output;
+                begin
+                    코드,code,code,char,6;
+                end
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    날짜,date,date,char,8;
+                    가격,price,price,long,3;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let raw_data = RawData::NonBlock(b"09653000002202101111002021011220 ".to_vec());
+
+        let columns = decode_columns(&tr_layout, &raw_data, "t0000OutBlock1").unwrap();
+        assert_eq!(columns["date"], ["20210111", "20210112"]);
+        assert_eq!(columns["price"], ["100", "20"]);
+        assert_eq!(
+            columns,
+            to_columns(
+                &decode(&tr_layout, raw_data.clone()).unwrap(),
+                "t0000OutBlock1"
+            )
+            .unwrap()
+        );
+
+        let columns = decode_columns(&tr_layout, &raw_data, "t0000OutBlock").unwrap();
+        assert_eq!(columns["code"], ["096530"]);
+
+        assert!(decode_columns(&tr_layout, &raw_data, "t0000OutBlock2").is_err());
+        assert!(decode_columns(
+            &tr_layout,
+            &RawData::NonBlock(b"0965300000".to_vec()),
+            "t0000OutBlock1"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_typed_columns() {
+        assert_eq!(parse_date32("19700101"), Some(0));
+        assert_eq!(parse_date32("20210111"), Some(18638));
+        assert_eq!(parse_date32("19691231"), Some(-1));
+        assert_eq!(parse_date32("20211301"), None);
+
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    날짜,date,date,date,8;
+                    이름,name,name,char,10;
+                    가격,price,price,long,8;
+                    비율,rate,rate,float,6.2;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let block = Block::Array(vec![
+            hashmap! { "date" => "20210111", "name" => "삼성", "price" => "-100", "rate" => "1.5" },
+            hashmap! { "date" => "00000000", "name" => "", "price" => "", "rate" => "-0.25" },
+        ]);
+
+        let columns = block.to_typed_columns(&tr_layout.out_blocks[0]).unwrap();
+        assert_eq!(
+            columns,
+            [
+                ("date".into(), TypedColumn::Date32(vec![Some(18638), None])),
+                (
+                    "name".into(),
+                    TypedColumn::Utf8(vec![Some("삼성".into()), None])
+                ),
+                ("price".into(), TypedColumn::Int64(vec![Some(-100), None])),
+                (
+                    "rate".into(),
+                    TypedColumn::Float64(vec![Some(1.5), Some(-0.25)])
+                ),
+            ]
+        );
+
+        let block =
+            Block::Block(hashmap! { "date" => "2021", "name" => "", "price" => "", "rate" => "" });
+        assert!(block.to_typed_columns(&tr_layout.out_blocks[0]).is_err());
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_record_batch() {
+        use arrow_array::{Array, Date32Array, Int64Array, StringArray};
+        use arrow_schema::DataType;
+
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    날짜,date,date,date,8;
+                    이름,name,name,char,10;
+                    가격,price,price,long,8;
+                    비율,rate,rate,float,6.2;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let block = Block::Array(vec![
+            hashmap! { "date" => "20210111", "name" => "삼성", "price" => "-100", "rate" => "1.5" },
+            hashmap! { "date" => "00000000", "name" => "", "price" => "", "rate" => "-0.25" },
+        ]);
+
+        let batch = block.to_record_batch(&tr_layout.out_blocks[0]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let schema = batch.schema();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(
+            types,
+            [
+                &DataType::Date32,
+                &DataType::Utf8,
+                &DataType::Int64,
+                &DataType::Float64
+            ]
+        );
+
+        let dates = batch.column(0).as_any().downcast_ref::<Date32Array>();
+        assert_eq!(dates.unwrap(), &Date32Array::from(vec![Some(18638), None]));
+        let names = batch.column(1).as_any().downcast_ref::<StringArray>();
+        assert_eq!(names.unwrap().value(0), "삼성");
+        assert!(names.unwrap().is_null(1));
+        let prices = batch.column(2).as_any().downcast_ref::<Int64Array>();
+        assert_eq!(prices.unwrap(), &Int64Array::from(vec![Some(-100), None]));
+
+        let batch = Block::Array(vec![])
+            .to_record_batch(&tr_layout.out_blocks[0])
+            .unwrap();
+        assert_eq!((batch.num_rows(), batch.num_columns()), (0, 4));
+    }
+}
@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use std::str::FromStr;
+
+// `YYYYMMDD` 형식의 날짜 필드 값
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    // 1970년 1월 1일로부터 경과한 일수로 날짜를 생성합니다.
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    // 1970년 1월 1일로부터 경과한 일수를 반환합니다.
+    pub fn days(self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146097 + doe - 719468
+    }
+}
+
+impl FromStr for Date {
+    type Err = ();
+
+    // `YYYYMMDD` 형식의 문자열을 파싱합니다.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.len() != 8 || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+
+        let date = Self {
+            year: text[0..4].parse().unwrap(),
+            month: text[4..6].parse().unwrap(),
+            day: text[6..8].parse().unwrap(),
+        };
+
+        // 존재하지 않는 날짜는 일수로 변환했다가 되돌리면 달라집니다.
+        if !(1..=12).contains(&date.month) || Self::from_days(date.days()) != date {
+            return Err(());
+        }
+
+        Ok(date)
+    }
+}
+
+impl std::fmt::Display for Date {
+    // `YYYYMMDD` 형식으로 출력합니다.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+// `HHMMSS`나 1/100초를 포함한 `HHMMSSmm` 형식의 시각 필드 값
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Time {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub centisecond: u32,
+}
+
+impl Time {
+    // 자정으로부터 경과한 밀리초를 반환합니다.
+    pub fn millis(self) -> u32 {
+        ((self.hour * 60 + self.minute) * 60 + self.second) * 1000 + self.centisecond * 10
+    }
+}
+
+impl FromStr for Time {
+    type Err = ();
+
+    // `HHMMSS`나 `HHMMSSmm` 형식의 문자열을 파싱합니다.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if !matches!(text.len(), 6 | 8) || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+
+        let time = Self {
+            hour: text[0..2].parse().unwrap(),
+            minute: text[2..4].parse().unwrap(),
+            second: text[4..6].parse().unwrap(),
+            centisecond: text.get(6..8).map_or(0, |s| s.parse().unwrap()),
+        };
+
+        if time.hour > 23 || time.minute > 59 || time.second > 59 {
+            return Err(());
+        }
+
+        Ok(time)
+    }
+}
+
+impl std::fmt::Display for Time {
+    // `HHMMSS` 형식으로 출력합니다. `{:#}`인 경우 `HHMMSSmm` 형식으로
+    // 출력합니다.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}{:02}{:02}", self.hour, self.minute, self.second)?;
+        if f.alternate() {
+            write!(f, "{:02}", self.centisecond)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Date {
+    fn from(date: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        Self {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Date> for chrono::NaiveDate {
+    fn from(date: Date) -> Self {
+        // 파싱할 때 존재하는 날짜인지 확인합니다.
+        chrono::NaiveDate::from_ymd_opt(date.year, date.month, date.day).unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for Time {
+    fn from(time: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+
+        // 윤초는 나노초가 10억 이상이므로 99로 제한합니다.
+        Self {
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+            centisecond: (time.nanosecond() / 10_000_000).min(99),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Time> for chrono::NaiveTime {
+    fn from(time: Time) -> Self {
+        chrono::NaiveTime::from_hms_milli_opt(
+            time.hour,
+            time.minute,
+            time.second,
+            time.centisecond * 10,
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, Time};
+
+    #[test]
+    fn test_date() {
+        let date: Date = "20210111".parse().unwrap();
+        assert_eq!(
+            date,
+            Date {
+                year: 2021,
+                month: 1,
+                day: 11
+            }
+        );
+        assert_eq!(date.days(), 18638);
+        assert_eq!(date.to_string(), "20210111");
+
+        for days in [-1, 0, 59, 18628, 19417, 40000] {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+
+        assert!("20210229".parse::<Date>().is_err());
+        assert!("20201301".parse::<Date>().is_err());
+        assert!("00000000".parse::<Date>().is_err());
+        assert!("2021011".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        let time: Time = "09001523".parse().unwrap();
+        assert_eq!(time.millis(), (9 * 3600 + 15) * 1000 + 230);
+        assert_eq!(time.to_string(), "090015");
+        assert_eq!(format!("{:#}", time), "09001523");
+        assert_eq!("153000".parse::<Time>().unwrap().centisecond, 0);
+        assert!("246000".parse::<Time>().is_err());
+        assert!("0900".parse::<Time>().is_err());
+    }
+}
@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{Block, Data};
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// 두 데이터 사이의 변경 사항
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Change {
+    /// TR 코드 또는 데이터 종류가 다릅니다.
+    Header,
+    /// 블록이 추가되었습니다.
+    AddedBlock { block: String },
+    /// 블록이 제거되었습니다.
+    RemovedBlock { block: String },
+    /// 블록 타입이 다릅니다.
+    BlockType { block: String },
+    /// 배열 블록에 행이 추가되었습니다.
+    AddedRow { block: String, row: usize },
+    /// 배열 블록에서 행이 제거되었습니다.
+    RemovedRow { block: String, row: usize },
+    /// 필드가 추가되었습니다.
+    AddedField {
+        block: String,
+        row: Option<usize>,
+        field: String,
+        value: String,
+    },
+    /// 필드가 제거되었습니다.
+    RemovedField {
+        block: String,
+        row: Option<usize>,
+        field: String,
+        value: String,
+    },
+    /// 필드의 값이 변경되었습니다.
+    ChangedField {
+        block: String,
+        row: Option<usize>,
+        field: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        struct Path<'a>(&'a str, &'a Option<usize>, &'a str);
+
+        impl std::fmt::Display for Path<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.1 {
+                    Some(row) => write!(f, "{}[{}].{}", self.0, row, self.2),
+                    None => write!(f, "{}.{}", self.0, self.2),
+                }
+            }
+        }
+
+        match self {
+            Self::Header => "mismatch header".fmt(f),
+            Self::AddedBlock { block } => write!(f, "+ {}", block),
+            Self::RemovedBlock { block } => write!(f, "- {}", block),
+            Self::BlockType { block } => write!(f, "mismatch type of {} block", block),
+            Self::AddedRow { block, row } => write!(f, "+ {}[{}]", block, row),
+            Self::RemovedRow { block, row } => write!(f, "- {}[{}]", block, row),
+            Self::AddedField {
+                block,
+                row,
+                field,
+                value,
+            } => write!(f, "+ {}: {:?}", Path(block, row, field), value),
+            Self::RemovedField {
+                block,
+                row,
+                field,
+                value,
+            } => write!(f, "- {}: {:?}", Path(block, row, field), value),
+            Self::ChangedField {
+                block,
+                row,
+                field,
+                old,
+                new,
+            } => write!(f, "~ {}: {:?} -> {:?}", Path(block, row, field), old, new),
+        }
+    }
+}
+
+/// [`diff()`]가 반환하는 변경 사항 목록
+///
+/// 변경 사항은 블록과 필드 이름 순으로 정렬됩니다.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataDiff {
+    /// 변경 사항
+    pub changes: Vec<Change>,
+}
+
+impl DataDiff {
+    /// 변경 사항이 없는지 여부를 반환합니다.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// 변경 사항의 개수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// 변경 사항을 순회합니다.
+    pub fn iter(&self) -> std::slice::Iter<'_, Change> {
+        self.changes.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a DataDiff {
+    type Item = &'a Change;
+    type IntoIter = std::slice::Iter<'a, Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Display for DataDiff {
+    /// 변경 사항을 한 줄에 하나씩 출력합니다.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// 두 데이터를 비교하여 변경 사항을 반환합니다.
+///
+/// 배열 블록은 같은 위치의 행끼리 비교합니다. 연속으로 조회한 응답이나
+/// 재현한 응답을 기록된 응답과 비교할 때 사용할 수 있습니다.
+pub fn diff(old: &Data, new: &Data) -> DataDiff {
+    let mut changes = Vec::new();
+
+    if old.tr_code != new.tr_code || old.data_type != new.data_type {
+        changes.push(Change::Header);
+    }
+
+    let names: BTreeSet<_> = old.blocks.keys().chain(new.blocks.keys()).collect();
+
+    for name in names {
+        let block = || name.clone();
+
+        match (old.blocks.get(name), new.blocks.get(name)) {
+            (Some(_), None) => changes.push(Change::RemovedBlock { block: block() }),
+            (None, Some(_)) => changes.push(Change::AddedBlock { block: block() }),
+            (Some(Block::Block(old)), Some(Block::Block(new))) => {
+                diff_fields(name, None, old, new, &mut changes);
+            }
+            (Some(Block::Array(old)), Some(Block::Array(new))) => {
+                for (row, (old, new)) in old.iter().zip(new).enumerate() {
+                    diff_fields(name, Some(row), old, new, &mut changes);
+                }
+                for row in new.len()..old.len() {
+                    changes.push(Change::RemovedRow {
+                        block: block(),
+                        row,
+                    });
+                }
+                for row in old.len()..new.len() {
+                    changes.push(Change::AddedRow {
+                        block: block(),
+                        row,
+                    });
+                }
+            }
+            (Some(_), Some(_)) => changes.push(Change::BlockType { block: block() }),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    DataDiff { changes }
+}
+
+fn diff_fields(
+    block: &str,
+    row: Option<usize>,
+    old: &HashMap<Arc<str>, String>,
+    new: &HashMap<Arc<str>, String>,
+    changes: &mut Vec<Change>,
+) {
+    let names: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+
+    for name in names {
+        let block = block.to_owned();
+        let field = name.to_string();
+
+        match (old.get(name), new.get(name)) {
+            (Some(old), Some(new)) if old != new => changes.push(Change::ChangedField {
+                block,
+                row,
+                field,
+                old: old.clone(),
+                new: new.clone(),
+            }),
+            (Some(value), None) => changes.push(Change::RemovedField {
+                block,
+                row,
+                field,
+                value: value.clone(),
+            }),
+            (None, Some(value)) => changes.push(Change::AddedField {
+                block,
+                row,
+                field,
+                value: value.clone(),
+            }),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Change};
+    use crate::data;
+
+    #[test]
+    fn test_diff() {
+        let old = data!("t0424", output {
+            t0424OutBlock { sunamt: "1000", dtsunik: "0" },
+            t0424OutBlock1 [
+                { expcode: "096530", janqty: "10" },
+                { expcode: "005930", janqty: "5" },
+            ],
+            t0424OutBlock2 { },
+        });
+        let new = data!("t0424", output {
+            t0424OutBlock { sunamt: "1200", dtsunik: "0", mamt: "0" },
+            t0424OutBlock1 [
+                { expcode: "096530", janqty: "8" },
+            ],
+            t0424OutBlock3 [ ],
+        });
+
+        let result = diff(&old, &new);
+        assert_eq!(
+            result.to_string(),
+            concat!(
+                "+ t0424OutBlock.mamt: \"0\"\n",
+                "~ t0424OutBlock.sunamt: \"1000\" -> \"1200\"\n",
+                "~ t0424OutBlock1[0].janqty: \"10\" -> \"8\"\n",
+                "- t0424OutBlock1[1]\n",
+                "- t0424OutBlock2\n",
+                "+ t0424OutBlock3\n",
+            )
+        );
+        assert_eq!(
+            result.changes[3],
+            Change::RemovedRow {
+                block: "t0424OutBlock1".into(),
+                row: 1,
+            }
+        );
+
+        assert!(diff(&old, &old).is_empty());
+        assert_eq!(diff(&old, &data!("t0425", output {})).len(), 4);
+    }
+}
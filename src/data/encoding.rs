@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use encoding_rs::{Encoder, EUC_KR, UTF_8};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+static ENCODING: OnceLock<Encoding> = OnceLock::new();
+static DECODE_POLICY: AtomicU8 = AtomicU8::new(DecodePolicy::Strict as u8);
+
+/// XingAPI가 반환하는 문자열의 인코딩
+///
+/// 기본값은 EUC-KR입니다. [`set_encoding()`]으로 UTF-8을 지정하면 DLL을
+/// 불러올 때 DLL의 출력도 UTF-8로 변경합니다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// EUC-KR
+    #[default]
+    EucKr,
+    /// UTF-8
+    Utf8,
+}
+
+impl Encoding {
+    /// 잘못된 바이트가 있는 경우 `None`을 반환합니다.
+    pub(crate) fn decode(self, data: &[u8]) -> Option<Cow<'_, str>> {
+        match self {
+            Self::EucKr => EUC_KR.decode_without_bom_handling_and_without_replacement(data),
+            Self::Utf8 => std::str::from_utf8(data).ok().map(Cow::Borrowed),
+        }
+    }
+
+    /// 잘못된 바이트는 대체 문자로 치환합니다.
+    pub(crate) fn decode_lossy(self, data: &[u8]) -> Cow<'_, str> {
+        match self {
+            Self::EucKr => EUC_KR.decode_without_bom_handling(data).0,
+            Self::Utf8 => UTF_8.decode_without_bom_handling(data).0,
+        }
+    }
+
+    /// 인코딩할 수 없는 문자는 HTML 숫자 참조로 치환하며, 치환 여부를 함께
+    /// 반환합니다.
+    pub(crate) fn encode(self, text: &str) -> (Cow<'_, [u8]>, bool) {
+        let (encoded, _, had_errors) = self.as_encoding_rs().encode(text);
+        (encoded, had_errors)
+    }
+
+    /// 버퍼에 직접 인코딩하기 위한 인코더를 생성합니다.
+    pub(crate) fn new_encoder(self) -> Encoder {
+        self.as_encoding_rs().new_encoder()
+    }
+
+    fn as_encoding_rs(self) -> &'static encoding_rs::Encoding {
+        match self {
+            Self::EucKr => EUC_KR,
+            Self::Utf8 => UTF_8,
+        }
+    }
+}
+
+/// 크레이트 전체에서 사용할 문자열 인코딩을 지정합니다.
+///
+/// DLL을 불러오기 전에 한 번만 지정할 수 있습니다. 이미 다른 인코딩이
+/// 지정된 경우 지정된 인코딩을 에러로 반환합니다.
+pub fn set_encoding(encoding: Encoding) -> Result<(), Encoding> {
+    match ENCODING.set(encoding) {
+        Err(_) if ENCODING.get() != Some(&encoding) => Err(*ENCODING.get().unwrap()),
+        _ => Ok(()),
+    }
+}
+
+/// 크레이트 전체에서 사용하는 문자열 인코딩을 반환합니다.
+pub fn encoding() -> Encoding {
+    ENCODING.get().copied().unwrap_or_default()
+}
+
+// DLL을 불러올 때 인코딩을 확정하여 반환합니다.
+//
+// 이후에는 DLL의 출력과 다른 인코딩을 지정할 수 없습니다.
+#[cfg(windows)]
+pub(crate) fn lock_encoding() -> Encoding {
+    *ENCODING.get_or_init(Encoding::default)
+}
+
+/// 디코딩할 수 없는 문자열 필드를 처리하는 방식
+///
+/// 실시간 응답과 같이 일부 필드가 잘못되어도 나머지 필드를 사용해야 하는
+/// 경우 [`set_decode_policy()`]로 변경합니다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum DecodePolicy {
+    /// [`DecodeError::MalformedString`](super::DecodeError::MalformedString)을
+    /// 반환합니다.
+    #[default]
+    Strict,
+    /// 잘못된 바이트를 대체 문자(U+FFFD)로 치환합니다.
+    Replace,
+    /// 필드를 블록에서 제외합니다. 열 단위 디코딩에서는 빈 문자열로
+    /// 디코딩합니다.
+    SkipField,
+}
+
+/// 디코딩할 수 없는 문자열 필드를 처리하는 방식을 지정합니다.
+pub fn set_decode_policy(policy: DecodePolicy) {
+    DECODE_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// 디코딩할 수 없는 문자열 필드를 처리하는 방식을 반환합니다.
+pub fn decode_policy() -> DecodePolicy {
+    match DECODE_POLICY.load(Ordering::Relaxed) {
+        1 => DecodePolicy::Replace,
+        2 => DecodePolicy::SkipField,
+        _ => DecodePolicy::Strict,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodePolicy, Encoding};
+    use crate::data::{decode_cow_with, DecodeError};
+
+    #[test]
+    fn test_decode() {
+        let euckr = [0xc7, 0xd1, 0xb1, 0xdb];
+        assert_eq!(Encoding::EucKr.decode(&euckr).unwrap(), "한글");
+        assert!(Encoding::Utf8.decode(&euckr).is_none());
+        assert!(Encoding::Utf8.decode_lossy(&euckr).contains('\u{fffd}'));
+
+        let utf8 = "한글".as_bytes();
+        assert_eq!(Encoding::Utf8.decode(utf8).unwrap(), "한글");
+        assert_eq!(Encoding::EucKr.decode_lossy(b"abc"), "abc");
+    }
+
+    #[test]
+    fn test_decode_policy() {
+        let data = [b' ', b'a', 0xc7, b' '];
+        assert!(matches!(
+            decode_cow_with(&data, DecodePolicy::Strict),
+            Err(DecodeError::MalformedString)
+        ));
+        assert_eq!(
+            decode_cow_with(&data, DecodePolicy::Replace).unwrap(),
+            "a\u{fffd}"
+        );
+        assert!(matches!(
+            decode_cow_with(&data, DecodePolicy::SkipField),
+            Err(DecodeError::MalformedString)
+        ));
+    }
+}
@@ -0,0 +1,510 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// 데이터를 JSON 문자열로 변환하거나 JSON 문자열을 파싱합니다.
+//
+// `serde` 기능 없이도 다른 언어나 프로세스와 데이터를 주고받을 수 있도록
+// 사용합니다. 객체의 키는 항상 정렬된 순서로 출력됩니다.
+
+use super::{Block, Data, DataType, EncodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+// 데이터를 JSON 객체로 변환합니다.
+pub(crate) fn to_json(data: &Data) -> String {
+    let mut out = String::new();
+    write_data(&mut out, data);
+    out
+}
+
+pub(crate) fn write_data(out: &mut String, data: &Data) {
+    out.push_str("{\"tr_code\":");
+    write_str(out, &data.tr_code);
+    out.push_str(",\"data_type\":");
+    write_str(
+        out,
+        match data.data_type {
+            DataType::Input => "input",
+            DataType::Output => "output",
+        },
+    );
+    out.push_str(",\"blocks\":{");
+
+    let mut blocks: Vec<_> = data.blocks.iter().collect();
+    blocks.sort_unstable_by_key(|(name, _)| *name);
+
+    for (i, (name, block)) in blocks.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_str(out, name);
+        out.push(':');
+
+        match block {
+            Block::Block(fields) => write_fields(out, fields),
+            Block::Array(arr) => {
+                out.push('[');
+                for (j, fields) in arr.iter().enumerate() {
+                    if j > 0 {
+                        out.push(',');
+                    }
+                    write_fields(out, fields);
+                }
+                out.push(']');
+            }
+        }
+    }
+
+    out.push_str("}}");
+}
+
+fn write_fields(out: &mut String, fields: &HashMap<Arc<str>, String>) {
+    let mut fields: Vec<_> = fields.iter().collect();
+    fields.sort_unstable_by_key(|(name, _)| *name);
+
+    out.push('{');
+    for (i, (name, value)) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_str(out, name);
+        out.push(':');
+        write_str(out, value);
+    }
+    out.push('}');
+}
+
+// 문자열을 이스케이프하여 JSON 문자열로 씁니다.
+pub(crate) fn write_str(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// 파싱된 JSON 값
+//
+// 숫자는 원래의 문자열 그대로 보관합니다.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    // 문자열이나 숫자를 필드 값으로 변환합니다.
+    fn as_field(&self) -> Option<&str> {
+        match self {
+            Self::String(value) | Self::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+// JSON 문자열의 형식이 잘못되어 발생하는 에러
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ParseError {
+    pub(crate) pos: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid json at {}", self.pos)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// 중첩이 지나치게 깊은 입력으로 스택이 넘치지 않도록 제한합니다.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> ParseError {
+        ParseError { pos: self.pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, ParseError> {
+        if self.text[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+
+        self.skip_ws();
+        match self.peek().ok_or_else(|| self.error())? {
+            b'n' => self.keyword("null", Value::Null),
+            b't' => self.keyword("true", Value::Bool(true)),
+            b'f' => self.keyword("false", Value::Bool(false)),
+            b'"' => self.string().map(Value::String),
+            b'[' => {
+                self.pos += 1;
+                let mut values = Vec::new();
+
+                self.skip_ws();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(values));
+                }
+
+                loop {
+                    values.push(self.value(depth + 1)?);
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(values));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'{' => {
+                self.pos += 1;
+                let mut members = Vec::new();
+
+                self.skip_ws();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+
+                loop {
+                    self.skip_ws();
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    members.push((key, self.value(depth + 1)?));
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(members));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+
+        let number = &self.text[start..self.pos];
+        if number.parse::<f64>().is_err() {
+            self.pos = start;
+            return Err(self.error());
+        }
+
+        Ok(Value::Number(number.to_owned()))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error())?;
+        let code = u32::from_str_radix(hex, 16).map_err(|_| self.error())?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error());
+        }
+        self.pos += 1;
+
+        let mut value = String::new();
+        loop {
+            let c = self.text[self.pos..]
+                .chars()
+                .next()
+                .ok_or_else(|| self.error())?;
+            self.pos += c.len_utf8();
+
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error())?;
+                    self.pos += 1;
+
+                    match escaped {
+                        b'"' => value.push('"'),
+                        b'\\' => value.push('\\'),
+                        b'/' => value.push('/'),
+                        b'b' => value.push('\u{8}'),
+                        b'f' => value.push('\u{c}'),
+                        b'n' => value.push('\n'),
+                        b'r' => value.push('\r'),
+                        b't' => value.push('\t'),
+                        b'u' => {
+                            let mut code = self.hex4()?;
+
+                            // 서로게이트 쌍을 하나의 문자로 합칩니다.
+                            if (0xd800..0xdc00).contains(&code) {
+                                if !self.text[self.pos..].starts_with("\\u") {
+                                    return Err(self.error());
+                                }
+                                self.pos += 2;
+
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(self.error());
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+
+                            value.push(char::from_u32(code).ok_or_else(|| self.error())?);
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+                c if (c as u32) < 0x20 => return Err(self.error()),
+                c => value.push(c),
+            }
+        }
+    }
+}
+
+// JSON 문자열을 파싱합니다.
+pub(crate) fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value(0)?;
+
+    parser.skip_ws();
+    if parser.pos != text.len() {
+        return Err(parser.error());
+    }
+
+    Ok(value)
+}
+
+// 블록 이름을 키로 하는 JSON 객체로 요청 데이터를 생성합니다.
+//
+// 지정하지 않은 단일 블록의 필드는 빈 문자열로 채워집니다. 필드 값은
+// 문자열이나 숫자여야 합니다.
+pub(crate) fn to_input(tr_layout: &TrLayout, blocks: &Value) -> Result<Data, EncodeError> {
+    let mut data = super::empty_input(tr_layout);
+
+    let members = match blocks {
+        Value::Object(members) => members,
+        _ => return Err(EncodeError::MismatchLayout),
+    };
+
+    for (block_name, value) in members {
+        let block_layout =
+            tr_layout
+                .in_block(block_name)
+                .ok_or_else(|| EncodeError::MissingBlock {
+                    block: block_name.clone(),
+                })?;
+        let mismatch_block_type = || EncodeError::MismatchBlockType {
+            block: block_name.clone(),
+        };
+
+        let to_fields = |value: &Value| -> Result<HashMap<Arc<str>, String>, EncodeError> {
+            match value {
+                Value::Object(fields) => fields
+                    .iter()
+                    .map(|(field_name, value)| {
+                        let value = value.as_field().ok_or_else(|| EncodeError::MissingField {
+                            block: block_name.clone(),
+                            field: field_name.clone(),
+                        })?;
+                        Ok((field_name.as_str().into(), value.to_owned()))
+                    })
+                    .collect(),
+                _ => Err(mismatch_block_type()),
+            }
+        };
+
+        let block = data.blocks.get_mut(block_name).unwrap();
+
+        if block_layout.occurs {
+            let arr = value.as_array().ok_or_else(mismatch_block_type)?;
+            *block = Block::Array(arr.iter().map(to_fields).collect::<Result<_, _>>()?);
+        } else if let Block::Block(fields) = block {
+            fields.extend(to_fields(value)?);
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, to_input, to_json, Value};
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_to_json() {
+        let data = Data {
+            tr_code: "t1102".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1102OutBlock" => Block::Block(hashmap! {
+                    "price" => "91000",
+                    "hname" => "삼성\"전자\"\u{1}",
+                }),
+                "t1102OutBlock1" => Block::Array(vec![
+                    hashmap! { "price" => "1" },
+                    hashmap! { "price" => "2" },
+                ]),
+            },
+        };
+
+        assert_eq!(
+            to_json(&data),
+            concat!(
+                r#"{"tr_code":"t1102","data_type":"output","blocks":{"#,
+                r#""t1102OutBlock":{"hname":"삼성\"전자\"\u0001","price":"91000"},"#,
+                r#""t1102OutBlock1":[{"price":"1"},{"price":"2"}]}}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(r#" {"a": [1, -2.5e3, true, null], "b": "\"\u0041\ud83d\ude00"} "#),
+            Ok(Value::Object(vec![
+                (
+                    "a".into(),
+                    Value::Array(vec![
+                        Value::Number("1".into()),
+                        Value::Number("-2.5e3".into()),
+                        Value::Bool(true),
+                        Value::Null,
+                    ])
+                ),
+                ("b".into(), Value::String("\"A\u{1f600}".into())),
+            ]))
+        );
+
+        for text in ["", "{", "[1,]", "{\"a\" 1}", "\"\\ud800\"", "1 2", "-"] {
+            assert!(parse(text).is_err(), "{}", text);
+        }
+        assert!(parse(&"[".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn test_to_input() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000,attr;
+                BEGIN_DATA_MAP
+                t0000InBlock,입력,input;
+                begin
+                    shcode,shcode,shcode,char,6;
+                    gubun,gubun,gubun,char,1;
+                end
+                t0000InBlock1,입력,input,occurs;
+                begin
+                    qty,qty,qty,long,8;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let blocks =
+            parse(r#"{"t0000InBlock": {"shcode": "005930"}, "t0000InBlock1": [{"qty": 10}]}"#)
+                .unwrap();
+
+        assert_eq!(
+            to_input(&tr_layout, &blocks).unwrap().blocks,
+            hashmap! {
+                "t0000InBlock" => Block::Block(hashmap! { "shcode" => "005930", "gubun" => "" }),
+                "t0000InBlock1" => Block::Array(vec![hashmap! { "qty" => "10" }]),
+            }
+        );
+
+        let blocks = parse(r#"{"t0000InBlock": []}"#).unwrap();
+        assert!(to_input(&tr_layout, &blocks).is_err());
+        let blocks = parse(r#"{"t0000OutBlock": {}}"#).unwrap();
+        assert!(to_input(&tr_layout, &blocks).is_err());
+    }
+}
@@ -4,12 +4,52 @@
 
 #![allow(dead_code)]
 
+mod access;
+mod attr;
+mod borrowed;
+mod builder;
+mod columnar;
+mod date;
+mod diff;
+mod encoding;
+pub(crate) mod json;
+mod number;
 mod tests;
-
-use crate::layout::{BlockLayout, TrLayout};
-
-use encoding_rs::EUC_KR;
-use std::{collections::HashMap, ops::Index};
+mod validate;
+
+#[cfg(any(test, feature = "arbitrary", feature = "proptest"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "arbitrary", feature = "proptest"))))]
+pub mod strategy;
+
+pub use self::access::{Decimal, FieldError, FieldsExt};
+pub use self::attr::{AttrBlock, Attrs};
+pub use self::borrowed::{BlockRef, DataRef};
+pub use self::builder::DataBuilder;
+pub use self::columnar::{Columns, TypedColumn};
+pub use self::diff::{diff, Change, DataDiff};
+pub use self::encoding::{
+    decode_policy, encoding, set_decode_policy, set_encoding, DecodePolicy, Encoding,
+};
+pub use self::number::{pad_number, parse_signed, signed_value, strip_zeros, Sign};
+pub use self::validate::ValidationIssue;
+
+#[cfg(any(windows, feature = "sim"))]
+pub(crate) use self::attr::decode_attrs;
+#[cfg(any(windows, feature = "sim"))]
+pub(crate) use self::borrowed::decode_ref;
+#[cfg(any(windows, feature = "sim"))]
+pub(crate) use self::columnar::{decode_columns, to_columns};
+#[cfg(windows)]
+pub(crate) use self::encoding::lock_encoding;
+
+use crate::layout::{BlockLayout, FieldLayout, FieldType, TrLayout};
+
+use encoding_rs::CoderResult;
+use std::borrow::Cow;
+use std::hash::Hash;
+use std::io::Write;
+use std::sync::Arc;
+use std::{collections::HashMap, ops::Index, str::FromStr};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -40,6 +80,57 @@ macro_rules! hashmap {
     }};
 }
 
+/// [`Data`]를 초기화하는 매크로
+///
+/// 블록과 필드의 이름은 식별자로, 값은 [`Into<String>`][Into]으로 변환되는
+/// 식으로 지정합니다. 배열 블록은 대괄호 안에 각 행을 나열합니다. 데이터
+/// 종류는 `input` 또는 `output`입니다.
+///
+/// ## 예제
+/// ```rust
+/// use xingapi::data;
+/// use xingapi::data::{Block, DataType};
+///
+/// let data = data!("t1104", input {
+///     t1104InBlock { code: "096530", nrec: "1" },
+///     t1104InBlock1 [
+///         { indx: "0", gubn: "1", dat1: "1", dat2: "1" },
+///     ],
+/// });
+///
+/// assert_eq!(data.data_type, DataType::Input);
+/// assert_eq!(data.blocks["t1104InBlock"]["code"], *"096530");
+/// assert!(matches!(&data.blocks["t1104InBlock1"], Block::Array(arr) if arr.len() == 1));
+/// ```
+#[macro_export]
+macro_rules! data {
+    ($tr_code:expr, $data_type:ident { $($block:ident $body:tt),* $(,)? }) => {
+        $crate::data::Data {
+            tr_code: $tr_code.into(),
+            data_type: $crate::data!(@type $data_type),
+            blocks: $crate::hashmap! {
+                $(stringify!($block) => $crate::data!(@block $body)),*
+            },
+        }
+    };
+    (@type input) => {
+        $crate::data::DataType::Input
+    };
+    (@type output) => {
+        $crate::data::DataType::Output
+    };
+    (@block { $($field:ident : $value:expr),* $(,)? }) => {
+        $crate::data::Block::Block($crate::hashmap! {
+            $(stringify!($field) => $value),*
+        })
+    };
+    (@block [ $({ $($field:ident : $value:expr),* $(,)? }),* $(,)? ]) => {
+        $crate::data::Block::Array(vec![
+            $($crate::hashmap! { $(stringify!($field) => $value),* }),*
+        ])
+    };
+}
+
 /// 서버와 주고받는 데이터를 나타내는 객체
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -52,6 +143,167 @@ pub struct Data {
     pub blocks: HashMap<String, Block>,
 }
 
+impl Data {
+    /// 데이터를 JSON 문자열로 변환합니다.
+    ///
+    /// `serde` 기능과 관계없이 사용할 수 있으며, 객체의 키는 정렬된 순서로
+    /// 출력됩니다.
+    pub fn to_json(&self) -> String {
+        json::to_json(self)
+    }
+
+    /// 데이터를 XingAPI에 전달하는 형태로 인코딩하여 버퍼 뒤에 추가합니다.
+    ///
+    /// 요청을 반복하는 경우 버퍼를 재사용하여 할당을 줄일 수 있습니다. 에러가
+    /// 발생한 경우 버퍼는 호출 전의 상태로 돌아갑니다.
+    pub fn encode_into(&self, tr_layout: &TrLayout, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+        encode_into(self, tr_layout, buf)
+    }
+
+    /// 레이아웃에 있지만 데이터에 없는 필드를 기본값으로 채웁니다.
+    ///
+    /// 공식 예제와 같이 지정하지 않은 필드를 문자열과 날짜는 공백으로, 숫자는
+    /// 0으로 채워서 [`EncodeError::MissingField`] 없이 인코딩할 수 있도록
+    /// 합니다. 없는 단일 블록은 새로 추가하고 없는 배열 블록은 빈 배열로
+    /// 추가합니다. 고정 배열은 지정된 개수가 될 때까지 행을 추가합니다.
+    pub fn fill_defaults(&mut self, tr_layout: &TrLayout) -> Result<(), EncodeError> {
+        if self.tr_code != tr_layout.code {
+            return Err(EncodeError::MismatchLayout);
+        }
+
+        let block_layouts = match self.data_type {
+            DataType::Input => &tr_layout.in_blocks,
+            DataType::Output => &tr_layout.out_blocks,
+        };
+
+        for block_layout in block_layouts {
+            let block = self
+                .blocks
+                .entry(block_layout.name.clone())
+                .or_insert_with(|| {
+                    if block_layout.occurs {
+                        Block::Array(Vec::new())
+                    } else {
+                        Block::Block(HashMap::new())
+                    }
+                });
+
+            match block {
+                Block::Block(block) if !block_layout.occurs => {
+                    fill_block_defaults(block_layout, block);
+                }
+                Block::Array(arr_block) if block_layout.occurs => {
+                    if let Some(count) = block_layout.occurs_count {
+                        if arr_block.len() < count {
+                            arr_block.resize_with(count, HashMap::new);
+                        }
+                    }
+
+                    for block in arr_block {
+                        fill_block_defaults(block_layout, block);
+                    }
+                }
+                _ => {
+                    return Err(EncodeError::MismatchBlockType {
+                        block: block_layout.name.clone(),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 소수점 자릿수가 지정된 필드의 정수 표현을 소수로 변환합니다.
+    ///
+    /// 레이아웃에서 `6.2`와 같이 소수점 자릿수가 지정된 필드 중 값에 소수점이
+    /// 없는 경우 `10^point`로 나눈 값으로 바꿉니다. 예를 들어 `-00125`는
+    /// `-1.25`가 됩니다. 이미 소수점이 있거나 정수가 아닌 값은 그대로 둡니다.
+    pub fn apply_points(&mut self, tr_layout: &TrLayout) {
+        let block_layouts = match self.data_type {
+            DataType::Input => &tr_layout.in_blocks,
+            DataType::Output => &tr_layout.out_blocks,
+        };
+
+        for block_layout in block_layouts {
+            let rows = match self.blocks.get_mut(&block_layout.name) {
+                Some(Block::Block(fields)) => std::slice::from_mut(fields),
+                Some(Block::Array(arr)) => arr.as_mut_slice(),
+                None => continue,
+            };
+
+            for field_layout in &block_layout.fields {
+                let scale = match field_layout.point {
+                    Some(point) if point > 0 => point as u32,
+                    _ => continue,
+                };
+
+                for fields in rows.iter_mut() {
+                    let value = match fields.get_mut(&field_layout.name) {
+                        Some(value) if !value.contains('.') => value,
+                        _ => continue,
+                    };
+
+                    if let Ok(mantissa) = value.parse::<i64>() {
+                        *value = Decimal { mantissa, scale }.to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    /// 레이아웃의 블록 순서대로 블록 레이아웃과 블록을 반환합니다.
+    ///
+    /// `HashMap`은 순서를 보존하지 않으므로 출력이나 비교 시 레이아웃의
+    /// 순서를 따르기 위해 사용합니다. 데이터에 없는 블록은 건너뜁니다.
+    pub fn ordered_blocks<'a>(
+        &'a self,
+        tr_layout: &'a TrLayout,
+    ) -> impl Iterator<Item = (&'a BlockLayout, &'a Block)> + 'a {
+        let block_layouts = match self.data_type {
+            DataType::Input => &tr_layout.in_blocks,
+            DataType::Output => &tr_layout.out_blocks,
+        };
+
+        block_layouts.iter().filter_map(move |block_layout| {
+            self.blocks
+                .get(&block_layout.name)
+                .map(|block| (block_layout, block))
+        })
+    }
+}
+
+/// 레이아웃의 필드 순서대로 필드 이름과 값을 반환합니다.
+///
+/// 이전 필드 이름으로 저장된 값도 찾으며, 블록에 없는 필드는 건너뜁니다.
+pub fn ordered_fields<'a>(
+    block_layout: &'a BlockLayout,
+    fields: &'a HashMap<Arc<str>, String>,
+) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+    block_layout.fields.iter().filter_map(move |field_layout| {
+        fields
+            .get(&field_layout.name)
+            .or_else(|| fields.get(field_layout.name_old.as_str()))
+            .map(|value| (&*field_layout.name, value.as_str()))
+    })
+}
+
+fn fill_block_defaults(block_layout: &BlockLayout, block: &mut HashMap<Arc<str>, String>) {
+    for field_layout in &block_layout.fields {
+        if block.contains_key(&field_layout.name)
+            || block.contains_key(field_layout.name_old.as_str())
+        {
+            continue;
+        }
+
+        let fill = match field_layout.field_type {
+            FieldType::Char | FieldType::Date | FieldType::Unknown(_) => " ",
+            FieldType::Int | FieldType::Float | FieldType::Double => "0",
+        };
+        block.insert(field_layout.name.clone(), fill.repeat(field_layout.len));
+    }
+}
+
 /// 데이터 종류 (요청 및 응답)
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -65,16 +317,27 @@ pub enum DataType {
 }
 
 /// 블록을 나타내는 객체
+///
+/// 필드 이름은 [`FieldLayout::name`]을 공유하는 `Arc<str>`이므로 디코딩할 때
+/// 행마다 이름을 복사하지 않습니다. `&str`로 필드를 조회할 수 있습니다.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
 pub enum Block {
     /// 단일 블록
-    Block(HashMap<String, String>),
+    Block(HashMap<Arc<str>, String>),
     /// 배열 블록
-    Array(Vec<HashMap<String, String>>),
+    Array(Vec<HashMap<Arc<str>, String>>),
 }
 
 impl Block {
+    /// 블록의 행을 반환합니다. 단일 블록은 하나의 행으로 취급합니다.
+    pub fn rows(&self) -> &[HashMap<Arc<str>, String>] {
+        match self {
+            Self::Block(block) => std::slice::from_ref(block),
+            Self::Array(arr) => arr,
+        }
+    }
+
     /// 단일 블록 여부를 반환합니다.
     pub fn is_block(&self) -> bool {
         matches!(self, Self::Block(_))
@@ -86,7 +349,7 @@ impl Block {
     }
 
     /// 단일 블록에 대한 참조자를 반환힙니다.
-    pub fn as_block(&self) -> Option<&HashMap<String, String>> {
+    pub fn as_block(&self) -> Option<&HashMap<Arc<str>, String>> {
         match self {
             Self::Block(block) => Some(block),
             Self::Array(_) => None,
@@ -94,7 +357,7 @@ impl Block {
     }
 
     /// 단일 블록에 대한 가변 참조자를 반환힙니다.
-    pub fn as_block_mut(&mut self) -> Option<&mut HashMap<String, String>> {
+    pub fn as_block_mut(&mut self) -> Option<&mut HashMap<Arc<str>, String>> {
         match self {
             Self::Block(block) => Some(block),
             Self::Array(_) => None,
@@ -102,7 +365,7 @@ impl Block {
     }
 
     /// 배열 블록에 대한 참조자를 반환합니다.
-    pub fn as_array(&self) -> Option<&Vec<HashMap<String, String>>> {
+    pub fn as_array(&self) -> Option<&Vec<HashMap<Arc<str>, String>>> {
         match self {
             Self::Array(array) => Some(array),
             Self::Block(_) => None,
@@ -110,7 +373,7 @@ impl Block {
     }
 
     /// 배열 블록에 대한 가변 참조자를 반환합니다.
-    pub fn as_array_mut(&mut self) -> Option<&mut Vec<HashMap<String, String>>> {
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<HashMap<Arc<str>, String>>> {
         match self {
             Self::Array(array) => Some(array),
             Self::Block(_) => None,
@@ -118,6 +381,10 @@ impl Block {
     }
 }
 
+/// 단일 블록의 필드 값을 반환합니다.
+///
+/// 배열 블록이거나 필드가 없는 경우 패닉이 발생하므로 서버 응답을 다룰 때는
+/// [`FieldsExt`]의 함수를 사용하는 것이 좋습니다.
 impl Index<&str> for Block {
     type Output = str;
     fn index(&self, index: &str) -> &Self::Output {
@@ -128,7 +395,7 @@ impl Index<&str> for Block {
 }
 
 impl Index<usize> for Block {
-    type Output = HashMap<String, String>;
+    type Output = HashMap<Arc<str>, String>;
     fn index(&self, index: usize) -> &Self::Output {
         &self
             .as_array()
@@ -138,6 +405,7 @@ impl Index<usize> for Block {
 
 /// 데이터를 디코딩에 실패하여 발생하는 에러
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum DecodeError {
     /// 레이아웃이 없습니다.
     UnknownLayout(String),
@@ -147,8 +415,14 @@ pub enum DecodeError {
     MismatchDataLength,
     /// 데이터에 명시된 배열 크기가 유효하지 않습니다.
     InvalidArrayLength,
-    /// EUC-KR 문자열에 잘못된 형식의 문자가 존재합니다.
+    /// 문자열에 지정된 인코딩으로 디코딩할 수 없는 문자가 존재합니다.
     MalformedString,
+    /// 블록이 누락되었습니다.
+    MissingBlock(String),
+    /// 필드가 누락되었습니다.
+    MissingField { block: String, field: String },
+    /// 필드의 값이 유효하지 않습니다.
+    InvalidField { block: String, field: String },
 }
 
 impl std::fmt::Display for DecodeError {
@@ -162,7 +436,16 @@ impl std::fmt::Display for DecodeError {
             }
             Self::MismatchDataLength => "mismatch data length".fmt(f),
             Self::InvalidArrayLength => "invalid array length".fmt(f),
-            Self::MalformedString => "malformed euc-kr string".fmt(f),
+            Self::MalformedString => "malformed string".fmt(f),
+            Self::MissingBlock(name) => {
+                write!(f, "missing {} block", name)
+            }
+            Self::MissingField { block, field } => {
+                write!(f, "missing {} field in {} block", field, block)
+            }
+            Self::InvalidField { block, field } => {
+                write!(f, "invalid value of {} field in {} block", field, block)
+            }
         }
     }
 }
@@ -171,19 +454,28 @@ impl std::error::Error for DecodeError {}
 
 /// 데이터를 인코딩에 실패하여 발생하는 에러
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum EncodeError {
     /// 레이아웃의 TR 코드가 일치하지 않습니다.
     MismatchLayout,
     /// 블록이 누락되었습니다.
     MissingBlock { block: String },
+    /// 레이아웃에 존재하지 않는 블록이 있습니다.
+    UnknownBlock { block: String },
     /// 블록 타입이 일치하지 않습니다.
     MismatchBlockType { block: String },
     /// 블록 배열이 최대 크기에 도달했습니다.
     ExceedArrayLength { block: String },
+    /// 고정 배열의 행 개수가 레이아웃과 일치하지 않습니다.
+    MismatchArrayLength { block: String },
     /// 필드가 누락되었습니다.
     MissingField { block: String, field: String },
+    /// 레이아웃에 존재하지 않는 필드가 있습니다.
+    UnknownField { block: String, field: String },
     /// 필드가 최대 크기에 도달했습니다.
     ExceedFieldLength { block: String, field: String },
+    /// 필드에 EUC-KR로 표현할 수 없는 문자가 있습니다.
+    UnencodableField { block: String, field: String },
 }
 
 impl std::fmt::Display for EncodeError {
@@ -193,15 +485,24 @@ impl std::fmt::Display for EncodeError {
             Self::MissingBlock { block } => {
                 write!(f, "missing {} block", block)
             }
+            Self::UnknownBlock { block } => {
+                write!(f, "unknown block: {}", block)
+            }
             Self::MismatchBlockType { block } => {
                 write!(f, "mismatch type of {} block", block)
             }
             Self::ExceedArrayLength { block } => {
                 write!(f, "reached max length of {} block array", block)
             }
+            Self::MismatchArrayLength { block } => {
+                write!(f, "mismatch length of {} fixed block array", block)
+            }
             Self::MissingField { block, field } => {
                 write!(f, "missing {} field in {} block", field, block)
             }
+            Self::UnknownField { block, field } => {
+                write!(f, "unknown {} field in {} block", field, block)
+            }
             Self::ExceedFieldLength { block, field } => {
                 write!(
                     f,
@@ -209,18 +510,140 @@ impl std::fmt::Display for EncodeError {
                     field, block
                 )
             }
+            Self::UnencodableField { block, field } => {
+                write!(
+                    f,
+                    "cannot encode {} field in {} block as euc-kr",
+                    field, block
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for EncodeError {}
 
+// 레이아웃의 요청 블록을 모두 빈 필드로 채운 요청 데이터를 생성합니다.
+pub(crate) fn empty_input(tr_layout: &TrLayout) -> Data {
+    let blocks = tr_layout
+        .in_blocks
+        .iter()
+        .map(|block_layout| {
+            let empty_fields = || -> HashMap<Arc<str>, String> {
+                block_layout
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.clone(), String::new()))
+                    .collect()
+            };
+
+            let block = if block_layout.occurs {
+                let count = block_layout.occurs_count.unwrap_or(0);
+                Block::Array((0..count).map(|_| empty_fields()).collect())
+            } else {
+                Block::Block(empty_fields())
+            };
+
+            (block_layout.name.clone(), block)
+        })
+        .collect();
+
+    Data {
+        tr_code: tr_layout.code.clone(),
+        data_type: DataType::Input,
+        blocks,
+    }
+}
+
+// 요청 데이터의 단일 블록에 필드 값을 설정합니다.
+pub(crate) fn set_field<T: Into<String>>(
+    data: &mut Data,
+    block_name: &str,
+    field_name: &str,
+    value: T,
+) -> Result<(), EncodeError> {
+    data.blocks
+        .get_mut(block_name)
+        .ok_or_else(|| EncodeError::MissingBlock {
+            block: block_name.to_owned(),
+        })?
+        .as_block_mut()
+        .ok_or_else(|| EncodeError::MismatchBlockType {
+            block: block_name.to_owned(),
+        })?
+        .insert(field_name.into(), value.into());
+
+    Ok(())
+}
+
+// 디코딩된 데이터에서 단일 블록을 가져옵니다.
+pub(crate) fn get_block<'a>(
+    data: &'a Data,
+    block_name: &str,
+) -> Result<&'a HashMap<Arc<str>, String>, DecodeError> {
+    data.blocks
+        .get(block_name)
+        .and_then(|b| b.as_block())
+        .ok_or_else(|| DecodeError::MissingBlock(block_name.to_owned()))
+}
+
+// 디코딩된 데이터에서 배열 블록을 가져옵니다.
+pub(crate) fn get_array<'a>(
+    data: &'a Data,
+    block_name: &str,
+) -> Result<&'a [HashMap<Arc<str>, String>], DecodeError> {
+    data.blocks
+        .get(block_name)
+        .and_then(|b| b.as_array())
+        .map(|a| a.as_slice())
+        .ok_or_else(|| DecodeError::MissingBlock(block_name.to_owned()))
+}
+
+// 블록의 필드 값을 지정된 타입으로 파싱합니다.
+pub(crate) fn parse_field<T: FromStr>(
+    fields: &HashMap<Arc<str>, String>,
+    block_name: &str,
+    field_name: &str,
+) -> Result<T, DecodeError> {
+    fields
+        .get(field_name)
+        .ok_or_else(|| DecodeError::MissingField {
+            block: block_name.to_owned(),
+            field: field_name.to_owned(),
+        })?
+        .parse()
+        .map_err(|_| DecodeError::InvalidField {
+            block: block_name.to_owned(),
+            field: field_name.to_owned(),
+        })
+}
+
+/// 서버로부터 수신한 디코딩 전의 응답 데이터
+///
+/// 서버의 응답을 그대로 보관하거나 RES 파일이 오래된 경우 직접 디코딩할 때
+/// 사용합니다.
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) enum RawData {
+pub enum RawData {
+    /// block mode인 응답의 블록 이름별 데이터
     Block(HashMap<String, Vec<u8>>),
+    /// non-block mode인 응답 데이터
     NonBlock(Vec<u8>),
 }
 
+impl RawData {
+    /// 지정된 레이아웃으로 데이터를 디코딩합니다.
+    ///
+    /// 레이아웃의 block mode 여부가 데이터와 다른 경우
+    /// [`DecodeError::UnknownLayout`]을 반환합니다.
+    pub fn decode(&self, tr_layout: &TrLayout) -> Result<Data, DecodeError> {
+        if tr_layout.block_mode != matches!(self, Self::Block(_)) {
+            return Err(DecodeError::UnknownLayout(tr_layout.code.clone()));
+        }
+
+        decode(tr_layout, self.clone())
+    }
+}
+
 // 응답 데이터를 디코딩합니다.
 pub(crate) fn decode(tr_layout: &TrLayout, raw_data: RawData) -> Result<Data, DecodeError> {
     match raw_data {
@@ -231,9 +654,7 @@ pub(crate) fn decode(tr_layout: &TrLayout, raw_data: RawData) -> Result<Data, De
 
             for (block_name, raw_block) in raw_block_tbl {
                 let block_layout = tr_layout
-                    .out_blocks
-                    .iter()
-                    .find(|b| b.name == block_name)
+                    .out_block(&block_name)
                     .ok_or_else(|| DecodeError::UnknownBlock(block_name.clone()))?;
 
                 blocks.insert(
@@ -266,144 +687,411 @@ fn decode_block(
     block_layout: &BlockLayout,
     raw_block: &[u8],
 ) -> Result<Block, DecodeError> {
-    assert!(tr_layout.block_mode && !block_layout.occurs);
+    block_fields(tr_layout, block_layout, raw_block, &owned_field).map(Block::Block)
+}
 
-    if raw_block.len() != block_layout.len {
-        return Err(DecodeError::MismatchDataLength);
-    }
+// block mode인 응답 데이터의 배열 블록을 디코딩합니다.
+fn decode_block_array(
+    tr_layout: &TrLayout,
+    block_layout: &BlockLayout,
+    raw_block: &[u8],
+) -> Result<Block, DecodeError> {
+    block_array_fields(tr_layout, block_layout, raw_block, &owned_field).map(Block::Array)
+}
+
+// non-block mode인 데이터를 디코딩합니다.
+pub(crate) fn decode_non_block(
+    tr_layout: &TrLayout,
+    data_type: DataType,
+    raw_data: &[u8],
+) -> Result<Data, DecodeError> {
+    let blocks = non_block_fields(tr_layout, raw_data, &owned_field)?
+        .into_iter()
+        .map(|(block_layout, block)| {
+            let block = match block {
+                Fields::Block(fields) => Block::Block(fields),
+                Fields::Array(arr) => Block::Array(arr),
+            };
+            (block_layout.name.clone(), block)
+        })
+        .collect();
+
+    Ok(Data {
+        tr_code: tr_layout.code.clone(),
+        data_type,
+        blocks,
+    })
+}
+
+// 디코딩된 블록의 형태
+//
+// 필드 테이블의 키와 값의 타입은 디코딩 방식에 따라 다릅니다.
+enum Fields<K, V> {
+    Block(HashMap<K, V>),
+    Array(Vec<HashMap<K, V>>),
+}
 
+// non-block mode인 데이터의 블록 레이아웃과 디코딩된 블록 목록
+type NonBlockFields<'a, K, V> = Vec<(&'a BlockLayout, Fields<K, V>)>;
+
+// 필드 이름과 값을 모두 복사하여 디코딩합니다.
+fn owned_field(
+    field_layout: &FieldLayout,
+    raw_field: &[u8],
+    _attr: Option<u8>,
+) -> Result<(Arc<str>, String), DecodeError> {
+    Ok((field_layout.name.clone(), decode_str(raw_field)?))
+}
+
+// 블록의 필드를 하나씩 디코딩하고 오프셋을 전진합니다.
+//
+// 레이아웃에 속성 바이트가 있는 경우 필드 뒤의 속성 바이트를 함께
+// 전달합니다.
+fn decode_fields<'a, K, V, F>(
+    tr_layout: &TrLayout,
+    block_layout: &'a BlockLayout,
+    raw_data: &'a [u8],
+    offset: &mut usize,
+    field: &F,
+) -> Result<HashMap<K, V>, DecodeError>
+where
+    K: Eq + Hash,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError>,
+{
     let mut fields = HashMap::with_capacity(block_layout.fields.len());
-    let mut offset = 0;
 
     for field_layout in &block_layout.fields {
-        fields.insert(
-            field_layout.name.clone(),
-            decode_str(&raw_block[offset..offset + field_layout.len])?,
-        );
-        offset += field_layout.len + if tr_layout.attr_byte { 1 } else { 0 };
+        let end = *offset + field_layout.len;
+        let attr = if tr_layout.attr_byte {
+            Some(raw_data[end])
+        } else {
+            None
+        };
+
+        match field(field_layout, &raw_data[*offset..end], attr) {
+            Ok((key, value)) => {
+                fields.insert(key, value);
+            }
+            Err(DecodeError::MalformedString) if decode_policy() == DecodePolicy::SkipField => {}
+            Err(err) => return Err(err),
+        }
+        *offset = end + if tr_layout.attr_byte { 1 } else { 0 };
     }
 
-    Ok(Block::Block(fields))
+    Ok(fields)
 }
 
-// block mode인 응답 데이터의 배열 블록을 디코딩합니다.
-fn decode_block_array(
+// 배열 블록의 행을 디코딩하고 오프셋을 전진합니다.
+//
+// 데이터의 길이는 미리 확인되어야 합니다.
+#[cfg(not(feature = "parallel"))]
+fn decode_rows<'a, K, V, F>(
     tr_layout: &TrLayout,
-    block_layout: &BlockLayout,
-    raw_block: &[u8],
-) -> Result<Block, DecodeError> {
-    assert!(tr_layout.block_mode && block_layout.occurs);
+    block_layout: &'a BlockLayout,
+    raw_data: &'a [u8],
+    offset: &mut usize,
+    rows: usize,
+    field: &F,
+) -> Result<Vec<HashMap<K, V>>, DecodeError>
+where
+    K: Eq + Hash,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError>,
+{
+    let mut arr = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        arr.push(decode_fields(
+            tr_layout,
+            block_layout,
+            raw_data,
+            offset,
+            field,
+        )?);
+    }
 
-    if raw_block.len() % block_layout.len != 0 {
+    Ok(arr)
+}
+
+// 행이 많은 경우 행을 나누어 여러 스레드에서 디코딩합니다.
+//
+// 각 행은 길이가 같고 서로 독립적이므로 행의 위치를 미리 계산할 수 있습니다.
+#[cfg(feature = "parallel")]
+fn decode_rows<'a, K, V, F>(
+    tr_layout: &TrLayout,
+    block_layout: &'a BlockLayout,
+    raw_data: &'a [u8],
+    offset: &mut usize,
+    rows: usize,
+    field: &F,
+) -> Result<Vec<HashMap<K, V>>, DecodeError>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError> + Sync,
+{
+    // 스레드를 생성하는 비용보다 디코딩 비용이 큰 최소 행 개수
+    const MIN_ROWS: usize = 1024;
+
+    // 사용할 수 있는 스레드 개수는 처음 한 번만 조회합니다.
+    static THREADS: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+
+    let start = *offset;
+    *offset += rows * block_layout.len;
+
+    let chunk_rows = if rows <= MIN_ROWS {
+        rows
+    } else {
+        let threads =
+            *THREADS.get_or_init(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+        rows.div_ceil(threads).max(MIN_ROWS)
+    };
+
+    if rows <= chunk_rows {
+        let mut offset = start;
+        return (0..rows)
+            .map(|_| decode_fields(tr_layout, block_layout, raw_data, &mut offset, field))
+            .collect();
+    }
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..rows)
+            .step_by(chunk_rows)
+            .map(|first| {
+                let mut offset = start + first * block_layout.len;
+                let count = chunk_rows.min(rows - first);
+                scope.spawn(move || {
+                    (0..count)
+                        .map(|_| {
+                            decode_fields(tr_layout, block_layout, raw_data, &mut offset, field)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            })
+            .collect();
+
+        let mut arr = Vec::with_capacity(rows);
+        for handle in handles {
+            arr.extend(handle.join().unwrap()?);
+        }
+        Ok(arr)
+    })
+}
+
+fn block_fields<'a, K, V, F>(
+    tr_layout: &TrLayout,
+    block_layout: &'a BlockLayout,
+    raw_block: &'a [u8],
+    field: &F,
+) -> Result<HashMap<K, V>, DecodeError>
+where
+    K: Eq + Hash,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError>,
+{
+    assert!(tr_layout.block_mode && !block_layout.occurs);
+
+    if raw_block.len() != block_layout.len {
         return Err(DecodeError::MismatchDataLength);
     }
 
-    let blocks_len = raw_block.len() / block_layout.len;
+    decode_fields(tr_layout, block_layout, raw_block, &mut 0, field)
+}
 
-    let mut blocks = Vec::with_capacity(blocks_len);
-    let mut offset = 0;
+fn block_array_fields<'a, K, V, F>(
+    tr_layout: &TrLayout,
+    block_layout: &'a BlockLayout,
+    raw_block: &'a [u8],
+    field: &F,
+) -> Result<Vec<HashMap<K, V>>, DecodeError>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError> + Sync,
+{
+    assert!(tr_layout.block_mode && block_layout.occurs);
 
-    for _ in 0..blocks_len {
-        let mut fields = HashMap::with_capacity(block_layout.fields.len());
-        for field_layout in &block_layout.fields {
-            fields.insert(
-                field_layout.name.clone(),
-                decode_str(&raw_block[offset..offset + field_layout.len])?,
-            );
-            offset += field_layout.len + if tr_layout.attr_byte { 1 } else { 0 };
+    if let Some(count) = block_layout.occurs_count {
+        if block_layout.len.checked_mul(count) != Some(raw_block.len()) {
+            return Err(DecodeError::MismatchDataLength);
         }
 
-        blocks.push(fields);
+        return decode_rows(tr_layout, block_layout, raw_block, &mut 0, count, field);
+    }
+
+    // 필드가 없는 블록은 길이가 0입니다.
+    if block_layout.len == 0 {
+        return if raw_block.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(DecodeError::MismatchDataLength)
+        };
     }
 
-    Ok(Block::Array(blocks))
+    if raw_block.len() % block_layout.len != 0 {
+        return Err(DecodeError::MismatchDataLength);
+    }
+
+    let blocks_len = raw_block.len() / block_layout.len;
+
+    decode_rows(
+        tr_layout,
+        block_layout,
+        raw_block,
+        &mut 0,
+        blocks_len,
+        field,
+    )
 }
 
-// non-block mode인 데이터를 디코딩합니다.
-pub(crate) fn decode_non_block(
-    tr_layout: &TrLayout,
-    data_type: DataType,
-    raw_data: &[u8],
-) -> Result<Data, DecodeError> {
+fn non_block_fields<'a, K, V, F>(
+    tr_layout: &'a TrLayout,
+    raw_data: &'a [u8],
+    field: &F,
+) -> Result<NonBlockFields<'a, K, V>, DecodeError>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    F: Fn(&'a FieldLayout, &'a [u8], Option<u8>) -> Result<(K, V), DecodeError> + Sync,
+{
     assert!(!tr_layout.block_mode);
 
-    let mut blocks = HashMap::new();
+    let mut blocks = Vec::with_capacity(tr_layout.out_blocks.len());
     let mut offset = 0;
 
     for block_layout in &tr_layout.out_blocks {
         let block = if block_layout.occurs {
-            if offset + 5 > raw_data.len() {
-                return Err(DecodeError::MismatchDataLength);
-            }
-
-            let blocks_len: usize = str::parse(
-                &EUC_KR
-                    .decode_without_bom_handling_and_without_replacement(
-                        &raw_data[offset..offset + 5],
+            let blocks_len = match block_layout.occurs_count {
+                // 고정 배열은 행 개수가 붙지 않습니다.
+                Some(count) => count,
+                None => {
+                    if offset + 5 > raw_data.len() {
+                        return Err(DecodeError::MismatchDataLength);
+                    }
+
+                    let blocks_len: usize = str::parse(
+                        &encoding()
+                            .decode(&raw_data[offset..offset + 5])
+                            .ok_or(DecodeError::InvalidArrayLength)?,
                     )
-                    .ok_or(DecodeError::InvalidArrayLength)?,
-            )
-            .map_err(|_| DecodeError::InvalidArrayLength)?;
+                    .map_err(|_| DecodeError::InvalidArrayLength)?;
 
-            offset += 5;
-
-            if offset + block_layout.len * blocks_len > raw_data.len() {
-                return Err(DecodeError::MismatchDataLength);
-            }
-
-            let mut blocks = Vec::with_capacity(blocks_len);
-
-            for _ in 0..blocks_len {
-                let mut fields = HashMap::with_capacity(block_layout.fields.len());
-                for field_layout in &block_layout.fields {
-                    fields.insert(
-                        field_layout.name.clone(),
-                        decode_str(&raw_data[offset..offset + field_layout.len])?,
-                    );
-
-                    offset += field_layout.len + if tr_layout.attr_byte { 1 } else { 0 };
+                    offset += 5;
+                    blocks_len
                 }
-
-                blocks.push(fields);
+            };
+
+            if block_layout
+                .len
+                .checked_mul(blocks_len)
+                .and_then(|len| len.checked_add(offset))
+                .is_none_or(|end| end > raw_data.len())
+            {
+                return Err(DecodeError::MismatchDataLength);
             }
 
-            Block::Array(blocks)
+            Fields::Array(decode_rows(
+                tr_layout,
+                block_layout,
+                raw_data,
+                &mut offset,
+                blocks_len,
+                field,
+            )?)
         } else {
-            if offset + block_layout.len > raw_data.len() {
+            if block_layout.len > raw_data.len() - offset {
                 return Err(DecodeError::MismatchDataLength);
             }
 
-            let mut fields = HashMap::with_capacity(block_layout.fields.len());
-            for field_layout in &block_layout.fields {
-                fields.insert(
-                    field_layout.name.clone(),
-                    decode_str(&raw_data[offset..offset + field_layout.len])?,
-                );
-                offset += field_layout.len + if tr_layout.attr_byte { 1 } else { 0 };
-            }
-
-            Block::Block(fields)
+            Fields::Block(decode_fields(
+                tr_layout,
+                block_layout,
+                raw_data,
+                &mut offset,
+                field,
+            )?)
         };
 
-        blocks.insert(block_layout.name.clone(), block);
+        blocks.push((block_layout, block));
     }
 
-    Ok(Data {
-        tr_code: tr_layout.code.clone(),
-        data_type,
-        blocks,
-    })
+    Ok(blocks)
 }
 
 fn decode_str(data: &[u8]) -> Result<String, DecodeError> {
-    EUC_KR
-        .decode_without_bom_handling_and_without_replacement(data)
-        .map(|s| s.trim_matches(|c| (c as u32) < 0x20 || c == ' ').to_owned())
-        .ok_or(DecodeError::MalformedString)
+    decode_cow(data).map(Cow::into_owned)
+}
+
+// 앞뒤의 공백과 제어 문자를 제거하여 필드 값을 디코딩합니다.
+//
+// ASCII 문자로만 이루어진 경우 복사하지 않고 원본 데이터를 빌립니다. 그
+// 외의 경우 [`encoding()`]으로 지정된 인코딩을 사용합니다.
+fn decode_cow(data: &[u8]) -> Result<Cow<'_, str>, DecodeError> {
+    decode_cow_with(data, decode_policy())
+}
+
+fn decode_cow_with(data: &[u8], policy: DecodePolicy) -> Result<Cow<'_, str>, DecodeError> {
+    // 공백과 제어 문자는 모두 ASCII 문자이며 EUC-KR과 UTF-8의 멀티바이트
+    // 문자에는 0x20 이하의 바이트가 존재하지 않으므로 디코딩 전에 바이트
+    // 단위로 제거할 수 있습니다.
+    let start = data.iter().position(|&b| b > b' ').unwrap_or(data.len());
+    let end = data
+        .iter()
+        .rposition(|&b| b > b' ')
+        .map_or(start, |i| i + 1);
+    let data = &data[start..end];
+
+    if data.is_ascii() {
+        // ASCII 문자열은 항상 UTF-8 문자열입니다.
+        return Ok(Cow::Borrowed(unsafe {
+            std::str::from_utf8_unchecked(data)
+        }));
+    }
+
+    match (encoding().decode(data), policy) {
+        (Some(text), _) => Ok(text),
+        (None, DecodePolicy::Replace) => Ok(encoding().decode_lossy(data)),
+        (None, _) => Err(DecodeError::MalformedString),
+    }
 }
 
 // non-block mode로 데이터를 인코딩합니다.
 pub(crate) fn encode(data: &Data, tr_layout: &TrLayout) -> Result<Vec<u8>, EncodeError> {
+    let mut enc_data = Vec::new();
+    encode_into(data, tr_layout, &mut enc_data)?;
+    Ok(enc_data)
+}
+
+// non-block mode로 데이터를 인코딩하여 버퍼 뒤에 추가합니다.
+//
+// 에러가 발생한 경우 버퍼는 호출 전의 상태로 돌아갑니다.
+pub(crate) fn encode_into(
+    data: &Data,
+    tr_layout: &TrLayout,
+    enc_data: &mut Vec<u8>,
+) -> Result<(), EncodeError> {
+    let start = enc_data.len();
+    let result = encode_blocks(data, tr_layout, enc_data);
+    if result.is_err() {
+        enc_data.truncate(start);
+    }
+
+    debug_assert!(
+        result.is_err()
+            || data.data_type == DataType::Output
+            || enc_data.len() - start
+                == tr_layout.expected_request_len(|block_layout| {
+                    data.blocks[&block_layout.name]
+                        .as_array()
+                        .map_or(0, Vec::len)
+                })
+    );
+
+    result
+}
+
+fn encode_blocks(
+    data: &Data,
+    tr_layout: &TrLayout,
+    enc_data: &mut Vec<u8>,
+) -> Result<(), EncodeError> {
     if data.tr_code != tr_layout.code {
         return Err(EncodeError::MismatchLayout);
     }
@@ -413,8 +1101,6 @@ pub(crate) fn encode(data: &Data, tr_layout: &TrLayout) -> Result<Vec<u8>, Encod
         DataType::Output => &tr_layout.out_blocks,
     };
 
-    let mut enc_data: Vec<u8> = Vec::new();
-
     for block_layout in block_layouts {
         let missing_block = || -> EncodeError {
             EncodeError::MissingBlock {
@@ -435,7 +1121,13 @@ pub(crate) fn encode(data: &Data, tr_layout: &TrLayout) -> Result<Vec<u8>, Encod
                 .as_array()
                 .ok_or_else(mismatch_block_type)?;
 
-            if !tr_layout.block_mode {
+            if let Some(count) = block_layout.occurs_count {
+                if arr_block.len() != count {
+                    return Err(EncodeError::MismatchArrayLength {
+                        block: block_layout.name.clone(),
+                    });
+                }
+            } else if !tr_layout.block_mode {
                 // 블럭의 최대 개수는 십진수로 5자리
                 if arr_block.len() >= 100000 {
                     return Err(EncodeError::ExceedArrayLength {
@@ -443,11 +1135,11 @@ pub(crate) fn encode(data: &Data, tr_layout: &TrLayout) -> Result<Vec<u8>, Encod
                     });
                 }
 
-                enc_data.extend(format!("{:0>5}", arr_block.len()).as_bytes());
+                write!(enc_data, "{:0>5}", arr_block.len()).unwrap();
             }
 
             for block in arr_block.iter() {
-                encode_block(tr_layout, block_layout, block, &mut enc_data)?;
+                encode_block(tr_layout, block_layout, block, enc_data)?;
             }
         } else {
             let block = data
@@ -457,45 +1149,132 @@ pub(crate) fn encode(data: &Data, tr_layout: &TrLayout) -> Result<Vec<u8>, Encod
                 .as_block()
                 .ok_or_else(mismatch_block_type)?;
 
-            encode_block(tr_layout, block_layout, block, &mut enc_data)?;
+            encode_block(tr_layout, block_layout, block, enc_data)?;
         }
     }
 
-    Ok(enc_data)
+    Ok(())
+}
+
+// 응답 데이터를 서버에서 수신하는 형태로 인코딩합니다.
+//
+// block mode인 경우 데이터에 존재하는 블록만 인코딩합니다.
+pub(crate) fn encode_raw(data: &Data, tr_layout: &TrLayout) -> Result<RawData, EncodeError> {
+    if !tr_layout.block_mode {
+        return encode(data, tr_layout).map(RawData::NonBlock);
+    }
+
+    if data.tr_code != tr_layout.code || data.data_type != DataType::Output {
+        return Err(EncodeError::MismatchLayout);
+    }
+
+    let mut raw_block_tbl = HashMap::new();
+
+    for block_layout in &tr_layout.out_blocks {
+        let mut enc_block = Vec::new();
+
+        match data.blocks.get(&block_layout.name) {
+            None => continue,
+            Some(Block::Block(block)) if !block_layout.occurs => {
+                encode_block(tr_layout, block_layout, block, &mut enc_block)?;
+            }
+            Some(Block::Array(arr_block)) if block_layout.occurs => {
+                if block_layout
+                    .occurs_count
+                    .is_some_and(|count| count != arr_block.len())
+                {
+                    return Err(EncodeError::MismatchArrayLength {
+                        block: block_layout.name.clone(),
+                    });
+                }
+
+                for block in arr_block {
+                    encode_block(tr_layout, block_layout, block, &mut enc_block)?;
+                }
+            }
+            Some(_) => {
+                return Err(EncodeError::MismatchBlockType {
+                    block: block_layout.name.clone(),
+                })
+            }
+        }
+
+        raw_block_tbl.insert(block_layout.name.clone(), enc_block);
+    }
+
+    Ok(RawData::Block(raw_block_tbl))
 }
 
 fn encode_block(
     tr_layout: &TrLayout,
     block_layout: &BlockLayout,
-    block: &HashMap<String, String>,
+    block: &HashMap<Arc<str>, String>,
     enc_data: &mut Vec<u8>,
 ) -> Result<(), EncodeError> {
     for field_layout in &block_layout.fields {
         let field = block
             .get(&field_layout.name)
-            .or_else(|| block.get(&field_layout.name_old))
+            .or_else(|| block.get(field_layout.name_old.as_str()))
             .ok_or_else(|| EncodeError::MissingField {
                 block: block_layout.name.clone(),
-                field: field_layout.name.clone(),
+                field: field_layout.name.to_string(),
             })?;
 
-        let mut enc_field = EUC_KR.encode(field).0.to_vec();
+        // 필드마다 버퍼를 할당하지 않도록 버퍼 뒤에 직접 인코딩합니다.
+        // 인코더는 남은 공간이 문자의 최대 길이(EUC-KR은 2바이트, UTF-8은
+        // 4바이트)보다 작으면 멈추고, 인코딩할 수 없는 문자는 최대 10바이트의
+        // HTML 숫자 참조로 치환되므로 여유 공간을 둡니다. 필드 뒤의 공간은 0으로
+        // 남겨둡니다.
+        let start = enc_data.len();
+        enc_data.resize(start + field_layout.len + 12, b'\0');
 
-        if enc_field.len() > field_layout.len {
+        let (result, _, written, _) =
+            encoding()
+                .new_encoder()
+                .encode_from_utf8(field, &mut enc_data[start..], true);
+
+        if result == CoderResult::OutputFull || written > field_layout.len {
             return Err(EncodeError::ExceedFieldLength {
                 block: block_layout.name.clone(),
-                field: field_layout.name.clone(),
+                field: field_layout.name.to_string(),
             });
         }
 
-        if tr_layout.attr_byte {
-            enc_field.resize(field_layout.len + 1, b'\0');
-        } else {
-            enc_field.resize(field_layout.len, b'\0');
+        let attr_len = if tr_layout.attr_byte { 1 } else { 0 };
+        enc_data.truncate(start + field_layout.len + attr_len);
+    }
+
+    Ok(())
+}
+
+/// 퍼징을 위한 디코딩 함수
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod fuzzing {
+    use super::{Block, Data, DataType, DecodeError};
+    use crate::layout::TrLayout;
+
+    pub fn decode_non_block(tr_layout: &TrLayout, raw_data: &[u8]) -> Result<Data, DecodeError> {
+        if tr_layout.block_mode {
+            return Err(DecodeError::UnknownLayout(tr_layout.code.clone()));
         }
 
-        enc_data.extend(enc_field.iter());
+        super::decode_non_block(tr_layout, DataType::Output, raw_data)
     }
 
-    Ok(())
+    pub fn decode_block_array(
+        tr_layout: &TrLayout,
+        raw_block: &[u8],
+    ) -> Result<Vec<Block>, DecodeError> {
+        if !tr_layout.block_mode {
+            return Err(DecodeError::UnknownLayout(tr_layout.code.clone()));
+        }
+
+        tr_layout
+            .out_blocks
+            .iter()
+            .filter(|block_layout| block_layout.occurs)
+            .map(|block_layout| super::decode_block_array(tr_layout, block_layout, raw_block))
+            .collect()
+    }
 }
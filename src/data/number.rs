@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// XingAPI가 사용하는 고정 너비 숫자 형식을 다루기 위한 함수들
+
+/// 전일 대비 구분
+///
+/// 가격 변동폭은 부호 없이 전송되며 이 필드로 방향을 구분합니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sign {
+    /// 상한 (`1`)
+    UpperLimit,
+    /// 상승 (`2`)
+    Up,
+    /// 보합 (`3`)
+    Unchanged,
+    /// 하한 (`4`)
+    LowerLimit,
+    /// 하락 (`5`)
+    Down,
+}
+
+impl Sign {
+    /// 구분 코드를 변환합니다.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "1" => Some(Self::UpperLimit),
+            "2" => Some(Self::Up),
+            "3" => Some(Self::Unchanged),
+            "4" => Some(Self::LowerLimit),
+            "5" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    /// 하락 방향인지 여부를 반환합니다.
+    pub fn is_negative(self) -> bool {
+        matches!(self, Self::LowerLimit | Self::Down)
+    }
+
+    /// 부호 없는 변동폭에 방향을 적용합니다.
+    pub fn apply(self, magnitude: i64) -> i64 {
+        if self.is_negative() {
+            -magnitude.abs()
+        } else {
+            magnitude.abs()
+        }
+    }
+}
+
+/// 숫자 문자열의 앞뒤 공백과 불필요한 앞자리 0을 제거합니다.
+///
+/// 부호는 유지하며 `"-000120"`은 `"-120"`, `"000.50"`은 `"0.50"`,
+/// `"0000"`은 `"0"`이 됩니다.
+pub fn strip_zeros(text: &str) -> String {
+    let text = text.trim();
+    let (sign, digits) = match text.as_bytes().first() {
+        Some(b'-') => ("-", &text[1..]),
+        Some(b'+') => ("", &text[1..]),
+        _ => ("", text),
+    };
+
+    let digits = digits.trim_start_matches('0');
+    let digits = if digits.is_empty() || digits.starts_with('.') {
+        format!("0{}", digits)
+    } else {
+        digits.to_owned()
+    };
+
+    if digits.bytes().all(|b| b == b'0' || b == b'.') {
+        digits
+    } else {
+        format!("{}{}", sign, digits)
+    }
+}
+
+/// 앞자리가 0으로 채워진 부호 있는 정수를 파싱합니다.
+///
+/// `"-000120"`, `"+00012"`, `" 12 "`와 같은 값을 허용합니다. 빈 문자열은
+/// 파싱하지 않습니다.
+pub fn parse_signed(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, text[1..].trim_start()),
+        Some(b'+') => (false, text[1..].trim_start()),
+        _ => (false, text),
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let value: i64 = digits.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// 대비 구분 필드와 부호 없는 변동폭 필드를 부호 있는 정수로 합칩니다.
+pub fn signed_value(sign: &str, magnitude: &str) -> Option<i64> {
+    Some(Sign::from_code(sign)?.apply(parse_signed(magnitude)?))
+}
+
+/// 정수를 지정된 너비에 맞게 앞자리를 0으로 채운 문자열로 변환합니다.
+///
+/// 음수는 첫 자리에 `-`를 씁니다. 너비를 넘는 경우 `None`을 반환합니다.
+pub fn pad_number(value: i64, width: usize) -> Option<String> {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+
+    if sign.len() + digits.len() > width {
+        return None;
+    }
+
+    Some(format!(
+        "{}{:0>width$}",
+        sign,
+        digits,
+        width = width - sign.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pad_number, parse_signed, signed_value, strip_zeros, Sign};
+
+    #[test]
+    fn test_strip_zeros() {
+        assert_eq!(strip_zeros("-000120"), "-120");
+        assert_eq!(strip_zeros("+00012"), "12");
+        assert_eq!(strip_zeros("000.50"), "0.50");
+        assert_eq!(strip_zeros("0000"), "0");
+        assert_eq!(strip_zeros("-0000"), "0");
+        assert_eq!(strip_zeros("  81000 "), "81000");
+    }
+
+    #[test]
+    fn test_parse_signed() {
+        assert_eq!(parse_signed("-000120"), Some(-120));
+        assert_eq!(parse_signed("+00012"), Some(12));
+        assert_eq!(parse_signed(" 12 "), Some(12));
+        assert_eq!(parse_signed("- 12"), Some(-12));
+        assert_eq!(parse_signed(""), None);
+        assert_eq!(parse_signed("-"), None);
+        assert_eq!(parse_signed("1.5"), None);
+    }
+
+    #[test]
+    fn test_signed_value() {
+        assert_eq!(signed_value("5", "00000500"), Some(-500));
+        assert_eq!(signed_value("2", "00000500"), Some(500));
+        assert_eq!(signed_value("3", "0"), Some(0));
+        assert_eq!(signed_value("9", "500"), None);
+        assert!(Sign::LowerLimit.is_negative());
+    }
+
+    #[test]
+    fn test_pad_number() {
+        assert_eq!(pad_number(120, 6).as_deref(), Some("000120"));
+        assert_eq!(pad_number(-120, 6).as_deref(), Some("-00120"));
+        assert_eq!(pad_number(0, 1).as_deref(), Some("0"));
+        assert_eq!(pad_number(1234, 3), None);
+        assert_eq!(pad_number(-12, 2), None);
+    }
+}
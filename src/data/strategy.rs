@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 속성 기반 테스트를 위한 레이아웃과 데이터 생성기
+//!
+//! 무작위 레이아웃과 그에 맞는 데이터, 그리고 데이터를 인코딩한 응답
+//! 데이터를 [`Sample`]로 생성합니다. `arbitrary` 기능을 사용하면 [`Sample`]이
+//! [`arbitrary::Arbitrary`]를 구현하며, `proptest` 기능을 사용하면
+//! [`sample()`]로 `proptest`의 전략을 만들 수 있습니다.
+//!
+//! ## 예제
+//! ```rust
+//! # #[cfg(feature = "proptest")]
+//! # {
+//! use proptest::prelude::*;
+//! use xingapi::data::strategy;
+//!
+//! proptest!(|(sample in strategy::sample())| {
+//!     prop_assert_eq!(sample.raw_data.decode(&sample.tr_layout).unwrap(), sample.data);
+//! });
+//! # }
+//! ```
+
+use super::{encode_raw, encoding, Block, Data, DataType, RawData};
+use crate::layout::{BlockLayout, BlockType, FieldLayout, FieldType, TrLayout, TrType};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// 디코딩 시 앞뒤의 공백이 제거되므로 공백은 사용하지 않습니다.
+const CHARS: &[char] = &['0', '9', 'A', 'z', '-', '.', '+', '가', '힣', '삼'];
+
+/// 생성된 레이아웃과 데이터
+///
+/// `raw_data`를 `tr_layout`으로 디코딩하면 `data`와 같습니다.
+#[derive(Clone, Debug)]
+pub struct Sample {
+    /// 출력 블록만 있는 조회 TR 레이아웃
+    pub tr_layout: TrLayout,
+    /// 레이아웃에 맞는 출력 데이터
+    pub data: Data,
+    /// 데이터를 레이아웃으로 인코딩한 응답 데이터
+    pub raw_data: RawData,
+}
+
+// 생성기가 사용하는 무작위 값
+trait Source {
+    // `0..n` 범위의 값을 반환합니다.
+    fn below(&mut self, n: usize) -> usize;
+
+    fn bool(&mut self) -> bool;
+}
+
+// xorshift 의사 난수 생성기
+#[cfg(any(test, feature = "proptest"))]
+struct Rng(u64);
+
+#[cfg(any(test, feature = "proptest"))]
+impl Rng {
+    // 상태가 0이면 항상 0을 반환하므로 최하위 비트를 설정합니다.
+    fn new(seed: u64) -> Self {
+        Self(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(any(test, feature = "proptest"))]
+impl Source for Rng {
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn bool(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+}
+
+// 입력 데이터가 부족한 경우 가장 작은 값을 반환합니다.
+#[cfg(feature = "arbitrary")]
+impl Source for arbitrary::Unstructured<'_> {
+    fn below(&mut self, n: usize) -> usize {
+        self.choose_index(n).unwrap_or(0)
+    }
+
+    fn bool(&mut self) -> bool {
+        self.arbitrary().unwrap_or(false)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "arbitrary")))]
+impl<'a> arbitrary::Arbitrary<'a> for Sample {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(gen_sample(u))
+    }
+}
+
+/// 무작위 [`Sample`]을 생성하는 `proptest`의 전략을 반환합니다.
+///
+/// 시드로부터 생성하므로 실패한 경우 축소되지 않습니다.
+#[cfg(feature = "proptest")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "proptest")))]
+pub fn sample() -> impl proptest::strategy::Strategy<Value = Sample> {
+    use proptest::arbitrary::any;
+    use proptest::strategy::Strategy;
+
+    any::<u64>()
+        .no_shrink()
+        .prop_map(|seed| gen_sample(&mut Rng::new(seed)))
+}
+
+fn gen_field_layout(src: &mut impl Source, index: usize) -> FieldLayout {
+    let field_type = match src.below(3) {
+        0 => FieldType::Char,
+        1 => FieldType::Int,
+        _ => FieldType::Double,
+    };
+
+    FieldLayout::new(
+        &format!("field{}", index),
+        field_type,
+        1 + src.below(12),
+        None,
+    )
+}
+
+fn gen_tr_layout(src: &mut impl Source) -> TrLayout {
+    let attr_byte = src.bool();
+    let block_mode = src.bool();
+
+    let out_blocks = (0..1 + src.below(3))
+        .map(|i| {
+            let fields = (0..1 + src.below(5))
+                .map(|j| gen_field_layout(src, j))
+                .collect();
+            let name = match i {
+                0 => "t0000OutBlock".to_owned(),
+                _ => format!("t0000OutBlock{}", i),
+            };
+
+            BlockLayout::new(&name, "출력", BlockType::Output, i > 0, fields, attr_byte)
+        })
+        .collect();
+
+    TrLayout {
+        tr_type: TrType::Func,
+        desc: "테스트".into(),
+        code: "t0000".into(),
+        attr_byte,
+        block_mode,
+        header_type: None,
+        params: Vec::new(),
+        in_blocks: Vec::new(),
+        out_blocks,
+    }
+}
+
+fn gen_field(src: &mut impl Source, field_layout: &FieldLayout) -> String {
+    let mut field = String::new();
+    let mut len = src.below(field_layout.len + 1);
+
+    while len > 0 {
+        let c = CHARS[src.below(CHARS.len())];
+        let c_len = encoding().encode(c.encode_utf8(&mut [0; 4])).0.len();
+        if c_len > len {
+            break;
+        }
+
+        field.push(c);
+        len -= c_len;
+    }
+
+    field
+}
+
+fn gen_block(src: &mut impl Source, block_layout: &BlockLayout) -> HashMap<Arc<str>, String> {
+    block_layout
+        .fields
+        .iter()
+        .map(|field_layout| (field_layout.name.clone(), gen_field(src, field_layout)))
+        .collect()
+}
+
+fn gen_data(src: &mut impl Source, tr_layout: &TrLayout) -> Data {
+    let blocks = tr_layout
+        .out_blocks
+        .iter()
+        .map(|block_layout| {
+            let block = if block_layout.occurs {
+                Block::Array(
+                    (0..src.below(4))
+                        .map(|_| gen_block(src, block_layout))
+                        .collect(),
+                )
+            } else {
+                Block::Block(gen_block(src, block_layout))
+            };
+
+            (block_layout.name.clone(), block)
+        })
+        .collect();
+
+    Data {
+        tr_code: tr_layout.code.clone(),
+        data_type: DataType::Output,
+        blocks,
+    }
+}
+
+fn gen_sample(src: &mut impl Source) -> Sample {
+    let tr_layout = gen_tr_layout(src);
+    let data = gen_data(src, &tr_layout);
+
+    // 필드의 길이를 넘지 않도록 생성하므로 항상 인코딩할 수 있습니다.
+    let raw_data = encode_raw(&data, &tr_layout).unwrap();
+
+    Sample {
+        tr_layout,
+        data,
+        raw_data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gen_sample, Rng};
+    use crate::data::decode;
+
+    const CASES: usize = 1000;
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..CASES {
+            let sample = gen_sample(&mut rng);
+            assert_eq!(
+                decode(&sample.tr_layout, sample.raw_data).unwrap(),
+                sample.data,
+                "{:?}",
+                sample.tr_layout
+            );
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_round_trip_arbitrary() {
+        use super::Sample;
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..4096u32).map(|i| (i * 31 % 251) as u8).collect();
+        let mut u = Unstructured::new(&bytes);
+
+        while !u.is_empty() {
+            let sample = Sample::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                sample.raw_data.decode(&sample.tr_layout).unwrap(),
+                sample.data
+            );
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_round_trip_proptest(sample in super::sample()) {
+            proptest::prop_assert_eq!(
+                sample.raw_data.decode(&sample.tr_layout).unwrap(),
+                sample.data
+            );
+        }
+    }
+}
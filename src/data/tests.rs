@@ -2,16 +2,19 @@
 
 #![cfg(all(test, windows))]
 
-use super::{decode_block, decode_block_array, decode_non_block, encode, Block, Data, DataType};
+use super::{
+    decode_block, decode_block_array, decode_non_block, encode, Block, Data, DataType, EncodeError,
+};
 use crate::hashmap;
 use crate::layout::{self, HeaderType, TrLayout};
 
 use hex_literal::hex;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 lazy_static! {
-    static ref LAYOUT_TBL: HashMap<String, TrLayout> =
+    static ref LAYOUT_TBL: HashMap<String, Arc<TrLayout>> =
         layout::load_dir("C:\\eBEST\\xingAPI\\Res").unwrap();
 }
 
@@ -168,11 +171,7 @@ fn test_decode_t1101() {
 
     let block = decode_block(
         tr_layout,
-        tr_layout
-            .out_blocks
-            .iter()
-            .find(|b| b.name == "t1101OutBlock")
-            .unwrap(),
+        tr_layout.out_block("t1101OutBlock").unwrap(),
         &t1101_data,
     )
     .unwrap();
@@ -282,11 +281,7 @@ fn test_decode_t1104() {
 
     let block = decode_block_array(
         tr_layout,
-        tr_layout
-            .out_blocks
-            .iter()
-            .find(|b| b.name == "t1104OutBlock1")
-            .unwrap(),
+        tr_layout.out_block("t1104OutBlock1").unwrap(),
         T1104_DATA,
     )
     .unwrap();
@@ -327,11 +322,7 @@ fn test_decode_t1764() {
 
     let block = decode_block_array(
         tr_layout,
-        tr_layout
-            .out_blocks
-            .iter()
-            .find(|b| b.name == "t1764OutBlock")
-            .unwrap(),
+        tr_layout.out_block("t1764OutBlock").unwrap(),
         &t1764_data,
     )
     .unwrap();
@@ -386,3 +377,296 @@ fn test_encode_t1104() {
         hex!("30 39 36 35 33 30 31 00 30 31 31 31 00 00 00 00 00 00 00")
     );
 }
+
+#[test]
+fn test_decode_empty_block_array() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,block;
+            BEGIN_DATA_MAP
+            t0000OutBlock1,출력,output,occurs;
+            begin
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+    let block_layout = &tr_layout.out_blocks[0];
+
+    assert_eq!(
+        decode_block_array(&tr_layout, block_layout, &[]).unwrap(),
+        Block::Array(vec![])
+    );
+    assert!(decode_block_array(&tr_layout, block_layout, b"0").is_err());
+}
+
+#[test]
+fn test_fill_defaults() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input;
+            begin
+                코드,code,code,char,6;
+                수량,qty,qty,long,3;
+            end
+            t0000InBlock1,입력1,input,occurs;
+            begin
+                가격,price,price,double,4.1;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let mut data = Data {
+        tr_code: "t0000".into(),
+        data_type: DataType::Input,
+        blocks: hashmap! {
+            "t0000InBlock" => Block::Block(hashmap! {
+                "code" => "096530",
+            }),
+        },
+    };
+    assert!(encode(&data, &tr_layout).is_err());
+
+    data.fill_defaults(&tr_layout).unwrap();
+    assert_eq!(data.blocks["t0000InBlock"]["qty"], *"000");
+    assert_eq!(data.blocks["t0000InBlock1"], Block::Array(vec![]));
+    assert_eq!(encode(&data, &tr_layout).unwrap(), b"09653000000000");
+}
+
+#[test]
+fn test_ordered_fields() {
+    use super::ordered_fields;
+
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+                이름,hname,name,char,20;
+                가격,price,price,long,8;
+            end
+            t0000OutBlock1,출력1,output,occurs;
+            begin
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let data = Data {
+        tr_code: "t0000".into(),
+        data_type: DataType::Output,
+        blocks: hashmap! {
+            "t0000OutBlock1" => Block::Array(vec![
+                hashmap! { "price" => "1" },
+                hashmap! { "price" => "2" },
+            ]),
+            "t0000OutBlock" => Block::Block(hashmap! {
+                "price" => "100",
+                "hname" => "이베스트",
+                "code" => "078020",
+            }),
+        },
+    };
+
+    let blocks: Vec<_> = data.ordered_blocks(&tr_layout).collect();
+    assert_eq!(blocks[0].0.name, "t0000OutBlock");
+    assert_eq!(blocks[1].0.name, "t0000OutBlock1");
+
+    let fields: Vec<_> = ordered_fields(blocks[0].0, &blocks[0].1.rows()[0]).collect();
+    assert_eq!(
+        fields,
+        [("code", "078020"), ("name", "이베스트"), ("price", "100")]
+    );
+    assert_eq!(blocks[1].1.rows().len(), 2);
+}
+
+#[test]
+fn test_apply_points() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                가격,price,price,long,8;
+                등락율,diff,diff,float,6.2;
+            end
+            t0000OutBlock1,출력1,output,occurs;
+            begin
+                비율,rate,rate,float,6.3;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let mut data = Data {
+        tr_code: "t0000".into(),
+        data_type: DataType::Output,
+        blocks: hashmap! {
+            "t0000OutBlock" => Block::Block(hashmap! {
+                "price" => "00081000",
+                "diff" => "-00125",
+            }),
+            "t0000OutBlock1" => Block::Array(vec![
+                hashmap! { "rate" => "5" },
+                hashmap! { "rate" => "1.5" },
+                hashmap! { "rate" => "" },
+            ]),
+        },
+    };
+
+    data.apply_points(&tr_layout);
+
+    assert_eq!(data.blocks["t0000OutBlock"]["price"], *"00081000");
+    assert_eq!(data.blocks["t0000OutBlock"]["diff"], *"-1.25");
+    assert_eq!(data.blocks["t0000OutBlock1"][0]["rate"], *"0.005");
+    assert_eq!(data.blocks["t0000OutBlock1"][1]["rate"], *"1.5");
+    assert_eq!(data.blocks["t0000OutBlock1"][2]["rate"], *"");
+}
+
+#[test]
+fn test_encode_into() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input;
+            begin
+                이름,name,name,char,6;
+            end
+            t0000InBlock1,입력1,input,occurs;
+            begin
+                코드,code,code,char,3;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let mut data = crate::data!("t0000", input {
+        t0000InBlock { name: "한글" },
+        t0000InBlock1 [ { code: "A" }, { code: "BC" } ],
+    });
+
+    let mut buf = b"xx".to_vec();
+    data.encode_into(&tr_layout, &mut buf).unwrap();
+    assert_eq!(buf, b"xx\xc7\xd1\xb1\xdb\0\0\x0000002A\0\0\0BC\0\0");
+    assert_eq!(encode(&data, &tr_layout).unwrap(), buf[2..]);
+
+    data.blocks
+        .get_mut("t0000InBlock1")
+        .unwrap()
+        .as_array_mut()
+        .unwrap()[1]
+        .insert("code".into(), "DEFG".into());
+    let mut buf = b"xx".to_vec();
+    assert!(matches!(
+        data.encode_into(&tr_layout, &mut buf),
+        Err(EncodeError::ExceedFieldLength { .. })
+    ));
+    assert_eq!(buf, b"xx");
+}
+
+#[test]
+fn test_decode_large_block_array() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output,occurs;
+            begin
+                코드,code,code,char,6;
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let rows: Vec<_> = (0..5000)
+        .map(|i| hashmap! { "code" => format!("{:06}", i), "price" => (i * 10).to_string() })
+        .collect();
+    let data = Data {
+        tr_code: "t0000".into(),
+        data_type: DataType::Output,
+        blocks: hashmap! { "t0000OutBlock" => Block::Array(rows) },
+    };
+
+    let raw_data = encode(&data, &tr_layout).unwrap();
+    let decoded = decode_non_block(&tr_layout, DataType::Output, &raw_data).unwrap();
+    assert_eq!(decoded, data);
+
+    // 행마다 필드 이름을 복사하지 않고 레이아웃의 이름을 공유합니다.
+    let name = &tr_layout.out_blocks[0].fields[0].name;
+    assert!(decoded.blocks["t0000OutBlock"]
+        .rows()
+        .iter()
+        .all(|fields| fields.keys().any(|key| Arc::ptr_eq(key, name))));
+
+    let mut raw_data = raw_data;
+    raw_data[5 + 4321 * 16] = 0xff;
+    assert!(decode_non_block(&tr_layout, DataType::Output, &raw_data).is_err());
+}
+
+#[test]
+fn test_fixed_block_array() {
+    let tr_layout: TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input,occurs 2;
+            begin
+                코드,code,code,char,3;
+            end
+            t0000OutBlock,출력,output,occurs 2;
+            begin
+                가격,price,price,long,4;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let mut data = crate::data!("t0000", input {
+        t0000InBlock [ { code: "A" } ],
+    });
+    assert!(matches!(
+        encode(&data, &tr_layout),
+        Err(EncodeError::MismatchArrayLength { .. })
+    ));
+
+    data.fill_defaults(&tr_layout).unwrap();
+    assert_eq!(encode(&data, &tr_layout).unwrap(), b"A\0\0   ");
+    assert_eq!(tr_layout.expected_request_len(|_| 0), 6);
+
+    assert_eq!(
+        decode_non_block(&tr_layout, DataType::Output, b"0100 200").unwrap(),
+        Data {
+            tr_code: "t0000".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t0000OutBlock" => Block::Array(vec![
+                    hashmap! { "price" => "0100" },
+                    hashmap! { "price" => "200" },
+                ]),
+            },
+        }
+    );
+    assert!(decode_non_block(&tr_layout, DataType::Output, b"0100").is_err());
+}
@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{encoding, Block, Data, DataType, EncodeError};
+use crate::layout::{BlockLayout, TrLayout};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 데이터 검사에서 발견된 문제
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    /// 배열 블록에서 문제가 발견된 행의 번호
+    pub row: Option<usize>,
+    /// 인코딩 시 발생할 에러
+    pub error: EncodeError,
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.row {
+            Some(row) => write!(f, "{} (row {})", self.error, row),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+impl Data {
+    /// 데이터를 레이아웃과 비교하여 발견된 모든 문제를 반환합니다.
+    ///
+    /// 인코딩은 첫 번째 문제에서 실패하지만 이 함수는 누락되거나 알 수 없는
+    /// 블록과 필드, 블록 타입, 필드 길이, EUC-KR로 표현할 수 없는 문자를 모두
+    /// 검사합니다. 문제가 없는 경우 빈 벡터를 반환합니다.
+    pub fn validate(&self, tr_layout: &TrLayout) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut push = |row, error| issues.push(ValidationIssue { row, error });
+
+        if self.tr_code != tr_layout.code {
+            push(None, EncodeError::MismatchLayout);
+            return issues;
+        }
+
+        let block_layouts = match self.data_type {
+            DataType::Input => &tr_layout.in_blocks,
+            DataType::Output => &tr_layout.out_blocks,
+        };
+
+        let mut names: Vec<_> = self.blocks.keys().collect();
+        names.sort_unstable();
+
+        for name in names {
+            if !block_layouts.iter().any(|b| &b.name == name) {
+                push(
+                    None,
+                    EncodeError::UnknownBlock {
+                        block: name.clone(),
+                    },
+                );
+            }
+        }
+
+        for block_layout in block_layouts {
+            let block = match self.blocks.get(&block_layout.name) {
+                Some(block) => block,
+                None => {
+                    push(
+                        None,
+                        EncodeError::MissingBlock {
+                            block: block_layout.name.clone(),
+                        },
+                    );
+                    continue;
+                }
+            };
+
+            match block {
+                Block::Block(fields) if !block_layout.occurs => {
+                    validate_fields(block_layout, fields, None, &mut push);
+                }
+                Block::Array(arr) if block_layout.occurs => {
+                    if block_layout
+                        .occurs_count
+                        .is_some_and(|count| count != arr.len())
+                    {
+                        push(
+                            None,
+                            EncodeError::MismatchArrayLength {
+                                block: block_layout.name.clone(),
+                            },
+                        );
+                    } else if !tr_layout.block_mode && arr.len() >= 100000 {
+                        push(
+                            None,
+                            EncodeError::ExceedArrayLength {
+                                block: block_layout.name.clone(),
+                            },
+                        );
+                    }
+
+                    for (row, fields) in arr.iter().enumerate() {
+                        validate_fields(block_layout, fields, Some(row), &mut push);
+                    }
+                }
+                _ => push(
+                    None,
+                    EncodeError::MismatchBlockType {
+                        block: block_layout.name.clone(),
+                    },
+                ),
+            }
+        }
+
+        issues
+    }
+}
+
+fn validate_fields<F>(
+    block_layout: &BlockLayout,
+    fields: &HashMap<Arc<str>, String>,
+    row: Option<usize>,
+    push: &mut F,
+) where
+    F: FnMut(Option<usize>, EncodeError),
+{
+    let block = || block_layout.name.clone();
+
+    let mut names: Vec<_> = fields.keys().collect();
+    names.sort_unstable();
+
+    for name in names {
+        if block_layout.field(name).is_none() {
+            push(
+                row,
+                EncodeError::UnknownField {
+                    block: block(),
+                    field: name.to_string(),
+                },
+            );
+        }
+    }
+
+    for field_layout in &block_layout.fields {
+        let field = || field_layout.name.to_string();
+
+        let value = match fields
+            .get(&field_layout.name)
+            .or_else(|| fields.get(field_layout.name_old.as_str()))
+        {
+            Some(value) => value,
+            None => {
+                push(
+                    row,
+                    EncodeError::MissingField {
+                        block: block(),
+                        field: field(),
+                    },
+                );
+                continue;
+            }
+        };
+
+        let (encoded, had_errors) = encoding().encode(value);
+        if had_errors {
+            push(
+                row,
+                EncodeError::UnencodableField {
+                    block: block(),
+                    field: field(),
+                },
+            );
+        } else if encoded.len() > field_layout.len {
+            push(
+                row,
+                EncodeError::ExceedFieldLength {
+                    block: block(),
+                    field: field(),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{Block, Data, DataType, EncodeError};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_validate() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000InBlock,입력,input;
+                begin
+                    코드,code,code,char,6;
+                    이름,name,name,char,4;
+                end
+                t0000InBlock1,입력1,input,occurs;
+                begin
+                    가격,price,price,long,3;
+                end
+                t0000InBlock2,입력2,input;
+                begin
+                    구분,gubun,gubun,char,1;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let data = Data {
+            tr_code: "t0000".into(),
+            data_type: DataType::Input,
+            blocks: hashmap! {
+                "t0000InBlock" => Block::Block(hashmap! {
+                    "code" => "0965301",
+                    "name" => "😀",
+                    "extra" => "",
+                }),
+                "t0000InBlock1" => Block::Array(vec![
+                    hashmap! { "price" => "100" },
+                    hashmap! {},
+                ]),
+                "t0000InBlock3" => Block::Block(hashmap! {}),
+            },
+        };
+
+        let issues = data.validate(&tr_layout);
+        let messages: Vec<_> = issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "unknown block: t0000InBlock3",
+                "unknown extra field in t0000InBlock block",
+                "reached max length of code field in t0000InBlock block",
+                "cannot encode name field in t0000InBlock block as euc-kr",
+                "missing price field in t0000InBlock1 block (row 1)",
+                "missing t0000InBlock2 block",
+            ]
+        );
+        assert!(matches!(issues[4].error, EncodeError::MissingField { .. }));
+
+        let data = Data::builder("t0000")
+            .block("t0000InBlock")
+            .field("code", "096530")
+            .field("name", "한글")
+            .array("t0000InBlock1")
+            .block("t0000InBlock2")
+            .field("gubun", "0")
+            .build(&tr_layout)
+            .unwrap();
+        assert!(data.validate(&tr_layout).is_empty());
+    }
+}
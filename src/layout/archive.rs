@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// RES 파일을 묶은 ZIP 파일을 `zip` 크레이트로 읽습니다.
+
+use super::conflict::{conflict_policy, LayoutTable};
+use super::error::{ArchiveError, ArchiveErrorKind, LoadError};
+use super::{decode_res, TrLayout};
+
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use std::collections::HashMap;
+use std::io::{self, Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+// 압축을 해제한 RES 파일 하나의 최대 크기
+const MAX_ENTRY_LEN: u64 = 16 * 1024 * 1024;
+
+/// ZIP 파일에 포함된 RES 파일에서 TR 레이아웃을 모두 불러옵니다.
+///
+/// 압축 파일 안의 디렉터리 구조와 관계없이 확장자가 `res`인 항목을 모두
+/// 불러오며, 압축을 해제한 데이터의 CRC를 검사합니다. 항목 경로는 파싱 에러를
+/// 보고할 때 사용합니다.
+pub fn load_zip<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    load_zip_bytes(&std::fs::read(path)?)
+}
+
+/// 메모리에 있는 ZIP 파일에서 TR 레이아웃을 모두 불러옵니다.
+///
+/// [`load_zip()`]과 같으며 [`include_bytes!`]로 실행 파일에 포함한 압축
+/// 파일에서 불러올 때 사용합니다.
+pub fn load_zip_bytes(data: &[u8]) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|err| archive_error(err, None))?;
+
+    // 충돌 처리 방식이 항목 순서에 따라 달라지지 않도록 정렬합니다.
+    let mut names = Vec::new();
+    for name in archive.file_names() {
+        let name = name.map_err(|err| archive_error(err, None))?;
+        if Path::new(name.as_ref()).extension() == Some("res".as_ref()) {
+            names.push(name.into_owned());
+        }
+    }
+    names.sort_unstable();
+
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
+
+    for name in names {
+        let raw_data = extract(&mut archive, &name)?;
+        let (text, _) =
+            decode_res(&raw_data).ok_or_else(|| LoadError::Encoding(name.clone().into()))?;
+        let layout = text
+            .parse()
+            .map_err(|err| LoadError::Parse(name.clone().into(), err))?;
+        layout_tbl.insert(layout, None)?;
+    }
+
+    Ok(layout_tbl.into_inner())
+}
+
+fn extract(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Vec<u8>, LoadError> {
+    let error = |kind| LoadError::Archive(ArchiveError::new(kind, Some(name)));
+
+    let mut file = archive
+        .by_name(name)
+        .map_err(|err| archive_error(err, Some(name)))?;
+    if file.size() > MAX_ENTRY_LEN {
+        return Err(error(ArchiveErrorKind::Unsupported));
+    }
+
+    // 압축을 해제하는 중에 발생한 에러는 CRC 불일치를 포함하여 모두 손상된
+    // 데이터로 처리합니다.
+    let mut raw_data = Vec::with_capacity(file.size() as usize);
+    match file.by_ref().take(MAX_ENTRY_LEN).read_to_end(&mut raw_data) {
+        Ok(_) if raw_data.len() as u64 == file.size() => Ok(raw_data),
+        Ok(_) => Err(error(ArchiveErrorKind::Malformed)),
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            Err(error(ArchiveErrorKind::Checksum))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn archive_error(err: ZipError, entry: Option<&str>) -> LoadError {
+    let kind = match err {
+        ZipError::Io(err) => return err.into(),
+        ZipError::UnsupportedArchive(_)
+        | ZipError::CompressionMethodNotSupported(_)
+        | ZipError::InvalidPassword => ArchiveErrorKind::Unsupported,
+        _ => ArchiveErrorKind::Malformed,
+    };
+
+    LoadError::Archive(ArchiveError::new(kind, entry))
+}
@@ -0,0 +1,605 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::conflict::{conflict_policy, ConflictPolicy, LayoutTable};
+use super::error::LoadError;
+use super::{BlockLayout, BlockType, FieldLayout, FieldType, HeaderType, TrLayout, TrType};
+use crate::codec::{
+    get_str, get_u32, get_u64, get_u8, invalid_data, put_str, put_u32, put_u64, take,
+};
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+const MAGIC: &[u8; 4] = b"XLAY";
+const MANIFEST_MAGIC: &[u8; 4] = b"XLAM";
+const VERSION: u8 = 3;
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// 지정된 디렉터리에서 TR 레이아웃을 모두 불러오며 파싱 결과를 캐시 파일에
+/// 저장합니다.
+///
+/// 디렉터리에 있는 RES 파일의 이름, 크기, 수정 시각과 충돌 처리 방식이
+/// 캐시를 만들 때와 같은 경우 RES 파일을 파싱하지 않고 캐시 파일에서
+/// 불러옵니다. 그렇지 않은 경우
+/// [`load_dir()`](super::load_dir)로 불러온 후 캐시 파일을 다시 씁니다.
+///
+/// 캐시 파일을 읽거나 쓰는데 실패하더라도 에러를 반환하지 않습니다.
+pub fn load_dir_cached<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    cache_path: Q,
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let fingerprint = fingerprint(path.as_ref(), conflict_policy())?;
+
+    if let Some(layout_tbl) = fs::read(&cache_path)
+        .ok()
+        .and_then(|buf| decode_cache(&buf, fingerprint).ok())
+    {
+        return Ok(layout_tbl);
+    }
+
+    let layout_tbl = super::load_dir(&path)?;
+
+    let mut buf = Vec::new();
+    encode_cache(&mut buf, fingerprint, &layout_tbl);
+
+    if let Some(parent) = cache_path.as_ref().parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&cache_path, buf);
+
+    Ok(layout_tbl)
+}
+
+/// 지정된 디렉터리에서 TR 레이아웃을 모두 불러오며 변경된 RES 파일만
+/// 파싱합니다.
+///
+/// 파일마다 이름, 크기, 수정 시각, 내용의 해시와 파싱 결과를 캐시 파일에
+/// 저장합니다. 크기와 수정 시각이 같은 파일은 읽지 않으며, 다르더라도 내용의
+/// 해시가 같은 경우 파싱하지 않습니다. 파일 하나가 추가되거나 변경되어도
+/// 나머지 파일은 다시 파싱하지 않으므로 [`load_dir_cached()`]보다 캐시를 다시
+/// 만드는 비용이 작습니다.
+///
+/// 캐시 파일을 읽거나 쓰는데 실패하더라도 에러를 반환하지 않습니다.
+pub fn load_dir_incremental<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    cache_path: Q,
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let mut manifest = fs::read(&cache_path)
+        .ok()
+        .and_then(|buf| decode_manifest(&buf).ok())
+        .unwrap_or_default();
+
+    let mut paths = super::res_files(path)?;
+    paths.sort_unstable();
+
+    let mut entries = Vec::with_capacity(paths.len());
+    let mut changed = Vec::new();
+
+    for path in &paths {
+        let meta = fs::metadata(path)?;
+        let name = file_name(path);
+        let modified = modified_nanos(&meta);
+
+        let cached = manifest.remove(&name);
+        if let Some(entry) = cached.as_ref() {
+            if entry.len == meta.len() && entry.modified == modified {
+                entries.push(cached);
+                continue;
+            }
+        }
+
+        let hash = fnv1a(FNV_OFFSET, &fs::read(path)?);
+        match cached.filter(|entry| entry.hash == hash) {
+            Some(entry) => entries.push(Some(ManifestEntry {
+                len: meta.len(),
+                modified,
+                ..entry
+            })),
+            None => {
+                entries.push(None);
+                changed.push((entries.len() - 1, meta.len(), modified, hash));
+            }
+        }
+    }
+
+    let parsed = super::parse_files(
+        changed.iter().map(|(i, ..)| paths[*i].clone()).collect(),
+        &mut |_| {},
+    );
+
+    for ((i, len, modified, hash), (_, result)) in changed.into_iter().zip(parsed) {
+        entries[i] = Some(ManifestEntry {
+            len,
+            modified,
+            hash,
+            layout: result?.layout,
+        });
+    }
+
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
+    let mut buf = Vec::new();
+    buf.extend_from_slice(MANIFEST_MAGIC);
+    buf.push(VERSION);
+    put_u32(&mut buf, entries.len() as u32);
+
+    for (path, entry) in paths.iter().zip(entries) {
+        let entry = entry.unwrap();
+        put_manifest_entry(&mut buf, &file_name(path), &entry);
+
+        let modified = UNIX_EPOCH + std::time::Duration::from_nanos(entry.modified);
+        layout_tbl.insert(entry.layout, Some(modified))?;
+    }
+
+    if let Some(parent) = cache_path.as_ref().parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&cache_path, buf);
+
+    Ok(layout_tbl.into_inner())
+}
+
+// 파일 하나의 정보와 파싱 결과
+struct ManifestEntry {
+    len: u64,
+    modified: u64,
+    hash: u64,
+    layout: TrLayout,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn modified_nanos(meta: &fs::Metadata) -> u64 {
+    meta.modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |dur| dur.as_nanos() as u64)
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+// RES 파일 목록의 이름, 크기, 수정 시각과 충돌 처리 방식으로 FNV-1a 해시를
+// 계산합니다.
+//
+// 사용자 지정 충돌 처리 방식은 함수의 주소로 구분하므로 프로세스마다 다른
+// 값이 될 수 있습니다.
+fn fingerprint(path: &Path, policy: ConflictPolicy) -> io::Result<u64> {
+    let mut entries = Vec::new();
+
+    for ent in fs::read_dir(path)? {
+        let ent = ent?;
+        if !super::is_res_file(&ent.path()) {
+            continue;
+        }
+
+        let meta = ent.metadata()?;
+        entries.push((ent.file_name(), meta.len(), modified_nanos(&meta)));
+    }
+
+    entries.sort_unstable();
+
+    let mut hash = FNV_OFFSET;
+    let mut write = |bytes: &[u8]| hash = fnv1a(hash, bytes);
+
+    match policy {
+        ConflictPolicy::Error => write(&[0]),
+        ConflictPolicy::FirstWins => write(&[1]),
+        ConflictPolicy::NewestFile => write(&[2]),
+        ConflictPolicy::Custom(resolve) => {
+            write(&[3]);
+            write(&(resolve as usize).to_le_bytes());
+        }
+    }
+
+    for (name, len, modified) in &entries {
+        write(name.to_string_lossy().as_bytes());
+        write(&[0]);
+        write(&len.to_le_bytes());
+        write(&modified.to_le_bytes());
+    }
+
+    Ok(hash)
+}
+
+fn put_usize(buf: &mut Vec<u8>, value: usize) {
+    put_u64(buf, value as u64);
+}
+
+fn encode_cache(buf: &mut Vec<u8>, fingerprint: u64, layout_tbl: &HashMap<String, Arc<TrLayout>>) {
+    buf.extend_from_slice(MAGIC);
+    buf.push(VERSION);
+    put_u64(buf, fingerprint);
+    put_u32(buf, layout_tbl.len() as u32);
+
+    for tr_layout in layout_tbl.values() {
+        put_layout(buf, tr_layout);
+    }
+}
+
+fn put_layout(buf: &mut Vec<u8>, tr_layout: &TrLayout) {
+    buf.push(match tr_layout.tr_type {
+        TrType::Func => 0,
+        TrType::Feed => 1,
+    });
+    put_str(buf, &tr_layout.desc);
+    put_str(buf, &tr_layout.code);
+    buf.push(tr_layout.attr_byte as u8);
+    buf.push(tr_layout.block_mode as u8);
+    buf.push(match tr_layout.header_type {
+        None => 0,
+        Some(HeaderType::A) => 1,
+        Some(HeaderType::B) => 2,
+        Some(HeaderType::C) => 3,
+        Some(HeaderType::D) => 4,
+    });
+
+    put_u32(buf, tr_layout.params.len() as u32);
+    for (name, value) in &tr_layout.params {
+        put_str(buf, name);
+        put_str(buf, value);
+    }
+
+    for blocks in [&tr_layout.in_blocks, &tr_layout.out_blocks] {
+        put_u32(buf, blocks.len() as u32);
+        for block in blocks {
+            put_block(buf, block);
+        }
+    }
+}
+
+fn put_block(buf: &mut Vec<u8>, block: &BlockLayout) {
+    put_str(buf, &block.name);
+    put_str(buf, &block.desc);
+    buf.push(match block.block_type {
+        BlockType::Input => 0,
+        BlockType::Output => 1,
+    });
+    buf.push(block.occurs as u8);
+    match block.occurs_count {
+        None => buf.push(0),
+        Some(count) => {
+            buf.push(1);
+            put_usize(buf, count);
+        }
+    }
+    put_usize(buf, block.len);
+    put_u32(buf, block.fields.len() as u32);
+
+    for field in &block.fields {
+        put_str(buf, &field.desc);
+        put_str(buf, &field.name_old);
+        put_str(buf, &field.name);
+        match &field.field_type {
+            FieldType::Char => buf.push(0),
+            FieldType::Date => buf.push(1),
+            FieldType::Int => buf.push(2),
+            FieldType::Float => buf.push(3),
+            FieldType::Double => buf.push(4),
+            FieldType::Unknown(name) => {
+                buf.push(5);
+                put_str(buf, name);
+            }
+        }
+        put_usize(buf, field.len);
+        match field.point {
+            None => buf.push(0),
+            Some(point) => {
+                buf.push(1);
+                put_usize(buf, point);
+            }
+        }
+    }
+}
+
+fn get_bool(buf: &mut &[u8]) -> io::Result<bool> {
+    match get_u8(buf)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(invalid_data()),
+    }
+}
+
+fn get_usize(buf: &mut &[u8]) -> io::Result<usize> {
+    get_u64(buf)?.try_into().map_err(|_| invalid_data())
+}
+
+fn put_manifest_entry(buf: &mut Vec<u8>, name: &str, entry: &ManifestEntry) {
+    put_str(buf, name);
+    put_u64(buf, entry.len);
+    put_u64(buf, entry.modified);
+    put_u64(buf, entry.hash);
+    put_layout(buf, &entry.layout);
+}
+
+fn decode_manifest(mut buf: &[u8]) -> io::Result<HashMap<String, ManifestEntry>> {
+    let buf = &mut buf;
+
+    if take(buf, 4)? != MANIFEST_MAGIC || get_u8(buf)? != VERSION {
+        return Err(invalid_data());
+    }
+
+    let len = get_u32(buf)? as usize;
+    let mut manifest = HashMap::with_capacity(len.min(buf.len()));
+
+    for _ in 0..len {
+        let name = get_str(buf)?;
+        let entry = ManifestEntry {
+            len: get_u64(buf)?,
+            modified: get_u64(buf)?,
+            hash: get_u64(buf)?,
+            layout: get_layout(buf)?,
+        };
+        manifest.insert(name, entry);
+    }
+
+    if !buf.is_empty() {
+        return Err(invalid_data());
+    }
+
+    Ok(manifest)
+}
+
+fn decode_cache(mut buf: &[u8], fingerprint: u64) -> io::Result<HashMap<String, Arc<TrLayout>>> {
+    let buf = &mut buf;
+
+    if take(buf, 4)? != MAGIC
+        || get_u8(buf)? != VERSION
+        || take(buf, 8)? != fingerprint.to_le_bytes()
+    {
+        return Err(invalid_data());
+    }
+
+    let len = get_u32(buf)? as usize;
+    let mut layout_tbl = HashMap::with_capacity(len.min(buf.len()));
+
+    for _ in 0..len {
+        let tr_layout = get_layout(buf)?;
+        layout_tbl.insert(tr_layout.code.clone(), Arc::new(tr_layout));
+    }
+
+    if !buf.is_empty() {
+        return Err(invalid_data());
+    }
+
+    Ok(layout_tbl)
+}
+
+fn get_layout(buf: &mut &[u8]) -> io::Result<TrLayout> {
+    let tr_type = match get_u8(buf)? {
+        0 => TrType::Func,
+        1 => TrType::Feed,
+        _ => return Err(invalid_data()),
+    };
+    let desc = get_str(buf)?;
+    let code = get_str(buf)?;
+    let attr_byte = get_bool(buf)?;
+    let block_mode = get_bool(buf)?;
+    let header_type = match get_u8(buf)? {
+        0 => None,
+        1 => Some(HeaderType::A),
+        2 => Some(HeaderType::B),
+        3 => Some(HeaderType::C),
+        4 => Some(HeaderType::D),
+        _ => return Err(invalid_data()),
+    };
+    let params = (0..get_u32(buf)?)
+        .map(|_| Ok((get_str(buf)?, get_str(buf)?)))
+        .collect::<io::Result<_>>()?;
+    let in_blocks = get_blocks(buf)?;
+    let out_blocks = get_blocks(buf)?;
+
+    Ok(TrLayout {
+        tr_type,
+        desc,
+        code,
+        attr_byte,
+        block_mode,
+        header_type,
+        params,
+        in_blocks,
+        out_blocks,
+    })
+}
+
+fn get_blocks(buf: &mut &[u8]) -> io::Result<Vec<BlockLayout>> {
+    let len = get_u32(buf)? as usize;
+    let mut blocks = Vec::with_capacity(len.min(buf.len()));
+
+    for _ in 0..len {
+        let name = get_str(buf)?;
+        let desc = get_str(buf)?;
+        let block_type = match get_u8(buf)? {
+            0 => BlockType::Input,
+            1 => BlockType::Output,
+            _ => return Err(invalid_data()),
+        };
+        let occurs = get_bool(buf)?;
+        let occurs_count = match get_u8(buf)? {
+            0 => None,
+            1 => Some(get_usize(buf)?),
+            _ => return Err(invalid_data()),
+        };
+        let block_len = get_usize(buf)?;
+
+        let len = get_u32(buf)? as usize;
+        let mut fields = Vec::with_capacity(len.min(buf.len()));
+
+        for _ in 0..len {
+            fields.push(FieldLayout {
+                desc: get_str(buf)?,
+                name_old: get_str(buf)?,
+                name: get_str(buf)?.into(),
+                field_type: match get_u8(buf)? {
+                    0 => FieldType::Char,
+                    1 => FieldType::Date,
+                    2 => FieldType::Int,
+                    3 => FieldType::Float,
+                    4 => FieldType::Double,
+                    5 => FieldType::Unknown(get_str(buf)?),
+                    _ => return Err(invalid_data()),
+                },
+                len: get_usize(buf)?,
+                point: match get_u8(buf)? {
+                    0 => None,
+                    1 => Some(get_usize(buf)?),
+                    _ => return Err(invalid_data()),
+                },
+            });
+        }
+
+        blocks.push(BlockLayout {
+            name,
+            desc,
+            block_type,
+            occurs,
+            occurs_count,
+            len: block_len,
+            fields,
+        });
+    }
+
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_cache, decode_manifest, encode_cache, fingerprint, load_dir_cached,
+        load_dir_incremental, put_manifest_entry, put_u32, MANIFEST_MAGIC, VERSION,
+    };
+    use crate::layout::{conflict_policy, ConflictPolicy, TrLayout};
+
+    use std::collections::HashMap;
+    use std::fs;
+    use std::sync::Arc;
+
+    const LAYOUT: &str = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr,headtype=A;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input;
+            begin
+                코드,code,code,char,6;
+            end
+            t0000OutBlock1,출력1,output,occurs;
+            begin
+                가격,price,price,double,10.2;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    #[test]
+    fn test_cache_round_trip() {
+        let tr_layout: TrLayout = LAYOUT.parse().unwrap();
+        let layout_tbl: HashMap<_, _> = [(tr_layout.code.clone(), Arc::new(tr_layout))].into();
+
+        let mut buf = Vec::new();
+        encode_cache(&mut buf, 42, &layout_tbl);
+        assert_eq!(decode_cache(&buf, 42).unwrap(), layout_tbl);
+        assert!(decode_cache(&buf, 43).is_err());
+        assert!(decode_cache(&buf[..buf.len() - 1], 42).is_err());
+    }
+
+    #[test]
+    fn test_load_dir_cached() {
+        let dir = std::env::temp_dir().join(format!("xingapi-cache-{}", std::process::id()));
+        let res_dir = dir.join("Res");
+        let cache_path = dir.join("cache").join("layouts.bin");
+        fs::create_dir_all(&res_dir).unwrap();
+
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(LAYOUT);
+        fs::write(res_dir.join("t0000.res"), &euckr).unwrap();
+
+        let layout_tbl = load_dir_cached(&res_dir, &cache_path).unwrap();
+        assert!(layout_tbl.contains_key("t0000"));
+        let cached = fs::read(&cache_path).unwrap();
+        assert_eq!(
+            decode_cache(&cached, fingerprint(&res_dir, conflict_policy()).unwrap()).unwrap(),
+            layout_tbl
+        );
+
+        // 충돌 처리 방식이 다르면 캐시를 사용하지 않습니다.
+        assert_ne!(
+            fingerprint(&res_dir, ConflictPolicy::Error).unwrap(),
+            fingerprint(&res_dir, ConflictPolicy::FirstWins).unwrap()
+        );
+
+        // RES 파일이 변경되면 캐시를 다시 만듭니다.
+        let layout = LAYOUT.replace("t0000", "t0001");
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(&layout);
+        fs::write(res_dir.join("t0001.res"), &euckr).unwrap();
+
+        let layout_tbl = load_dir_cached(&res_dir, &cache_path).unwrap();
+        assert_eq!(layout_tbl.len(), 2);
+        assert_ne!(fs::read(&cache_path).unwrap(), cached);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_dir_incremental() {
+        let dir = std::env::temp_dir().join(format!("xingapi-incremental-{}", std::process::id()));
+        let res_dir = dir.join("Res");
+        let cache_path = dir.join("layouts.manifest");
+        fs::create_dir_all(&res_dir).unwrap();
+
+        for code in ["t0000", "t0001"] {
+            let layout = LAYOUT.replace("t0000", code);
+            let (euckr, _, _) = encoding_rs::EUC_KR.encode(&layout);
+            fs::write(res_dir.join(format!("{}.res", code)), &euckr).unwrap();
+        }
+
+        let layout_tbl = load_dir_incremental(&res_dir, &cache_path).unwrap();
+        assert_eq!(layout_tbl.len(), 2);
+
+        // 변경되지 않은 파일은 파싱하지 않고 캐시된 결과를 사용합니다.
+        let mut manifest = decode_manifest(&fs::read(&cache_path).unwrap()).unwrap();
+        manifest.get_mut("t0000.res").unwrap().layout.desc = "캐시".into();
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MANIFEST_MAGIC);
+        buf.push(VERSION);
+        put_u32(&mut buf, manifest.len() as u32);
+        for (name, entry) in &manifest {
+            put_manifest_entry(&mut buf, name, entry);
+        }
+        fs::write(&cache_path, buf).unwrap();
+
+        // 변경된 파일만 다시 파싱합니다.
+        let layout = LAYOUT.replace("t0000", "t0001").replace("테스트", "변경");
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(&layout);
+        fs::write(res_dir.join("t0001.res"), &euckr).unwrap();
+
+        let layout_tbl = load_dir_incremental(&res_dir, &cache_path).unwrap();
+        assert_eq!(layout_tbl["t0000"].desc, "캐시");
+        assert_eq!(layout_tbl["t0001"].desc, "변경");
+
+        // 삭제된 파일은 캐시에서도 삭제됩니다.
+        fs::remove_file(res_dir.join("t0001.res")).unwrap();
+        let layout_tbl = load_dir_incremental(&res_dir, &cache_path).unwrap();
+        assert_eq!(layout_tbl.len(), 1);
+        assert_eq!(
+            decode_manifest(&fs::read(&cache_path).unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
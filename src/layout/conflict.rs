@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::error::LoadError;
+use super::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+static CONFLICT_POLICY: RwLock<ConflictPolicy> = RwLock::new(ConflictPolicy::Error);
+
+/// 코드는 같지만 서로 다른 두 레이아웃을 불러온 경우 처리하는 방식
+///
+/// SDK의 Res 디렉터리와 직접 수정한 RES 파일을 함께 불러오는 경우
+/// [`set_conflict_policy()`]로 변경합니다. 파일은 경로 순으로 불러옵니다.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ConflictPolicy {
+    /// [`LoadError::Confilict`]를 반환합니다.
+    #[default]
+    Error,
+    /// 먼저 불러온 레이아웃을 사용합니다.
+    FirstWins,
+    /// 수정 시각이 가장 늦은 파일의 레이아웃을 사용합니다.
+    ///
+    /// 수정 시각을 알 수 없는 경우 먼저 불러온 레이아웃을 사용합니다.
+    NewestFile,
+    /// 먼저 불러온 레이아웃과 나중에 불러온 레이아웃 중 사용할 레이아웃을
+    /// 반환합니다. `None`을 반환하면 [`LoadError::Confilict`]를 반환합니다.
+    Custom(fn(&TrLayout, &TrLayout) -> Option<TrLayout>),
+}
+
+/// 레이아웃이 충돌하는 경우 처리하는 방식을 지정합니다.
+pub fn set_conflict_policy(policy: ConflictPolicy) {
+    *CONFLICT_POLICY
+        .write()
+        .unwrap_or_else(|err| err.into_inner()) = policy;
+}
+
+/// 레이아웃이 충돌하는 경우 처리하는 방식을 반환합니다.
+pub fn conflict_policy() -> ConflictPolicy {
+    *CONFLICT_POLICY
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+// 불러온 레이아웃을 충돌 처리 방식에 따라 모으는 테이블
+pub(crate) struct LayoutTable {
+    policy: ConflictPolicy,
+    layouts: HashMap<String, (TrLayout, Option<SystemTime>)>,
+}
+
+impl LayoutTable {
+    pub fn new(policy: ConflictPolicy) -> Self {
+        Self {
+            policy,
+            layouts: HashMap::new(),
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        layout: TrLayout,
+        modified: Option<SystemTime>,
+    ) -> Result<(), LoadError> {
+        let Some((other, other_modified)) = self.layouts.get_mut(&layout.code) else {
+            self.layouts.insert(layout.code.clone(), (layout, modified));
+            return Ok(());
+        };
+
+        if layout == *other {
+            return Ok(());
+        }
+
+        match self.policy {
+            ConflictPolicy::Error => return Err(LoadError::Confilict(layout.code)),
+            ConflictPolicy::FirstWins => {}
+            ConflictPolicy::NewestFile => {
+                if let (Some(new), Some(old)) = (modified, *other_modified) {
+                    if new > old {
+                        *other = layout;
+                        *other_modified = modified;
+                    }
+                }
+            }
+            ConflictPolicy::Custom(resolve) => match resolve(other, &layout) {
+                Some(resolved) => *other = resolved,
+                None => return Err(LoadError::Confilict(layout.code)),
+            },
+        }
+
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> HashMap<String, Arc<TrLayout>> {
+        self.layouts
+            .into_iter()
+            .map(|(code, (layout, _))| (code, Arc::new(layout)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConflictPolicy, LayoutTable};
+    use crate::layout::{error::LoadError, TrLayout};
+
+    use std::time::{Duration, SystemTime};
+
+    fn layout(desc: &str) -> TrLayout {
+        format!(
+            "
+            BEGIN_FUNCTION_MAP
+                .Func,{},t0000,attr;
+                BEGIN_DATA_MAP
+                END_DATA_MAP
+            END_FUNCTION_MAP
+            ",
+            desc
+        )
+        .parse()
+        .unwrap()
+    }
+
+    fn resolve(policy: ConflictPolicy) -> Result<String, LoadError> {
+        let earlier = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let later = SystemTime::UNIX_EPOCH + Duration::from_secs(2);
+
+        let mut layout_tbl = LayoutTable::new(policy);
+        layout_tbl.insert(layout("a"), Some(earlier))?;
+        layout_tbl.insert(layout("a"), None)?;
+        layout_tbl.insert(layout("b"), Some(later))?;
+        layout_tbl.insert(layout("c"), None)?;
+        Ok(layout_tbl.into_inner()["t0000"].desc.clone())
+    }
+
+    #[test]
+    fn test_conflict_policy() {
+        assert!(matches!(
+            resolve(ConflictPolicy::Error),
+            Err(LoadError::Confilict(code)) if code == "t0000"
+        ));
+        assert_eq!(resolve(ConflictPolicy::FirstWins).unwrap(), "a");
+        assert_eq!(resolve(ConflictPolicy::NewestFile).unwrap(), "b");
+        assert_eq!(
+            resolve(ConflictPolicy::Custom(|_, new| Some(new.clone()))).unwrap(),
+            "c"
+        );
+        assert!(resolve(ConflictPolicy::Custom(|_, _| None)).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! TR 레이아웃 문서 생성 모듈
+//!
+//! 불러온 레이아웃의 설명, 블록, 필드의 타입과 길이, 오프셋을 Markdown이나
+//! HTML 문서로 변환합니다. 사용 중인 RES 파일의 버전과 정확히 일치하는 TR
+//! 목록을 팀에서 함께 볼 수 있습니다.
+//!
+//! ## 예제
+//! ```rust,no_run
+//! use xingapi::layout::{self, docgen};
+//!
+//! let layout_tbl = layout::load_dir("Res")?;
+//! std::fs::write("tr.md", docgen::to_markdown(layout_tbl.values().map(|l| &**l)))?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::{BlockLayout, BlockType, FieldLayout, FieldType, TrLayout, TrType};
+
+use std::fmt::Write;
+
+/// 레이아웃을 TR 코드 순으로 정렬하여 Markdown 문서로 변환합니다.
+pub fn to_markdown<'a, I: IntoIterator<Item = &'a TrLayout>>(layouts: I) -> String {
+    let mut out = String::new();
+
+    for (i, tr_layout) in sorted(layouts).into_iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        write_markdown(&mut out, tr_layout).unwrap();
+    }
+
+    out
+}
+
+/// 레이아웃을 TR 코드 순으로 정렬하여 목차를 포함한 HTML 문서로 변환합니다.
+pub fn to_html<'a, I: IntoIterator<Item = &'a TrLayout>>(layouts: I) -> String {
+    let layouts = sorted(layouts);
+    let mut out = String::new();
+
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>TR 레이아웃</title>\n</head>\n<body>\n<ul>\n");
+    for tr_layout in &layouts {
+        let code = escape_html(&tr_layout.code);
+        writeln!(
+            out,
+            "<li><a href=\"#{}\">{}</a> {}</li>",
+            code,
+            code,
+            escape_html(&tr_layout.desc)
+        )
+        .unwrap();
+    }
+    out.push_str("</ul>\n");
+
+    for tr_layout in layouts {
+        write_html(&mut out, tr_layout).unwrap();
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn sorted<'a, I: IntoIterator<Item = &'a TrLayout>>(layouts: I) -> Vec<&'a TrLayout> {
+    let mut layouts: Vec<_> = layouts.into_iter().collect();
+    layouts.sort_by(|a, b| a.code.cmp(&b.code));
+    layouts
+}
+
+// TR 레이아웃의 속성을 이름과 값의 쌍으로 반환합니다.
+fn summary(tr_layout: &TrLayout) -> Vec<(&'static str, String)> {
+    let yes_no = |value: bool| if value { "예" } else { "아니오" }.to_owned();
+
+    let mut rows = vec![
+        (
+            "타입",
+            match tr_layout.tr_type {
+                TrType::Func => "조회",
+                TrType::Feed => "실시간",
+            }
+            .to_owned(),
+        ),
+        ("attribute byte", yes_no(tr_layout.attr_byte)),
+        ("블록 모드", yes_no(tr_layout.block_mode)),
+    ];
+
+    if let Some(header_type) = tr_layout.header_type {
+        rows.push(("헤더 타입", format!("{:?}", header_type)));
+    }
+
+    if !tr_layout.params.is_empty() {
+        let params: Vec<_> = tr_layout
+            .params
+            .iter()
+            .map(|(name, value)| {
+                if value.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}={}", name, value)
+                }
+            })
+            .collect();
+        rows.push(("속성", params.join(", ")));
+    }
+
+    rows
+}
+
+fn block_title(block_layout: &BlockLayout) -> String {
+    let mut title = match block_layout.block_type {
+        BlockType::Input => "요청",
+        BlockType::Output => "응답",
+    }
+    .to_owned();
+
+    match block_layout.occurs_count {
+        Some(count) => write!(title, ", 배열 {}개", count).unwrap(),
+        None if block_layout.occurs => title.push_str(", 배열"),
+        None => {}
+    }
+
+    format!("{}, {} 바이트", title, block_layout.len)
+}
+
+// 필드마다 이름, 설명, 타입, 길이, 블록 내 오프셋을 반환합니다.
+fn field_rows(tr_layout: &TrLayout, block_layout: &BlockLayout) -> Vec<[String; 5]> {
+    let mut offset = 0;
+
+    block_layout
+        .fields
+        .iter()
+        .map(|field| {
+            let row = [
+                field.name.to_string(),
+                field.desc.clone(),
+                field_type(field).to_owned(),
+                field_len(field),
+                offset.to_string(),
+            ];
+            offset += field.len + tr_layout.attr_byte as usize;
+            row
+        })
+        .collect()
+}
+
+fn field_type(field: &FieldLayout) -> &str {
+    match &field.field_type {
+        FieldType::Char => "char",
+        FieldType::Date => "date",
+        FieldType::Int => "long",
+        FieldType::Float => "float",
+        FieldType::Double => "double",
+        FieldType::Unknown(name) => name,
+    }
+}
+
+fn field_len(field: &FieldLayout) -> String {
+    match field.point {
+        Some(point) => format!("{}.{}", field.len, point),
+        None => field.len.to_string(),
+    }
+}
+
+const FIELD_HEADER: [&str; 5] = ["이름", "설명", "타입", "길이", "오프셋"];
+
+fn write_markdown(out: &mut String, tr_layout: &TrLayout) -> std::fmt::Result {
+    writeln!(
+        out,
+        "## {} {}",
+        tr_layout.code,
+        escape_markdown(&tr_layout.desc)
+    )?;
+    writeln!(out)?;
+
+    for (name, value) in summary(tr_layout) {
+        writeln!(out, "- {}: {}", name, escape_markdown(&value))?;
+    }
+
+    for block_layout in tr_layout.in_blocks.iter().chain(&tr_layout.out_blocks) {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "### {} {} ({})",
+            block_layout.name,
+            escape_markdown(&block_layout.desc),
+            block_title(block_layout)
+        )?;
+        writeln!(out)?;
+        writeln!(out, "| {} |", FIELD_HEADER.join(" | "))?;
+        writeln!(out, "|---|---|---|---:|---:|")?;
+
+        for row in field_rows(tr_layout, block_layout) {
+            let row: Vec<_> = row.iter().map(|cell| escape_markdown(cell)).collect();
+            writeln!(out, "| {} |", row.join(" | "))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_html(out: &mut String, tr_layout: &TrLayout) -> std::fmt::Result {
+    let code = escape_html(&tr_layout.code);
+
+    writeln!(
+        out,
+        "<h2 id=\"{}\">{} {}</h2>",
+        code,
+        code,
+        escape_html(&tr_layout.desc)
+    )?;
+    writeln!(out, "<ul>")?;
+    for (name, value) in summary(tr_layout) {
+        writeln!(out, "<li>{}: {}</li>", name, escape_html(&value))?;
+    }
+    writeln!(out, "</ul>")?;
+
+    for block_layout in tr_layout.in_blocks.iter().chain(&tr_layout.out_blocks) {
+        writeln!(
+            out,
+            "<h3>{} {} ({})</h3>",
+            escape_html(&block_layout.name),
+            escape_html(&block_layout.desc),
+            block_title(block_layout)
+        )?;
+        writeln!(out, "<table>")?;
+        writeln!(out, "<tr><th>{}</th></tr>", FIELD_HEADER.join("</th><th>"))?;
+
+        for row in field_rows(tr_layout, block_layout) {
+            let row: Vec<_> = row.iter().map(|cell| escape_html(cell)).collect();
+            writeln!(out, "<tr><td>{}</td></tr>", row.join("</td><td>"))?;
+        }
+
+        writeln!(out, "</table>")?;
+    }
+
+    Ok(())
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_html, to_markdown};
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_docgen() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,주식현재가<시세>,t1102,attr,headtype=A;
+                BEGIN_DATA_MAP
+                t1102InBlock,기본입력,input;
+                begin
+                    단축코드,shcode,shcode,char,6;
+                end
+                t1102OutBlock1,출력,output,occurs;
+                begin
+                    현재가,price,price,long,8;
+                    등락율,diff,diff,float,6.2;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let markdown = to_markdown([&tr_layout]);
+        assert!(markdown.starts_with("## t1102 주식현재가<시세>\n"));
+        assert!(markdown.contains("- 헤더 타입: A\n"));
+        assert!(markdown.contains("### t1102OutBlock1 출력 (응답, 배열, 16 바이트)\n"));
+        assert!(markdown.contains("| diff | 등락율 | float | 6.2 | 9 |\n"));
+
+        let html = to_html([&tr_layout]);
+        assert!(html.contains("<li><a href=\"#t1102\">t1102</a> 주식현재가&lt;시세&gt;</li>"));
+        assert!(html.contains(
+            "<tr><td>shcode</td><td>단축코드</td><td>char</td><td>6</td><td>0</td></tr>"
+        ));
+    }
+}
@@ -65,8 +65,76 @@ impl std::fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+/// 관대한 파싱 모드에서 무시된 문제
+///
+/// [`TrLayout::parse_lenient()`](super::TrLayout::parse_lenient)이 반환합니다.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseWarning {
+    kind: ErrorKind,
+    line: usize,
+    column: usize,
+    symbol: String,
+}
+
+impl ParseWarning {
+    /// 엄격한 파싱 모드에서 발생했을 에러의 종류를 반환합니다.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// 문제가 발생한 행 위치를 반환합니다.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 문제가 발생한 열 위치를 반환합니다.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// 무시된 심볼을 반환합니다.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ignored {} {:?} at line {} column {}",
+            self.kind, self.symbol, self.line, self.column
+        )
+    }
+}
+
+// 관대한 파싱 모드인 경우 경고를 기록하고 계속 진행하며, 그렇지 않은 경우
+// 에러를 반환합니다.
+pub(crate) fn tolerate<'a, R: Read<'a>>(
+    reader: &R,
+    warnings: &mut Option<&mut Vec<ParseWarning>>,
+    kind: ErrorKind,
+    symbol: &str,
+) -> Result<(), Error> {
+    let pos = reader.position();
+
+    match warnings {
+        Some(warnings) => {
+            warnings.push(ParseWarning {
+                kind,
+                line: pos.line(),
+                column: pos.column(),
+                symbol: symbol.to_owned(),
+            });
+            Ok(())
+        }
+        None => Err(Error::new(pos, kind)),
+    }
+}
+
 /// 레이아웃 파싱에 실패하여 발생하는 에러의 종류
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// 구문 에러
     ///
@@ -101,17 +169,25 @@ impl std::fmt::Display for ErrorKind {
 
 /// TR 레이아웃을 디렉터리에서 불러오는데 실패하여 발생하는 에러
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum LoadError {
     /// 입출력 에러
     Io(std::io::Error),
-    /// EUC-KR 디코딩 에러
+    /// EUC-KR 및 UTF-8 디코딩 에러
     Encoding(PathBuf),
     /// TR 레이아웃 파싱 에러
     Parse(PathBuf, Error),
     /// TR 코드 중복 에러
     ///
     /// 코드는 같지만 서로 다른 두 레이아웃이 존재하는 경우 발생합니다.
+    /// [`set_conflict_policy()`](super::set_conflict_policy)로 처리 방식을
+    /// 변경할 수 있습니다.
     Confilict(String),
+    /// 압축 파일 에러
+    #[cfg(feature = "zip")]
+    Archive(ArchiveError),
+    /// JSON 에러
+    Json(JsonError),
 }
 
 impl From<std::io::Error> for LoadError {
@@ -125,7 +201,7 @@ impl std::fmt::Display for LoadError {
         match self {
             Self::Io(err) => err.fmt(f),
             Self::Encoding(path) => {
-                write!(f, "unable to decode file from euc-kr")?;
+                write!(f, "unable to decode file from euc-kr or utf-8")?;
                 write!(f, "; path: {}", path.display())
             }
             Self::Parse(path, err) => {
@@ -135,8 +211,118 @@ impl std::fmt::Display for LoadError {
             Self::Confilict(layout) => {
                 write!(f, "conflicts between files; name: {}", layout)
             }
+            #[cfg(feature = "zip")]
+            Self::Archive(err) => write!(f, "unable to read archive; {}", err),
+            Self::Json(err) => write!(f, "unable to read json; {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(_, err) => Some(err),
+            #[cfg(feature = "zip")]
+            Self::Archive(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Encoding(_) | Self::Confilict(_) => None,
+        }
+    }
+}
+
+/// 압축 파일을 읽는데 실패하여 발생하는 에러
+#[cfg(feature = "zip")]
+#[derive(Debug)]
+pub struct ArchiveError {
+    kind: ArchiveErrorKind,
+    entry: Option<String>,
+}
+
+#[cfg(feature = "zip")]
+impl ArchiveError {
+    pub(crate) fn new(kind: ArchiveErrorKind, entry: Option<&str>) -> Self {
+        Self {
+            kind,
+            entry: entry.map(ToOwned::to_owned),
+        }
+    }
+
+    /// 에러 종류를 반환합니다.
+    pub fn kind(&self) -> ArchiveErrorKind {
+        self.kind
+    }
+
+    /// 에러가 발생한 항목의 압축 파일 내 경로를 반환합니다.
+    ///
+    /// 압축 파일의 목차를 읽는데 실패한 경우 `None`을 반환합니다.
+    pub fn entry(&self) -> Option<&str> {
+        self.entry.as_deref()
+    }
+}
+
+#[cfg(feature = "zip")]
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.kind.fmt(f)?;
+        if let Some(entry) = &self.entry {
+            write!(f, "; entry: {}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zip")]
+impl std::error::Error for ArchiveError {}
+
+/// 압축 파일을 읽는데 실패하여 발생하는 에러의 종류
+#[cfg(feature = "zip")]
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum ArchiveErrorKind {
+    /// 압축 파일의 구조나 압축된 데이터가 잘못되었습니다.
+    Malformed,
+    /// 지원하지 않는 압축 방식이나 암호화된 항목, 또는 너무 큰 항목입니다.
+    Unsupported,
+    /// 항목의 데이터가 손상되었습니다.
+    ///
+    /// 압축을 해제한 데이터의 CRC가 일치하지 않거나 압축된 데이터를 해제할 수
+    /// 없는 경우입니다.
+    Checksum,
+}
+
+#[cfg(feature = "zip")]
+impl std::fmt::Display for ArchiveErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed => "malformed archive".fmt(f),
+            Self::Unsupported => "unsupported archive".fmt(f),
+            Self::Checksum => "checksum mismatch".fmt(f),
+        }
+    }
+}
+
+/// JSON 레이아웃을 읽는데 실패하여 발생하는 에러
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonError {
+    /// 올바른 JSON이 아닙니다.
+    ///
+    /// 에러가 발생한 바이트 위치를 포함합니다.
+    Syntax(usize),
+    /// 값이 없거나 레이아웃으로 변환할 수 없습니다.
+    ///
+    /// `t1101.in_blocks[0].len`과 같은 값의 경로를 포함합니다.
+    InvalidValue(String),
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(pos) => write!(f, "invalid json at {}", pos),
+            Self::InvalidValue(path) => write!(f, "invalid value; path: {}", path),
         }
     }
 }
 
-impl std::error::Error for LoadError {}
+impl std::error::Error for JsonError {}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// `serde` 기능으로 직렬화한 JSON 레이아웃을 `serde` 없이 읽습니다.
+
+use super::conflict::{conflict_policy, LayoutTable};
+use super::error::{JsonError, LoadError};
+use super::{BlockLayout, BlockType, FieldLayout, FieldType, HeaderType, TrLayout, TrType};
+use crate::data::json::{self, Value};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// JSON으로 직렬화된 TR 레이아웃을 모두 불러옵니다.
+///
+/// `serde` 기능으로 직렬화한 [`TrLayout`] 하나, 배열 또는 TR 코드를 키로 하는
+/// 객체를 읽을 수 있습니다. SDK가 설치된 환경에서 만든 JSON 파일을 SDK가
+/// 없는 환경에서 사용할 때 유용합니다. 객체의 키는 무시하며 레이아웃의 코드를
+/// 사용합니다.
+///
+/// ## 예제
+/// ```rust
+/// let json = r#"[{
+///     "tr_type": "func",
+///     "desc": "테스트",
+///     "code": "t0000",
+///     "attr_byte": false,
+///     "block_mode": true,
+///     "header_type": "A",
+///     "in_blocks": [],
+///     "out_blocks": []
+/// }]"#;
+///
+/// let layout_tbl = xingapi::layout::load_json(json.as_bytes()).unwrap();
+/// assert_eq!(layout_tbl["t0000"].desc, "테스트");
+/// ```
+pub fn load_json<R: std::io::Read>(
+    mut reader: R,
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let value = json::parse(&text).map_err(|err| LoadError::Json(JsonError::Syntax(err.pos)))?;
+
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
+
+    if value.get("tr_type").is_some() {
+        layout_tbl.insert(to_tr_layout(&value, "")?, None)?;
+    } else if let Some(values) = value.as_array() {
+        for (i, value) in values.iter().enumerate() {
+            layout_tbl.insert(to_tr_layout(value, &format!("[{}]", i))?, None)?;
+        }
+    } else if let Value::Object(members) = &value {
+        for (key, value) in members {
+            layout_tbl.insert(to_tr_layout(value, key)?, None)?;
+        }
+    } else {
+        return Err(invalid(""));
+    }
+
+    Ok(layout_tbl.into_inner())
+}
+
+fn invalid(path: &str) -> LoadError {
+    LoadError::Json(JsonError::InvalidValue(path.to_owned()))
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() || key.starts_with('[') {
+        format!("{}{}", path, key)
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+// 에러를 보고할 경로와 함께 객체의 멤버를 읽습니다.
+struct Object<'a> {
+    value: &'a Value,
+    path: &'a str,
+}
+
+impl<'a> Object<'a> {
+    fn get(&self, key: &str) -> Result<&'a Value, LoadError> {
+        self.value
+            .get(key)
+            .ok_or_else(|| invalid(&join(self.path, key)))
+    }
+
+    fn parse<T, F: FnOnce(&'a Value) -> Option<T>>(&self, key: &str, f: F) -> Result<T, LoadError> {
+        f(self.get(key)?).ok_or_else(|| invalid(&join(self.path, key)))
+    }
+
+    fn str(&self, key: &str) -> Result<String, LoadError> {
+        self.parse(key, |value| value.as_str().map(ToOwned::to_owned))
+    }
+
+    fn bool(&self, key: &str) -> Result<bool, LoadError> {
+        self.parse(key, |value| match value {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    fn usize(&self, key: &str) -> Result<usize, LoadError> {
+        self.parse(key, as_usize)
+    }
+
+    fn array<T, F>(&self, key: &str, f: F) -> Result<Vec<T>, LoadError>
+    where
+        F: Fn(&'a Value, &str) -> Result<T, LoadError>,
+    {
+        let path = join(self.path, key);
+        let values = self.get(key)?.as_array().ok_or_else(|| invalid(&path))?;
+
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| f(value, &join(&path, &format!("[{}]", i))))
+            .collect()
+    }
+}
+
+fn as_usize(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(number) => number.parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_tr_layout(value: &Value, path: &str) -> Result<TrLayout, LoadError> {
+    let object = Object { value, path };
+
+    Ok(TrLayout {
+        tr_type: object.parse("tr_type", |value| match value.as_str()? {
+            "func" => Some(TrType::Func),
+            "feed" => Some(TrType::Feed),
+            _ => None,
+        })?,
+        desc: object.str("desc")?,
+        code: object.str("code")?,
+        attr_byte: object.bool("attr_byte")?,
+        block_mode: object.bool("block_mode")?,
+        header_type: object.parse("header_type", |value| match value {
+            Value::Null => Some(None),
+            Value::String(value) => value.parse::<HeaderType>().ok().map(Some),
+            _ => None,
+        })?,
+        // `params`가 추가되기 전에 직렬화된 경우 없을 수 있습니다.
+        params: match value.get("params") {
+            Some(_) => object.array("params", to_param)?,
+            None => Vec::new(),
+        },
+        in_blocks: object.array("in_blocks", to_block_layout)?,
+        out_blocks: object.array("out_blocks", to_block_layout)?,
+    })
+}
+
+fn to_param(value: &Value, path: &str) -> Result<(String, String), LoadError> {
+    match value.as_array() {
+        Some([Value::String(name), Value::String(value)]) => Ok((name.clone(), value.clone())),
+        _ => Err(invalid(path)),
+    }
+}
+
+fn to_block_layout(value: &Value, path: &str) -> Result<BlockLayout, LoadError> {
+    let object = Object { value, path };
+
+    Ok(BlockLayout {
+        name: object.str("name")?,
+        desc: object.str("desc")?,
+        block_type: object.parse("block_type", |value| {
+            value.as_str()?.parse::<BlockType>().ok()
+        })?,
+        occurs: object.bool("occurs")?,
+        // `occurs_count`가 추가되기 전에 직렬화된 경우 없을 수 있습니다.
+        occurs_count: match value.get("occurs_count") {
+            None | Some(Value::Null) => None,
+            Some(_) => Some(object.usize("occurs_count")?),
+        },
+        len: object.usize("len")?,
+        fields: object.array("fields", to_field_layout)?,
+    })
+}
+
+fn to_field_layout(value: &Value, path: &str) -> Result<FieldLayout, LoadError> {
+    let object = Object { value, path };
+
+    Ok(FieldLayout {
+        desc: object.str("desc")?,
+        name_old: object.str("name_old")?,
+        name: object.str("name")?.into(),
+        field_type: object.parse("field_type", |value| match value {
+            Value::String(value) => value.parse::<FieldType>().ok(),
+            value => value
+                .get("unknown")?
+                .as_str()
+                .map(|name| FieldType::Unknown(name.to_owned())),
+        })?,
+        len: object.usize("len")?,
+        point: object.parse("point", |value| match value {
+            Value::Null => Some(None),
+            value => as_usize(value).map(Some),
+        })?,
+    })
+}
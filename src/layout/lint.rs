@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{block_len, TrLayout};
+
+use std::collections::HashSet;
+
+/// [`TrLayout::lint()`]가 발견한 의심스러운 레이아웃
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Lint {
+    /// 블록 안에 같은 이름의 필드가 있습니다.
+    ///
+    /// 디코딩된 블록에는 마지막 필드의 값만 남습니다.
+    DuplicateField { block: String, field: String },
+    /// 길이가 0인 필드가 있습니다.
+    ZeroLengthField { block: String, field: String },
+    /// 블록의 길이가 attribute byte 존재 여부와 맞지 않습니다.
+    ///
+    /// 반대의 경우로 계산한 길이와 같습니다.
+    AttrMismatch { block: String },
+    /// 블록의 길이가 필드 길이의 합과 다릅니다.
+    BlockLength {
+        block: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateField { block, field } => {
+                write!(f, "duplicated {} field in {} block", field, block)
+            }
+            Self::ZeroLengthField { block, field } => {
+                write!(f, "zero length {} field in {} block", field, block)
+            }
+            Self::AttrMismatch { block } => {
+                write!(f, "length of {} block disagrees with attr byte", block)
+            }
+            Self::BlockLength {
+                block,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "length of {} block is {}, expected {}",
+                block, actual, expected
+            ),
+        }
+    }
+}
+
+impl TrLayout {
+    /// 의심스러운 레이아웃을 찾습니다.
+    ///
+    /// 파싱에는 성공하지만 인코딩이나 디코딩 결과가 잘못될 수 있는 경우를
+    /// 블록 순서대로 반환합니다. 파싱된 레이아웃의 블록 길이는 항상 맞으므로
+    /// 길이 검사는 직접 생성하거나 수정한 레이아웃에 유용합니다.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+
+        for block_layout in self.in_blocks.iter().chain(&self.out_blocks) {
+            let block = || block_layout.name.clone();
+            let mut names = HashSet::new();
+
+            for field_layout in &block_layout.fields {
+                if !names.insert(&field_layout.name) {
+                    lints.push(Lint::DuplicateField {
+                        block: block(),
+                        field: field_layout.name.to_string(),
+                    });
+                }
+
+                if field_layout.len == 0 {
+                    lints.push(Lint::ZeroLengthField {
+                        block: block(),
+                        field: field_layout.name.to_string(),
+                    });
+                }
+            }
+
+            let expected = block_len(&block_layout.fields, self.attr_byte);
+            if expected == Some(block_layout.len) {
+                continue;
+            }
+
+            if block_len(&block_layout.fields, !self.attr_byte) == Some(block_layout.len) {
+                lints.push(Lint::AttrMismatch { block: block() });
+            } else {
+                lints.push(Lint::BlockLength {
+                    block: block(),
+                    expected: expected.unwrap_or(usize::MAX),
+                    actual: block_layout.len,
+                });
+            }
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lint;
+    use crate::layout::TrLayout;
+
+    #[test]
+    fn test_lint() {
+        let mut tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000,attr;
+                BEGIN_DATA_MAP
+                t0000InBlock,입력,input;
+                begin
+                    코드,code,code,char,6;
+                    예비,spare,spare,char,0;
+                end
+                t0000OutBlock1,출력1,output,occurs;
+                begin
+                    매도호가9,offerho9,offerho9,long,8;
+                    매도호가10,offerho10,offerho9,long,8;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            tr_layout.lint(),
+            [
+                Lint::ZeroLengthField {
+                    block: "t0000InBlock".into(),
+                    field: "spare".into(),
+                },
+                Lint::DuplicateField {
+                    block: "t0000OutBlock1".into(),
+                    field: "offerho9".into(),
+                },
+            ]
+        );
+
+        tr_layout.in_blocks[0].len = 6;
+        tr_layout.out_blocks[0].len = 20;
+
+        let lints = tr_layout.lint();
+        assert_eq!(
+            lints[1],
+            Lint::AttrMismatch {
+                block: "t0000InBlock".into()
+            }
+        );
+        assert_eq!(
+            lints[3].to_string(),
+            "length of t0000OutBlock1 block is 20, expected 18"
+        );
+    }
+}
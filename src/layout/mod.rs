@@ -2,17 +2,45 @@
 
 //! 데이터에 대한 레이아웃을 파싱하는 모듈
 //!
-//! 레이아웃은 EUC-KR로 인코딩된 'RES 파일'에서 가져올 수 있습니다.
+//! 레이아웃은 EUC-KR로 인코딩된 'RES 파일'에서 가져올 수 있습니다. UTF-8로
+//! 저장된 RES 파일도 불러올 수 있습니다.
 
+pub mod docgen;
 pub mod error;
 
+#[cfg(feature = "zip")]
+mod archive;
+mod cache;
+mod conflict;
+mod json;
+mod lint;
 mod read;
+mod registry;
 mod tests;
 
-use self::error::{Error, LoadError};
+#[cfg(feature = "zip")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "zip")))]
+pub use self::archive::{load_zip, load_zip_bytes};
+pub use self::cache::{load_dir_cached, load_dir_incremental};
+pub use self::conflict::{conflict_policy, set_conflict_policy, ConflictPolicy};
+pub use self::json::load_json;
+pub use self::lint::Lint;
+pub use self::registry::LayoutRegistry;
+
+use self::conflict::LayoutTable;
+use self::error::{tolerate, Error, ErrorKind, LoadError, ParseWarning};
 use self::read::{Read, StrRead};
-
-use std::{collections::HashMap, convert::AsRef, path::Path, str::FromStr};
+use crate::data::Encoding;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    convert::AsRef,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -20,67 +48,364 @@ use serde::{Deserialize, Serialize};
 /// XingAPI SDK의 기본 설치 경로에서 TR 레이아웃을 모두 불러옵니다.
 #[cfg(any(doc, windows))]
 #[cfg_attr(doc_cfg, doc(cfg(windows)))]
-pub fn load() -> Result<HashMap<String, TrLayout>, LoadError> {
+pub fn load() -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
     load_dir("C:\\eBEST\\xingAPI\\Res")
 }
 
+/// XingAPI SDK의 기본 설치 경로에서 TR 레이아웃을 모두 불러오며 파싱 결과를
+/// `%LOCALAPPDATA%\xingapi\layouts.cache`에 저장합니다.
+///
+/// 자세한 내용은 [`load_dir_cached()`]를 참고하세요.
+#[cfg(any(doc, windows))]
+#[cfg_attr(doc_cfg, doc(cfg(windows)))]
+pub fn load_cached() -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let cache_dir =
+        std::env::var_os("LOCALAPPDATA").map_or_else(std::env::temp_dir, std::path::PathBuf::from);
+    load_dir_cached(
+        "C:\\eBEST\\xingAPI\\Res",
+        cache_dir.join("xingapi").join("layouts.cache"),
+    )
+}
+
 /// 지정된 디렉터리에서 TR 레이아웃을 모두 불러옵니다.
 ///
-/// 하위 디렉터리는 탐색하지 않습니다.
-pub fn load_dir<P: AsRef<Path>>(path: P) -> Result<HashMap<String, TrLayout>, LoadError> {
-    use encoding_rs::EUC_KR;
-    use std::{fs, sync::mpsc};
-    use threadpool::ThreadPool;
+/// 하위 디렉터리는 탐색하지 않습니다. 하위 디렉터리까지 탐색하려면
+/// [`load_dir_recursive()`]를 사용하세요.
+pub fn load_dir<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    load_files(res_files(path)?, &mut |_| {})
+}
+
+/// 파일 하나를 불러올 때마다 [`load_dir_with_progress()`]가 전달하는 이벤트
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct LoadedEvent<'a> {
+    /// 불러온 파일의 경로
+    pub path: &'a Path,
+    /// 지금까지 불러온 파일의 개수
+    pub loaded: usize,
+    /// 불러올 파일의 전체 개수
+    pub total: usize,
+}
 
-    let pool = ThreadPool::new(16);
-    let (tx, rx) = mpsc::channel();
+/// 지정된 디렉터리에서 TR 레이아웃을 모두 불러오며 진행 상황을 알립니다.
+///
+/// [`load_dir()`]와 같으며 파일 하나의 파싱이 끝날 때마다 `progress`를
+/// 호출합니다. 파일은 여러 스레드에서 파싱되므로 완료된 순서대로 전달되지만
+/// `progress`는 항상 이 함수를 호출한 스레드에서 호출됩니다.
+///
+/// ## 예제
+/// ```rust,no_run
+/// let layout_tbl = xingapi::layout::load_dir_with_progress("Res", |event| {
+///     eprint!("\r{}/{}", event.loaded, event.total);
+/// })?;
+/// # Ok::<(), xingapi::layout::error::LoadError>(())
+/// ```
+pub fn load_dir_with_progress<P, F>(
+    path: P,
+    mut progress: F,
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError>
+where
+    P: AsRef<Path>,
+    F: FnMut(LoadedEvent),
+{
+    load_files(res_files(path)?, &mut progress)
+}
 
-    for ent in fs::read_dir(&path)? {
+/// 지정된 디렉터리에서 주어진 TR 코드의 레이아웃만 불러옵니다.
+///
+/// 파일 이름이 TR 코드와 같은 RES 파일만 파싱하며 대소문자는 구분하지
+/// 않습니다. 하위 디렉터리는 탐색하지 않습니다.
+pub fn load_dir_filtered<P: AsRef<Path>>(
+    path: P,
+    codes: &[&str],
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let mut paths = Vec::new();
+
+    for ent in std::fs::read_dir(&path)? {
         let path = ent?.path();
-        if !path.is_file() || path.extension() != Some("res".as_ref()) {
-            continue;
+        let matches = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| codes.iter().any(|code| code.eq_ignore_ascii_case(stem)));
+
+        if matches && is_res_file(&path) {
+            paths.push(path);
         }
+    }
 
-        let tx = tx.clone();
+    load_files(paths, &mut |_| {})
+}
 
-        pool.execute(move || {
-            let parse_layout = || -> Result<TrLayout, LoadError> {
-                let raw_data = fs::read(&path)?;
+/// 지정된 디렉터리와 하위 디렉터리에서 TR 레이아웃을 모두 불러옵니다.
+///
+/// `max_depth`는 탐색할 하위 디렉터리의 최대 깊이이며 `Some(0)`인 경우
+/// [`load_dir()`]와 같습니다. `None`인 경우 깊이를 제한하지 않습니다.
+///
+/// 심볼릭 링크로 연결된 디렉터리도 탐색하지만 이미 탐색한 디렉터리는 다시
+/// 탐색하지 않으므로 순환하는 링크가 있어도 종료됩니다.
+pub fn load_dir_recursive<P: AsRef<Path>>(
+    path: P,
+    max_depth: Option<usize>,
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    use std::{collections::HashSet, fs};
+
+    let mut paths = Vec::new();
+    let mut visited = HashSet::new();
+    let mut dirs = vec![(path.as_ref().to_owned(), 0)];
+
+    while let Some((dir, depth)) = dirs.pop() {
+        if !visited.insert(fs::canonicalize(&dir)?) {
+            continue;
+        }
 
-                let (data, _, had_errors) = EUC_KR.decode(&raw_data);
-                if had_errors {
-                    return Err(LoadError::Encoding(path));
+        for ent in fs::read_dir(&dir)? {
+            let path = ent?.path();
+            if path.is_dir() {
+                if max_depth.is_none_or(|max_depth| depth < max_depth) {
+                    dirs.push((path, depth + 1));
                 }
+            } else if is_res_file(&path) {
+                paths.push(path);
+            }
+        }
+    }
 
-                data.parse().map_err(|err| LoadError::Parse(path, err))
-            };
+    load_files(paths, &mut |_| {})
+}
 
-            tx.send(parse_layout()).unwrap();
-        });
+fn is_res_file(path: &Path) -> bool {
+    path.is_file() && path.extension() == Some("res".as_ref())
+}
+
+// 하위 디렉터리를 제외한 디렉터리의 RES 파일 목록을 반환합니다.
+fn res_files<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for ent in std::fs::read_dir(&path)? {
+        let path = ent?.path();
+        if is_res_file(&path) {
+            paths.push(path);
+        }
     }
 
-    drop(tx);
+    Ok(paths)
+}
 
-    let mut layout_tbl = HashMap::new();
+fn load_files(
+    paths: Vec<PathBuf>,
+    progress: &mut dyn FnMut(LoadedEvent),
+) -> Result<HashMap<String, Arc<TrLayout>>, LoadError> {
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
 
-    while let Ok(result) = rx.recv() {
-        let layout = result?;
+    for (_, result) in parse_files(paths, progress) {
+        let parsed = result?;
+        layout_tbl.insert(parsed.layout, parsed.modified)?;
+    }
 
-        if let Some(other) = layout_tbl.get(&layout.code) {
-            if layout != *other {
-                return Err(LoadError::Confilict(layout.code));
-            }
-        } else {
-            layout_tbl.insert(layout.code.clone(), layout);
+    Ok(layout_tbl.into_inner())
+}
+
+/// [`load_dir_partial()`]의 결과
+#[derive(Debug)]
+pub struct PartialLoad {
+    /// 불러온 레이아웃 테이블
+    pub layouts: HashMap<String, Arc<TrLayout>>,
+    /// 불러오지 못한 파일의 경로와 에러 목록
+    ///
+    /// 레이아웃이 충돌하는 경우 나중에 불러온 파일이 실패한 것으로 기록됩니다.
+    pub failures: Vec<(PathBuf, LoadError)>,
+    /// EUC-KR이 아닌 인코딩으로 불러온 파일의 경로와 인코딩 목록
+    pub encodings: Vec<(PathBuf, Encoding)>,
+}
+
+/// 지정된 디렉터리에서 TR 레이아웃을 불러오며 실패한 파일은 건너뜁니다.
+///
+/// 손상된 RES 파일이 있어도 나머지 레이아웃을 사용할 수 있습니다.
+/// 디렉터리를 읽을 수 없는 경우에만 에러를 반환합니다.
+pub fn load_dir_partial<P: AsRef<Path>>(path: P) -> Result<PartialLoad, LoadError> {
+    let paths = res_files(path)?;
+
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
+    let mut failures = Vec::new();
+    let mut encodings = Vec::new();
+
+    for (path, result) in parse_files(paths, &mut |_| {}) {
+        let result = result.and_then(|parsed| {
+            layout_tbl.insert(parsed.layout, parsed.modified)?;
+            Ok(parsed.encoding)
+        });
+
+        match result {
+            Ok(Encoding::EucKr) => {}
+            Ok(encoding) => encodings.push((path, encoding)),
+            Err(err) => failures.push((path, err)),
         }
     }
 
-    pool.join();
+    Ok(PartialLoad {
+        layouts: layout_tbl.into_inner(),
+        failures,
+        encodings,
+    })
+}
+
+struct Parsed {
+    layout: TrLayout,
+    modified: Option<SystemTime>,
+    encoding: Encoding,
+}
+
+type ParseResult = Result<Parsed, LoadError>;
+
+// 파일을 나누어 여러 스레드에서 파싱하고 경로 순으로 결과를 반환합니다.
+//
+// 파일 하나의 파싱이 끝날 때마다 호출한 스레드에서 `progress`를 호출합니다.
+fn parse_files(
+    mut paths: Vec<PathBuf>,
+    progress: &mut dyn FnMut(LoadedEvent),
+) -> Vec<(PathBuf, ParseResult)> {
+    use std::fs;
+
+    // 파일 하나를 파싱하는 비용이 작으므로 스레드 수를 제한합니다.
+    const MAX_THREADS: usize = 16;
+
+    let parse_layout = |path: &PathBuf| -> ParseResult {
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        let raw_data = fs::read(path)?;
+
+        let (data, encoding) =
+            decode_res(&raw_data).ok_or_else(|| LoadError::Encoding(path.clone()))?;
+
+        match data.parse() {
+            Ok(layout) => Ok(Parsed {
+                layout,
+                modified,
+                encoding,
+            }),
+            Err(err) => Err(LoadError::Parse(path.clone(), err)),
+        }
+    };
+
+    // 충돌 처리 방식이 파일 순서에 따라 달라지지 않도록 정렬합니다.
+    paths.sort_unstable();
+
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_THREADS);
+    let chunk_len = paths.len().div_ceil(threads).max(1);
+
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let handles: Vec<_> = paths
+            .chunks(chunk_len)
+            .map(|chunk| {
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| {
+                            let result = parse_layout(path);
+                            let _ = tx.send(path);
+                            result
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        drop(tx);
+
+        for (i, path) in rx.iter().enumerate() {
+            progress(LoadedEvent {
+                path,
+                loaded: i + 1,
+                total: paths.len(),
+            });
+        }
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    paths.into_iter().zip(results).collect()
+}
+
+/// 메모리에 있는 RES 파일의 내용에서 TR 레이아웃을 모두 불러옵니다.
+///
+/// 각 항목은 파일 이름과 디코딩된 내용의 쌍이며 파일 이름은 에러를 보고할
+/// 때만 사용합니다. 실행 파일에 RES 파일을 포함하려면 [`include_res!`]를
+/// 사용하세요.
+pub fn load_from_iter<I, N, T>(iter: I) -> Result<HashMap<String, Arc<TrLayout>>, LoadError>
+where
+    I: IntoIterator<Item = (N, T)>,
+    N: AsRef<str>,
+    T: AsRef<str>,
+{
+    let mut layout_tbl = LayoutTable::new(conflict_policy());
+
+    for (name, text) in iter {
+        let layout = text
+            .as_ref()
+            .parse()
+            .map_err(|err| LoadError::Parse(name.as_ref().into(), err))?;
+        layout_tbl.insert(layout, None)?;
+    }
+
+    Ok(layout_tbl.into_inner())
+}
+
+#[doc(hidden)]
+pub fn __decode_res(name: &str, data: &[u8]) -> (String, String) {
+    let text = match decode_res(data) {
+        Some((text, _)) => text,
+        None => encoding_rs::EUC_KR.decode_without_bom_handling(data).0,
+    };
+    (name.to_owned(), text.into_owned())
+}
+
+/// RES 파일의 인코딩을 판별하여 디코딩합니다.
+///
+/// UTF-8 BOM으로 시작하거나 ASCII가 아닌 문자를 포함한 올바른 UTF-8인 경우
+/// UTF-8로, 그 외에는 EUC-KR로 디코딩합니다. 한글을 포함한 EUC-KR 문자열은
+/// 올바른 UTF-8이 되는 경우가 거의 없습니다. 디코딩할 수 없는 경우 `None`을
+/// 반환합니다.
+pub fn decode_res(data: &[u8]) -> Option<(Cow<'_, str>, Encoding)> {
+    if let Some(data) = data.strip_prefix(b"\xef\xbb\xbf") {
+        return Some((Encoding::Utf8.decode(data)?, Encoding::Utf8));
+    }
+
+    if !data.is_ascii() {
+        if let Some(text) = Encoding::Utf8.decode(data) {
+            return Some((text, Encoding::Utf8));
+        }
+    }
 
-    assert_eq!(pool.queued_count(), 0);
-    assert_eq!(pool.panic_count(), 0);
+    Some((Encoding::EucKr.decode(data)?, Encoding::EucKr))
+}
 
-    Ok(layout_tbl)
+/// RES 파일을 실행 파일에 포함하는 매크로
+///
+/// 경로는 [`include_bytes!`]와 같이 매크로를 호출한 소스 파일을 기준으로
+/// 합니다. EUC-KR로 디코딩된 `(String, String)` 배열로 확장되며
+/// [`load_from_iter()`]에 전달하여 SDK의 Res 디렉터리 없이 레이아웃을
+/// 불러올 수 있습니다.
+///
+/// ## 예제
+/// ```rust,ignore
+/// use xingapi::{include_res, layout};
+///
+/// let layout_tbl = layout::load_from_iter(include_res!(
+///     "../res/t1101.res",
+///     "../res/t0424.res",
+/// ))?;
+/// ```
+#[macro_export]
+macro_rules! include_res {
+    ($($path:literal),* $(,)?) => {
+        [$($crate::layout::__decode_res($path, include_bytes!($path))),*]
+    };
 }
 
 fn next_sym<'a, R: Read<'a>>(reader: &R) -> Result<&'a str, Error> {
@@ -119,6 +444,21 @@ pub enum HeaderType {
     D,
 }
 
+impl HeaderType {
+    /// DLL이 요청 데이터 앞에 추가하는 패킷 헤더의 바이트 길이를 반환합니다.
+    ///
+    /// [`TrLayout::expected_request_len()`]은 헤더를 포함하지 않으므로 전송되는
+    /// 패킷의 길이는 두 값을 더한 값입니다.
+    pub fn packet_header_len(self) -> usize {
+        match self {
+            Self::A => 27,
+            Self::B => 41,
+            Self::C => 34,
+            Self::D => 25,
+        }
+    }
+}
+
 impl FromStr for HeaderType {
     type Err = ();
     fn from_str(text: &str) -> Result<Self, Self::Err> {
@@ -175,6 +515,13 @@ pub struct TrLayout {
     pub block_mode: bool,
     /// 헤더 타입
     pub header_type: Option<HeaderType>,
+    /// 그 외의 헤더 속성
+    ///
+    /// `key`, `group`, `tuxcode`, `svr` 등의 속성 이름과 값을 RES 파일의
+    /// 순서대로 보관합니다. `ENCRYPT`와 같이 값이 없는 속성의 값은 빈
+    /// 문자열입니다.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: Vec<(String, String)>,
     /// 요청 블록 목록
     pub in_blocks: Vec<BlockLayout>,
     /// 응답 블록 목록
@@ -182,7 +529,146 @@ pub struct TrLayout {
 }
 
 impl TrLayout {
-    fn from_reader<'a, R: Read<'a>>(reader: &R) -> Result<Self, Error> {
+    /// 이름으로 헤더 속성의 값을 찾습니다.
+    ///
+    /// 실시간 TR의 `key`와 `group` 등 [`params`](Self::params)에 보관된
+    /// 속성을 찾으며 값이 없는 속성은 빈 문자열을 반환합니다.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 이름으로 요청 블록을 찾습니다.
+    pub fn in_block(&self, name: &str) -> Option<&BlockLayout> {
+        self.in_blocks.iter().find(|b| b.name == name)
+    }
+
+    /// 이름으로 응답 블록을 찾습니다.
+    pub fn out_block(&self, name: &str) -> Option<&BlockLayout> {
+        self.out_blocks.iter().find(|b| b.name == name)
+    }
+
+    /// 요청 데이터의 바이트 길이를 계산합니다.
+    ///
+    /// `rows`는 배열 블록의 행 개수를 반환합니다. non-block mode인 경우 배열
+    /// 블록 앞의 5자리 행 개수를 포함합니다. 고정 배열은 `rows` 대신 지정된
+    /// 개수를 사용합니다. 헤더는 DLL이 추가하므로 포함하지 않으며, 헤더의
+    /// 길이는 [`HeaderType::packet_header_len()`]으로 구할 수 있습니다.
+    pub fn expected_request_len<F: Fn(&BlockLayout) -> usize>(&self, rows: F) -> usize {
+        self.in_blocks
+            .iter()
+            .map(|block_layout| {
+                if !block_layout.occurs {
+                    block_layout.len
+                } else if let Some(count) = block_layout.occurs_count {
+                    count * block_layout.len
+                } else if self.block_mode {
+                    rows(block_layout) * block_layout.len
+                } else {
+                    5 + rows(block_layout) * block_layout.len
+                }
+            })
+            .sum()
+    }
+
+    /// RES 파일 형식의 문자열로 변환합니다.
+    ///
+    /// 반환된 문자열을 다시 파싱하면 같은 레이아웃이 됩니다. 주석은
+    /// 포함되지 않으며 요청 블록 뒤에 응답 블록이 옵니다. 다른 도구와
+    /// 공유하려면 EUC-KR로 인코딩하여 저장해야 합니다.
+    pub fn to_res_string(&self) -> String {
+        use std::fmt::Write;
+
+        let mut text = String::new();
+
+        let tr_type = match self.tr_type {
+            TrType::Func => ".Func",
+            TrType::Feed => ".Feed",
+        };
+
+        text.push_str("BEGIN_FUNCTION_MAP\n");
+        write!(text, "\t{},{},{}", tr_type, self.desc, self.code).unwrap();
+
+        if self.attr_byte {
+            text.push_str(",attr");
+        }
+        if self.block_mode {
+            text.push_str(",block");
+        }
+        if let Some(header_type) = self.header_type {
+            write!(text, ",headtype={:?}", header_type).unwrap();
+        }
+        for (name, value) in &self.params {
+            if value.is_empty() {
+                write!(text, ",{}", name).unwrap();
+            } else {
+                write!(text, ",{}={}", name, value).unwrap();
+            }
+        }
+
+        text.push_str(";\n\tBEGIN_DATA_MAP\n");
+
+        for block in self.in_blocks.iter().chain(&self.out_blocks) {
+            let block_type = match block.block_type {
+                BlockType::Input => "input",
+                BlockType::Output => "output",
+            };
+
+            write!(text, "\t{},{},{}", block.name, block.desc, block_type).unwrap();
+            match block.occurs_count {
+                Some(count) => write!(text, ",occurs {}", count).unwrap(),
+                None if block.occurs => text.push_str(",occurs"),
+                None => {}
+            }
+            text.push_str(";\n\tbegin\n");
+
+            for field in &block.fields {
+                let field_type = match &field.field_type {
+                    FieldType::Char => "char",
+                    FieldType::Date => "date",
+                    FieldType::Int => "long",
+                    FieldType::Float => "float",
+                    FieldType::Double => "double",
+                    FieldType::Unknown(name) => name,
+                };
+
+                write!(
+                    text,
+                    "\t\t{},{},{},{},{}",
+                    field.desc, field.name_old, field.name, field_type, field.len
+                )
+                .unwrap();
+                if let Some(point) = field.point {
+                    write!(text, ".{}", point).unwrap();
+                }
+                text.push_str(";\n");
+            }
+
+            text.push_str("\tend\n");
+        }
+
+        text.push_str("\tEND_DATA_MAP\nEND_FUNCTION_MAP\n");
+        text
+    }
+
+    /// 관대한 모드로 파싱합니다.
+    ///
+    /// 알 수 없는 헤더 속성과 블록 속성, 필드 끝의 추가 데이터, 레이아웃
+    /// 뒤의 내용을 무시하고 무시한 위치를 경고로 함께 반환합니다. 알 수 없는
+    /// 필드 타입은 [`FieldType::Unknown`]으로 불러오고 경고를 반환합니다. 그
+    /// 외의 문제는 [`FromStr`]과 같이 에러를 반환합니다.
+    pub fn parse_lenient(text: &str) -> Result<(Self, Vec<ParseWarning>), Error> {
+        let mut warnings = Vec::new();
+        let layout = Self::from_reader(&StrRead::new(text), &mut Some(&mut warnings))?;
+        Ok((layout, warnings))
+    }
+
+    fn from_reader<'a, R: Read<'a>>(
+        reader: &R,
+        warnings: &mut Option<&mut Vec<ParseWarning>>,
+    ) -> Result<Self, Error> {
         if next_sym(reader)? != "BEGIN_FUNCTION_MAP" {
             return Err(Error::unexpected_syntax(reader));
         }
@@ -203,6 +689,7 @@ impl TrLayout {
         let mut attr_byte = false;
         let mut block_mode = false;
         let mut header_type = None;
+        let mut params = Vec::new();
 
         loop {
             match next_sym(reader)? {
@@ -215,19 +702,20 @@ impl TrLayout {
 
             if let Some((key, val)) = param.split_once('=') {
                 if key.chars().any(|c| !c.is_ascii_alphabetic()) || val.contains('=') {
-                    return Err(Error::unexpected_data(reader));
+                    tolerate(reader, warnings, ErrorKind::Data, param)?;
+                    continue;
                 }
 
                 match key {
-                    "headtype" => {
-                        header_type = Some(
-                            HeaderType::from_str(val)
-                                .map_err(|_| Error::unexpected_data(reader))?,
-                        );
+                    "headtype" => match HeaderType::from_str(val) {
+                        Ok(val) => header_type = Some(val),
+                        Err(_) => tolerate(reader, warnings, ErrorKind::Data, param)?,
+                    },
+                    "key" | "group" | "tuxcode" | "svr" | "SERVICE" | "CREATOR" | "CREDATE" => {
+                        params.push((key.to_owned(), val.to_owned()));
                     }
-                    "key" | "group" | "tuxcode" | "svr" | "SERVICE" | "CREATOR" | "CREDATE" => {}
                     _ => {
-                        return Err(Error::unexpected_data(reader));
+                        tolerate(reader, warnings, ErrorKind::Data, param)?;
                     }
                 }
             } else {
@@ -238,9 +726,11 @@ impl TrLayout {
                     "block" => {
                         block_mode = true;
                     }
-                    "ENCRYPT" | "SIGNATURE" => {}
+                    "ENCRYPT" | "SIGNATURE" => {
+                        params.push((param.to_owned(), String::new()));
+                    }
                     _ => {
-                        return Err(Error::unexpected_data(reader));
+                        tolerate(reader, warnings, ErrorKind::Data, param)?;
                     }
                 }
             }
@@ -259,7 +749,7 @@ impl TrLayout {
                 break;
             }
 
-            let block = BlockLayout::from_reader(reader, attr_byte)?;
+            let block = BlockLayout::from_reader(reader, attr_byte, warnings)?;
 
             match block.block_type {
                 BlockType::Input => {
@@ -271,6 +761,18 @@ impl TrLayout {
             }
         }
 
+        // 엄격한 모드에서는 레이아웃 뒤의 내용을 읽지 않습니다.
+        if warnings.is_some() {
+            match reader.next_sym() {
+                Some("END_FUNCTION_MAP") | None => {}
+                Some(sym) => tolerate(reader, warnings, ErrorKind::Syntax, sym)?,
+            }
+
+            if let Some(sym) = reader.next_sym() {
+                tolerate(reader, warnings, ErrorKind::Syntax, sym)?;
+            }
+        }
+
         Ok(TrLayout {
             tr_type,
             desc,
@@ -278,6 +780,7 @@ impl TrLayout {
             attr_byte,
             block_mode,
             header_type,
+            params,
             in_blocks,
             out_blocks,
         })
@@ -288,7 +791,7 @@ impl FromStr for TrLayout {
     type Err = Error;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        Self::from_reader(&StrRead::new(text))
+        Self::from_reader(&StrRead::new(text), &mut None)
     }
 }
 
@@ -327,6 +830,13 @@ pub struct BlockLayout {
     pub block_type: BlockType,
     /// 배열 여부
     pub occurs: bool,
+    /// 배열의 고정된 행 개수
+    ///
+    /// RES 파일에 `occurs 10`과 같이 개수가 지정된 경우입니다. 고정 배열은
+    /// 항상 지정된 개수만큼 인코딩되며 non-block mode에서도 앞에 5자리 행
+    /// 개수가 붙지 않습니다.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub occurs_count: Option<usize>,
     /// 블록 하나의 길이
     ///
     /// 각 필드의 끝에 attribute byte가 존재하는 경우 모두 포함하여 계산합니다.
@@ -336,7 +846,68 @@ pub struct BlockLayout {
 }
 
 impl BlockLayout {
-    fn from_reader<'a, R: Read<'a>>(reader: &R, attr_byte: bool) -> Result<Self, Error> {
+    /// 블록 레이아웃을 생성합니다.
+    ///
+    /// 블록의 길이는 필드 목록과 attribute byte 존재 여부로 계산합니다.
+    ///
+    /// # Panics
+    /// 블록의 길이가 `usize`의 범위를 넘는 경우 패닉이 발생합니다.
+    pub fn new(
+        name: &str,
+        desc: &str,
+        block_type: BlockType,
+        occurs: bool,
+        fields: Vec<FieldLayout>,
+        attr_byte: bool,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: desc.to_owned(),
+            block_type,
+            occurs,
+            occurs_count: None,
+            len: block_len(&fields, attr_byte).expect("block length overflow"),
+            fields,
+        }
+    }
+
+    /// 이름으로 필드를 찾습니다.
+    ///
+    /// 필드의 두 이름을 모두 비교합니다.
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields
+            .iter()
+            .find(|f| &*f.name == name || f.name_old == name)
+    }
+
+    /// 블록 안에서 필드가 시작하는 바이트 위치를 반환합니다.
+    ///
+    /// 블록의 길이로 attribute byte 존재 여부를 판단하여 앞선 필드의
+    /// attribute byte를 포함합니다.
+    pub fn field_offset(&self, name: &str) -> Option<usize> {
+        let attr_len = if block_len(&self.fields, false) == Some(self.len) {
+            0
+        } else {
+            1
+        };
+
+        let mut offset = 0;
+
+        for field in &self.fields {
+            if &*field.name == name || field.name_old == name {
+                return Some(offset);
+            }
+            offset += field.len + attr_len;
+        }
+
+        None
+    }
+
+    fn from_reader<'a, R: Read<'a>>(
+        reader: &R,
+        attr_byte: bool,
+        warnings: &mut Option<&mut Vec<ParseWarning>>,
+    ) -> Result<Self, Error> {
         let name = next_sym(reader)?.to_owned();
 
         let (prefix, suffix) = name
@@ -359,6 +930,7 @@ impl BlockLayout {
             BlockType::from_str(next_sym(reader)?).map_err(|_| Error::unexpected_data(reader))?;
 
         let mut occurs = false;
+        let mut occurs_count = None;
 
         loop {
             match next_sym(reader)? {
@@ -371,8 +943,17 @@ impl BlockLayout {
                 "occurs" => {
                     occurs = true;
                 }
-                _ => {
-                    return Err(Error::unexpected_data(reader));
+                option if option.starts_with("occurs ") => {
+                    let count = option["occurs ".len()..]
+                        .trim_start()
+                        .parse()
+                        .map_err(|_| Error::unexpected_data(reader))?;
+
+                    occurs = true;
+                    occurs_count = Some(count);
+                }
+                option => {
+                    tolerate(reader, warnings, ErrorKind::Data, option)?;
                 }
             }
         }
@@ -389,25 +970,30 @@ impl BlockLayout {
                 break;
             }
 
-            fields.push(FieldLayout::from_reader(reader)?);
+            fields.push(FieldLayout::from_reader(reader, warnings)?);
         }
 
-        let len = fields
-            .iter()
-            .map(|f| f.len + if attr_byte { 1 } else { 0 })
-            .sum();
+        let len = block_len(&fields, attr_byte).ok_or_else(|| Error::unexpected_data(reader))?;
 
         Ok(BlockLayout {
             name,
             desc,
             block_type,
             occurs,
+            occurs_count,
             len,
             fields,
         })
     }
 }
 
+fn block_len(fields: &[FieldLayout], attr_byte: bool) -> Option<usize> {
+    fields.iter().try_fold(0usize, |sum, f| {
+        sum.checked_add(f.len)?
+            .checked_add(if attr_byte { 1 } else { 0 })
+    })
+}
+
 impl AsRef<BlockLayout> for BlockLayout {
     fn as_ref(&self) -> &BlockLayout {
         self
@@ -415,7 +1001,7 @@ impl AsRef<BlockLayout> for BlockLayout {
 }
 
 /// 필드 타입
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FieldType {
     /// 문자열
@@ -433,6 +1019,12 @@ pub enum FieldType {
     /// 64비트 실수
     #[cfg_attr(feature = "serde", serde(rename = "double"))]
     Double,
+    /// 알 수 없는 타입
+    ///
+    /// 관대한 모드에서 알 수 없는 타입의 이름을 보관합니다. 문자열과 같이
+    /// 취급합니다.
+    #[cfg_attr(feature = "serde", serde(rename = "unknown"))]
+    Unknown(String),
 }
 
 impl FromStr for FieldType {
@@ -458,7 +1050,9 @@ pub struct FieldLayout {
     /// 필드의 첫 번째 이름
     pub name_old: String,
     /// 필드의 두 번째 이름
-    pub name: String,
+    ///
+    /// 디코딩한 블록의 필드 이름으로 공유됩니다.
+    pub name: Arc<str>,
     /// 필드 타입
     pub field_type: FieldType,
     /// 필드 길이
@@ -470,7 +1064,24 @@ pub struct FieldLayout {
 }
 
 impl FieldLayout {
-    fn from_reader<'a, R: Read<'a>>(reader: &R) -> Result<Self, Error> {
+    /// 필드 레이아웃을 생성합니다.
+    ///
+    /// 필드의 두 이름과 설명은 모두 `name`으로 지정됩니다.
+    pub fn new(name: &str, field_type: FieldType, len: usize, point: Option<usize>) -> Self {
+        Self {
+            desc: name.to_owned(),
+            name_old: name.to_owned(),
+            name: name.into(),
+            field_type,
+            len,
+            point,
+        }
+    }
+
+    fn from_reader<'a, R: Read<'a>>(
+        reader: &R,
+        warnings: &mut Option<&mut Vec<ParseWarning>>,
+    ) -> Result<Self, Error> {
         let desc = next_sym(reader)?.to_owned();
         skip_delimiter(reader)?;
 
@@ -480,8 +1091,14 @@ impl FieldLayout {
         let name = next_sym(reader)?.to_owned();
         skip_delimiter(reader)?;
 
-        let field_type =
-            FieldType::from_str(next_sym(reader)?).map_err(|_| Error::unexpected_data(reader))?;
+        let raw_type = next_sym(reader)?;
+        let field_type = match FieldType::from_str(raw_type) {
+            Ok(field_type) => field_type,
+            Err(()) => {
+                tolerate(reader, warnings, ErrorKind::Data, raw_type)?;
+                FieldType::Unknown(raw_type.to_owned())
+            }
+        };
         skip_delimiter(reader)?;
 
         let raw_len = next_sym(reader)?;
@@ -497,6 +1114,12 @@ impl FieldLayout {
             (parse_num(raw_len)?, None)
         };
 
+        // 관대한 모드에서는 길이 뒤의 추가 데이터를 무시합니다.
+        while warnings.is_some() && peek_sym(reader)? == "," {
+            reader.next_sym().unwrap();
+            tolerate(reader, warnings, ErrorKind::Data, next_sym(reader)?)?;
+        }
+
         // 필드가 세미콜론으로 끝나지 않는 경우도 있습니다.
         if peek_sym(reader)? == ";" {
             reader.next_sym().unwrap();
@@ -505,7 +1128,7 @@ impl FieldLayout {
         Ok(FieldLayout {
             desc,
             name_old,
-            name,
+            name: name.into(),
             field_type,
             len,
             point,
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// 여러 스레드와 객체가 함께 사용하는 TR 레이아웃 테이블
+///
+/// 복제한 객체는 같은 테이블을 가리킵니다. 레이아웃은 [`Arc`]로 저장되므로
+/// 조회한 레이아웃을 사용하는 동안 테이블을 잠그지 않습니다.
+///
+/// ## 예제
+/// ```rust
+/// use xingapi::layout::{LayoutRegistry, TrLayout};
+///
+/// let registry = LayoutRegistry::new();
+/// let tr_layout: TrLayout = "
+///     BEGIN_FUNCTION_MAP
+///         .Func,테스트,t0000,attr;
+///         BEGIN_DATA_MAP
+///         END_DATA_MAP
+///     END_FUNCTION_MAP
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// registry.clone().insert(tr_layout);
+/// assert!(registry.get("t0000").is_some());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LayoutRegistry {
+    layout_tbl: Arc<RwLock<HashMap<String, Arc<TrLayout>>>>,
+}
+
+impl LayoutRegistry {
+    /// 빈 테이블을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Arc<TrLayout>>> {
+        self.layout_tbl
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, Arc<TrLayout>>> {
+        self.layout_tbl
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// 레이아웃을 추가하고 같은 TR 코드의 이전 레이아웃을 반환합니다.
+    pub fn insert<T: Into<Arc<TrLayout>>>(&self, tr_layout: T) -> Option<Arc<TrLayout>> {
+        let tr_layout = tr_layout.into();
+        self.write().insert(tr_layout.code.clone(), tr_layout)
+    }
+
+    /// 레이아웃을 모두 추가합니다.
+    pub fn extend<I, T>(&self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Arc<TrLayout>>,
+    {
+        let mut layout_tbl = self.write();
+        for tr_layout in iter {
+            let tr_layout = tr_layout.into();
+            layout_tbl.insert(tr_layout.code.clone(), tr_layout);
+        }
+    }
+
+    /// 레이아웃을 삭제하여 반환합니다.
+    pub fn remove(&self, tr_code: &str) -> Option<Arc<TrLayout>> {
+        self.write().remove(tr_code)
+    }
+
+    /// TR 코드로 레이아웃을 찾습니다.
+    pub fn get(&self, tr_code: &str) -> Option<Arc<TrLayout>> {
+        self.read().get(tr_code).cloned()
+    }
+
+    /// 레이아웃이 있는지 여부를 반환합니다.
+    pub fn contains(&self, tr_code: &str) -> bool {
+        self.read().contains_key(tr_code)
+    }
+
+    /// 레이아웃의 개수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// 레이아웃이 없는지 여부를 반환합니다.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// 현재 테이블을 복사하여 반환합니다.
+    ///
+    /// 반환된 테이블은 이후의 추가 및 삭제에 영향을 받지 않습니다.
+    pub fn snapshot(&self) -> HashMap<String, Arc<TrLayout>> {
+        self.read().clone()
+    }
+}
+
+impl From<HashMap<String, Arc<TrLayout>>> for LayoutRegistry {
+    fn from(layout_tbl: HashMap<String, Arc<TrLayout>>) -> Self {
+        layout_tbl.into_values().collect()
+    }
+}
+
+impl<T: Into<Arc<TrLayout>>> FromIterator<T> for LayoutRegistry {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let registry = Self::new();
+        registry.extend(iter);
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutRegistry;
+    use crate::layout::TrLayout;
+
+    use std::sync::Arc;
+
+    fn layout(code: &str) -> TrLayout {
+        format!(
+            "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,{},attr;
+                BEGIN_DATA_MAP
+                END_DATA_MAP
+            END_FUNCTION_MAP
+            ",
+            code
+        )
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry: LayoutRegistry = [layout("t0000"), layout("t0001")].into_iter().collect();
+        let shared = registry.clone();
+        let snapshot = registry.snapshot();
+
+        let tr_layout = Arc::new(layout("t0002"));
+        assert!(shared.insert(tr_layout.clone()).is_none());
+        assert!(Arc::ptr_eq(&registry.get("t0002").unwrap(), &tr_layout));
+        assert_eq!(registry.len(), 3);
+
+        assert!(shared.remove("t0000").is_some());
+        assert!(!registry.contains("t0000"));
+        assert!(registry.remove("t0000").is_none());
+
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.contains_key("t0000"));
+    }
+}
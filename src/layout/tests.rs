@@ -10,3 +10,726 @@ fn test_load_dir() {
     println!("total number of loaded layouts: {:?}", layout_tbl.len());
     println!("loaded layouts: {:?}", layout_codes);
 }
+
+#[test]
+fn test_overflowing_field_length() {
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                필드1,field1,field1,char,18446744073709551615;
+                필드2,field2,field2,char,1;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    assert!(text.parse::<super::TrLayout>().is_err());
+}
+
+#[test]
+fn test_block_layout_new() {
+    use super::{BlockLayout, BlockType, FieldLayout, FieldType};
+
+    let tr_layout: super::TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output,occurs;
+            begin
+                price,price,price,long,8;
+                rate,rate,rate,float,6.2;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    let block_layout = BlockLayout::new(
+        "t0000OutBlock",
+        "출력",
+        BlockType::Output,
+        true,
+        vec![
+            FieldLayout::new("price", FieldType::Int, 8, None),
+            FieldLayout::new("rate", FieldType::Float, 6, Some(2)),
+        ],
+        true,
+    );
+
+    assert_eq!(block_layout.len, 16);
+    assert_eq!(tr_layout.out_blocks, [block_layout]);
+}
+
+#[test]
+fn test_load_error_source() {
+    use std::error::Error;
+
+    let err = super::load_dir("/nonexistent/xingapi/res").unwrap_err();
+    assert!(matches!(err, super::LoadError::Io(_)));
+    assert!(err.source().is_some());
+
+    // 크레이트 공통 에러로 변환해도 원인을 유지합니다.
+    #[cfg(any(windows, feature = "sim"))]
+    {
+        let err = crate::Error::from(err);
+        assert!(matches!(err, crate::Error::Layout(_)));
+        assert!(err.source().unwrap().is::<super::LoadError>());
+    }
+}
+
+#[test]
+fn test_load_dir_recursive() {
+    use std::fs;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let dir = std::env::temp_dir().join(format!("xingapi-recursive-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    for (i, sub_dir) in ["", "a", "a/b"].iter().enumerate() {
+        let sub_dir = dir.join(sub_dir);
+        let code = format!("t000{}", i);
+        let text = text.replace("t0000", &code);
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(&text);
+        fs::create_dir_all(&sub_dir).unwrap();
+        fs::write(sub_dir.join(code + ".res"), &euckr).unwrap();
+    }
+
+    // 상위 디렉터리를 가리키는 링크가 있어도 종료되어야 합니다.
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&dir, dir.join("a/b/loop")).unwrap();
+
+    let count = |max_depth| super::load_dir_recursive(&dir, max_depth).unwrap().len();
+    assert_eq!(count(Some(0)), 1);
+    assert_eq!(count(Some(1)), 2);
+    assert_eq!(count(None), 3);
+    assert_eq!(super::load_dir(&dir).unwrap().len(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_load_from_iter() {
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let (euckr, _, _) = encoding_rs::EUC_KR.encode(text);
+    let layout_tbl = super::load_from_iter([
+        super::__decode_res("t0000.res", &euckr),
+        ("t0001.res".into(), text.replace("t0000", "t0001")),
+    ])
+    .unwrap();
+    assert_eq!(layout_tbl["t0000"].out_blocks[0].desc, "출력");
+    assert_eq!(layout_tbl.len(), 2);
+
+    let err = super::load_from_iter([
+        ("t0000.res", text),
+        ("t0000_.res", &text.replace(",6;", ",8;")),
+    ])
+    .unwrap_err();
+    assert!(matches!(err, super::LoadError::Confilict(code) if code == "t0000"));
+
+    let err = super::load_from_iter([("broken.res", "BEGIN_FUNCTION_MAP")]).unwrap_err();
+    assert!(matches!(err, super::LoadError::Parse(path, _) if path.ends_with("broken.res")));
+}
+
+#[test]
+fn test_load_dir_filtered() {
+    use std::fs;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let dir = std::env::temp_dir().join(format!("xingapi-filtered-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    for code in ["t0000", "t0001", "T0002"] {
+        let text = text.replace("t0000", &code.to_lowercase());
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(&text);
+        fs::write(dir.join(format!("{}.res", code)), &euckr).unwrap();
+    }
+
+    // 요청하지 않은 파일은 파싱하지 않으므로 잘못된 파일이 있어도 됩니다.
+    fs::write(dir.join("t0003.res"), "BEGIN_FUNCTION_MAP").unwrap();
+
+    let layout_tbl = super::load_dir_filtered(&dir, &["t0000", "t0002", "t9999"]).unwrap();
+    let mut codes: Vec<_> = layout_tbl.keys().collect();
+    codes.sort_unstable();
+    assert_eq!(codes, ["t0000", "t0002"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_to_res_string() {
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,주식현재가,t1102,attr,block,headtype=A,SERVICE=t1102;
+            BEGIN_DATA_MAP
+            t1102InBlock,기본입력,input;
+            begin
+                단축코드,shcode,shcode,char,6;
+            end
+            t1102OutBlock,출력,output,occurs;
+            begin
+                현재가,price,price,long,8;
+                등락율,diff,diff,float,6.2;
+                일자,date,date,date,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let tr_layout: super::TrLayout = text.parse().unwrap();
+    let res = tr_layout.to_res_string();
+
+    assert_eq!(tr_layout.param("SERVICE"), Some("t1102"));
+    assert!(res.contains("\t.Func,주식현재가,t1102,attr,block,headtype=A,SERVICE=t1102;\n"));
+    assert!(res.contains("\t\t등락율,diff,diff,float,6.2;\n"));
+    assert_eq!(res.parse::<super::TrLayout>().unwrap(), tr_layout);
+
+    let (_, _, had_errors) = encoding_rs::EUC_KR.encode(&res);
+    assert!(!had_errors);
+}
+
+#[test]
+fn test_parse_lenient() {
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr,UNKNOWN,headtype=Z,svc=t0000;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output,compress;
+            begin
+                코드,code,code,char,6,extra;
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+        garbage
+    ";
+
+    assert!(text.parse::<super::TrLayout>().is_err());
+
+    let (tr_layout, warnings) = super::TrLayout::parse_lenient(text).unwrap();
+    assert_eq!(tr_layout.header_type, None);
+    assert_eq!(tr_layout.out_blocks[0].len, 16);
+
+    let symbols: Vec<_> = warnings.iter().map(|w| w.symbol()).collect();
+    assert_eq!(
+        symbols,
+        [
+            "UNKNOWN",
+            "headtype=Z",
+            "svc=t0000",
+            "compress",
+            "extra",
+            "garbage"
+        ]
+    );
+    assert_eq!(warnings[3].line(), 5);
+    assert_eq!(warnings[5].kind(), super::error::ErrorKind::Syntax);
+
+    // 관대한 모드에서도 구조가 잘못된 경우 에러를 반환합니다.
+    assert!(super::TrLayout::parse_lenient("BEGIN_FUNCTION_MAP .Func,테스트;").is_err());
+}
+
+#[test]
+fn test_load_dir_partial() {
+    use std::fs;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let dir = std::env::temp_dir().join(format!("xingapi-partial-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    for (name, text) in [
+        ("t0000.res", text.to_owned()),
+        ("t0001.res", text.replace("t0000", "t0001")),
+        ("t0002.res", "BEGIN_FUNCTION_MAP".to_owned()),
+        ("t0003.res", text.replace(",6;", ",8;")),
+    ] {
+        let (euckr, _, _) = encoding_rs::EUC_KR.encode(&text);
+        fs::write(dir.join(name), &euckr).unwrap();
+    }
+    fs::write(dir.join("t0004.res"), b"\xff\xff").unwrap();
+
+    let result = super::load_dir_partial(&dir).unwrap();
+    assert_eq!(result.layouts.len(), 2);
+    assert_eq!(result.layouts["t0000"].out_blocks[0].fields[0].len, 6);
+
+    let failures: Vec<_> = result
+        .failures
+        .iter()
+        .map(|(path, err)| (path.file_name().unwrap().to_str().unwrap(), err))
+        .collect();
+    assert!(matches!(
+        failures[..],
+        [
+            ("t0002.res", super::LoadError::Parse(..)),
+            ("t0003.res", super::LoadError::Confilict(_)),
+            ("t0004.res", super::LoadError::Encoding(_)),
+        ]
+    ));
+
+    assert!(super::load_dir(&dir).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_lookup() {
+    let tr_layout: super::TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input;
+            begin
+                코드,shcode,code,char,6;
+            end
+            t0000OutBlock,출력,output;
+            begin
+                코드,shcode,code,char,6;
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    assert!(tr_layout.in_block("t0000OutBlock").is_none());
+    let block_layout = tr_layout.out_block("t0000OutBlock").unwrap();
+
+    assert_eq!(&*block_layout.field("shcode").unwrap().name, "code");
+    assert_eq!(block_layout.field_offset("code"), Some(0));
+    assert_eq!(block_layout.field_offset("price"), Some(7));
+    assert_eq!(block_layout.field_offset("volume"), None);
+
+    let mut block_layout = block_layout.clone();
+    block_layout.len -= 2;
+    assert_eq!(block_layout.field_offset("price"), Some(6));
+}
+
+#[test]
+fn test_expected_request_len() {
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000InBlock,입력,input;
+            begin
+                코드,code,code,char,6;
+            end
+            t0000InBlock1,입력1,input,occurs;
+            begin
+                구분,gubn,gubn,char,1;
+            end
+            t0000OutBlock,출력,output;
+            begin
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let mut tr_layout: super::TrLayout = text.parse().unwrap();
+    assert_eq!(tr_layout.expected_request_len(|_| 3), 7 + 5 + 3 * 2);
+
+    tr_layout.block_mode = true;
+    assert_eq!(tr_layout.expected_request_len(|_| 3), 7 + 3 * 2);
+}
+
+#[test]
+fn test_packet_header_len() {
+    use super::HeaderType;
+
+    let header_lens: Vec<_> = [HeaderType::A, HeaderType::B, HeaderType::C, HeaderType::D]
+        .into_iter()
+        .map(HeaderType::packet_header_len)
+        .collect();
+    assert_eq!(header_lens, [27, 41, 34, 25]);
+}
+
+#[test]
+fn test_decode_res() {
+    use crate::data::Encoding;
+    use std::fs;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let (euckr, _, _) = encoding_rs::EUC_KR.encode(text);
+    let bom = [b"\xef\xbb\xbf", text.as_bytes()].concat();
+
+    assert_eq!(super::decode_res(&euckr).unwrap().1, Encoding::EucKr);
+    assert_eq!(
+        super::decode_res(text.as_bytes()).unwrap().1,
+        Encoding::Utf8
+    );
+    assert_eq!(
+        super::decode_res(&bom).unwrap(),
+        (text.into(), Encoding::Utf8)
+    );
+    assert_eq!(super::decode_res(b"abc").unwrap().1, Encoding::EucKr);
+    assert!(super::decode_res(b"\xef\xbb\xbf\xff").is_none());
+
+    let dir = std::env::temp_dir().join(format!("xingapi-decode-res-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("t0000.res"), &euckr).unwrap();
+    fs::write(dir.join("t0001.res"), text.replace("t0000", "t0001")).unwrap();
+    fs::write(
+        dir.join("t0002.res"),
+        [&bom[..3], text.replace("t0000", "t0002").as_bytes()].concat(),
+    )
+    .unwrap();
+
+    let result = super::load_dir_partial(&dir).unwrap();
+    assert_eq!(result.layouts.len(), 3);
+    assert!(result.failures.is_empty());
+    assert_eq!(result.layouts["t0002"].out_blocks[0].desc, "출력");
+    assert_eq!(
+        result.encodings,
+        [
+            (dir.join("t0001.res"), Encoding::Utf8),
+            (dir.join("t0002.res"), Encoding::Utf8),
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn test_load_zip() {
+    use super::error::{ArchiveErrorKind, LoadError};
+    use std::fs;
+
+    // res/t0000.res (deflate, 동적 허프만), t0001.res (저장), res/t0002.res
+    // (deflate, 고정 허프만), res/t0003.res (deflate, 저장 블록), readme.txt
+    const ZIP_BASE64_DATA: &str = "
+        UEsDBBQAAAAAAAAAIQAAAAAAAAAAAAAAAAAEAAAAcmVzL1BLAwQUAAAACACCi09dxa7VAVABAABc
+        BAAADQAAAHJlcy90MDAwMC5yZXNd1M9Kw0AQx/Fz8iyLdLf/KR4S20oORg/1XNIk2mCJpaQHX0rw
+        IEJFEEEFz0Lvgu9hnZ2dzMRDvzi/1MOH1nByGsXz6WV8MovO4/lZcOF7R9Ntmar3/cvu475qHX6U
+        fU2qaqMWq9v0Ri3zJKvu1vlxMPK9EP7GOJgF9v3wdFSG8OTDz+759fvpTRXlelsdnl7k10Xpe97n
+        1+NvS10V+SqjpMtko/QIV23PFFiNW409U2Btu7VtzxRYO27t2DMF1q5bu/ZMgbXn1p49U2Dtu7Vv
+        zxRYB24d2DMF1qFbh/ZMaWigEqsE0Q6qrjTRaMUqWTRysUoZjWKsEkcjGqv00ejGKok00rFKJY16
+        rA0oBGRtfHJQkFVCGRRklVDGferqSiiDgqwSyqAgq4QyKMgqoQwKskoog4KsEsqgIGsDCgVZCSov
+        M9+bxOP6e///i/hH8gdQSwMEFAAAAAAAgotPXcEExhvYAAAA2AAAAAkAAAB0MDAwMS5yZXNCRUdJ
+        Tl9GVU5DVElPTl9NQVAKCS5GdW5jLMXXvbrGrnQwMDAxLHQwMDAxLGF0dHIsYmxvY2ssaGVhZHR5
+        cGU9QTsKCUJFR0lOX0RBVEFfTUFQCgl0MDAwMUluQmxvY2ssseK6u8DUt8IsaW5wdXQ7CgliZWdp
+        bgoJCcfKteUwLGZpZWxkMCxmaWVsZDAsY2hhciwxOwoJCcfKteUxLGZpZWxkMSxmaWVsZDEsY2hh
+        ciwyOwoJZW5kCglFTkRfREFUQV9NQVAKRU5EX0ZVTkNUSU9OX01BUApQSwMEFAAAAAgAgotPXWr/
+        /L2PAAAAugAAAA0AAAByZXMvdDAwMDIucmVzc3J19/SLdwv1cw7x9PeL93UM4OLUcyvNS9Y5en3v
+        rmPrSgwMDIx0IGRiSUmRTlJOfnK2TkZqYkpJZUGqraM1F6cT2AwXxxBHiH6was88J7DKjY927T5w
+        Zfshncy8gtISoOqk1PTMPC5OzuOntj410EnLTM1JgVPJGYlFOoZARal5KVycrn4uCFNBHBRnAgBQ
+        SwMEFAAAAAgAgotPXQPT/zC/AAAAugAAAA0AAAByZXMvdDAwMDMucmVzAboARf9CRUdJTl9GVU5D
+        VElPTl9NQVAKCS5GdW5jLMXXvbrGrnQwMDAzLHQwMDAzLGF0dHIsYmxvY2ssaGVhZHR5cGU9QTsK
+        CUJFR0lOX0RBVEFfTUFQCgl0MDAwM0luQmxvY2ssseK6u8DUt8IsaW5wdXQ7CgliZWdpbgoJCcfK
+        teUwLGZpZWxkMCxmaWVsZDAsY2hhciwxOwoJZW5kCglFTkRfREFUQV9NQVAKRU5EX0ZVTkNUSU9O
+        X01BUApQSwMEFAAAAAAAgotPXYamEDYFAAAABQAAAAoAAAByZWFkbWUudHh0aGVsbG9QSwECFAMU
+        AAAAAAAAACEAAAAAAAAAAAAAAAAABAAAAAAAAAAAAAAAgAEAAAAAcmVzL1BLAQIUAxQAAAAIAIKL
+        T13FrtUBUAEAAFwEAAANAAAAAAAAAAAAAACAASIAAAByZXMvdDAwMDAucmVzUEsBAhQDFAAAAAAA
+        gotPXcEExhvYAAAA2AAAAAkAAAAAAAAAAAAAAIABnQEAAHQwMDAxLnJlc1BLAQIUAxQAAAAIAIKL
+        T11q//y9jwAAALoAAAANAAAAAAAAAAAAAACAAZwCAAByZXMvdDAwMDIucmVzUEsBAhQDFAAAAAgA
+        gotPXQPT/zC/AAAAugAAAA0AAAAAAAAAAAAAAIABVgMAAHJlcy90MDAwMy5yZXNQSwECFAMUAAAA
+        AACCi09dhqYQNgUAAAAFAAAACgAAAAAAAAAAAAAAgAFABAAAcmVhZG1lLnR4dFBLBQYAAAAABgAG
+        AFIBAABtBAAAAAA=
+    ";
+
+    let data = base64::decode(ZIP_BASE64_DATA.replace(char::is_whitespace, "")).unwrap();
+
+    let layout_tbl = super::load_zip_bytes(&data).unwrap();
+    assert_eq!(layout_tbl.len(), 4);
+    assert_eq!(layout_tbl["t0000"].in_blocks[0].fields.len(), 30);
+    assert_eq!(layout_tbl["t0001"].desc, "테스트t0001");
+
+    let path = std::env::temp_dir().join(format!("xingapi-load-zip-{}.zip", std::process::id()));
+    fs::write(&path, &data).unwrap();
+    assert_eq!(super::load_zip(&path).unwrap(), layout_tbl);
+    fs::remove_file(&path).unwrap();
+
+    // t0001.res의 내용을 변경합니다.
+    let mut corrupted = data.clone();
+    corrupted[452 + 20] ^= 1;
+    match super::load_zip_bytes(&corrupted) {
+        Err(LoadError::Archive(err)) => {
+            assert_eq!(err.kind(), ArchiveErrorKind::Checksum);
+            assert_eq!(err.entry(), Some("t0001.res"));
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    assert!(matches!(
+        super::load_zip_bytes(&data[..data.len() - 1]),
+        Err(LoadError::Archive(err)) if err.kind() == ArchiveErrorKind::Malformed
+    ));
+}
+
+#[test]
+fn test_load_json() {
+    use super::error::{JsonError, LoadError};
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,주식현재가,t1102,attr,headtype=B;
+            BEGIN_DATA_MAP
+            t1102InBlock,기본입력,input;
+            begin
+                단축코드,shcode,shcode,char,6;
+            end
+            t1102OutBlock,출력,output,occurs;
+            begin
+                현재가,price,price,long,8;
+                등락율,diff,diff,float,6.2;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+    let tr_layout: super::TrLayout = text.parse().unwrap();
+
+    let json = r#"{"t1102": {
+        "tr_type": "func", "desc": "주식현재가", "code": "t1102", "attr_byte": true,
+        "block_mode": false, "header_type": "B",
+        "in_blocks": [{
+            "name": "t1102InBlock", "desc": "기본입력", "block_type": "input",
+            "occurs": false, "len": 7,
+            "fields": [{"desc": "단축코드", "name_old": "shcode", "name": "shcode",
+                "field_type": "char", "len": 6, "point": null}]
+        }],
+        "out_blocks": [{
+            "name": "t1102OutBlock", "desc": "출력", "block_type": "output",
+            "occurs": true, "len": 16,
+            "fields": [
+                {"desc": "현재가", "name_old": "price", "name": "price",
+                    "field_type": "int", "len": 8, "point": null},
+                {"desc": "등락율", "name_old": "diff", "name": "diff",
+                    "field_type": "float", "len": 6, "point": 2}
+            ]
+        }]
+    }}"#;
+
+    let layout_tbl = super::load_json(json.as_bytes()).unwrap();
+    assert_eq!(*layout_tbl["t1102"], tr_layout);
+
+    let json = json.replace(r#""point": 2"#, r#""point": "2""#);
+    assert!(matches!(
+        super::load_json(json.as_bytes()),
+        Err(LoadError::Json(JsonError::InvalidValue(path)))
+            if path == "t1102.out_blocks[0].fields[1].point"
+    ));
+
+    assert!(matches!(
+        super::load_json(&b"[{]"[..]),
+        Err(LoadError::Json(JsonError::Syntax(2)))
+    ));
+}
+
+#[test]
+fn test_header_params() {
+    let tr_layout: super::TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Feed,KOSPI체결,S3_,attr,key=6,group=1,ENCRYPT,svr=REAL;
+            BEGIN_DATA_MAP
+            InBlock,입력,input;
+            begin
+                단축코드,shcode,shcode,char,6;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        tr_layout.params,
+        [
+            ("key".to_owned(), "6".to_owned()),
+            ("group".to_owned(), "1".to_owned()),
+            ("ENCRYPT".to_owned(), String::new()),
+            ("svr".to_owned(), "REAL".to_owned()),
+        ]
+    );
+    assert_eq!(tr_layout.param("key"), Some("6"));
+    assert_eq!(tr_layout.param("ENCRYPT"), Some(""));
+    assert_eq!(tr_layout.param("tuxcode"), None);
+    assert!(tr_layout
+        .to_res_string()
+        .contains(",attr,key=6,group=1,ENCRYPT,svr=REAL;"));
+}
+
+#[test]
+fn test_load_dir_with_progress() {
+    use std::fs;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    let dir = std::env::temp_dir().join(format!("xingapi-progress-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    for code in ["t0000", "t0001", "t0002"] {
+        fs::write(
+            dir.join(format!("{}.res", code)),
+            text.replace("t0000", code),
+        )
+        .unwrap();
+    }
+
+    let mut events = Vec::new();
+    let layout_tbl = super::load_dir_with_progress(&dir, |event| {
+        events.push((event.path.to_owned(), event.loaded, event.total));
+    })
+    .unwrap();
+    assert_eq!(layout_tbl.len(), 3);
+
+    events.sort_unstable();
+    let paths: Vec<_> = events.iter().map(|(path, _, _)| path.clone()).collect();
+    assert_eq!(
+        paths,
+        ["t0000.res", "t0001.res", "t0002.res"].map(|name| dir.join(name))
+    );
+
+    let mut loaded: Vec<_> = events.iter().map(|(_, loaded, _)| *loaded).collect();
+    loaded.sort_unstable();
+    assert_eq!(loaded, [1, 2, 3]);
+    assert!(events.iter().all(|(_, _, total)| *total == 3));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_occurs_count() {
+    let tr_layout: super::TrLayout = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output,occurs;
+            begin
+                가격,price,price,long,8;
+            end
+            t0000OutBlock1,출력1,output,occurs 10;
+            begin
+                가격,price,price,long,8;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse()
+    .unwrap();
+
+    assert!(tr_layout.out_blocks[0].occurs);
+    assert_eq!(tr_layout.out_blocks[0].occurs_count, None);
+    assert!(tr_layout.out_blocks[1].occurs);
+    assert_eq!(tr_layout.out_blocks[1].occurs_count, Some(10));
+    assert_eq!(
+        tr_layout
+            .to_res_string()
+            .parse::<super::TrLayout>()
+            .unwrap(),
+        tr_layout
+    );
+
+    assert!("
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output,occurs x;
+            begin
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    "
+    .parse::<super::TrLayout>()
+    .is_err());
+}
+
+#[test]
+fn test_unknown_field_type() {
+    use super::FieldType;
+
+    let text = "
+        BEGIN_FUNCTION_MAP
+            .Func,테스트,t0000,attr;
+            BEGIN_DATA_MAP
+            t0000OutBlock,출력,output;
+            begin
+                코드,code,code,char,6;
+                시각,time,time,timestamp,12;
+            end
+            END_DATA_MAP
+        END_FUNCTION_MAP
+    ";
+
+    assert!(text.parse::<super::TrLayout>().is_err());
+
+    let (tr_layout, warnings) = super::TrLayout::parse_lenient(text).unwrap();
+    let field = &tr_layout.out_blocks[0].fields[1];
+    assert_eq!(field.field_type, FieldType::Unknown("timestamp".to_owned()));
+    assert_eq!(field.len, 12);
+    assert_eq!(tr_layout.out_blocks[0].len, 20);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].symbol(), "timestamp");
+
+    let res_string = tr_layout.to_res_string();
+    assert!(res_string.contains(",time,timestamp,12;"));
+    assert_eq!(
+        super::TrLayout::parse_lenient(&res_string).unwrap().0,
+        tr_layout
+    );
+}
@@ -2,7 +2,9 @@
 
 //! eBEST 증권의 XingAPI를 쉽고 안전하게 사용할 수 있는 래퍼 라이브러리입니다.
 //!
-//! 현재는 윈도우용 XingAPI만 지원하고 있습니다.
+//! 현재는 윈도우용 XingAPI만 지원하고 있습니다. 다른 운영체제에서는 `sim`
+//! 기능을 활성화하여 지정한 데이터를 반환하는 시뮬레이션 백엔드를 사용할 수
+//! 있습니다.
 //!
 //! # 요구 사항
 //! - 시스템에 다음의 구성 요소가 설치되어 있어야 합니다.
@@ -39,8 +41,13 @@
 pub mod data;
 pub mod layout;
 
-#[cfg(windows)]
+mod codec;
+
+#[cfg(any(windows, feature = "sim"))]
 mod os;
 
 #[cfg(windows)]
 pub use os::windows::*;
+
+#[cfg(all(not(windows), feature = "sim"))]
+pub use os::sim::*;
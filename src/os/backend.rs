@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 백엔드 추상화 모듈
+//!
+//! 서버 연결, 로그인, 조회 요청을 [`Backend`] 트레잇으로 묶어서 제공합니다.
+//! 애플리케이션 로직을 트레잇에 대해 작성하면 실제 DLL을 사용하는 백엔드
+//! 대신 [`MockBackend`]로 DLL 없이 시험할 수 있으며, 윈도우가 아닌 환경에서도
+//! 사용할 수 있습니다.
+//!
+//! [`MockBackend`]는 미리 지정한 응답 데이터를 레이아웃으로 인코딩한 후
+//! 실제 수신한 데이터와 같은 경로로 디코딩하여 반환합니다.
+
+use super::common::{Account, Error, LazyData, LoginResponse, QueryResponse, RealResponse};
+use crate::data::{self, Data, DataType, EncodeError, RawData};
+use crate::layout::TrLayout;
+
+use crossbeam_channel::{Receiver, Sender};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// 서버와 통신하는 백엔드
+pub trait Backend: Send + Sync {
+    /// 서버에 연결합니다.
+    fn connect(&self, addr: &str, port: u16, timeout: Duration) -> Result<(), Error>;
+
+    /// 서버 연결 여부를 반환합니다.
+    fn is_connected(&self) -> bool;
+
+    /// 서버와의 연결을 종료합니다.
+    fn disconnect(&self);
+
+    /// 서버에 로그인 요청을 합니다.
+    fn login(
+        &self,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<LoginResponse, Error>;
+
+    /// 서버에 조회 TR 요청을 합니다.
+    ///
+    /// 레이아웃은 응답을 디코딩할 때까지 공유되므로 복제하지 않습니다.
+    fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error>;
+
+    /// 계좌 목록을 반환합니다.
+    fn accounts(&self) -> Vec<Account>;
+}
+
+// 조회 TR 요청에 대해 반환할 응답
+pub(crate) struct CannedResponse {
+    pub code: String,
+    pub message: String,
+    pub next_key: Option<String>,
+    pub data: Option<CannedData>,
+}
+
+pub(crate) enum CannedData {
+    // 수신한 형태로 인코딩된 데이터
+    Raw(RawData),
+    // 요청에 사용한 레이아웃으로 인코딩할 데이터
+    Data(Data),
+}
+
+impl CannedData {
+    pub fn into_raw(self, tr_layout: &TrLayout) -> Result<RawData, EncodeError> {
+        match self {
+            Self::Raw(raw_data) => Ok(raw_data),
+            Self::Data(data) => data::encode_raw(&data, tr_layout),
+        }
+    }
+}
+
+struct MockState {
+    connected: bool,
+    login_res: LoginResponse,
+    accounts: Vec<Account>,
+    res_tbl: HashMap<String, VecDeque<CannedResponse>>,
+    requests: Vec<Data>,
+}
+
+/// 미리 지정한 응답을 반환하는 백엔드
+///
+/// 조회 TR 요청 시 TR 코드별로 지정한 응답을 지정한 순서대로 반환하며, 남은
+/// 응답이 없는 경우 `Error::TimedOut`을 반환합니다. 실시간 응답은
+/// [`push_real()`](Self::push_real)로 보내고 [`try_recv()`](Self::try_recv)로
+/// 수신합니다.
+pub struct MockBackend {
+    layout_tbl: HashMap<String, Arc<TrLayout>>,
+    state: Mutex<MockState>,
+    tx_real: Sender<RealResponse>,
+    rx_real: Receiver<RealResponse>,
+}
+
+impl MockBackend {
+    /// 응답을 인코딩하기 위한 레이아웃 목록으로 객체를 생성합니다.
+    pub fn new(layout_tbl: HashMap<String, Arc<TrLayout>>) -> Self {
+        let (tx_real, rx_real) = crossbeam_channel::unbounded();
+
+        Self {
+            layout_tbl,
+            state: Mutex::new(MockState {
+                connected: false,
+                login_res: LoginResponse {
+                    code: "0000".into(),
+                    message: String::new(),
+                },
+                accounts: Vec::new(),
+                res_tbl: HashMap::new(),
+                requests: Vec::new(),
+            }),
+            tx_real,
+            rx_real,
+        }
+    }
+
+    /// 로그인 요청에 대한 응답 코드와 메시지를 지정합니다.
+    pub fn set_login_response(&self, code: &str, message: &str) {
+        self.state.lock().unwrap().login_res = LoginResponse {
+            code: code.to_owned(),
+            message: message.to_owned(),
+        };
+    }
+
+    /// 계좌 목록을 지정합니다.
+    pub fn set_accounts(&self, accounts: Vec<Account>) {
+        self.state.lock().unwrap().accounts = accounts;
+    }
+
+    /// 조회 TR 요청에 대해 반환할 정상 응답을 추가합니다.
+    ///
+    /// 응답 데이터는 레이아웃으로 인코딩되며, 인코딩할 수 없는 경우 에러를
+    /// 반환합니다.
+    pub fn push_response(&self, data: &Data, next_key: Option<&str>) -> Result<(), EncodeError> {
+        let raw_data = data::encode_raw(data, self.layout(&data.tr_code)?)?;
+
+        self.push(
+            &data.tr_code,
+            CannedResponse {
+                code: "00000".into(),
+                message: "조회완료".into(),
+                next_key: next_key.map(|key| key.to_owned()),
+                data: Some(CannedData::Raw(raw_data)),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// 조회 TR 요청에 대해 반환할 정상 응답을 추가합니다.
+    ///
+    /// [`push_response()`](Self::push_response)와 달리 응답 데이터는 요청할 때
+    /// 요청에 사용한 레이아웃으로 인코딩되므로 레이아웃 목록에 없는 TR에도
+    /// 사용할 수 있습니다. 인코딩할 수 없는 경우 요청이 에러를 반환합니다.
+    pub fn push_data(&self, data: Data, next_key: Option<&str>) {
+        let tr_code = data.tr_code.clone();
+
+        self.push(
+            &tr_code,
+            CannedResponse {
+                code: "00000".into(),
+                message: "조회완료".into(),
+                next_key: next_key.map(|key| key.to_owned()),
+                data: Some(CannedData::Data(data)),
+            },
+        );
+    }
+
+    /// 조회 TR 요청에 대해 반환할 데이터가 없는 응답을 추가합니다.
+    pub fn push_message(&self, tr_code: &str, code: &str, message: &str) {
+        self.push(
+            tr_code,
+            CannedResponse {
+                code: code.to_owned(),
+                message: message.to_owned(),
+                next_key: None,
+                data: None,
+            },
+        );
+    }
+
+    pub(crate) fn push(&self, tr_code: &str, res: CannedResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .res_tbl
+            .entry(tr_code.to_owned())
+            .or_default()
+            .push_back(res);
+    }
+
+    // TR 코드에 대해 다음으로 반환할 응답을 가져옵니다.
+    pub(crate) fn pop(&self, tr_code: &str) -> Option<CannedResponse> {
+        self.state
+            .lock()
+            .unwrap()
+            .res_tbl
+            .get_mut(tr_code)
+            .and_then(|queue| queue.pop_front())
+    }
+
+    /// 실시간 응답을 보냅니다.
+    ///
+    /// 응답 데이터는 레이아웃으로 인코딩한 후 다시 디코딩됩니다.
+    pub fn push_real(&self, key: &str, data: &Data) -> Result<(), EncodeError> {
+        // 실시간 TR은 block mode를 사용하지 않습니다.
+        let tr_layout = self.layout(&data.tr_code)?;
+        if tr_layout.block_mode {
+            return Err(EncodeError::MismatchLayout);
+        }
+
+        let raw_data = data::encode(data, tr_layout)?;
+
+        let _ = self.tx_real.send(RealResponse {
+            tr_code: data.tr_code.clone(),
+            key: key.to_owned(),
+            data: data::decode_non_block(tr_layout, DataType::Output, &raw_data),
+            received_at: SystemTime::now(),
+        });
+
+        Ok(())
+    }
+
+    /// 보낸 실시간 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        self.rx_real.try_recv().ok()
+    }
+
+    /// 보낸 실시간 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.rx_real.recv_timeout(timeout).ok()
+    }
+
+    /// 지금까지 [`Backend::request()`]로 요청한 데이터 목록을 반환합니다.
+    pub fn requests(&self) -> Vec<Data> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub(crate) fn layout(&self, tr_code: &str) -> Result<&Arc<TrLayout>, EncodeError> {
+        self.layout_tbl
+            .get(tr_code)
+            .ok_or(EncodeError::MismatchLayout)
+    }
+}
+
+impl Backend for MockBackend {
+    fn connect(&self, _addr: &str, _port: u16, _timeout: Duration) -> Result<(), Error> {
+        self.state.lock().unwrap().connected = true;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.state.lock().unwrap().connected
+    }
+
+    fn disconnect(&self) {
+        self.state.lock().unwrap().connected = false;
+    }
+
+    fn login(
+        &self,
+        _id: &str,
+        _pw: &str,
+        _cert_pw: &str,
+        _cert_err_dialog: bool,
+    ) -> Result<LoginResponse, Error> {
+        Ok(self.state.lock().unwrap().login_res.clone())
+    }
+
+    fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        _next_key: Option<&str>,
+        _timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        // 실제 요청과 같이 요청 데이터를 인코딩할 수 있는지 확인합니다.
+        data::encode(data, tr_layout)?;
+
+        self.state.lock().unwrap().requests.push(data.clone());
+        let res = self.pop(&data.tr_code).ok_or(Error::TimedOut)?;
+
+        let raw_data = res.data.map(|data| data.into_raw(tr_layout)).transpose()?;
+
+        Ok(QueryResponse {
+            code: res.code,
+            message: res.message,
+            elapsed: Duration::ZERO,
+            next_key: res.next_key,
+            data: raw_data.map(|raw_data| LazyData::new(raw_data, tr_layout.clone())),
+        })
+    }
+
+    fn accounts(&self) -> Vec<Account> {
+        self.state.lock().unwrap().accounts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Backend, MockBackend};
+    use crate::data::{self, Block, Data, DataType};
+    use crate::hashmap;
+    use crate::os::testdata::{s3_layout, t1102_layout};
+    use crate::{Error, Response};
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_mock_backend() {
+        let tr_layout = Arc::new(t1102_layout());
+        let backend = MockBackend::new(hashmap! {
+            "t1102" => tr_layout.clone(),
+            "S3_" => s3_layout(),
+        });
+
+        let out_data = Data {
+            tr_code: "t1102".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+            },
+        };
+        backend.push_response(&out_data, None).unwrap();
+
+        let mut in_data = data::empty_input(&tr_layout);
+        data::set_field(&mut in_data, "t1102InBlock", "shcode", "005930").unwrap();
+
+        let timeout = Duration::from_secs(1);
+        let res = backend
+            .request(&in_data, &tr_layout, None, timeout)
+            .unwrap();
+        assert!(res.is_ok());
+        assert_eq!(res.data().unwrap(), &out_data);
+
+        assert_eq!(backend.requests(), [in_data.clone()]);
+
+        assert!(matches!(
+            backend.request(&in_data, &tr_layout, None, timeout),
+            Err(Error::TimedOut)
+        ));
+
+        let real_data = Data {
+            tr_code: "S3_".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "OutBlock" => Block::Block(hashmap! { "price" => "91100" }),
+            },
+        };
+        backend.push_real("005930", &real_data).unwrap();
+
+        let res = backend.try_recv().unwrap();
+        assert_eq!(res.key(), "005930");
+        assert_eq!(res.data().unwrap(), &real_data);
+
+        assert!(backend.push_real("005930", &out_data).is_err());
+        assert!(backend.try_recv().is_none());
+    }
+}
@@ -0,0 +1,856 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// 플랫폼에 관계없이 공통으로 사용하는 응답 및 에러 타입
+
+use crate::data::{
+    self, Attrs, Columns, Data, DataRef, DecodeError, EncodeError, FieldError, RawData,
+};
+use crate::layout::error::LoadError as LayoutLoadError;
+use crate::layout::TrLayout;
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 이베스트투자증권 계좌 정보
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Account {
+    /// 계좌번호
+    pub code: String,
+    /// 계좌명
+    pub name: String,
+    /// 계좌 상세명
+    pub detailed_name: String,
+    /// 계좌 별명
+    pub nickname: String,
+}
+
+/// XingAPI 함수가 실패하여 발생하는 에러
+///
+/// 크레이트에서 발생하는 다른 에러들은 이 타입으로 변환할 수 있으므로 `?`
+/// 연산자로 함께 처리할 수 있습니다.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// XingAPI 에러
+    XingApi {
+        /// 음수로 표현되는 에러 코드
+        code: i32,
+        /// 에러 메시지
+        message: String,
+    },
+    /// 인코딩 에러
+    Encode(EncodeError),
+    /// 디코딩 에러
+    Decode(DecodeError),
+    /// 필드 값을 가져오지 못함
+    Field(FieldError),
+    /// 서버가 요청을 정상적으로 처리하지 못함
+    Rejected {
+        /// 응답 코드
+        code: String,
+        /// 응답 메시지
+        message: String,
+    },
+    /// 중복 주문
+    ///
+    /// [`OrderGuard`](crate::order::OrderGuard)가 동일한 주문을 거부한
+    /// 경우입니다.
+    DuplicateOrder,
+    /// 시간 초과
+    TimedOut,
+    /// 응답을 기다리는 요청이 너무 많음
+    ///
+    /// 요청 슬롯이 모두 사용 중인 경우입니다. 진행 중인 요청이 끝난 후 다시
+    /// 요청할 수 있습니다.
+    Busy,
+    /// 내부 에러
+    ///
+    /// 요청을 보낸 후 XingAPI가 예상하지 못한 값을 반환한 경우입니다. 요청이
+    /// 이미 서버로 전송되었을 수 있으므로 다시 요청하기 전에 결과를 확인해야
+    /// 합니다.
+    Internal(&'static str),
+    /// 다른 프로세스와의 통신 에러
+    ///
+    /// `broker` 기능의 헬퍼 프로세스와 통신하지 못한 경우입니다.
+    Ipc(std::io::Error),
+    /// TR 레이아웃을 불러오지 못함
+    Layout(LayoutLoadError),
+    /// XingAPI를 불러오지 못함
+    #[cfg(windows)]
+    Load(super::windows::LoadError),
+}
+
+impl From<EncodeError> for Error {
+    fn from(err: EncodeError) -> Self {
+        Self::Encode(err)
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<FieldError> for Error {
+    fn from(err: FieldError) -> Self {
+        Self::Field(err)
+    }
+}
+
+impl From<LayoutLoadError> for Error {
+    fn from(err: LayoutLoadError) -> Self {
+        Self::Layout(err)
+    }
+}
+
+#[cfg(windows)]
+impl From<super::windows::LoadError> for Error {
+    fn from(err: super::windows::LoadError) -> Self {
+        Self::Load(err)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::XingApi { code, message } => {
+                write!(f, "xingapi error; code: {}, message: {}", code, message)
+            }
+            Self::Encode(err) => err.fmt(f),
+            Self::Decode(err) => err.fmt(f),
+            Self::Field(err) => err.fmt(f),
+            Self::Rejected { code, message } => {
+                write!(f, "request rejected; code: {}, message: {}", code, message)
+            }
+            Self::DuplicateOrder => "duplicate order".fmt(f),
+            Self::TimedOut => "request timed out".fmt(f),
+            Self::Busy => "too many pending requests".fmt(f),
+            Self::Internal(msg) => write!(f, "internal error: {}", msg),
+            Self::Ipc(err) => write!(f, "ipc error: {}", err),
+            Self::Layout(err) => write!(f, "layout error: {}", err),
+            #[cfg(windows)]
+            Self::Load(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Encode(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::Field(err) => Some(err),
+            Self::Ipc(err) => Some(err),
+            Self::Layout(err) => Some(err),
+            #[cfg(windows)]
+            Self::Load(err) => Some(err),
+            Self::XingApi { .. }
+            | Self::Rejected { .. }
+            | Self::DuplicateOrder
+            | Self::TimedOut
+            | Self::Busy
+            | Self::Internal(_) => None,
+        }
+    }
+}
+
+impl Error {
+    /// XingAPI 에러인 경우 에러 코드의 종류를 반환합니다.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::XingApi { code, .. } => Some(ErrorKind::from_code(*code)),
+            _ => None,
+        }
+    }
+
+    /// 요청이 서버로 전송되지 않은 것이 확실한 에러인지 확인합니다.
+    ///
+    /// 인코딩 에러, XingAPI 에러, [`Error::Busy`]가 해당하며, 같은 요청을 다시
+    /// 보내더라도 중복되지 않습니다.
+    pub fn is_unsent(&self) -> bool {
+        matches!(self, Self::Encode(_) | Self::XingApi { .. } | Self::Busy)
+    }
+}
+
+/// XingAPI 에러 코드의 종류
+///
+/// 문서화된 에러 코드를 열거하며 그 외의 코드는 [`ErrorKind::Other`]로
+/// 표현합니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// 소켓 생성 실패 (-1)
+    SocketCreationFailed,
+    /// 서버 연결 실패 (-2)
+    ConnectionFailed,
+    /// 서버 주소가 맞지 않음 (-3)
+    InvalidAddress,
+    /// 서버 연결 시간 초과 (-4)
+    ConnectionTimedOut,
+    /// 이미 서버에 연결 중 (-5)
+    AlreadyConnected,
+    /// 사용할 수 없는 TR (-6)
+    TrUnavailable,
+    /// 로그인이 필요함 (-7)
+    LoginRequired,
+    /// 시세 전용에서는 사용할 수 없음 (-8)
+    QuoteOnly,
+    /// 해당 계좌번호를 가지고 있지 않음 (-9)
+    AccountNotOwned,
+    /// 패킷의 크기가 잘못됨 (-10)
+    InvalidPacketSize,
+    /// 데이터의 크기가 다름 (-11)
+    DataSizeMismatch,
+    /// 계좌가 존재하지 않음 (-12)
+    AccountNotFound,
+    /// 요청 ID 부족 (-13)
+    RequestIdExhausted,
+    /// 소켓이 생성되지 않음 (-14)
+    SocketNotCreated,
+    /// 암호화 생성 실패 (-15)
+    EncryptionFailed,
+    /// 데이터 전송 실패 (-16)
+    SendFailed,
+    /// 암호화(RTN) 처리 실패 (-17)
+    DecryptionFailed,
+    /// 공인인증 파일이 없음 (-18)
+    CertFileNotFound,
+    /// 공인인증 함수가 없음 (-19)
+    CertFunctionNotFound,
+    /// 메모리 부족 (-20)
+    OutOfMemory,
+    /// TR의 초당 요청 횟수 초과 (-21)
+    RateLimited,
+    /// 해당 TR에서 사용할 수 없는 함수 (-22)
+    UnsupportedFunction,
+    /// TR 정보를 찾을 수 없음 (-23)
+    TrInfoNotFound,
+    /// 계좌 위치가 지정되지 않음 (-24)
+    AccountIndexNotSet,
+    /// 계좌를 가지고 있지 않음 (-25)
+    NoAccount,
+    /// 문서화되지 않은 에러 코드
+    Other(i32),
+}
+
+impl ErrorKind {
+    /// 에러 코드를 종류로 변환합니다.
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            -1 => Self::SocketCreationFailed,
+            -2 => Self::ConnectionFailed,
+            -3 => Self::InvalidAddress,
+            -4 => Self::ConnectionTimedOut,
+            -5 => Self::AlreadyConnected,
+            -6 => Self::TrUnavailable,
+            -7 => Self::LoginRequired,
+            -8 => Self::QuoteOnly,
+            -9 => Self::AccountNotOwned,
+            -10 => Self::InvalidPacketSize,
+            -11 => Self::DataSizeMismatch,
+            -12 => Self::AccountNotFound,
+            -13 => Self::RequestIdExhausted,
+            -14 => Self::SocketNotCreated,
+            -15 => Self::EncryptionFailed,
+            -16 => Self::SendFailed,
+            -17 => Self::DecryptionFailed,
+            -18 => Self::CertFileNotFound,
+            -19 => Self::CertFunctionNotFound,
+            -20 => Self::OutOfMemory,
+            -21 => Self::RateLimited,
+            -22 => Self::UnsupportedFunction,
+            -23 => Self::TrInfoNotFound,
+            -24 => Self::AccountIndexNotSet,
+            -25 => Self::NoAccount,
+            code => Self::Other(code),
+        }
+    }
+
+    /// 에러 코드를 반환합니다.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::SocketCreationFailed => -1,
+            Self::ConnectionFailed => -2,
+            Self::InvalidAddress => -3,
+            Self::ConnectionTimedOut => -4,
+            Self::AlreadyConnected => -5,
+            Self::TrUnavailable => -6,
+            Self::LoginRequired => -7,
+            Self::QuoteOnly => -8,
+            Self::AccountNotOwned => -9,
+            Self::InvalidPacketSize => -10,
+            Self::DataSizeMismatch => -11,
+            Self::AccountNotFound => -12,
+            Self::RequestIdExhausted => -13,
+            Self::SocketNotCreated => -14,
+            Self::EncryptionFailed => -15,
+            Self::SendFailed => -16,
+            Self::DecryptionFailed => -17,
+            Self::CertFileNotFound => -18,
+            Self::CertFunctionNotFound => -19,
+            Self::OutOfMemory => -20,
+            Self::RateLimited => -21,
+            Self::UnsupportedFunction => -22,
+            Self::TrInfoNotFound => -23,
+            Self::AccountIndexNotSet => -24,
+            Self::NoAccount => -25,
+            Self::Other(code) => code,
+        }
+    }
+
+    /// 같은 요청을 다시 보내면 성공할 수 있는지 여부를 반환합니다.
+    ///
+    /// 요청 제한이나 요청 ID 부족으로 실패한 요청은 서버로 전송되지 않으므로
+    /// 다시 요청하더라도 중복되지 않습니다.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::RequestIdExhausted)
+    }
+
+    /// 로그인이나 공인인증서 문제로 발생한 에러인지 여부를 반환합니다.
+    pub fn is_auth_error(self) -> bool {
+        matches!(
+            self,
+            Self::LoginRequired | Self::CertFileNotFound | Self::CertFunctionNotFound
+        )
+    }
+
+    /// 다시 요청하기 전에 기다릴 시간을 반환합니다.
+    ///
+    /// [`is_retryable()`](Self::is_retryable)이 거짓인 경우 `None`을
+    /// 반환합니다.
+    pub fn suggested_backoff(self) -> Option<Duration> {
+        match self {
+            Self::RateLimited => Some(Duration::from_millis(10)),
+            Self::RequestIdExhausted => Some(Duration::from_millis(100)),
+            _ => None,
+        }
+    }
+}
+
+/// 응답에 대한 트레이트
+///
+/// 서버에서 발생하는 응답의 공통 부분인 코드와 메시지를 트레이트로 묶어서
+/// 제공합니다.
+pub trait Response {
+    /// 4자리 이상의 응답 코드를 반환합니다. 응답 메시지가 없는 경우 빈 문자열을
+    /// 반환합니다.
+    ///
+    /// | 코드        | 내용        |
+    /// | ----------- | ----------- |
+    /// | 0000 - 0999 | 정상        |
+    /// | 1000 - 7999 | 업무 오류   |
+    /// | 8000 - 9999 | 시스템 오류 |
+    fn code(&self) -> &str;
+
+    /// 응답 메시지를 반환합니다. 응답 메시지가 없는 경우 빈 문자열을
+    /// 반환합니다.
+    fn message(&self) -> &str;
+
+    /// 응답 코드에 대한 영문 설명을 반환합니다.
+    ///
+    /// 알려진 코드는 내장된 표에서 찾고, 그 외의 코드는 코드 범위에 따른
+    /// 일반적인 설명을 반환합니다. 응답 코드가 숫자가 아닌 경우 `None`을
+    /// 반환합니다.
+    fn message_en(&self) -> Option<&'static str> {
+        super::messages::translate(self.code())
+    }
+
+    /// 정상 처리 여부를 반환합니다.
+    ///
+    /// 제공되는 구현은 응답 코드가 `0 <= x < 1000`이거나 응답 메시지와 코드가
+    /// 비어 있으면 참으로 간주합니다.
+    ///
+    /// t1764 TR과 같이 정상 처리시에 응답 메시지와 코드가 발생하지 않는 경우도
+    /// 고려하였습니다.
+    fn is_ok(&self) -> bool {
+        if let Ok(code) = self.code().parse::<i32>() {
+            (0..1000).contains(&code)
+        } else {
+            self.code().is_empty() && self.message().is_empty()
+        }
+    }
+
+    /// 처리 실패 여부를 반환합니다.
+    ///
+    /// 제공되는 구현은 `is_ok()`의 논리 부정 값을 반환합니다.
+    fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+}
+
+/// 로그인 요청에 대한 서버 응답
+#[derive(Clone, Debug)]
+pub struct LoginResponse {
+    pub(crate) code: String,
+    pub(crate) message: String,
+}
+
+impl Response for LoginResponse {
+    fn code(&self) -> &str {
+        &self.code
+    }
+    fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl LoginResponse {
+    /// 응답 코드와 메시지를 분류한 로그인 결과를 반환합니다.
+    ///
+    /// 서버는 실패 사유를 메시지로만 구분하는 경우가 많으므로 성공 코드가
+    /// 아닌 경우 메시지의 내용으로 분류합니다. 분류할 수 없는 경우
+    /// [`LoginCode::Other`]를 반환합니다.
+    pub fn login_code(&self) -> LoginCode {
+        LoginCode::parse(&self.code, &self.message)
+    }
+}
+
+impl std::fmt::Display for LoginResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// 로그인 결과
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoginCode {
+    /// 로그인 성공
+    Success,
+    /// 로그인에 성공했으나 비밀번호 만료가 임박함
+    PasswordExpiring,
+    /// 비밀번호가 만료됨
+    PasswordExpired,
+    /// 비밀번호가 틀림
+    WrongPassword,
+    /// 공인인증서 비밀번호가 틀림
+    WrongCertPassword,
+    /// 공인인증서가 만료됨
+    CertExpired,
+    /// 공인인증서를 찾을 수 없음
+    CertNotFound,
+    /// 비밀번호 연속 오류 등으로 사용이 제한됨
+    Locked,
+    /// 분류할 수 없는 결과
+    Other(String),
+}
+
+impl LoginCode {
+    /// 응답 코드와 메시지로 로그인 결과를 분류합니다.
+    pub fn parse(code: &str, message: &str) -> Self {
+        let expired = message.contains("만료");
+        let pw = message.contains("비밀번호") || message.contains("암호");
+        let cert = message.contains("인증서");
+
+        if code == "0000" {
+            return if pw && expired {
+                Self::PasswordExpiring
+            } else {
+                Self::Success
+            };
+        }
+
+        if message.contains("제한") || message.contains("정지") || message.contains("잠금") {
+            Self::Locked
+        } else if cert && expired {
+            Self::CertExpired
+        } else if cert && pw {
+            Self::WrongCertPassword
+        } else if cert {
+            Self::CertNotFound
+        } else if pw && expired {
+            Self::PasswordExpired
+        } else if pw {
+            Self::WrongPassword
+        } else {
+            Self::Other(code.to_owned())
+        }
+    }
+
+    /// 로그인에 성공했는지 여부를 반환합니다.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success | Self::PasswordExpiring)
+    }
+
+    /// 비밀번호 만료가 임박했는지 여부를 반환합니다.
+    pub fn is_password_expiring(&self) -> bool {
+        *self == Self::PasswordExpiring
+    }
+
+    /// 공인인증서 문제로 실패했는지 여부를 반환합니다.
+    pub fn is_cert_error(&self) -> bool {
+        matches!(
+            self,
+            Self::WrongCertPassword | Self::CertExpired | Self::CertNotFound
+        )
+    }
+}
+
+// 처음 접근할 때 디코딩하는 응답 데이터
+//
+// 응답을 대부분 버리는 경우 사용하지 않는 데이터를 디코딩하지 않도록 원본
+// 데이터와 레이아웃을 보관하고 디코딩 결과는 한 번만 계산합니다.
+#[derive(Clone, Debug)]
+pub(crate) struct LazyData {
+    raw: Option<(RawData, Arc<TrLayout>)>,
+    decoded: OnceLock<Result<Data, DecodeError>>,
+}
+
+impl LazyData {
+    pub(crate) fn new(raw_data: RawData, tr_layout: Arc<TrLayout>) -> Self {
+        Self {
+            raw: Some((raw_data, tr_layout)),
+            decoded: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn get(&self) -> &Result<Data, DecodeError> {
+        self.decoded.get_or_init(|| {
+            let (raw_data, tr_layout) = self.raw.as_ref().unwrap();
+            data::decode(tr_layout, raw_data.clone())
+        })
+    }
+
+    // 블록을 열 단위로 디코딩합니다. 이미 디코딩된 경우 결과를 변환합니다.
+    pub(crate) fn columns(&self, block_name: &str) -> Result<Columns, DecodeError> {
+        match (self.decoded.get(), &self.raw) {
+            (None, Some((raw_data, tr_layout))) => {
+                data::decode_columns(tr_layout, raw_data, block_name)
+            }
+            _ => data::to_columns(self.get().as_ref().map_err(Clone::clone)?, block_name),
+        }
+    }
+
+    pub(crate) fn raw(&self) -> Option<&RawData> {
+        self.raw.as_ref().map(|(raw_data, _)| raw_data)
+    }
+
+    // 원본 데이터를 보관하는 경우 필드 속성 바이트를 디코딩합니다.
+    pub(crate) fn attrs(&self) -> Option<Result<Attrs, DecodeError>> {
+        let (raw_data, tr_layout) = self.raw.as_ref()?;
+        Some(data::decode_attrs(tr_layout, raw_data))
+    }
+
+    // 이미 디코딩된 경우 결과를 빌리고, 그렇지 않은 경우 원본 데이터를
+    // 빌려서 디코딩합니다. 결과는 저장하지 않습니다.
+    pub(crate) fn get_ref(&self) -> Result<DataRef<'_>, DecodeError> {
+        match (self.decoded.get(), &self.raw) {
+            (Some(_), _) | (None, None) => decoded_ref(self.get()),
+            (None, Some((raw_data, tr_layout))) => data::decode_ref(tr_layout, raw_data),
+        }
+    }
+}
+
+fn decoded_ref(decoded: &Result<Data, DecodeError>) -> Result<DataRef<'_>, DecodeError> {
+    decoded.as_ref().map(DataRef::from).map_err(Clone::clone)
+}
+
+impl From<Result<Data, DecodeError>> for LazyData {
+    fn from(decoded: Result<Data, DecodeError>) -> Self {
+        Self {
+            raw: None,
+            decoded: OnceLock::from(decoded),
+        }
+    }
+}
+
+/// 조회 TR에 대한 서버 응답
+#[derive(Clone, Debug)]
+pub struct QueryResponse {
+    pub(crate) code: String,
+    pub(crate) message: String,
+    pub(crate) elapsed: Duration,
+    pub(crate) next_key: Option<String>,
+    pub(crate) data: Option<LazyData>,
+}
+
+impl QueryResponse {
+    /// 서버 요청 후 응답까지 소요된 시간을 밀리초 정확도로 반환합니다.
+    ///
+    /// XingAPI의 수신 이벤트에서 반환한 값을 사용합니다.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// 연속 조회 키가 존재하는 경우 연속 조회 키를 반환합니다.
+    ///
+    /// 연속 조회 키는 TR당 하나입니다.
+    pub fn next_key(&self) -> Option<&str> {
+        self.next_key.as_deref()
+    }
+
+    /// 수신한 데이터에 대한 디코딩 결과를 반환합니다.
+    ///
+    /// 데이터는 처음 호출할 때 디코딩되며 결과는 재사용됩니다.
+    ///
+    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
+    pub fn data(&self) -> Result<&Data, DecodeError> {
+        self.data
+            .as_ref()
+            .expect("this response has no data")
+            .get()
+            .as_ref()
+            .map_err(|err| err.clone())
+    }
+
+    /// 수신한 데이터의 블록을 열 단위로 디코딩하여 반환합니다.
+    ///
+    /// 배열 블록을 행마다 필드 테이블로 만들지 않으므로 t8430 TR과 같이 행이
+    /// 많은 응답을 분석할 때 할당이 적습니다. 단일 블록은 길이가 1인 열로
+    /// 반환합니다. 이미 디코딩된 경우 그 결과를 변환합니다.
+    ///
+    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
+    pub fn columns(&self, block_name: &str) -> Result<Columns, DecodeError> {
+        self.data
+            .as_ref()
+            .expect("this response has no data")
+            .columns(block_name)
+    }
+
+    /// 수신한 데이터를 복사하지 않고 디코딩한 결과를 반환합니다.
+    ///
+    /// [`data()`](Self::data)와 달리 결과를 저장하지 않으므로 데이터를 한
+    /// 번만 읽는 경우에 사용합니다. 이미 디코딩된 경우 그 결과를 빌립니다.
+    ///
+    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
+    pub fn data_ref(&self) -> Result<DataRef<'_>, DecodeError> {
+        self.data
+            .as_ref()
+            .expect("this response has no data")
+            .get_ref()
+    }
+
+    /// 수신한 데이터를 디코딩하지 않은 원본 그대로 반환합니다.
+    ///
+    /// 서버의 응답을 보관하거나 [`RawData::decode()`]로 다른 레이아웃을
+    /// 적용할 때 사용합니다. 헬퍼 프로세스를 통해 수신한 응답과 같이 원본
+    /// 데이터가 없는 경우 `None`을 반환합니다.
+    ///
+    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
+    pub fn raw_blocks(&self) -> Option<&RawData> {
+        self.data.as_ref().expect("this response has no data").raw()
+    }
+
+    /// 수신한 데이터의 필드 속성 바이트를 디코딩하여 반환합니다.
+    ///
+    /// 레이아웃에 속성 바이트가 없는 경우 빈 테이블을 반환합니다. 헬퍼
+    /// 프로세스를 통해 수신한 응답과 같이 원본 데이터가 없는 경우 `None`을
+    /// 반환합니다.
+    ///
+    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
+    pub fn attrs(&self) -> Option<Result<Attrs, DecodeError>> {
+        self.data
+            .as_ref()
+            .expect("this response has no data")
+            .attrs()
+    }
+
+    // 응답을 JSON 객체의 멤버로 씁니다.
+    //
+    // 정상 처리되지 않은 응답의 `data`는 `null`입니다.
+    #[cfg(all(windows, any(feature = "capi", feature = "gateway")))]
+    pub(crate) fn write_json_members(&self, out: &mut String) -> Result<(), DecodeError> {
+        use crate::data::json;
+
+        out.push_str("\"code\":");
+        json::write_str(out, &self.code);
+        out.push_str(",\"message\":");
+        json::write_str(out, &self.message);
+        out.push_str(&format!(",\"elapsed_ms\":{}", self.elapsed.as_millis()));
+        out.push_str(",\"next_key\":");
+        match &self.next_key {
+            Some(next_key) => json::write_str(out, next_key),
+            None => out.push_str("null"),
+        }
+        out.push_str(",\"data\":");
+        if self.is_ok() {
+            json::write_data(out, self.data()?);
+        } else {
+            out.push_str("null");
+        }
+
+        Ok(())
+    }
+}
+
+impl Response for QueryResponse {
+    fn code(&self) -> &str {
+        &self.code
+    }
+    fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// 실시간 TR에 대한 서버의 응답
+#[derive(Clone, Debug)]
+pub struct RealResponse {
+    pub(crate) tr_code: String,
+    pub(crate) key: String,
+    pub(crate) data: Result<Data, DecodeError>,
+    pub(crate) received_at: SystemTime,
+}
+
+impl RealResponse {
+    /// 실시간 TR의 코드를 반환합니다.
+    pub fn tr_code(&self) -> &str {
+        &self.tr_code
+    }
+
+    /// 실시간 TR을 등록하는데 사용한 키를 반환합니다.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 수신한 데이터에 대한 디코딩 결과를 반환합니다.
+    pub fn data(&self) -> Result<&Data, DecodeError> {
+        self.data.as_ref().map_err(|err| err.clone())
+    }
+
+    /// 응답을 수신한 시각을 반환합니다.
+    pub fn received_at(&self) -> SystemTime {
+        self.received_at
+    }
+
+    /// 응답을 한 줄의 JSON 객체로 변환합니다.
+    ///
+    /// 수신 시각은 유닉스 시간 기준 밀리초(`received_at_ms`)로 출력되며,
+    /// 디코딩에 실패한 경우 `data`는 `null`이고 `error`에 에러 메시지가
+    /// 출력됩니다.
+    pub fn to_json(&self) -> String {
+        use crate::data::json;
+
+        let received_at_ms = self
+            .received_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+
+        let mut out = String::from("{\"tr_code\":");
+        json::write_str(&mut out, &self.tr_code);
+        out.push_str(",\"key\":");
+        json::write_str(&mut out, &self.key);
+        out.push_str(&format!(",\"received_at_ms\":{}", received_at_ms));
+        match &self.data {
+            Ok(data) => {
+                out.push_str(",\"data\":");
+                json::write_data(&mut out, data);
+                out.push_str(",\"error\":null}");
+            }
+            Err(err) => {
+                out.push_str(",\"data\":null,\"error\":");
+                json::write_str(&mut out, &err.to_string());
+                out.push('}');
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ErrorKind, LazyData, LoginCode};
+    use crate::data::RawData;
+    use crate::layout::TrLayout;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn test_error_kind() {
+        for code in -30..0 {
+            assert_eq!(ErrorKind::from_code(code).code(), code);
+        }
+
+        assert_eq!(ErrorKind::from_code(-21), ErrorKind::RateLimited);
+        assert_eq!(ErrorKind::from_code(-100), ErrorKind::Other(-100));
+
+        assert!(ErrorKind::RateLimited.is_retryable());
+        assert!(ErrorKind::RateLimited.suggested_backoff().is_some());
+        assert!(!ErrorKind::LoginRequired.is_retryable());
+        assert!(ErrorKind::LoginRequired.suggested_backoff().is_none());
+        assert!(ErrorKind::CertFileNotFound.is_auth_error());
+
+        let err = Error::XingApi {
+            code: -21,
+            message: String::new(),
+        };
+        assert_eq!(err.kind(), Some(ErrorKind::RateLimited));
+        assert_eq!(Error::TimedOut.kind(), None);
+
+        assert!(err.is_unsent());
+        assert!(Error::Busy.is_unsent());
+        assert!(!Error::TimedOut.is_unsent());
+        assert!(!Error::Internal("request id out of range").is_unsent());
+    }
+
+    #[test]
+    fn test_login_code() {
+        assert_eq!(LoginCode::parse("0000", "로그인 성공"), LoginCode::Success);
+        assert!(LoginCode::parse("0000", "비밀번호 만료일이 7일 남았습니다").is_password_expiring());
+        assert!(LoginCode::parse("0000", "").is_success());
+
+        assert_eq!(
+            LoginCode::parse("2005", "비밀번호가 틀렸습니다"),
+            LoginCode::WrongPassword
+        );
+        assert_eq!(
+            LoginCode::parse("5201", "인증서 비밀번호가 올바르지 않습니다"),
+            LoginCode::WrongCertPassword
+        );
+        assert!(LoginCode::parse("5202", "인증서가 만료되었습니다").is_cert_error());
+        assert_eq!(
+            LoginCode::parse("9999", "알 수 없음"),
+            LoginCode::Other("9999".into())
+        );
+    }
+
+    #[test]
+    fn test_lazy_data() {
+        let tr_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,테스트,t0000;
+                BEGIN_DATA_MAP
+                t0000OutBlock,출력,output;
+                begin
+                    가격,price,price,long,4;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        let lazy = LazyData::new(RawData::NonBlock(b"1234".to_vec()), Arc::new(tr_layout));
+        let data = lazy.get().as_ref().unwrap();
+        assert_eq!(data.blocks["t0000OutBlock"]["price"], *"1234");
+        assert!(std::ptr::eq(data, lazy.get().as_ref().unwrap()));
+
+        let lazy = LazyData::new(RawData::NonBlock(b"5678".to_vec()), lazy.raw.unwrap().1);
+        let data = lazy.get_ref().unwrap();
+        assert_eq!(
+            data.blocks["t0000OutBlock"].as_block().unwrap()["price"],
+            "5678"
+        );
+        assert!(lazy.decoded.get().is_none());
+
+        let lazy = LazyData::new(RawData::NonBlock(b"123".to_vec()), lazy.raw.unwrap().1);
+        assert!(lazy.get().is_err());
+        assert_eq!(lazy.raw(), Some(&RawData::NonBlock(b"123".to_vec())));
+
+        let tr_layout = lazy.raw.as_ref().unwrap().1.clone();
+        let raw_data = RawData::NonBlock(b"0042".to_vec());
+        let data = raw_data.decode(&tr_layout).unwrap();
+        assert_eq!(data.blocks["t0000OutBlock"]["price"], *"0042");
+        assert!(RawData::Block(Default::default())
+            .decode(&tr_layout)
+            .is_err());
+
+        let lazy = LazyData::from(raw_data.decode(&tr_layout));
+        assert!(lazy.raw().is_none());
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::common::RealResponse;
+
+use std::io::{self, Write};
+
+/// 실시간 응답을 JSON Lines 형식으로 쓰는 객체
+///
+/// 각 응답을 [`RealResponse::to_json()`]으로 변환하여 한 줄씩 씁니다. ELK나
+/// ClickHouse 등에 그대로 수집할 수 있습니다.
+///
+/// 쓰기 성능을 위해 `BufWriter`로 감싼 파일을 사용하는 것이 좋습니다.
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    count: u64,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    /// 지정된 출력에 쓰는 객체를 생성합니다.
+    pub fn new(writer: W) -> Self {
+        Self { writer, count: 0 }
+    }
+
+    /// 응답을 한 줄로 씁니다.
+    pub fn write(&mut self, res: &RealResponse) -> io::Result<()> {
+        let mut line = res.to_json();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// 지금까지 쓴 응답의 개수를 반환합니다.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 버퍼에 남아있는 내용을 씁니다.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// 내부 출력 객체를 반환합니다.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonLinesWriter;
+    use crate::data::{Block, Data, DataType, DecodeError};
+    use crate::hashmap;
+    use crate::os::common::RealResponse;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_write() {
+        let received_at = UNIX_EPOCH + Duration::from_millis(1610325000123);
+
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer
+            .write(&RealResponse {
+                tr_code: "S3_".into(),
+                key: "005930".into(),
+                data: Ok(Data {
+                    tr_code: "S3_".into(),
+                    data_type: DataType::Output,
+                    blocks: hashmap! {
+                        "OutBlock" => Block::Block(hashmap! { "price" => "81000" }),
+                    },
+                }),
+                received_at,
+            })
+            .unwrap();
+        writer
+            .write(&RealResponse {
+                tr_code: "S3_".into(),
+                key: "\"".into(),
+                data: Err(DecodeError::MismatchDataLength),
+                received_at,
+            })
+            .unwrap();
+        assert_eq!(writer.count(), 2);
+
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                concat!(
+                    r#"{"tr_code":"S3_","key":"005930","received_at_ms":1610325000123,"#,
+                    r#""data":{"tr_code":"S3_","data_type":"output","#,
+                    r#""blocks":{"OutBlock":{"price":"81000"}}},"error":null}"#
+                ),
+                concat!(
+                    r#"{"tr_code":"S3_","key":"\"","received_at_ms":1610325000123,"#,
+                    r#""data":null,"error":"mismatch data length"}"#
+                ),
+            ]
+        );
+    }
+}
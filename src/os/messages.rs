@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// 서버 응답 코드에 대한 영문 설명
+
+// 알려진 응답 코드와 영문 설명
+//
+// 코드 순으로 정렬되어 있어야 합니다.
+const TABLE: &[(&str, &str)] = &[
+    ("0000", "Login succeeded"),
+    ("00000", "Request completed successfully"),
+    ("00039", "Sell order accepted"),
+    ("00040", "Buy order accepted"),
+];
+
+// 응답 코드에 대한 영문 설명을 반환합니다.
+//
+// 표에 없는 코드는 범위에 따른 일반적인 설명을 반환합니다.
+pub(super) fn translate(code: &str) -> Option<&'static str> {
+    if let Ok(i) = TABLE.binary_search_by(|(key, _)| (*key).cmp(code)) {
+        return Some(TABLE[i].1);
+    }
+
+    match code.parse::<i32>() {
+        Ok(0..=999) => Some("Request completed successfully"),
+        Ok(1000..=7999) => Some("Request rejected by business rule"),
+        Ok(8000..=9999) => Some("Server system error"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{translate, TABLE};
+
+    #[test]
+    fn test_translate() {
+        assert!(TABLE.windows(2).all(|w| w[0].0 < w[1].0));
+
+        assert_eq!(translate("0000"), Some("Login succeeded"));
+        assert_eq!(translate("00040"), Some("Buy order accepted"));
+        assert_eq!(translate("0001"), Some("Request completed successfully"));
+        assert_eq!(
+            translate("01234"),
+            Some("Request rejected by business rule")
+        );
+        assert_eq!(translate("8001"), Some("Server system error"));
+        assert_eq!(translate(""), None);
+        assert_eq!(translate("-1"), None);
+    }
+}
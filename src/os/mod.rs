@@ -1,4 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
+pub mod backend;
+mod common;
+mod jsonl;
+mod messages;
+mod testdata;
+
 #[cfg(windows)]
 pub mod windows;
+
+#[cfg(all(not(windows), feature = "sim"))]
+pub mod sim;
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::RealResponse;
+use crate::data::{self, Data, DataType, DecodeError, EncodeError};
+use crate::layout::{LayoutRegistry, TrLayout};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+lazy_static! {
+    static ref EVENTS: Mutex<Vec<Arc<Shared>>> = Mutex::new(Vec::new());
+}
+
+struct Shared {
+    tx_res: Sender<RealResponse>,
+    registry: LayoutRegistry,
+    subscriptions: Mutex<HashMap<String, BTreeSet<String>>>,
+}
+
+impl Shared {
+    fn send(&self, key: &str, data: &Data) -> Result<(), EncodeError> {
+        let subscribed = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .get(&data.tr_code)
+            .is_some_and(|keys| keys.contains(key));
+        if !subscribed {
+            return Ok(());
+        }
+
+        // 실제 수신한 데이터와 같이 인코딩한 후 디코딩합니다.
+        let decoded = match self.registry.get(&data.tr_code) {
+            Some(tr_layout) => {
+                let raw_data = data::encode(data, &tr_layout)?;
+                data::decode_non_block(&tr_layout, DataType::Output, &raw_data)
+            }
+            None => Err(DecodeError::UnknownLayout(data.tr_code.clone())),
+        };
+
+        let _ = self.tx_res.send(RealResponse {
+            tr_code: data.tr_code.clone(),
+            key: key.to_owned(),
+            data: decoded,
+            received_at: SystemTime::now(),
+        });
+
+        Ok(())
+    }
+}
+
+// 등록된 모든 객체로 실시간 응답을 보냅니다.
+//
+// 인코딩에 실패한 객체는 응답을 수신하지 않으며 마지막 에러를 반환합니다.
+pub(super) fn dispatch(key: &str, data: &Data) -> Result<(), EncodeError> {
+    let mut result = Ok(());
+    for shared in EVENTS.lock().unwrap().iter() {
+        if let Err(err) = shared.send(key, data) {
+            result = Err(err);
+        }
+    }
+
+    result
+}
+
+/// 실시간 TR을 등록하고 응답을 수신하는 객체
+///
+/// [`fixture::push_real()`](super::fixture::push_real)로 보낸 응답 중에서
+/// 등록한 TR 코드와 키에 해당하는 응답만 수신합니다.
+pub struct RealEvent {
+    shared: Arc<Shared>,
+    rx_res: Receiver<RealResponse>,
+}
+
+impl RealEvent {
+    /// 객체를 생성합니다.
+    pub fn new() -> Result<Self, std::io::Error> {
+        Self::with_registry(LayoutRegistry::new())
+    }
+
+    /// 지정된 레이아웃 테이블을 사용하는 객체를 생성합니다.
+    ///
+    /// 테이블을 다른 객체와 공유하는 경우 한 곳에서 추가한 레이아웃으로 모든
+    /// 객체의 응답을 디코딩합니다.
+    pub fn with_registry(registry: LayoutRegistry) -> Result<Self, std::io::Error> {
+        let (tx_res, rx_res) = crossbeam_channel::unbounded();
+        let shared = Arc::new(Shared {
+            tx_res,
+            registry,
+            subscriptions: Mutex::new(HashMap::new()),
+        });
+
+        EVENTS.lock().unwrap().push(shared.clone());
+
+        Ok(Self { shared, rx_res })
+    }
+
+    /// 응답을 디코딩하기 위한 레이아웃 테이블을 반환합니다.
+    pub fn registry(&self) -> &LayoutRegistry {
+        &self.shared.registry
+    }
+
+    /// 응답을 디코딩하기 위한 레이아웃을 추가합니다.
+    pub fn insert_layout(&self, tr_layout: TrLayout) {
+        self.shared.registry.insert(tr_layout);
+    }
+
+    /// 응답을 디코딩하기 위한 레이아웃을 삭제합니다.
+    pub fn remove_layout(&self, tr_code: &str) {
+        self.shared.registry.remove(tr_code);
+    }
+
+    /// 실시간 TR을 등록합니다.
+    pub fn subscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) {
+        self.shared
+            .subscriptions
+            .lock()
+            .unwrap()
+            .entry(tr_code.to_owned())
+            .or_default()
+            .extend(keys.iter().map(|key| key.as_ref().to_owned()));
+    }
+
+    /// 실시간 TR을 등록 해제합니다.
+    pub fn unsubscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) {
+        let mut subscriptions = self.shared.subscriptions.lock().unwrap();
+        if let Some(set) = subscriptions.get_mut(tr_code) {
+            for key in keys {
+                set.remove(key.as_ref());
+            }
+            if set.is_empty() {
+                subscriptions.remove(tr_code);
+            }
+        }
+    }
+
+    /// 모든 실시간 TR을 등록 해제합니다.
+    pub fn unsubscribe_all(&self) {
+        self.shared.subscriptions.lock().unwrap().clear();
+    }
+
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        self.rx_res.try_recv().ok()
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.rx_res.recv_timeout(timeout).ok()
+    }
+}
+
+impl Drop for RealEvent {
+    fn drop(&mut self) {
+        EVENTS
+            .lock()
+            .unwrap()
+            .retain(|shared| !Arc::ptr_eq(shared, &self.shared));
+    }
+}
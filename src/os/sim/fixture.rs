@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 시뮬레이션 데이터 지정 모듈
+
+use super::{event, Account, MockBackend, BACKEND};
+use crate::data::{Data, EncodeError};
+
+use std::collections::HashMap;
+
+/// 지정한 데이터와 요청 기록을 모두 삭제하고 연결을 종료합니다.
+pub fn reset() {
+    *BACKEND.write().unwrap() = MockBackend::new(HashMap::new());
+}
+
+/// 로그인 요청에 대한 응답 코드와 메시지를 지정합니다.
+///
+/// 기본값은 정상 처리(`0000`)입니다.
+pub fn set_login_response(code: &str, message: &str) {
+    BACKEND.read().unwrap().set_login_response(code, message);
+}
+
+/// 계좌 목록을 지정합니다.
+pub fn set_accounts(accounts: Vec<Account>) {
+    BACKEND.read().unwrap().set_accounts(accounts);
+}
+
+/// 조회 TR 요청에 대해 반환할 정상 응답을 추가합니다.
+pub fn push_response(data: Data, next_key: Option<&str>) {
+    BACKEND.read().unwrap().push_data(data, next_key);
+}
+
+/// 조회 TR 요청에 대해 반환할 데이터가 없는 응답을 추가합니다.
+pub fn push_message(tr_code: &str, code: &str, message: &str) {
+    BACKEND.read().unwrap().push_message(tr_code, code, message);
+}
+
+/// 실시간 응답을 보냅니다.
+///
+/// 해당 TR 코드와 키를 등록한 모든 [`RealEvent`](super::RealEvent)가
+/// 수신합니다. 객체의 레이아웃으로 데이터를 인코딩할 수 없는 경우 에러를
+/// 반환합니다.
+pub fn push_real(key: &str, data: &Data) -> Result<(), EncodeError> {
+    event::dispatch(key, data)
+}
+
+/// 지금까지 요청한 데이터 목록을 반환합니다.
+pub fn requests() -> Vec<Data> {
+    BACKEND.read().unwrap().requests()
+}
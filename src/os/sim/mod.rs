@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 시뮬레이션 백엔드
+//!
+//! 윈도우가 아닌 환경에서 `sim` 기능을 활성화하면 XingAPI DLL 대신
+//! [`fixture`] 모듈로 지정한 데이터를 반환하는 같은 이름의 함수와
+//! [`RealEvent`]를 제공합니다. 애플리케이션 로직을 윈도우에 배포하기 전에
+//! 시험하는 데 사용합니다.
+//!
+//! 함수는 전역 [`MockBackend`](backend::MockBackend)로 응답합니다. 응답
+//! 데이터는 요청에 사용한 레이아웃으로 인코딩한 후 다시 디코딩되므로
+//! 레이아웃과 맞지 않는 데이터는 실제와 같이 에러로 반환됩니다.
+
+mod event;
+pub mod fixture;
+
+pub use self::event::RealEvent;
+pub use super::backend;
+pub use super::common::{
+    Account, Error, ErrorKind, LoginCode, LoginResponse, QueryResponse, RealResponse, Response,
+};
+pub use super::jsonl::JsonLinesWriter;
+
+use super::backend::{Backend, MockBackend};
+use crate::data::{Data, DecodeError};
+use crate::layout::{LayoutRegistry, TrLayout};
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+lazy_static! {
+    // 레이아웃은 요청할 때 전달되므로 응답은 요청에 사용한 레이아웃으로
+    // 인코딩합니다.
+    static ref BACKEND: RwLock<MockBackend> = RwLock::new(MockBackend::new(HashMap::new()));
+}
+
+/// 서버에 연결합니다.
+///
+/// 항상 성공합니다.
+pub fn connect(addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
+    BACKEND.read().unwrap().connect(addr, port, timeout)
+}
+
+/// 서버 연결 여부를 반환합니다.
+pub fn is_connected() -> bool {
+    BACKEND.read().unwrap().is_connected()
+}
+
+/// 서버와의 연결을 종료합니다.
+pub fn disconnect() {
+    BACKEND.read().unwrap().disconnect()
+}
+
+/// 서버에 로그인 요청을 합니다.
+///
+/// [`fixture::set_login_response()`]로 지정한 응답을 반환합니다.
+pub fn login(
+    id: &str,
+    pw: &str,
+    cert_pw: &str,
+    cert_err_dialog: bool,
+) -> Result<LoginResponse, Error> {
+    BACKEND
+        .read()
+        .unwrap()
+        .login(id, pw, cert_pw, cert_err_dialog)
+}
+
+/// 서버에 조회 TR 요청을 합니다.
+///
+/// TR 코드별로 [`fixture`] 모듈로 추가한 응답을 추가한 순서대로 반환하며,
+/// 남은 응답이 없는 경우 기다리지 않고 `Error::TimedOut`을 반환합니다.
+pub fn request<L: Into<Arc<TrLayout>>>(
+    data: &Data,
+    tr_layout: L,
+    next_key: Option<&str>,
+    timeout: Duration,
+) -> Result<QueryResponse, Error> {
+    BACKEND
+        .read()
+        .unwrap()
+        .request(data, &tr_layout.into(), next_key, timeout)
+}
+
+/// 레이아웃 테이블에서 TR 코드에 해당하는 레이아웃을 찾아 조회 TR 요청을
+/// 합니다.
+///
+/// 레이아웃이 없는 경우 `DecodeError::UnknownLayout`을 반환합니다.
+pub fn request_registered(
+    data: &Data,
+    registry: &LayoutRegistry,
+    next_key: Option<&str>,
+    timeout: Duration,
+) -> Result<QueryResponse, Error> {
+    let tr_layout = registry
+        .get(&data.tr_code)
+        .ok_or_else(|| DecodeError::UnknownLayout(data.tr_code.clone()))?;
+    request(data, tr_layout, next_key, timeout)
+}
+
+/// 계좌 목록을 반환합니다.
+pub fn accounts() -> Vec<Account> {
+    BACKEND.read().unwrap().accounts()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fixture, Error, RealEvent, Response};
+    use crate::data::{self, Block, Data, DataType};
+    use crate::hashmap;
+    use crate::os::testdata::{s3_layout, t1102_layout};
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sim() {
+        let tr_layout = Arc::new(t1102_layout());
+
+        let real_layout = s3_layout();
+
+        fixture::reset();
+        let timeout = Duration::from_secs(1);
+
+        super::connect("127.0.0.1", 20001, timeout).unwrap();
+        assert!(super::is_connected());
+        assert!(super::login("id", "pw", "", false).unwrap().is_ok());
+
+        let out_data = Data {
+            tr_code: "t1102".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+            },
+        };
+        fixture::push_response(out_data.clone(), None);
+        fixture::push_message("t1102", "02714", "조회할 자료가 없습니다.");
+
+        let mut in_data = data::empty_input(&tr_layout);
+        data::set_field(&mut in_data, "t1102InBlock", "shcode", "005930").unwrap();
+
+        let res = super::request(&in_data, tr_layout.clone(), None, timeout).unwrap();
+        assert_eq!(res.data().unwrap(), &out_data);
+        assert!(super::request(&in_data, tr_layout.clone(), None, timeout)
+            .unwrap()
+            .is_err());
+        assert!(matches!(
+            super::request(&in_data, tr_layout.clone(), None, timeout),
+            Err(Error::TimedOut)
+        ));
+        assert_eq!(fixture::requests().len(), 3);
+
+        let real = RealEvent::new().unwrap();
+        real.insert_layout(real_layout);
+        real.subscribe("S3_", &["005930"]);
+
+        let real_data = Data {
+            tr_code: "S3_".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "OutBlock" => Block::Block(hashmap! { "price" => "91100" }),
+            },
+        };
+        fixture::push_real("000660", &real_data).unwrap();
+        fixture::push_real("005930", &real_data).unwrap();
+
+        let res = real.recv_timeout(timeout).unwrap();
+        assert_eq!(res.key(), "005930");
+        assert_eq!(res.data().unwrap(), &real_data);
+        assert!(real.try_recv().is_none());
+
+        real.unsubscribe_all();
+        fixture::push_real("005930", &real_data).unwrap();
+        assert!(real.try_recv().is_none());
+    }
+}
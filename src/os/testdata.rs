@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 시험에 공통으로 사용하는 레이아웃
+
+#![cfg(test)]
+
+use crate::layout::TrLayout;
+
+const T1102_RES: &str = "
+BEGIN_FUNCTION_MAP
+    .Func,주식현재가(시세)조회(t1102),t1102,block,headtype=A;
+    BEGIN_DATA_MAP
+    t1102InBlock,기본입력,input;
+    begin
+        단축코드,shcode,shcode,char,6;
+    end
+    t1102OutBlock,출력,output;
+    begin
+        현재가,price,price,long,8;
+    end
+    END_DATA_MAP
+END_FUNCTION_MAP
+";
+
+const S3_RES: &str = "
+BEGIN_FUNCTION_MAP
+    .Feed,KOSPI체결(S3_),S3_,attr,key=6,group=1;
+    BEGIN_DATA_MAP
+    InBlock,입력,input;
+    begin
+        단축코드,shcode,shcode,char,6;
+    end
+    OutBlock,출력,output;
+    begin
+        현재가,price,price,long,8;
+    end
+    END_DATA_MAP
+END_FUNCTION_MAP
+";
+
+// 주식현재가 조회(t1102) TR의 레이아웃을 반환합니다.
+pub(crate) fn t1102_layout() -> TrLayout {
+    T1102_RES.parse().unwrap()
+}
+
+// KOSPI 체결(S3_) 실시간 TR의 레이아웃을 반환합니다.
+pub(crate) fn s3_layout() -> TrLayout {
+    S3_RES.parse().unwrap()
+}
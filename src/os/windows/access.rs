@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// HTTP 브릿지와 웹소켓 게이트웨이에서 공통으로 사용하는 접근 제한 모듈입니다.
+
+use crate::layout::{HeaderType, TrLayout};
+
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// `Authorization` 헤더 값을 검사하는 함수
+///
+/// 헤더가 없는 경우 `None`을 받으며, 요청을 허용하는 경우 참을 반환합니다.
+pub type AuthHook = fn(Option<&str>) -> bool;
+
+// 요청할 수 있는 TR과 연결에 대한 제한
+pub(crate) struct Access {
+    pub(crate) io_timeout: Duration,
+    pub(crate) max_connections: usize,
+    pub(crate) allowed_trs: Option<HashSet<String>>,
+    pub(crate) auth: Option<AuthHook>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl Default for Access {
+    fn default() -> Self {
+        Self {
+            io_timeout: Duration::from_secs(10),
+            max_connections: 64,
+            allowed_trs: None,
+            auth: None,
+            connections: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl Access {
+    // 허용된 TR을 지정하지 않은 경우 헤더 타입이 B인 계좌 및 주문 TR을
+    // 제외한 모든 TR을 허용합니다.
+    pub(crate) fn is_allowed(&self, tr_layout: &TrLayout) -> bool {
+        match &self.allowed_trs {
+            Some(allowed_trs) => allowed_trs.contains(&tr_layout.code),
+            None => tr_layout.header_type != Some(HeaderType::B),
+        }
+    }
+
+    pub(crate) fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        self.auth.is_none_or(|auth| auth(authorization))
+    }
+
+    // 최대 연결 개수를 초과하지 않은 경우 연결 하나를 차지합니다.
+    pub(crate) fn try_connect(&self) -> Option<ConnectionSlot> {
+        self.connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |connections| {
+                (connections < self.max_connections).then_some(connections + 1)
+            })
+            .ok()?;
+
+        Some(ConnectionSlot {
+            connections: self.connections.clone(),
+        })
+    }
+}
+
+// 처리 중인 연결 하나
+//
+// 연결을 처리하는 스레드가 끝나면 연결 개수를 줄입니다.
+pub(crate) struct ConnectionSlot {
+    connections: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.connections.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+// 지정된 시각이 지나면 읽기가 시간 초과로 실패하는 스트림
+pub(crate) struct DeadlineStream {
+    stream: TcpStream,
+    deadline: Option<Instant>,
+}
+
+impl DeadlineStream {
+    pub(crate) fn new(stream: TcpStream, timeout: Duration) -> Self {
+        Self {
+            stream,
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
+    // 시각 제한을 해제하고 읽기마다 지정된 시간을 기다리도록 합니다.
+    #[cfg(feature = "gateway")]
+    pub(crate) fn clear_deadline(&mut self, read_timeout: Option<Duration>) -> io::Result<()> {
+        self.deadline = None;
+        self.stream.set_read_timeout(read_timeout)
+    }
+}
+
+impl Read for DeadlineStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(deadline) = self.deadline {
+            let remaining = deadline
+                .checked_duration_since(Instant::now())
+                .filter(|remaining| !remaining.is_zero())
+                .ok_or(io::ErrorKind::TimedOut)?;
+
+            self.stream.set_read_timeout(Some(remaining))?;
+        }
+
+        self.stream.read(buf)
+    }
+}
+
+impl Write for DeadlineStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 계좌 조회 모듈
+//!
+//! 주식 잔고 TR인 t0424를 타입이 지정된 구조체로 조회할 수 있도록 감싼 함수를
+//! 제공합니다.
+
+use super::{ensure_ok, request_with_retry, set_fields, Error};
+use crate::data::{self, Data, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 주식 잔고 (t0424)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Balance {
+    /// 추정순자산
+    pub estimated_net_assets: i64,
+    /// 실현손익
+    pub realized_pnl: i64,
+    /// 매입금액
+    pub purchase_amount: i64,
+    /// 추정D2예수금
+    pub estimated_d2_deposit: i64,
+    /// 평가금액
+    pub evaluation_amount: i64,
+    /// 평가손익
+    pub unrealized_pnl: i64,
+    /// 보유 종목
+    pub positions: Vec<Position>,
+}
+
+impl Balance {
+    /// t0424 TR의 응답 데이터를 변환합니다.
+    ///
+    /// 연속 조회 결과는 포함되지 않으며, [`balance`] 함수는 연속 조회한 보유
+    /// 종목을 모두 합쳐서 반환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        let fields = data::get_block(data, "t0424OutBlock")?;
+        let parse = |field_name| data::parse_field(fields, "t0424OutBlock", field_name);
+
+        Ok(Self {
+            estimated_net_assets: parse("sunamt")?,
+            realized_pnl: parse("dtsunik")?,
+            purchase_amount: parse("mamt")?,
+            estimated_d2_deposit: parse("sunamt1")?,
+            evaluation_amount: parse("tappamt")?,
+            unrealized_pnl: parse("tdtsunik")?,
+            positions: Position::from_data(data)?,
+        })
+    }
+}
+
+/// 보유 종목
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    /// 종목번호
+    pub symbol: String,
+    /// 종목명
+    pub name: String,
+    /// 잔고 수량
+    pub qty: u64,
+    /// 매도 가능 수량
+    pub sellable_qty: u64,
+    /// 평균 단가
+    pub avg_price: f64,
+    /// 현재가
+    pub price: f64,
+    /// 매입금액
+    pub purchase_amount: i64,
+    /// 평가금액
+    pub evaluation_amount: i64,
+    /// 평가손익
+    pub unrealized_pnl: i64,
+    /// 수수료
+    pub fee: i64,
+    /// 제세금
+    pub tax: i64,
+}
+
+impl Position {
+    /// 매입금액 대비 평가손익의 비율을 백분율로 반환합니다.
+    pub fn profit_rate(&self) -> f64 {
+        if self.purchase_amount == 0 {
+            0.0
+        } else {
+            self.unrealized_pnl as f64 / self.purchase_amount as f64 * 100.0
+        }
+    }
+
+    fn from_data(data: &Data) -> Result<Vec<Self>, DecodeError> {
+        data::get_array(data, "t0424OutBlock1")?
+            .iter()
+            .map(|fields| Self::from_fields(fields, "t0424OutBlock1"))
+            .collect()
+    }
+
+    fn from_fields(
+        fields: &HashMap<Arc<str>, String>,
+        block_name: &str,
+    ) -> Result<Self, DecodeError> {
+        let parse_i64 = |field_name| data::parse_field::<i64>(fields, block_name, field_name);
+
+        Ok(Self {
+            symbol: data::parse_field(fields, block_name, "expcode")?,
+            name: data::parse_field(fields, block_name, "hname")?,
+            qty: data::parse_field(fields, block_name, "janqty")?,
+            sellable_qty: data::parse_field(fields, block_name, "mdposqt")?,
+            avg_price: data::parse_field(fields, block_name, "pamt")?,
+            price: data::parse_field(fields, block_name, "price")?,
+            purchase_amount: parse_i64("mamt")?,
+            evaluation_amount: parse_i64("appamt")?,
+            unrealized_pnl: parse_i64("dtsunik")?,
+            fee: parse_i64("fee")?,
+            tax: parse_i64("tax")?,
+        })
+    }
+}
+
+/// 계좌의 주식 잔고를 조회합니다.
+///
+/// t0424 TR을 연속 조회하여 모든 보유 종목을 반환합니다. 잔고 합계는 첫 번째
+/// 응답의 값을 사용하며, 단가는 평균 단가, 체결 구분은 체결 기준, 수수료는
+/// 제비용 포함으로 조회합니다.
+pub fn balance(
+    account: &str,
+    password: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Balance, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "t0424InBlock",
+        &[
+            ("accno", account.to_owned()),
+            ("passwd", password.to_owned()),
+            ("prcgb", "1".into()),
+            ("chegb", "2".into()),
+            ("dangb", "0".into()),
+            ("charge", "1".into()),
+        ],
+    )?;
+
+    let mut balance: Option<Balance> = None;
+    let mut next_key = None;
+
+    loop {
+        let res = ensure_ok(request_with_retry(
+            &req_data,
+            tr_layout.clone(),
+            next_key.as_deref(),
+            timeout,
+        )?)?;
+
+        let res_data = res.data()?;
+        match &mut balance {
+            Some(balance) => balance.positions.extend(Position::from_data(res_data)?),
+            None => balance = Some(Balance::from_data(res_data)?),
+        }
+
+        match res.next_key() {
+            Some(key) => {
+                let cts_expcode: String = data::parse_field(
+                    data::get_block(res_data, "t0424OutBlock")?,
+                    "t0424OutBlock",
+                    "cts_expcode",
+                )?;
+                set_fields(
+                    &mut req_data,
+                    "t0424InBlock",
+                    &[("cts_expcode", cts_expcode)],
+                )?;
+                next_key = Some(key.to_owned());
+            }
+            None => break,
+        }
+    }
+
+    Ok(balance.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Balance;
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+
+    #[test]
+    fn test_balance_from_data() {
+        let data = Data {
+            tr_code: "t0424".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t0424OutBlock" => Block::Block(hashmap! {
+                    "sunamt" => "000000000500007907",
+                    "dtsunik" => "000000000000000000",
+                    "mamt" => "000000000001957400",
+                    "sunamt1" => "500000000",
+                    "cts_expcode" => "",
+                    "tappamt" => "000000000001965600",
+                    "tdtsunik" => "000000000000008200",
+                }),
+                "t0424OutBlock1" => Block::Array(vec![hashmap! {
+                    "expcode" => "005930",
+                    "janqty" => "20",
+                    "mdposqt" => "20",
+                    "pamt" => "90700",
+                    "mamt" => "1814000",
+                    "hname" => "삼성전자",
+                    "price" => "00091000",
+                    "appamt" => "000000000001820000",
+                    "dtsunik" => "6000",
+                    "fee" => "0000000545",
+                    "tax" => "0000004550",
+                }]),
+            },
+        };
+
+        let balance = Balance::from_data(&data).unwrap();
+        assert_eq!(balance.estimated_net_assets, 500007907);
+        assert_eq!(balance.purchase_amount, 1957400);
+        assert_eq!(balance.unrealized_pnl, 8200);
+        assert_eq!(balance.positions.len(), 1);
+
+        let position = &balance.positions[0];
+        assert_eq!(position.symbol, "005930");
+        assert_eq!(position.name, "삼성전자");
+        assert_eq!(position.qty, 20);
+        assert_eq!(position.avg_price, 90700.0);
+        assert_eq!(position.price, 91000.0);
+        assert_eq!(position.evaluation_amount, 1820000);
+        assert_eq!(position.tax, 4550);
+        assert!((position.profit_rate() - 6000.0 / 1814000.0 * 100.0).abs() < 1e-9);
+    }
+}
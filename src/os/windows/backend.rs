@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 백엔드 추상화 모듈
+//!
+//! 서버 연결, 로그인, 조회 요청을 [`Backend`] 트레잇으로 묶어서 제공합니다.
+//! 애플리케이션 로직을 트레잇에 대해 작성하면 실제 DLL을 사용하는
+//! [`DllBackend`] 대신 [`MockBackend`]로 DLL 없이 시험할 수 있습니다.
+//!
+//! [`MockBackend`]는 미리 지정한 응답 데이터를 레이아웃으로 인코딩한 후
+//! 실제 수신한 데이터와 같은 경로로 디코딩하여 반환합니다.
+//! [`loader::load_mock()`](super::loader::load_mock)으로 불러오면 DLL 대신
+//! 세션과 실행기를 거쳐 응답을 반환하므로 [`request()`](super::request)와
+//! 같은 함수도 그대로 시험할 수 있습니다.
+
+pub use crate::os::backend::{Backend, MockBackend};
+
+use super::capture::QueryRecord;
+use super::replay::RealSource;
+use super::{Account, Error, LoginResponse, QueryResponse, RealResponse};
+use crate::data::Data;
+use crate::layout::TrLayout;
+use crate::os::backend::{CannedData, CannedResponse};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 불러온 XingAPI DLL을 사용하는 백엔드
+///
+/// 함수를 호출하기 전에 [`loader`](super::loader)로 DLL을 불러와야 합니다.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DllBackend;
+
+impl Backend for DllBackend {
+    fn connect(&self, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
+        super::connect(addr, port, timeout)
+    }
+
+    fn is_connected(&self) -> bool {
+        super::is_connected()
+    }
+
+    fn disconnect(&self) {
+        super::disconnect()
+    }
+
+    fn login(
+        &self,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<LoginResponse, Error> {
+        super::login(id, pw, cert_pw, cert_err_dialog)
+    }
+
+    fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        super::request(data, tr_layout.clone(), next_key, timeout)
+    }
+
+    fn accounts(&self) -> Vec<Account> {
+        super::accounts()
+    }
+}
+
+impl MockBackend {
+    /// 기록된 응답을 조회 TR 요청에 대해 반환할 응답으로 추가합니다.
+    pub fn push_record(&self, record: &QueryRecord) {
+        self.push(
+            &record.request.tr_code,
+            CannedResponse {
+                code: record.code.clone(),
+                message: record.message.clone(),
+                next_key: record.res_next_key.clone(),
+                data: record.raw_data.clone().map(CannedData::Raw),
+            },
+        );
+    }
+}
+
+impl RealSource for MockBackend {
+    fn try_recv(&self) -> Option<RealResponse> {
+        MockBackend::try_recv(self)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        MockBackend::recv_timeout(self, timeout)
+    }
+}
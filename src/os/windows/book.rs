@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 실시간 호가 모듈
+//!
+//! 실시간 호가 잔량 TR인 H1_(코스피), HA_(코스닥)를 수신하여 종목별 호가를
+//! 메모리에 유지합니다.
+//!
+//! 실시간 호가 잔량에는 현재가가 포함되지 않으므로, 현재가가 필요한 경우
+//! [`market::orderbook()`](super::market::orderbook)으로 조회한 호가를
+//! [`OrderBookMirror::insert()`]로 먼저 추가해야 합니다.
+
+use super::market::OrderBook;
+use super::realtime::QuoteUpdate;
+use super::{RealEvent, RealResponse};
+use crate::data::{Data, DecodeError};
+
+use std::collections::HashMap;
+
+type ChangeCallback = Box<dyn FnMut(&OrderBook) + Send>;
+
+/// 실시간 호가를 반영하는 종목별 호가 목록
+#[derive(Default)]
+pub struct OrderBookMirror {
+    books: HashMap<String, OrderBook>,
+    callbacks: Vec<ChangeCallback>,
+}
+
+impl OrderBookMirror {
+    /// 실시간 호가 잔량 TR 코드
+    pub const TR_CODES: [&'static str; 2] = QuoteUpdate::TR_CODES;
+
+    /// 호가가 없는 객체를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 종목들의 실시간 호가 잔량 TR을 등록합니다.
+    ///
+    /// 코스피와 코스닥 TR을 모두 등록하며, 응답을 디코딩하기 위해
+    /// [`TR_CODES`](Self::TR_CODES)의 레이아웃이 `real`에 추가되어 있어야
+    /// 합니다.
+    pub fn subscribe<T: AsRef<str>>(&self, real: &RealEvent, symbols: &[T]) {
+        for tr_code in Self::TR_CODES {
+            real.subscribe(tr_code, symbols);
+        }
+    }
+
+    /// 종목들의 실시간 호가 잔량 TR을 등록 해제합니다.
+    pub fn unsubscribe<T: AsRef<str>>(&self, real: &RealEvent, symbols: &[T]) {
+        for tr_code in Self::TR_CODES {
+            real.unsubscribe(tr_code, symbols);
+        }
+    }
+
+    /// 호가가 갱신될 때마다 호출할 함수를 추가합니다.
+    pub fn on_change<F: FnMut(&OrderBook) + Send + 'static>(&mut self, callback: F) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// 조회한 호가를 추가하거나 교체합니다.
+    pub fn insert(&mut self, book: OrderBook) {
+        self.books.insert(book.symbol.clone(), book);
+    }
+
+    /// 수신한 실시간 응답을 반영하고 갱신된 호가를 반환합니다.
+    ///
+    /// 실시간 호가 잔량 TR이 아닌 응답은 무시합니다.
+    pub fn apply(&mut self, res: &RealResponse) -> Result<Option<&OrderBook>, DecodeError> {
+        self.apply_data(res.data()?)
+    }
+
+    /// 디코딩된 실시간 데이터를 반영하고 갱신된 호가를 반환합니다.
+    pub fn apply_data(&mut self, data: &Data) -> Result<Option<&OrderBook>, DecodeError> {
+        if !Self::TR_CODES.contains(&data.tr_code.as_str()) {
+            return Ok(None);
+        }
+
+        let quote = QuoteUpdate::from_data(data)?;
+
+        let book = self
+            .books
+            .entry(quote.symbol.clone())
+            .or_insert_with(|| OrderBook {
+                symbol: quote.symbol,
+                price: 0.0,
+                asks: quote.asks,
+                bids: quote.bids,
+                total_ask_qty: quote.total_ask_qty,
+                total_bid_qty: quote.total_bid_qty,
+                time: String::new(),
+            });
+
+        book.asks = quote.asks;
+        book.bids = quote.bids;
+        book.total_ask_qty = quote.total_ask_qty;
+        book.total_bid_qty = quote.total_bid_qty;
+        book.time = quote.time;
+
+        for callback in &mut self.callbacks {
+            callback(book);
+        }
+
+        Ok(Some(book))
+    }
+
+    /// 종목의 호가를 반환합니다.
+    pub fn get(&self, symbol: &str) -> Option<&OrderBook> {
+        self.books.get(symbol)
+    }
+
+    /// 종목의 최우선 매도 호가를 반환합니다.
+    pub fn best_ask(&self, symbol: &str) -> Option<(f64, u64)> {
+        self.get(symbol).map(|b| b.best_ask())
+    }
+
+    /// 종목의 최우선 매수 호가를 반환합니다.
+    pub fn best_bid(&self, symbol: &str) -> Option<(f64, u64)> {
+        self.get(symbol).map(|b| b.best_bid())
+    }
+
+    /// 호가 목록을 반환합니다.
+    pub fn books(&self) -> impl Iterator<Item = &OrderBook> {
+        self.books.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderBookMirror;
+    use crate::data::{Block, Data, DataType};
+
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn h1_data(best_ask: u64) -> Data {
+        let mut fields: HashMap<Arc<str>, String> = HashMap::new();
+        for i in 1..=10 {
+            fields.insert(
+                format!("offerho{}", i).into(),
+                (best_ask + (i - 1) * 10).to_string(),
+            );
+            fields.insert(format!("offerrem{}", i).into(), "100".into());
+            fields.insert(
+                format!("bidho{}", i).into(),
+                (best_ask - i * 10).to_string(),
+            );
+            fields.insert(format!("bidrem{}", i).into(), "200".into());
+        }
+        fields.insert("shcode".into(), "005930".into());
+        fields.insert("totofferrem".into(), "1000".into());
+        fields.insert("totbidrem".into(), "2000".into());
+        fields.insert("hotime".into(), "090000".into());
+
+        Data {
+            tr_code: "H1_".into(),
+            data_type: DataType::Output,
+            blocks: [("OutBlock".to_owned(), Block::Block(fields))]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_order_book_mirror() {
+        let changes = Arc::new(AtomicUsize::new(0));
+
+        let mut mirror = OrderBookMirror::new();
+        mirror.on_change({
+            let changes = changes.clone();
+            move |_| {
+                changes.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        mirror.apply_data(&h1_data(91000)).unwrap().unwrap();
+        assert_eq!(mirror.best_ask("005930"), Some((91000.0, 100)));
+        assert_eq!(mirror.best_bid("005930"), Some((90990.0, 200)));
+
+        mirror.apply_data(&h1_data(91100)).unwrap().unwrap();
+        assert_eq!(mirror.best_ask("005930"), Some((91100.0, 100)));
+        assert_eq!(mirror.get("005930").unwrap().total_bid_qty, 2000);
+        assert_eq!(changes.load(Ordering::Relaxed), 2);
+
+        let mut other = h1_data(91000);
+        other.tr_code = "S3_".into();
+        assert!(mirror.apply_data(&other).unwrap().is_none());
+    }
+}
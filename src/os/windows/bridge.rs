@@ -0,0 +1,617 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! HTTP 브릿지 모듈
+//!
+//! 윈도우가 아닌 환경의 서비스가 HTTP로 조회 TR을 요청할 수 있도록
+//! 중계하는 작은 HTTP 서버를 제공합니다. 요청마다 연결을 종료하며, JSON은
+//! `serde` 기능으로 직렬화합니다.
+//!
+//! | 경로                  | 메서드 | 내용                      |
+//! |-----------------------|--------|---------------------------|
+//! | `/request/{tr_code}`  | POST   | 조회 TR 요청              |
+//! | `/accounts`           | GET    | 계좌 목록                 |
+//!
+//! 조회 TR 요청의 본문은 블록 이름을 키로 하는 JSON 객체이며, 필드 값은
+//! 문자열이나 숫자여야 합니다. 지정하지 않은 단일 블록의 필드는 빈 문자열로
+//! 요청합니다. 연속 조회 키는 `X-Next-Key` 헤더로 지정합니다.
+//!
+//! ```text
+//! POST /request/t1102
+//! {"t1102InBlock":{"shcode":"005930"}}
+//!
+//! 200 OK
+//! {"code":"00000","message":"...","elapsed_ms":12,"next_key":null,"data":{...}}
+//! ```
+//!
+//! 실패한 경우 `{"error":"..."}` 본문과 함께 4xx 또는 5xx 상태 코드로
+//! 응답합니다.
+//!
+//! 기본적으로 헤더 타입이 B인 계좌 및 주문 TR은 요청할 수 없습니다.
+//! [`Bridge::with_allowed_trs()`]로 요청할 수 있는 TR을 지정하고,
+//! [`Bridge::with_auth()`]로 `Authorization` 헤더를 검사할 수 있습니다.
+
+pub use super::access::AuthHook;
+
+use super::access::{Access, DeadlineStream};
+use super::backend::Backend;
+use super::{Error, QueryResponse, Response};
+use crate::data::json::{self, Value};
+use crate::data::{self, Data};
+use crate::layout::TrLayout;
+
+use serde::Serialize;
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+// 요청 헤더의 최대 크기
+const MAX_HEADER_LEN: usize = 8 * 1024;
+// 요청 본문의 최대 크기
+const MAX_BODY_LEN: usize = 1024 * 1024;
+
+/// HTTP 브릿지 서버
+pub struct Bridge {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    backend: Arc<dyn Backend>,
+    layout_tbl: HashMap<String, Arc<TrLayout>>,
+    timeout: Duration,
+    access: Access,
+}
+
+impl Bridge {
+    /// 지정된 주소에서 연결을 받는 서버를 생성합니다.
+    ///
+    /// 조회 TR은 `backend`로 요청하며, 요청에 사용할 레이아웃을 모두 지정해야
+    /// 합니다. 조회 TR의 응답을 기다리는 시간은 30초입니다.
+    pub fn bind<A: ToSocketAddrs>(
+        addr: A,
+        backend: Arc<dyn Backend>,
+        layout_tbl: HashMap<String, Arc<TrLayout>>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            shared: Arc::new(Shared {
+                backend,
+                layout_tbl,
+                timeout: Duration::from_secs(30),
+                access: Access::default(),
+            }),
+        })
+    }
+
+    fn shared_mut(&mut self) -> &mut Shared {
+        Arc::get_mut(&mut self.shared).unwrap()
+    }
+
+    /// 조회 TR의 응답을 기다리는 시간을 변경합니다.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.shared_mut().timeout = timeout;
+        self
+    }
+
+    /// 요청을 읽고 응답을 쓰는 데 허용하는 시간을 변경합니다.
+    ///
+    /// 기본값은 10초이며, 요청을 끝까지 보내지 않는 연결은 이 시간이 지나면
+    /// 종료됩니다.
+    pub fn with_io_timeout(mut self, io_timeout: Duration) -> Self {
+        self.shared_mut().access.io_timeout = io_timeout;
+        self
+    }
+
+    /// 동시에 처리하는 최대 연결 개수를 변경합니다.
+    ///
+    /// 기본값은 64개이며, 초과한 연결에는 503 상태 코드로 응답합니다.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.shared_mut().access.max_connections = max_connections;
+        self
+    }
+
+    /// 요청할 수 있는 TR 코드를 지정합니다.
+    ///
+    /// 지정하지 않은 경우 헤더 타입이 B인 계좌 및 주문 TR을 제외한 모든
+    /// 레이아웃의 TR을 요청할 수 있습니다. 허용되지 않은 TR은 403 상태 코드로
+    /// 거부합니다.
+    pub fn with_allowed_trs<I, S>(mut self, tr_codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.shared_mut().access.allowed_trs = Some(tr_codes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// `Authorization` 헤더를 검사할 함수를 지정합니다.
+    ///
+    /// 모든 경로에 적용되며, 함수가 거짓을 반환한 요청은 401 상태 코드로
+    /// 거부합니다.
+    pub fn with_auth(mut self, auth: AuthHook) -> Self {
+        self.shared_mut().access.auth = Some(auth);
+        self
+    }
+
+    /// 서버의 주소를 반환합니다.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// 연결을 받아 처리합니다.
+    ///
+    /// 연결마다 스레드를 생성하며, 연결을 받는 데 실패하기 전까지 반환하지
+    /// 않습니다.
+    pub fn serve(&self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+
+            let slot = match self.shared.access.try_connect() {
+                Some(slot) => slot,
+                None => {
+                    let _ = reject_connection(stream, &self.shared);
+                    continue;
+                }
+            };
+            let shared = self.shared.clone();
+
+            std::thread::spawn(move || {
+                let _ = handle_connection(stream, &shared);
+                drop(slot);
+            });
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+// 요청을 읽지 못한 이유
+enum RequestError {
+    // 요청 형식이 잘못됨
+    Malformed(&'static str),
+    // 헤더나 본문이 최대 크기를 넘음
+    TooLarge(&'static str),
+    // 연결 에러
+    Io(io::Error),
+}
+
+impl From<io::Error> for RequestError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+// 상태 코드와 JSON 본문
+type Reply = (u16, String);
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, RequestError> {
+    let mut header_len = 0;
+    let mut read_line = |reader: &mut R| -> Result<String, RequestError> {
+        let mut line = String::new();
+        let len = reader
+            .by_ref()
+            .take((MAX_HEADER_LEN + 1 - header_len) as u64)
+            .read_line(&mut line)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::InvalidData => RequestError::Malformed("invalid utf-8 header"),
+                _ => err.into(),
+            })?;
+        header_len += len;
+
+        if len == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        if header_len > MAX_HEADER_LEN {
+            return Err(RequestError::TooLarge("header too large"));
+        }
+
+        Ok(line.trim_end().to_owned())
+    };
+
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+        _ => return Err(RequestError::Malformed("invalid request line")),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or(RequestError::Malformed("invalid header"))?;
+        headers.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+
+    if let Some(len) = request.header("content-length") {
+        let len: usize = len
+            .parse()
+            .map_err(|_| RequestError::Malformed("invalid content length"))?;
+        if len > MAX_BODY_LEN {
+            return Err(RequestError::TooLarge("body too large"));
+        }
+
+        request.body = vec![0; len];
+        reader.read_exact(&mut request.body)?;
+    }
+
+    Ok(request)
+}
+
+fn write_reply<W: Write>(writer: &mut W, (status, body): &Reply) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    };
+
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    writer.flush()
+}
+
+fn json_reply<T: Serialize>(status: u16, value: &T) -> Reply {
+    match serde_json::to_string(value) {
+        Ok(body) => (status, body),
+        Err(err) => error_reply(500, &err.to_string()),
+    }
+}
+
+fn error_reply(status: u16, message: &str) -> Reply {
+    #[derive(Serialize)]
+    struct ErrorBody<'a> {
+        error: &'a str,
+    }
+
+    (
+        status,
+        serde_json::to_string(&ErrorBody { error: message }).unwrap(),
+    )
+}
+
+// 요청 에러에 해당하는 상태 코드를 반환합니다.
+fn error_status(err: &Error) -> u16 {
+    match err {
+        Error::Encode(_) => 400,
+        Error::Busy => 503,
+        Error::TimedOut => 504,
+        _ => 502,
+    }
+}
+
+// 처리할 수 있는 연결 개수를 초과한 연결을 거부합니다.
+fn reject_connection(mut stream: TcpStream, shared: &Shared) -> io::Result<()> {
+    stream.set_write_timeout(Some(shared.access.io_timeout))?;
+    write_reply(&mut stream, &error_reply(503, "too many connections"))
+}
+
+fn handle_connection(stream: TcpStream, shared: &Shared) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    writer.set_write_timeout(Some(shared.access.io_timeout))?;
+
+    let mut reader = BufReader::new(DeadlineStream::new(stream, shared.access.io_timeout));
+
+    let reply = match read_request(&mut reader) {
+        Ok(request) => route(&request, shared),
+        Err(RequestError::Malformed(message)) => error_reply(400, message),
+        Err(RequestError::TooLarge(message)) => error_reply(413, message),
+        Err(RequestError::Io(err)) => return Err(err),
+    };
+
+    write_reply(&mut writer, &reply)
+}
+
+fn route(request: &Request, shared: &Shared) -> Reply {
+    if !shared.access.is_authorized(request.header("authorization")) {
+        return error_reply(401, "unauthorized");
+    }
+
+    let path = request.path.split('?').next().unwrap_or_default();
+
+    if let Some(tr_code) = path.strip_prefix("/request/") {
+        if request.method != "POST" {
+            return error_reply(405, "method not allowed");
+        }
+        query(request, tr_code, shared)
+    } else if path == "/accounts" {
+        if request.method != "GET" {
+            return error_reply(405, "method not allowed");
+        }
+        json_reply(200, &shared.backend.accounts())
+    } else {
+        error_reply(404, "not found")
+    }
+}
+
+fn query(request: &Request, tr_code: &str, shared: &Shared) -> Reply {
+    let tr_layout = match shared.layout_tbl.get(tr_code) {
+        Some(tr_layout) => tr_layout,
+        None => return error_reply(404, &format!("unknown tr code: {}", tr_code)),
+    };
+    if !shared.access.is_allowed(tr_layout) {
+        return error_reply(403, &format!("tr code not allowed: {}", tr_code));
+    }
+
+    let blocks = match std::str::from_utf8(&request.body) {
+        Ok(body) if body.trim().is_empty() => Value::Object(vec![]),
+        Ok(body) => match json::parse(body) {
+            Ok(blocks) => blocks,
+            Err(err) => return error_reply(400, &err.to_string()),
+        },
+        Err(_) => return error_reply(400, "invalid utf-8 body"),
+    };
+
+    let data = match json::to_input(tr_layout, &blocks) {
+        Ok(data) => data,
+        Err(err) => return error_reply(400, &err.to_string()),
+    };
+
+    let res = match shared.backend.request(
+        &data,
+        tr_layout,
+        request.header("x-next-key"),
+        shared.timeout,
+    ) {
+        Ok(res) => res,
+        Err(err) => return error_reply(error_status(&err), &err.to_string()),
+    };
+
+    match QueryReply::new(&res) {
+        Ok(reply) => json_reply(200, &reply),
+        Err(err) => error_reply(502, &err.to_string()),
+    }
+}
+
+// 조회 TR 응답의 JSON 본문
+#[derive(Serialize)]
+struct QueryReply<'a> {
+    code: &'a str,
+    message: &'a str,
+    elapsed_ms: u64,
+    next_key: Option<&'a str>,
+    data: Option<&'a Data>,
+}
+
+impl<'a> QueryReply<'a> {
+    fn new(res: &'a QueryResponse) -> Result<Self, data::DecodeError> {
+        Ok(Self {
+            code: res.code(),
+            message: res.message(),
+            elapsed_ms: res.elapsed().as_millis() as u64,
+            next_key: res.next_key(),
+            data: if res.is_ok() { Some(res.data()?) } else { None },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_request, Bridge, RequestError};
+    use crate::backend::MockBackend;
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+    use crate::os::testdata::t1102_layout;
+    use crate::Account;
+
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn send(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn layout_tbl() -> HashMap<String, Arc<TrLayout>> {
+        let tr_layout = t1102_layout();
+        let order_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,현물정상주문,CSPAT00600,block,headtype=B;
+                BEGIN_DATA_MAP
+                CSPAT00600InBlock1,In(*EMPTY*),input;
+                begin
+                    종목번호,IsuNo,IsuNo,char,12;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+
+        [
+            ("t1102".to_owned(), Arc::new(tr_layout)),
+            ("CSPAT00600".to_owned(), Arc::new(order_layout)),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_bridge() {
+        let layout_tbl = layout_tbl();
+
+        let backend = Arc::new(MockBackend::new(layout_tbl.clone()));
+        backend
+            .push_response(
+                &Data {
+                    tr_code: "t1102".into(),
+                    data_type: DataType::Output,
+                    blocks: hashmap! {
+                        "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+                    },
+                },
+                Some("1"),
+            )
+            .unwrap();
+        backend.set_accounts(vec![Account {
+            code: "55501234567".into(),
+            name: "홍길동".into(),
+            detailed_name: "종합매매".into(),
+            nickname: String::new(),
+        }]);
+
+        let bridge = Bridge::bind("127.0.0.1:0", backend.clone(), layout_tbl).unwrap();
+        let addr = bridge.local_addr().unwrap();
+        std::thread::spawn(move || bridge.serve());
+
+        let body = r#"{"t1102InBlock":{"shcode":"005930"}}"#;
+        let response = send(
+            addr,
+            &format!(
+                "POST /request/t1102 HTTP/1.1\r\nX-Next-Key: 0\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(concat!(
+            r#"{"code":"00000","message":"조회완료","elapsed_ms":0,"next_key":"1","#,
+            r#""data":{"tr_code":"t1102","data_type":"output","#,
+            r#""blocks":{"t1102OutBlock":{"price":"91000"}}}}"#,
+        )));
+        assert_eq!(
+            backend.requests()[0].blocks["t1102InBlock"],
+            Block::Block(hashmap! { "shcode" => "005930" })
+        );
+
+        // 남은 응답이 없는 경우 시간 초과로 처리됩니다.
+        let response = send(addr, "POST /request/t1102 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 504"));
+
+        let response = send(
+            addr,
+            "POST /request/t1102 HTTP/1.1\r\nContent-Length: 1\r\n\r\n{",
+        );
+        assert!(response.starts_with("HTTP/1.1 400"));
+
+        let response = send(addr, "POST /request/t9999 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404"));
+
+        // 주문 TR은 기본적으로 요청할 수 없습니다.
+        let response = send(addr, "POST /request/CSPAT00600 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+        assert_eq!(backend.requests().len(), 2);
+
+        let response = send(addr, "GET /request/t1102 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405"));
+
+        let response = send(addr, "GET /accounts HTTP/1.1\r\n\r\n");
+        assert!(response.ends_with(
+            r#"[{"code":"55501234567","name":"홍길동","detailed_name":"종합매매","nickname":""}]"#
+        ));
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = format!("GET / HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "0".repeat(9000));
+        assert!(matches!(
+            read_request(&mut request.as_bytes()),
+            Err(RequestError::TooLarge(_))
+        ));
+
+        let request = "GET / HTTP/1.1\r\nContent-Length: 2000000\r\n\r\n";
+        assert!(matches!(
+            read_request(&mut request.as_bytes()),
+            Err(RequestError::TooLarge(_))
+        ));
+
+        let request = "GET / HTTP/1.1\r\nX-Padding\r\n\r\n";
+        assert!(matches!(
+            read_request(&mut request.as_bytes()),
+            Err(RequestError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_bridge_access() {
+        let layout_tbl = layout_tbl();
+        let backend = Arc::new(MockBackend::new(layout_tbl.clone()));
+
+        let bridge = Bridge::bind("127.0.0.1:0", backend.clone(), layout_tbl)
+            .unwrap()
+            .with_allowed_trs(["CSPAT00600"])
+            .with_auth(|auth| auth == Some("Bearer secret"))
+            .with_io_timeout(Duration::from_millis(100))
+            .with_timeout(Duration::ZERO);
+        let addr = bridge.local_addr().unwrap();
+        std::thread::spawn(move || bridge.serve());
+
+        let response = send(addr, "POST /request/CSPAT00600 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+
+        let response = send(
+            addr,
+            "POST /request/t1102 HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 403"));
+
+        let response = send(
+            addr,
+            "POST /request/CSPAT00600 HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 504"));
+        assert_eq!(backend.requests()[0].tr_code, "CSPAT00600");
+
+        // 요청을 끝까지 보내지 않는 연결은 시간이 지나면 종료됩니다.
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"POST /request/CSPAT00600 HTTP/1.1\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.is_empty());
+    }
+}
@@ -0,0 +1,728 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 헬퍼 프로세스를 통해 XingAPI를 사용하는 모듈
+//!
+//! XingAPI DLL은 32비트로만 제공되므로 64비트 프로세스에서는 불러올 수
+//! 없습니다. 32비트로 빌드한 `xingapi-broker` 헬퍼 프로세스가 DLL을 불러오고,
+//! 애플리케이션은 [`BrokerBackend`]로 named pipe를 통해 요청합니다.
+//! [`BrokerBackend`]는 [`Backend`]와 [`RealSource`]를 구현하므로 트레잇에 대해
+//! 작성한 코드를 그대로 사용할 수 있습니다.
+//!
+//! ```text
+//! cargo build --release --features broker --bin xingapi-broker --target i686-pc-windows-msvc
+//! ```
+//!
+//! 헬퍼 프로세스는 조회 TR의 요청 데이터를 인코딩하고 응답 데이터를
+//! 디코딩하기 위해 자체적으로 RES 파일을 불러오며, 디코딩된 데이터를
+//! 전달합니다.
+//!
+//! 32비트 애플리케이션에서도 DLL 내부의 크래시로부터 프로세스를 보호하려면
+//! 헬퍼 프로세스를 감독하고 자동으로 재시작하는 [`SupervisedBackend`]를
+//! 사용합니다.
+
+mod protocol;
+mod supervisor;
+
+use self::protocol::{read_frame, write_frame, Reply, Request};
+pub use self::supervisor::SupervisedBackend;
+use super::backend::{Backend, DllBackend};
+use super::replay::RealSource;
+use super::{Account, Error, LoginResponse, QueryResponse, RealEvent, RealResponse};
+use crate::data::{self, Data, EncodeError};
+use crate::layout::TrLayout;
+
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CString, OsStr};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use winapi::shared::minwindef::{DWORD, ULONG};
+use winapi::shared::winerror::{ERROR_BROKEN_PIPE, ERROR_FILE_NOT_FOUND};
+use winapi::shared::winerror::{ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED};
+use winapi::um::fileapi::{CreateFileA, ReadFile, WriteFile, OPEN_EXISTING};
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::um::namedpipeapi::ConnectNamedPipe;
+use winapi::um::ntsecapi::RtlGenRandom;
+use winapi::um::winbase::{CreateNamedPipeA, GetNamedPipeServerProcessId};
+use winapi::um::winbase::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_REJECT_REMOTE_CLIENTS};
+use winapi::um::winbase::{PIPE_ACCESS_INBOUND, PIPE_ACCESS_OUTBOUND};
+use winapi::um::winbase::{PIPE_TYPE_BYTE, PIPE_WAIT};
+use winapi::um::winnt::{GENERIC_READ, GENERIC_WRITE, HANDLE};
+
+const PIPE_BUF_SIZE: DWORD = 64 * 1024;
+
+// 헬퍼 프로세스가 파이프를 생성할 때까지 기다리는 최대 시간
+const SPAWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 시간 제한이 없는 요청의 응답을 기다리는 최대 시간
+const IPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+// 시간 제한이 있는 요청의 응답을 제한 시간보다 더 기다리는 시간
+const IPC_MARGIN: Duration = Duration::from_secs(5);
+
+// 다른 프로세스가 추측하여 먼저 생성할 수 없도록 임의의 값을 포함한 파이프
+// 이름을 생성합니다.
+fn random_pipe_name() -> io::Result<String> {
+    let mut nonce = [0u8; 16];
+    if unsafe { RtlGenRandom(nonce.as_mut_ptr() as _, nonce.len() as ULONG) } == 0 {
+        return Err(io::Error::other("failed to generate a pipe name"));
+    }
+
+    let nonce: String = nonce.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!(
+        "\\\\.\\pipe\\xingapi-broker-{}-{}",
+        std::process::id(),
+        nonce
+    ))
+}
+
+// 요청과 응답은 각각 별도의 파이프를 사용합니다. 동기 방식의 파이프 핸들은
+// 읽기와 쓰기가 서로를 기다리므로 하나의 파이프로는 실시간 응답을 요청과
+// 동시에 보낼 수 없습니다.
+fn req_pipe_name(pipe_name: &str) -> String {
+    format!("{}-req", pipe_name)
+}
+
+fn res_pipe_name(pipe_name: &str) -> String {
+    format!("{}-res", pipe_name)
+}
+
+// 파이프 핸들
+struct Pipe(HANDLE);
+
+// 핸들은 스레드에 관계없이 사용할 수 있습니다.
+unsafe impl Send for Pipe {}
+
+impl Pipe {
+    // 파이프를 생성하고 클라이언트가 연결할 때까지 기다립니다.
+    //
+    // 같은 이름의 파이프가 이미 존재하는 경우 다른 프로세스가 먼저 생성한
+    // 것이므로 실패합니다.
+    fn accept(name: &str, access: DWORD) -> io::Result<Self> {
+        let name = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+        let handle = unsafe {
+            CreateNamedPipeA(
+                name.as_ptr(),
+                access | FILE_FLAG_FIRST_PIPE_INSTANCE,
+                PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                1,
+                PIPE_BUF_SIZE,
+                PIPE_BUF_SIZE,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(io::Error::last_os_error());
+        }
+
+        let pipe = Self(handle);
+
+        if unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } == 0 {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() != Some(ERROR_PIPE_CONNECTED as i32) {
+                return Err(err);
+            }
+        }
+
+        Ok(pipe)
+    }
+
+    // 파이프가 생성될 때까지 기다린 후 연결합니다.
+    fn open(name: &str, access: DWORD, timeout: Duration) -> io::Result<Self> {
+        let name = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let start = Instant::now();
+
+        loop {
+            let handle = unsafe {
+                CreateFileA(
+                    name.as_ptr(),
+                    access,
+                    0,
+                    std::ptr::null_mut(),
+                    OPEN_EXISTING,
+                    0,
+                    std::ptr::null_mut(),
+                )
+            };
+
+            if handle != INVALID_HANDLE_VALUE {
+                return Ok(Self(handle));
+            }
+
+            let err = io::Error::last_os_error();
+            let retry = matches!(
+                err.raw_os_error().map(|code| code as DWORD),
+                Some(ERROR_FILE_NOT_FOUND | ERROR_PIPE_BUSY)
+            );
+
+            if !retry || start.elapsed() >= timeout {
+                return Err(err);
+            }
+
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    // 파이프를 생성한 프로세스의 ID를 반환합니다.
+    fn server_process_id(&self) -> io::Result<u32> {
+        let mut pid = 0;
+        if unsafe { GetNamedPipeServerProcessId(self.0, &mut pid) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(pid)
+    }
+}
+
+impl Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(DWORD::MAX as usize) as DWORD;
+        let mut read = 0;
+
+        let ok = unsafe {
+            ReadFile(
+                self.0,
+                buf.as_mut_ptr() as _,
+                len,
+                &mut read,
+                std::ptr::null_mut(),
+            )
+        };
+
+        if ok != 0 {
+            Ok(read as usize)
+        } else {
+            let err = io::Error::last_os_error();
+            // 상대방이 파이프를 닫은 경우 EOF로 처리합니다.
+            if err.raw_os_error() == Some(ERROR_BROKEN_PIPE as i32) {
+                Ok(0)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(DWORD::MAX as usize) as DWORD;
+        let mut written = 0;
+
+        let ok = unsafe {
+            WriteFile(
+                self.0,
+                buf.as_ptr() as _,
+                len,
+                &mut written,
+                std::ptr::null_mut(),
+            )
+        };
+
+        if ok != 0 {
+            Ok(written as usize)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for Pipe {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+fn unexpected_reply() -> Error {
+    Error::Ipc(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "unexpected reply",
+    ))
+}
+
+/// 헬퍼 프로세스를 통해 서버와 통신하는 백엔드
+///
+/// 요청은 한 번에 하나씩 처리되며, 헬퍼 프로세스와의 연결이 끊어진 경우
+/// `Error::Ipc`를 반환합니다. 헬퍼 프로세스가 요청의 제한 시간 내에 응답하지
+/// 않으면 연결이 끊어진 것으로 간주합니다.
+pub struct BrokerBackend {
+    child: Mutex<Option<Child>>,
+    alive: Arc<AtomicBool>,
+    writer: Mutex<Pipe>,
+    rx_reply: Receiver<Reply>,
+    rx_real: Receiver<RealResponse>,
+}
+
+impl BrokerBackend {
+    /// 헬퍼 프로세스를 실행하고 연결합니다.
+    ///
+    /// RES 파일 경로를 지정하지 않은 경우 XingAPI SDK의 기본 설치 경로를
+    /// 사용합니다. 객체가 소멸되면 헬퍼 프로세스도 종료됩니다.
+    pub fn spawn<P: AsRef<OsStr>>(exe_path: P, res_dir: Option<&Path>) -> io::Result<Self> {
+        let pipe_name = random_pipe_name()?;
+
+        let mut command = Command::new(exe_path);
+        command.arg(&pipe_name);
+        if let Some(res_dir) = res_dir {
+            command.arg(res_dir);
+        }
+
+        let mut child = command.spawn()?;
+
+        // 로그인 정보를 보내기 전에 파이프가 실행한 프로세스의 것인지
+        // 확인합니다.
+        match Self::open(&pipe_name, SPAWN_TIMEOUT, Some(child.id())) {
+            Ok(backend) => {
+                *backend.child.lock().unwrap() = Some(child);
+                Ok(backend)
+            }
+            Err(err) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                Err(err)
+            }
+        }
+    }
+
+    /// 이미 실행 중인 헬퍼 프로세스의 파이프에 연결합니다.
+    ///
+    /// 파이프를 생성한 프로세스를 확인하지 않으므로 다른 프로세스가 이름을
+    /// 추측할 수 없는 파이프에만 연결해야 합니다.
+    pub fn connect_pipe(pipe_name: &str, timeout: Duration) -> io::Result<Self> {
+        Self::open(pipe_name, timeout, None)
+    }
+
+    // 파이프에 연결하고, 프로세스 ID가 지정된 경우 파이프를 생성한
+    // 프로세스가 일치하는지 확인합니다.
+    fn open(pipe_name: &str, timeout: Duration, server_pid: Option<u32>) -> io::Result<Self> {
+        let writer = Pipe::open(&req_pipe_name(pipe_name), GENERIC_WRITE, timeout)?;
+        let mut reader = Pipe::open(&res_pipe_name(pipe_name), GENERIC_READ, timeout)?;
+
+        if let Some(server_pid) = server_pid {
+            for pipe in [&writer, &reader] {
+                if pipe.server_process_id()? != server_pid {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "pipe is not owned by the helper process",
+                    ));
+                }
+            }
+        }
+
+        let (tx_reply, rx_reply) = crossbeam_channel::unbounded();
+        let (tx_real, rx_real) = crossbeam_channel::unbounded();
+        let alive = Arc::new(AtomicBool::new(true));
+
+        let reader_alive = alive.clone();
+        thread::spawn(move || {
+            while let Ok(buf) = read_frame(&mut reader) {
+                match Reply::decode(&buf) {
+                    Ok(Reply::Real(res)) => {
+                        let _ = tx_real.send(res);
+                    }
+                    Ok(reply) => {
+                        if tx_reply.send(reply).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            reader_alive.store(false, Ordering::Relaxed);
+        });
+
+        Ok(Self {
+            child: Mutex::new(None),
+            alive,
+            writer: Mutex::new(writer),
+            rx_reply,
+            rx_real,
+        })
+    }
+
+    /// 헬퍼 프로세스와의 연결이 유지되고 있는지 확인합니다.
+    pub fn is_alive(&self) -> bool {
+        if !self.alive.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        match &mut *self.child.lock().unwrap() {
+            Some(child) => matches!(child.try_wait(), Ok(None)),
+            None => true,
+        }
+    }
+
+    fn call(&self, req: &Request) -> Result<Reply, Error> {
+        // 응답을 받을 때까지 다른 요청을 보내지 않습니다.
+        let mut writer = self.writer.lock().unwrap();
+        if !self.alive.load(Ordering::Relaxed) {
+            return Err(Error::Ipc(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        write_frame(&mut *writer, &req.encode()).map_err(Error::Ipc)?;
+
+        let timeout = match req {
+            Request::Connect { timeout, .. } | Request::Query { timeout, .. } => {
+                *timeout + IPC_MARGIN
+            }
+            _ => IPC_TIMEOUT,
+        };
+
+        match self.rx_reply.recv_timeout(timeout) {
+            Ok(Reply::Error(err)) => Err(err),
+            Ok(reply) => Ok(reply),
+            Err(RecvTimeoutError::Timeout) => {
+                // 늦게 도착한 응답이 다음 요청의 응답으로 처리되지 않도록
+                // 이후의 요청은 모두 실패합니다.
+                self.alive.store(false, Ordering::Relaxed);
+                Err(Error::Ipc(io::ErrorKind::TimedOut.into()))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(Error::Ipc(io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+    }
+
+    /// 실시간 TR을 등록합니다.
+    pub fn subscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) -> Result<(), Error> {
+        let req = Request::Subscribe {
+            tr_code: tr_code.to_owned(),
+            keys: keys.iter().map(|key| key.as_ref().to_owned()).collect(),
+        };
+
+        match self.call(&req)? {
+            Reply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    /// 실시간 TR을 등록 해제합니다.
+    pub fn unsubscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) -> Result<(), Error> {
+        let req = Request::Unsubscribe {
+            tr_code: tr_code.to_owned(),
+            keys: keys.iter().map(|key| key.as_ref().to_owned()).collect(),
+        };
+
+        match self.call(&req)? {
+            Reply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+}
+
+impl Drop for BrokerBackend {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Backend for BrokerBackend {
+    fn connect(&self, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
+        let req = Request::Connect {
+            addr: addr.to_owned(),
+            port,
+            timeout,
+        };
+
+        match self.call(&req)? {
+            Reply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        matches!(self.call(&Request::IsConnected), Ok(Reply::Connected(true)))
+    }
+
+    fn disconnect(&self) {
+        let _ = self.call(&Request::Disconnect);
+    }
+
+    fn login(
+        &self,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<LoginResponse, Error> {
+        let req = Request::Login {
+            id: id.to_owned(),
+            pw: pw.to_owned(),
+            cert_pw: cert_pw.to_owned(),
+            cert_err_dialog,
+        };
+
+        match self.call(&req)? {
+            Reply::Login(res) => Ok(res),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        // 인코딩 에러는 헬퍼 프로세스에 요청하기 전에 반환합니다.
+        data::encode(data, tr_layout)?;
+
+        let req = Request::Query {
+            data: data.clone(),
+            next_key: next_key.map(|key| key.to_owned()),
+            timeout,
+        };
+
+        match self.call(&req)? {
+            Reply::Query { res, .. } => Ok(res),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn accounts(&self) -> Vec<Account> {
+        match self.call(&Request::Accounts) {
+            Ok(Reply::Accounts(accounts)) => accounts,
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl RealSource for BrokerBackend {
+    fn try_recv(&self) -> Option<RealResponse> {
+        self.rx_real.try_recv().ok()
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.rx_real.recv_timeout(timeout).ok()
+    }
+}
+
+type SharedWriter = Arc<Mutex<dyn Write + Send>>;
+
+// 헬퍼 프로세스에서 요청을 처리하는 객체
+struct Server<'a> {
+    backend: &'a dyn Backend,
+    layout_tbl: &'a HashMap<String, Arc<TrLayout>>,
+    writer: SharedWriter,
+    real: Option<RealEvent>,
+    tr_codes: HashSet<String>,
+}
+
+impl Server<'_> {
+    fn handle(&mut self, req: Request) -> Reply {
+        let result = match req {
+            Request::Connect {
+                addr,
+                port,
+                timeout,
+            } => self
+                .backend
+                .connect(&addr, port, timeout)
+                .map(|_| Reply::Done),
+            Request::IsConnected => Ok(Reply::Connected(self.backend.is_connected())),
+            Request::Disconnect => {
+                self.backend.disconnect();
+                Ok(Reply::Done)
+            }
+            Request::Login {
+                id,
+                pw,
+                cert_pw,
+                cert_err_dialog,
+            } => self
+                .backend
+                .login(&id, &pw, &cert_pw, cert_err_dialog)
+                .map(Reply::Login),
+            Request::Query {
+                data,
+                next_key,
+                timeout,
+            } => match self.layout_tbl.get(&data.tr_code) {
+                Some(tr_layout) => self
+                    .backend
+                    .request(&data, tr_layout, next_key.as_deref(), timeout)
+                    .map(|res| Reply::Query {
+                        tr_code: data.tr_code,
+                        res,
+                    }),
+                None => Err(EncodeError::MismatchLayout.into()),
+            },
+            Request::Accounts => Ok(Reply::Accounts(self.backend.accounts())),
+            Request::Subscribe { tr_code, keys } => {
+                self.subscribe(tr_code, &keys).map(|_| Reply::Done)
+            }
+            Request::Unsubscribe { tr_code, keys } => {
+                if let Some(real) = &self.real {
+                    real.unsubscribe(&tr_code, &keys);
+                }
+                Ok(Reply::Done)
+            }
+        };
+
+        result.unwrap_or_else(Reply::Error)
+    }
+
+    fn subscribe(&mut self, tr_code: String, keys: &[String]) -> Result<(), Error> {
+        if self.real.is_none() {
+            self.real = Some(RealEvent::new().map_err(Error::Ipc)?);
+        }
+        let real = self.real.as_ref().unwrap();
+
+        if !self.tr_codes.contains(&tr_code) {
+            if let Some(tr_layout) = self.layout_tbl.get(&tr_code) {
+                real.insert_layout(tr_layout.clone());
+            }
+
+            // 실시간 응답은 요청에 대한 응답과 관계없이 바로 보냅니다.
+            let writer = self.writer.clone();
+            real.on(&tr_code, move |res| {
+                let reply = Reply::Real(res.clone());
+                let _ = write_frame(&mut *writer.lock().unwrap(), &reply.encode());
+            });
+
+            self.tr_codes.insert(tr_code.clone());
+        }
+
+        real.subscribe(&tr_code, keys);
+        Ok(())
+    }
+}
+
+/// 헬퍼 프로세스에서 지정된 이름의 파이프로 요청을 처리합니다.
+///
+/// 클라이언트가 연결할 때까지 기다리며, 연결이 종료되면 반환합니다. 함수를
+/// 호출하기 전에 [`loader`](super::loader)로 DLL을 불러와야 합니다.
+pub fn serve(pipe_name: &str, layout_tbl: &HashMap<String, Arc<TrLayout>>) -> io::Result<()> {
+    let mut reader = Pipe::accept(&req_pipe_name(pipe_name), PIPE_ACCESS_INBOUND)?;
+    let writer = Pipe::accept(&res_pipe_name(pipe_name), PIPE_ACCESS_OUTBOUND)?;
+
+    let mut server = Server {
+        backend: &DllBackend,
+        layout_tbl,
+        writer: Arc::new(Mutex::new(writer)),
+        real: None,
+        tr_codes: HashSet::new(),
+    };
+
+    loop {
+        let buf = match read_frame(&mut reader) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        let reply = server.handle(Request::decode(&buf)?);
+        write_frame(&mut *server.writer.lock().unwrap(), &reply.encode())?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::protocol::{Reply, Request};
+    use super::Server;
+    use crate::backend::MockBackend;
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+    use crate::os::testdata::t1102_layout;
+    use crate::{Error, Response};
+
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn test_server() {
+        let tr_layout = t1102_layout();
+        let layout_tbl: HashMap<_, _> = [("t1102".to_owned(), Arc::new(tr_layout))].into();
+
+        let backend = MockBackend::new(layout_tbl.clone());
+        backend
+            .push_response(
+                &Data {
+                    tr_code: "t1102".into(),
+                    data_type: DataType::Output,
+                    blocks: hashmap! {
+                        "t1102OutBlock" => Block::Block(hashmap! {
+                            "price" => "82000"
+                        })
+                    },
+                },
+                None,
+            )
+            .unwrap();
+
+        let mut server = Server {
+            backend: &backend,
+            layout_tbl: &layout_tbl,
+            writer: Arc::new(Mutex::new(Vec::<u8>::new())),
+            real: None,
+            tr_codes: HashSet::new(),
+        };
+
+        let query = |tr_code: &str| Request::Query {
+            data: Data {
+                tr_code: tr_code.into(),
+                data_type: DataType::Input,
+                blocks: hashmap! {
+                    "t1102InBlock" => Block::Block(hashmap! {
+                        "shcode" => "005930"
+                    })
+                },
+            },
+            next_key: None,
+            timeout: Duration::from_secs(1),
+        };
+
+        // 응답을 인코딩한 후 클라이언트와 같은 경로로 디코딩합니다.
+        let reply = server.handle(query("t1102"));
+        match Reply::decode(&reply.encode()).unwrap() {
+            Reply::Query { tr_code, res } => {
+                assert_eq!(tr_code, "t1102");
+                assert_eq!(res.code(), "00000");
+                assert_eq!(
+                    res.data().unwrap().blocks["t1102OutBlock"]["price"],
+                    *"82000"
+                );
+            }
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+
+        assert_eq!(backend.requests().len(), 1);
+
+        // 남은 응답이 없는 경우
+        assert!(matches!(
+            server.handle(query("t1102")),
+            Reply::Error(Error::TimedOut)
+        ));
+
+        // 레이아웃이 없는 경우
+        assert!(matches!(
+            server.handle(query("t1101")),
+            Reply::Error(Error::Encode(_))
+        ));
+
+        assert!(matches!(
+            server.handle(Request::IsConnected),
+            Reply::Connected(false)
+        ));
+    }
+}
@@ -0,0 +1,483 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// 브로커 프로세스와 주고받는 메시지
+//
+// 각 메시지는 리틀 엔디언 `u32` 길이 다음에 메시지 종류(1바이트)와 내용이
+// 이어지며, 데이터는 기록 파일과 같은 형식으로 인코딩합니다.
+
+use super::super::capture::{get_opt_str, put_opt_str};
+use super::super::recorder::{get_blocks, get_data, get_time, put_blocks, put_data, put_time};
+use super::super::{Account, Error, LoginResponse, QueryResponse, RealResponse};
+use crate::codec::{get_str, get_u32, get_u8, invalid_data, put_str, put_u32};
+use crate::data::{Data, DataType};
+
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+// 한 메시지의 최대 크기
+const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Request {
+    Connect {
+        addr: String,
+        port: u16,
+        timeout: Duration,
+    },
+    IsConnected,
+    Disconnect,
+    Login {
+        id: String,
+        pw: String,
+        cert_pw: String,
+        cert_err_dialog: bool,
+    },
+    Query {
+        data: Data,
+        next_key: Option<String>,
+        timeout: Duration,
+    },
+    Accounts,
+    Subscribe {
+        tr_code: String,
+        keys: Vec<String>,
+    },
+    Unsubscribe {
+        tr_code: String,
+        keys: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+pub(super) enum Reply {
+    Done,
+    Connected(bool),
+    Login(LoginResponse),
+    Query { tr_code: String, res: QueryResponse },
+    Accounts(Vec<Account>),
+    Error(Error),
+    // 요청과 관계없이 수신한 실시간 응답
+    Real(RealResponse),
+}
+
+pub(super) fn write_frame<W: Write + ?Sized>(writer: &mut W, buf: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(4 + buf.len());
+    put_u32(&mut frame, buf.len() as u32);
+    frame.extend_from_slice(buf);
+
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+pub(super) fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(invalid_data());
+    }
+
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn put_keys(buf: &mut Vec<u8>, keys: &[String]) {
+    put_u32(buf, keys.len() as u32);
+    for key in keys {
+        put_str(buf, key);
+    }
+}
+
+fn get_keys(buf: &mut &[u8]) -> io::Result<Vec<String>> {
+    let len = get_u32(buf)? as usize;
+    let mut keys = Vec::with_capacity(len.min(buf.len()));
+    for _ in 0..len {
+        keys.push(get_str(buf)?);
+    }
+
+    Ok(keys)
+}
+
+impl Request {
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::Connect {
+                addr,
+                port,
+                timeout,
+            } => {
+                buf.push(0);
+                put_str(&mut buf, addr);
+                put_u32(&mut buf, *port as u32);
+                put_u32(&mut buf, timeout.as_millis() as u32);
+            }
+            Self::IsConnected => buf.push(1),
+            Self::Disconnect => buf.push(2),
+            Self::Login {
+                id,
+                pw,
+                cert_pw,
+                cert_err_dialog,
+            } => {
+                buf.push(3);
+                put_str(&mut buf, id);
+                put_str(&mut buf, pw);
+                put_str(&mut buf, cert_pw);
+                buf.push(*cert_err_dialog as u8);
+            }
+            Self::Query {
+                data,
+                next_key,
+                timeout,
+            } => {
+                buf.push(4);
+                put_str(&mut buf, &data.tr_code);
+                put_blocks(&mut buf, &data.blocks);
+                put_opt_str(&mut buf, next_key.as_deref());
+                put_u32(&mut buf, timeout.as_millis() as u32);
+            }
+            Self::Accounts => buf.push(5),
+            Self::Subscribe { tr_code, keys } => {
+                buf.push(6);
+                put_str(&mut buf, tr_code);
+                put_keys(&mut buf, keys);
+            }
+            Self::Unsubscribe { tr_code, keys } => {
+                buf.push(7);
+                put_str(&mut buf, tr_code);
+                put_keys(&mut buf, keys);
+            }
+        }
+
+        buf
+    }
+
+    pub(super) fn decode(mut buf: &[u8]) -> io::Result<Self> {
+        let buf = &mut buf;
+        let req = match get_u8(buf)? {
+            0 => Self::Connect {
+                addr: get_str(buf)?,
+                port: get_u32(buf)?.try_into().map_err(|_| invalid_data())?,
+                timeout: Duration::from_millis(get_u32(buf)? as u64),
+            },
+            1 => Self::IsConnected,
+            2 => Self::Disconnect,
+            3 => Self::Login {
+                id: get_str(buf)?,
+                pw: get_str(buf)?,
+                cert_pw: get_str(buf)?,
+                cert_err_dialog: get_u8(buf)? != 0,
+            },
+            4 => Self::Query {
+                data: Data {
+                    tr_code: get_str(buf)?,
+                    data_type: DataType::Input,
+                    blocks: get_blocks(buf)?,
+                },
+                next_key: get_opt_str(buf)?,
+                timeout: Duration::from_millis(get_u32(buf)? as u64),
+            },
+            5 => Self::Accounts,
+            6 => Self::Subscribe {
+                tr_code: get_str(buf)?,
+                keys: get_keys(buf)?,
+            },
+            7 => Self::Unsubscribe {
+                tr_code: get_str(buf)?,
+                keys: get_keys(buf)?,
+            },
+            _ => return Err(invalid_data()),
+        };
+
+        Ok(req)
+    }
+}
+
+impl Reply {
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::Done => buf.push(0),
+            Self::Connected(connected) => {
+                buf.push(1);
+                buf.push(*connected as u8);
+            }
+            Self::Login(res) => {
+                buf.push(2);
+                put_str(&mut buf, &res.code);
+                put_str(&mut buf, &res.message);
+            }
+            Self::Query { tr_code, res } => {
+                buf.push(3);
+                put_str(&mut buf, tr_code);
+                put_str(&mut buf, &res.code);
+                put_str(&mut buf, &res.message);
+                put_u32(&mut buf, res.elapsed.as_millis() as u32);
+                put_opt_str(&mut buf, res.next_key.as_deref());
+                match &res.data {
+                    Some(data) => {
+                        buf.push(1);
+                        put_data(&mut buf, data.get());
+                    }
+                    None => buf.push(0),
+                }
+            }
+            Self::Accounts(accounts) => {
+                buf.push(4);
+                put_u32(&mut buf, accounts.len() as u32);
+                for account in accounts {
+                    put_str(&mut buf, &account.code);
+                    put_str(&mut buf, &account.name);
+                    put_str(&mut buf, &account.detailed_name);
+                    put_str(&mut buf, &account.nickname);
+                }
+            }
+            Self::Error(err) => {
+                buf.push(5);
+                put_error(&mut buf, err);
+            }
+            Self::Real(res) => {
+                buf.push(6);
+                put_time(&mut buf, res.received_at);
+                put_str(&mut buf, &res.tr_code);
+                put_str(&mut buf, &res.key);
+                put_data(&mut buf, &res.data);
+            }
+        }
+
+        buf
+    }
+
+    pub(super) fn decode(mut buf: &[u8]) -> io::Result<Self> {
+        let buf = &mut buf;
+        let reply = match get_u8(buf)? {
+            0 => Self::Done,
+            1 => Self::Connected(get_u8(buf)? != 0),
+            2 => Self::Login(LoginResponse {
+                code: get_str(buf)?,
+                message: get_str(buf)?,
+            }),
+            3 => {
+                let tr_code = get_str(buf)?;
+                let res = QueryResponse {
+                    code: get_str(buf)?,
+                    message: get_str(buf)?,
+                    elapsed: Duration::from_millis(get_u32(buf)? as u64),
+                    next_key: get_opt_str(buf)?,
+                    data: match get_u8(buf)? {
+                        0 => None,
+                        1 => Some(get_data(buf, &tr_code, DataType::Output)?.into()),
+                        _ => return Err(invalid_data()),
+                    },
+                };
+                Self::Query { tr_code, res }
+            }
+            4 => {
+                let len = get_u32(buf)? as usize;
+                let mut accounts = Vec::with_capacity(len.min(buf.len()));
+                for _ in 0..len {
+                    accounts.push(Account {
+                        code: get_str(buf)?,
+                        name: get_str(buf)?,
+                        detailed_name: get_str(buf)?,
+                        nickname: get_str(buf)?,
+                    });
+                }
+                Self::Accounts(accounts)
+            }
+            5 => Self::Error(get_error(buf)?),
+            6 => {
+                let received_at = get_time(buf)?;
+                let tr_code = get_str(buf)?;
+                let key = get_str(buf)?;
+                let data = get_data(buf, &tr_code, DataType::Output)?;
+                Self::Real(RealResponse {
+                    tr_code,
+                    key,
+                    data,
+                    received_at,
+                })
+            }
+            _ => return Err(invalid_data()),
+        };
+
+        Ok(reply)
+    }
+}
+
+// 헬퍼 프로세스에서 발생한 에러를 씁니다. 응답 코드가 있는 에러를 제외하면
+// 메시지만 전달합니다.
+fn put_error(buf: &mut Vec<u8>, err: &Error) {
+    match err {
+        Error::XingApi { code, message } => {
+            buf.push(0);
+            put_u32(buf, *code as u32);
+            put_str(buf, message);
+        }
+        Error::Rejected { code, message } => {
+            buf.push(1);
+            put_str(buf, code);
+            put_str(buf, message);
+        }
+        Error::TimedOut => buf.push(2),
+        Error::Busy => buf.push(4),
+        err => {
+            buf.push(3);
+            put_str(buf, &err.to_string());
+        }
+    }
+}
+
+fn get_error(buf: &mut &[u8]) -> io::Result<Error> {
+    let err = match get_u8(buf)? {
+        0 => Error::XingApi {
+            code: get_u32(buf)? as i32,
+            message: get_str(buf)?,
+        },
+        1 => Error::Rejected {
+            code: get_str(buf)?,
+            message: get_str(buf)?,
+        },
+        2 => Error::TimedOut,
+        3 => Error::Ipc(io::Error::other(get_str(buf)?)),
+        4 => Error::Busy,
+        _ => return Err(invalid_data()),
+    };
+
+    Ok(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_frame, write_frame, Reply, Request};
+    use crate::data::{Block, Data, DataType, DecodeError};
+    use crate::hashmap;
+    use crate::{Error, QueryResponse, RealResponse};
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_request() {
+        let reqs = vec![
+            Request::Connect {
+                addr: "demo.ebestsec.co.kr".into(),
+                port: 20001,
+                timeout: Duration::from_secs(10),
+            },
+            Request::Login {
+                id: "id".into(),
+                pw: "pw".into(),
+                cert_pw: String::new(),
+                cert_err_dialog: true,
+            },
+            Request::Query {
+                data: Data {
+                    tr_code: "t1101".into(),
+                    data_type: DataType::Input,
+                    blocks: hashmap! {
+                        "t1101InBlock" => Block::Block(hashmap! {
+                            "shcode" => "078020"
+                        })
+                    },
+                },
+                next_key: Some("1".into()),
+                timeout: Duration::from_secs(30),
+            },
+            Request::Subscribe {
+                tr_code: "S3_".into(),
+                keys: vec!["005930".into(), "000660".into()],
+            },
+        ];
+
+        let mut pipe = Vec::new();
+        for req in &reqs {
+            write_frame(&mut pipe, &req.encode()).unwrap();
+        }
+
+        let mut reader = pipe.as_slice();
+        for req in &reqs {
+            assert_eq!(
+                Request::decode(&read_frame(&mut reader).unwrap()).unwrap(),
+                *req
+            );
+        }
+        assert!(read_frame(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_reply() {
+        let data = Data {
+            tr_code: "t1101".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1101OutBlock" => Block::Block(hashmap! {
+                    "hname" => "이베스트투자증권"
+                })
+            },
+        };
+
+        let reply = Reply::Query {
+            tr_code: "t1101".into(),
+            res: QueryResponse {
+                code: "00000".into(),
+                message: "조회완료".into(),
+                elapsed: Duration::from_millis(12),
+                next_key: None,
+                data: Some(Ok(data.clone()).into()),
+            },
+        };
+        match Reply::decode(&reply.encode()).unwrap() {
+            Reply::Query { tr_code, res } => {
+                assert_eq!(tr_code, "t1101");
+                assert_eq!(res.code, "00000");
+                assert_eq!(res.message, "조회완료");
+                assert_eq!(res.elapsed, Duration::from_millis(12));
+                assert_eq!(res.next_key, None);
+                assert_eq!(res.data().unwrap(), &data);
+            }
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+
+        let reply = Reply::Real(RealResponse {
+            tr_code: "S3_".into(),
+            key: "005930".into(),
+            data: Err(DecodeError::MissingBlock("OutBlock".into())),
+            received_at: UNIX_EPOCH + Duration::from_secs(1610325000),
+        });
+        match Reply::decode(&reply.encode()).unwrap() {
+            Reply::Real(res) => {
+                assert_eq!(res.tr_code, "S3_");
+                assert_eq!(res.key, "005930");
+                assert!(
+                    matches!(res.data, Err(DecodeError::MissingBlock(name)) if name == "OutBlock")
+                );
+                assert_eq!(
+                    res.received_at,
+                    UNIX_EPOCH + Duration::from_secs(1610325000)
+                );
+            }
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+
+        let reply = Reply::Error(Error::XingApi {
+            code: -21,
+            message: "TR 전송 제한".into(),
+        });
+        match Reply::decode(&reply.encode()).unwrap() {
+            Reply::Error(Error::XingApi { code, message }) => {
+                assert_eq!(code, -21);
+                assert_eq!(message, "TR 전송 제한");
+            }
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+
+        let reply = Reply::Error(Error::Busy);
+        assert!(matches!(
+            Reply::decode(&reply.encode()).unwrap(),
+            Reply::Error(Error::Busy)
+        ));
+    }
+}
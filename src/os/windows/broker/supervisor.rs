@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::super::backend::Backend;
+use super::super::replay::RealSource;
+use super::super::Subscriptions;
+use super::super::{Account, Error, LoginResponse, QueryResponse, RealResponse, Response};
+use super::BrokerBackend;
+use crate::data::Data;
+use crate::layout::TrLayout;
+
+use crossbeam_channel::{RecvTimeoutError, Sender};
+use std::ffi::OsString;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// 헬퍼 프로세스의 상태를 확인하는 주기
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// 재시작에 실패한 경우 다시 시도할 때까지 기다리는 최대 시간
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+// 재시작 후 복원할 세션 상태
+#[derive(Default)]
+struct Session {
+    connect: Option<(String, u16, Duration)>,
+    login: Option<(String, String, String)>,
+    subscriptions: Subscriptions,
+}
+
+struct Shared {
+    exe_path: OsString,
+    res_dir: Option<PathBuf>,
+    backend: RwLock<Arc<BrokerBackend>>,
+    session: Mutex<Session>,
+    restarts: AtomicUsize,
+}
+
+impl Shared {
+    fn current(&self) -> Arc<BrokerBackend> {
+        self.backend.read().unwrap().clone()
+    }
+
+    // 헬퍼 프로세스를 다시 실행하고 세션을 복원합니다.
+    //
+    // 다른 스레드가 이미 재시작한 경우 아무것도 하지 않습니다.
+    fn restart(&self, failed: &Arc<BrokerBackend>) -> Result<(), Error> {
+        let session = self.session.lock().unwrap();
+        if !Arc::ptr_eq(failed, &self.current()) {
+            return Ok(());
+        }
+
+        let backend =
+            BrokerBackend::spawn(&self.exe_path, self.res_dir.as_deref()).map_err(Error::Ipc)?;
+
+        if let Some((addr, port, timeout)) = &session.connect {
+            backend.connect(addr, *port, *timeout)?;
+
+            if let Some((id, pw, cert_pw)) = &session.login {
+                let res = backend.login(id, pw, cert_pw, false)?;
+                if !res.is_ok() {
+                    return Err(Error::Rejected {
+                        code: res.code,
+                        message: res.message,
+                    });
+                }
+            }
+        }
+
+        for (tr_code, keys) in session.subscriptions.iter() {
+            let keys: Vec<_> = keys.iter().collect();
+            backend.subscribe(tr_code, &keys)?;
+        }
+
+        *self.backend.write().unwrap() = Arc::new(backend);
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    // 헬퍼 프로세스와 통신하지 못한 경우 재시작한 후 에러를 그대로
+    // 반환합니다. 요청이 서버에 전달되었는지 알 수 없으므로 다시 요청하지
+    // 않습니다.
+    fn call<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&BrokerBackend) -> Result<T, Error>,
+    {
+        let backend = self.current();
+        let result = f(&backend);
+
+        if let Err(Error::Ipc(_)) = &result {
+            let _ = self.restart(&backend);
+        }
+
+        result
+    }
+}
+
+/// 헬퍼 프로세스에서 DLL을 사용하며 비정상 종료 시 자동으로 재시작하는 백엔드
+///
+/// DLL 내부에서 발생한 크래시가 애플리케이션을 종료시키지 않도록
+/// [`BrokerBackend`]를 감독합니다. 헬퍼 프로세스가 종료된 경우 다시 실행한
+/// 후 서버 연결, 로그인, 실시간 TR 등록을 복원합니다.
+///
+/// 헬퍼 프로세스가 종료되는 동안 처리 중이던 요청은 `Error::Ipc`를 반환하며
+/// 다시 요청하지 않습니다. 또한 종료 전에 수신하지 못한 실시간 응답은
+/// 유실됩니다.
+pub struct SupervisedBackend {
+    shared: Arc<Shared>,
+    tx_quit: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SupervisedBackend {
+    /// 헬퍼 프로세스를 실행하고 감독을 시작합니다.
+    ///
+    /// 인자는 [`BrokerBackend::spawn()`]과 같습니다.
+    pub fn spawn<P: Into<OsString>>(exe_path: P, res_dir: Option<PathBuf>) -> io::Result<Self> {
+        let exe_path = exe_path.into();
+        let backend = BrokerBackend::spawn(&exe_path, res_dir.as_deref())?;
+
+        let shared = Arc::new(Shared {
+            exe_path,
+            res_dir,
+            backend: RwLock::new(Arc::new(backend)),
+            session: Mutex::new(Session::default()),
+            restarts: AtomicUsize::new(0),
+        });
+
+        let (tx_quit, rx_quit) = crossbeam_channel::bounded(1);
+
+        let thread = {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let mut backoff = CHECK_INTERVAL;
+
+                // 종료 요청을 받거나 객체가 소멸될 때까지 반복합니다.
+                while let Err(RecvTimeoutError::Timeout) = rx_quit.recv_timeout(backoff) {
+                    let backend = shared.current();
+                    if backend.is_alive() {
+                        continue;
+                    }
+
+                    backoff = match shared.restart(&backend) {
+                        Ok(()) => CHECK_INTERVAL,
+                        Err(_) => (backoff * 2).min(MAX_BACKOFF),
+                    };
+                }
+            })
+        };
+
+        Ok(Self {
+            shared,
+            tx_quit,
+            thread: Some(thread),
+        })
+    }
+
+    /// 헬퍼 프로세스를 재시작한 횟수를 반환합니다.
+    pub fn restarts(&self) -> usize {
+        self.shared.restarts.load(Ordering::Relaxed)
+    }
+
+    /// 재시작 후 복원할 실시간 TR 목록을 반환합니다.
+    pub fn subscriptions(&self) -> Subscriptions {
+        self.shared.session.lock().unwrap().subscriptions.clone()
+    }
+
+    /// 실시간 TR을 등록합니다.
+    pub fn subscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) -> Result<(), Error> {
+        self.shared
+            .session
+            .lock()
+            .unwrap()
+            .subscriptions
+            .insert(tr_code, keys);
+
+        self.shared.call(|backend| backend.subscribe(tr_code, keys))
+    }
+
+    /// 실시간 TR을 등록 해제합니다.
+    pub fn unsubscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) -> Result<(), Error> {
+        self.shared
+            .session
+            .lock()
+            .unwrap()
+            .subscriptions
+            .remove(tr_code, keys);
+
+        self.shared
+            .call(|backend| backend.unsubscribe(tr_code, keys))
+    }
+}
+
+impl Drop for SupervisedBackend {
+    fn drop(&mut self) {
+        let _ = self.tx_quit.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Backend for SupervisedBackend {
+    fn connect(&self, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
+        self.shared
+            .call(|backend| backend.connect(addr, port, timeout))?;
+
+        self.shared.session.lock().unwrap().connect = Some((addr.to_owned(), port, timeout));
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.shared.current().is_connected()
+    }
+
+    fn disconnect(&self) {
+        {
+            let mut session = self.shared.session.lock().unwrap();
+            session.connect = None;
+            session.login = None;
+        }
+
+        self.shared.current().disconnect();
+    }
+
+    fn login(
+        &self,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<LoginResponse, Error> {
+        let res = self
+            .shared
+            .call(|backend| backend.login(id, pw, cert_pw, cert_err_dialog))?;
+
+        if res.is_ok() {
+            self.shared.session.lock().unwrap().login =
+                Some((id.to_owned(), pw.to_owned(), cert_pw.to_owned()));
+        }
+
+        Ok(res)
+    }
+
+    fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        self.shared
+            .call(|backend| backend.request(data, tr_layout, next_key, timeout))
+    }
+
+    fn accounts(&self) -> Vec<Account> {
+        self.shared.current().accounts()
+    }
+}
+
+impl RealSource for SupervisedBackend {
+    fn try_recv(&self) -> Option<RealResponse> {
+        self.shared.current().try_recv()
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.shared.current().recv_timeout(timeout)
+    }
+}
@@ -0,0 +1,503 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! C ABI 모듈
+//!
+//! Rust 이외의 언어에서 이 크레이트를 사용할 수 있도록 `extern "C"` 함수를
+//! 제공합니다. 동적 라이브러리는 다음과 같이 빌드할 수 있습니다.
+//!
+//! ```text
+//! cargo rustc --release --features capi --crate-type cdylib
+//! ```
+//!
+//! 모든 문자열은 NUL로 끝나는 UTF-8 문자열입니다. 라이브러리가 반환한
+//! 문자열은 [`xingapi_free_string()`]으로 해제해야 하며, 실패한 함수의 에러
+//! 메시지는 같은 스레드에서 [`xingapi_last_error()`]로 가져올 수 있습니다.
+//! 함수 내부에서 패닉이 발생한 경우 호출자의 프로세스를 종료하지 않고
+//! 실패로 반환합니다.
+//!
+//! 조회 TR의 응답과 실시간 TR의 데이터는 다음과 같은 JSON 객체로
+//! 전달됩니다.
+//!
+//! ```text
+//! {"tr_code":"t1102","data_type":"output","blocks":{"t1102OutBlock":{"price":"91000"}}}
+//! ```
+
+use super::{RealEvent, Response};
+use crate::data::{self, json};
+use crate::layout::{self, TrLayout};
+
+use lazy_static::lazy_static;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// 정상 처리
+pub const XINGAPI_OK: c_int = 0;
+/// 잘못된 인자
+pub const XINGAPI_ERR_INVALID_ARG: c_int = -1;
+/// DLL 또는 레이아웃을 불러오지 못함
+pub const XINGAPI_ERR_LOAD: c_int = -2;
+/// XingAPI 함수 또는 요청이 실패함
+pub const XINGAPI_ERR_REQUEST: c_int = -3;
+/// 서버가 요청을 정상적으로 처리하지 못함
+pub const XINGAPI_ERR_REJECTED: c_int = -4;
+/// 내부에서 패닉이 발생함
+pub const XINGAPI_ERR_PANIC: c_int = -5;
+
+/// 실시간 TR 데이터를 수신했을 때 호출되는 콜백
+///
+/// `tr_code`, `key`, `json`은 콜백이 반환된 후 해제됩니다. 디코딩에 실패한
+/// 경우 `json`은 null입니다. 콜백은 내부 스레드에서 호출됩니다.
+pub type XingApiRealCallback = extern "C" fn(
+    user_data: *mut c_void,
+    tr_code: *const c_char,
+    key: *const c_char,
+    json: *const c_char,
+);
+
+/// 실시간 TR을 등록하는 객체
+pub struct XingApiReal {
+    event: RealEvent,
+    callback: XingApiRealCallback,
+    user_data: UserData,
+}
+
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+
+// 콜백에 그대로 전달할 뿐이며, 스레드 안전성은 호출자가 보장합니다.
+unsafe impl Send for UserData {}
+
+lazy_static! {
+    static ref LAYOUT_TBL: RwLock<HashMap<String, Arc<TrLayout>>> = RwLock::new(HashMap::new());
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error<T: ToString>(err: T) {
+    let message = CString::new(err.to_string().replace('\0', "")).unwrap();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn to_c_string(string: String) -> *mut c_char {
+    CString::new(string.replace('\0', "")).unwrap().into_raw()
+}
+
+// null인 경우 `None`을 반환합니다.
+unsafe fn opt_str<'a>(ptr: *const c_char) -> Result<Option<&'a str>, c_int> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+
+    CStr::from_ptr(ptr).to_str().map(Some).map_err(|err| {
+        set_last_error(err);
+        XINGAPI_ERR_INVALID_ARG
+    })
+}
+
+unsafe fn req_str<'a>(ptr: *const c_char) -> Result<&'a str, c_int> {
+    opt_str(ptr)?.ok_or_else(null_ptr)
+}
+
+unsafe fn str_array<'a>(ptr: *const *const c_char, len: usize) -> Result<Vec<&'a str>, c_int> {
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    if ptr.is_null() {
+        return Err(null_ptr());
+    }
+
+    std::slice::from_raw_parts(ptr, len)
+        .iter()
+        .map(|&ptr| req_str(ptr))
+        .collect()
+}
+
+// 패닉이 C 코드로 전파되지 않도록 함수를 실행하며, 패닉이 발생한 경우
+// `on_panic`을 반환합니다.
+fn catch_panic<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
+        set_last_error(format!("panic: {}", message));
+        on_panic
+    })
+}
+
+fn status(f: impl FnOnce() -> Result<(), c_int>) -> c_int {
+    catch_panic(XINGAPI_ERR_PANIC, || f().err().unwrap_or(XINGAPI_OK))
+}
+
+fn null_ptr() -> c_int {
+    set_last_error("null pointer");
+    XINGAPI_ERR_INVALID_ARG
+}
+
+/// 마지막으로 실패한 함수의 에러 메시지를 반환합니다.
+///
+/// 에러가 없는 경우 null을 반환합니다. 반환된 문자열은 같은 스레드에서 다른
+/// 함수를 호출하기 전까지 유효합니다.
+#[no_mangle]
+pub extern "C" fn xingapi_last_error() -> *const c_char {
+    catch_panic(std::ptr::null(), || {
+        LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map_or(std::ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// 라이브러리가 반환한 문자열을 해제합니다.
+///
+/// # Safety
+/// `string`은 null이거나 이 라이브러리가 반환한 문자열이어야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_free_string(string: *mut c_char) {
+    catch_panic((), || {
+        if !string.is_null() {
+            drop(CString::from_raw(string));
+        }
+    })
+}
+
+/// XingAPI DLL과 TR 레이아웃을 불러옵니다.
+///
+/// `dll_path`와 `res_dir`이 null인 경우 XingAPI SDK의 기본 설치 경로를
+/// 사용합니다.
+///
+/// # Safety
+/// 인자는 null이거나 유효한 문자열이어야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_load(dll_path: *const c_char, res_dir: *const c_char) -> c_int {
+    status(|| {
+        let layout_tbl = match opt_str(res_dir)? {
+            Some(res_dir) => layout::load_dir(res_dir),
+            None => layout::load(),
+        }
+        .map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_LOAD
+        })?;
+
+        match opt_str(dll_path)? {
+            Some(dll_path) => super::loader::load_with_path(&Path::new(dll_path)),
+            None => super::loader::load(),
+        }
+        .map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_LOAD
+        })?;
+
+        *LAYOUT_TBL.write().unwrap() = layout_tbl;
+        Ok(())
+    })
+}
+
+/// XingAPI DLL을 언로드합니다.
+#[no_mangle]
+pub extern "C" fn xingapi_unload() {
+    catch_panic((), super::loader::unload)
+}
+
+/// 서버에 연결합니다.
+///
+/// # Safety
+/// `addr`는 유효한 문자열이어야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_connect(addr: *const c_char, port: u16, timeout_ms: u32) -> c_int {
+    status(|| {
+        super::connect(
+            req_str(addr)?,
+            port,
+            Duration::from_millis(timeout_ms as u64),
+        )
+        .map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_REQUEST
+        })
+    })
+}
+
+/// 서버 연결 여부를 반환합니다.
+#[no_mangle]
+pub extern "C" fn xingapi_is_connected() -> c_int {
+    catch_panic(0, || super::is_connected() as c_int)
+}
+
+/// 서버와의 연결을 종료합니다.
+#[no_mangle]
+pub extern "C" fn xingapi_disconnect() {
+    catch_panic((), super::disconnect)
+}
+
+/// 서버에 로그인 요청을 합니다.
+///
+/// 서버가 로그인을 거부한 경우 `XINGAPI_ERR_REJECTED`를 반환하며, 응답
+/// 메시지는 [`xingapi_last_error()`]로 가져올 수 있습니다.
+///
+/// # Safety
+/// `id`와 `pw`는 유효한 문자열이어야 하며, `cert_pw`는 null일 수 있습니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_login(
+    id: *const c_char,
+    pw: *const c_char,
+    cert_pw: *const c_char,
+) -> c_int {
+    status(|| {
+        let res = super::login(
+            req_str(id)?,
+            req_str(pw)?,
+            opt_str(cert_pw)?.unwrap_or(""),
+            false,
+        )
+        .map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_REQUEST
+        })?;
+
+        if res.is_ok() {
+            Ok(())
+        } else {
+            set_last_error(format!("[{}] {}", res.code(), res.message()));
+            Err(XINGAPI_ERR_REJECTED)
+        }
+    })
+}
+
+/// 서버에 조회 TR 요청을 하고 응답을 JSON 문자열로 반환합니다.
+///
+/// 요청 필드는 `블록 이름.필드 이름` 형식의 `keys`와 같은 길이의 `values`로
+/// 지정하며, 지정하지 않은 필드는 빈 문자열로 요청합니다. 응답은 다음과
+/// 같은 형식이며 `data`는 응답 데이터가 없는 경우 null입니다.
+///
+/// ```text
+/// {"code":"00000","message":"...","elapsed_ms":12,"next_key":null,"data":{...}}
+/// ```
+///
+/// 실패한 경우 null을 반환합니다. 반환된 문자열은
+/// [`xingapi_free_string()`]으로 해제해야 합니다.
+///
+/// # Safety
+/// `keys`와 `values`는 `len` 길이의 유효한 문자열 배열이어야 하며,
+/// `next_key`는 null일 수 있습니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_request(
+    tr_code: *const c_char,
+    keys: *const *const c_char,
+    values: *const *const c_char,
+    len: usize,
+    next_key: *const c_char,
+    timeout_ms: u32,
+) -> *mut c_char {
+    let result = || -> Result<String, c_int> {
+        let tr_code = req_str(tr_code)?;
+        let keys = str_array(keys, len)?;
+        let values = str_array(values, len)?;
+        let next_key = opt_str(next_key)?;
+
+        // 요청하는 동안 레이아웃 테이블을 잠그지 않도록 레이아웃을 복제합니다.
+        let tr_layout = LAYOUT_TBL.read().unwrap().get(tr_code).cloned();
+        let tr_layout = tr_layout.ok_or_else(|| {
+            set_last_error(format!("unknown tr code: {}", tr_code));
+            XINGAPI_ERR_INVALID_ARG
+        })?;
+
+        let mut data = data::empty_input(&tr_layout);
+        for (key, value) in keys.into_iter().zip(values) {
+            let (block_name, field_name) = key.split_once('.').ok_or_else(|| {
+                set_last_error(format!("invalid field key: {}", key));
+                XINGAPI_ERR_INVALID_ARG
+            })?;
+
+            data::set_field(&mut data, block_name, field_name, value).map_err(|err| {
+                set_last_error(err);
+                XINGAPI_ERR_INVALID_ARG
+            })?;
+        }
+
+        let res = super::request(
+            &data,
+            tr_layout,
+            next_key,
+            Duration::from_millis(timeout_ms as u64),
+        )
+        .map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_REQUEST
+        })?;
+
+        let mut out = String::from("{");
+        res.write_json_members(&mut out).map_err(|err| {
+            set_last_error(err);
+            XINGAPI_ERR_REQUEST
+        })?;
+        out.push('}');
+
+        Ok(out)
+    };
+
+    catch_panic(std::ptr::null_mut(), || {
+        result().map_or(std::ptr::null_mut(), to_c_string)
+    })
+}
+
+/// 실시간 TR을 수신하는 객체를 생성합니다.
+///
+/// 실패한 경우 null을 반환합니다. 생성된 객체는 [`xingapi_real_free()`]로
+/// 해제해야 합니다.
+#[no_mangle]
+pub extern "C" fn xingapi_real_new(
+    callback: XingApiRealCallback,
+    user_data: *mut c_void,
+) -> *mut XingApiReal {
+    catch_panic(std::ptr::null_mut(), || match RealEvent::new() {
+        Ok(event) => Box::into_raw(Box::new(XingApiReal {
+            event,
+            callback,
+            user_data: UserData(user_data),
+        })),
+        Err(err) => {
+            set_last_error(err);
+            std::ptr::null_mut()
+        }
+    })
+}
+
+/// 실시간 TR을 등록합니다.
+///
+/// 처음 등록하는 TR 코드인 경우 콜백이 연결됩니다.
+///
+/// # Safety
+/// `real`은 [`xingapi_real_new()`]가 반환한 객체여야 하며, `keys`는 `len`
+/// 길이의 유효한 문자열 배열이어야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_real_subscribe(
+    real: *mut XingApiReal,
+    tr_code: *const c_char,
+    keys: *const *const c_char,
+    len: usize,
+) -> c_int {
+    status(|| {
+        let real = real.as_ref().ok_or_else(null_ptr)?;
+        let tr_code = req_str(tr_code)?;
+        let keys = str_array(keys, len)?;
+
+        if real.event.current().get(tr_code).is_none() {
+            let tr_layout = LAYOUT_TBL.read().unwrap().get(tr_code).cloned();
+            let tr_layout = tr_layout.ok_or_else(|| {
+                set_last_error(format!("unknown tr code: {}", tr_code));
+                XINGAPI_ERR_INVALID_ARG
+            })?;
+
+            real.event.insert_layout(tr_layout);
+            real.event.off(tr_code);
+
+            let callback = real.callback;
+            let user_data = real.user_data;
+            real.event.on(tr_code, move |res| {
+                let user_data = user_data;
+                let tr_code = CString::new(res.tr_code()).unwrap_or_default();
+                let key = CString::new(res.key()).unwrap_or_default();
+                let json = res
+                    .data()
+                    .ok()
+                    .and_then(|data| CString::new(json::to_json(data)).ok());
+
+                callback(
+                    user_data.0,
+                    tr_code.as_ptr(),
+                    key.as_ptr(),
+                    json.as_ref().map_or(std::ptr::null(), |json| json.as_ptr()),
+                );
+            });
+        }
+
+        real.event.subscribe(tr_code, &keys);
+        Ok(())
+    })
+}
+
+/// 실시간 TR을 등록 해제합니다.
+///
+/// # Safety
+/// `real`은 [`xingapi_real_new()`]가 반환한 객체여야 하며, `keys`는 `len`
+/// 길이의 유효한 문자열 배열이어야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_real_unsubscribe(
+    real: *mut XingApiReal,
+    tr_code: *const c_char,
+    keys: *const *const c_char,
+    len: usize,
+) -> c_int {
+    status(|| {
+        let real = real.as_ref().ok_or_else(null_ptr)?;
+        real.event
+            .unsubscribe(req_str(tr_code)?, &str_array(keys, len)?);
+        Ok(())
+    })
+}
+
+/// 객체를 해제하고 등록된 실시간 TR을 모두 등록 해제합니다.
+///
+/// # Safety
+/// `real`은 null이거나 [`xingapi_real_new()`]가 반환한 객체여야 합니다.
+#[no_mangle]
+pub unsafe extern "C" fn xingapi_real_free(real: *mut XingApiReal) {
+    catch_panic((), || {
+        if !real.is_null() {
+            drop(Box::from_raw(real));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        catch_panic, xingapi_last_error, xingapi_real_subscribe, xingapi_real_unsubscribe,
+        XINGAPI_ERR_INVALID_ARG, XINGAPI_ERR_PANIC,
+    };
+
+    use std::ffi::{CStr, CString};
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(xingapi_last_error()) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(
+            catch_panic(XINGAPI_ERR_PANIC, || panic!("boom")),
+            XINGAPI_ERR_PANIC
+        );
+        assert_eq!(last_error(), "panic: boom");
+    }
+
+    #[test]
+    fn test_null_real() {
+        let tr_code = CString::new("S3_").unwrap();
+
+        let code = unsafe {
+            xingapi_real_subscribe(std::ptr::null_mut(), tr_code.as_ptr(), std::ptr::null(), 0)
+        };
+        assert_eq!(code, XINGAPI_ERR_INVALID_ARG);
+        assert_eq!(last_error(), "null pointer");
+
+        let code = unsafe {
+            xingapi_real_unsubscribe(std::ptr::null_mut(), tr_code.as_ptr(), std::ptr::null(), 0)
+        };
+        assert_eq!(code, XINGAPI_ERR_INVALID_ARG);
+    }
+}
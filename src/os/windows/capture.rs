@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 조회 TR 기록 및 재생 모듈
+//!
+//! 조회 TR의 요청 데이터와 서버에서 수신한 디코딩 전의 원본 데이터를 파일에
+//! 기록하고, 이후 기록된 원본 데이터를 같은 디코딩 경로로 재생합니다. 실제
+//! 서버의 응답으로 디코더를 시험하는 데 사용할 수 있습니다.
+//!
+//! [`start()`]로 기록을 시작하면 이후 [`request()`](super::request)와
+//! [`request_service()`](super::request_service)로 수신한 응답이 모두
+//! 기록됩니다.
+//!
+//! ## 파일 형식
+//! 파일은 `XQRY`와 버전(1바이트)으로 시작하며, 각 레코드는 실시간 기록
+//! 파일과 같이 리틀 엔디언 `u32` 길이 다음에 레코드 내용이 이어집니다.
+
+use super::recorder::{get_blocks, get_time, put_blocks, put_time};
+use super::{session, QueryResponse};
+use crate::codec::{get_str, get_u32, get_u8, invalid_data, put_str, put_u32, take};
+use crate::data::{self, Data, DataType, DecodeError, RawData};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const MAGIC: &[u8; 4] = b"XQRY";
+const VERSION: u8 = 1;
+
+/// 기록된 조회 TR 요청과 응답
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryRecord {
+    /// 응답을 수신한 시각
+    pub time: SystemTime,
+    /// 요청 데이터
+    pub request: Data,
+    /// 요청에 사용한 연속 조회 키
+    pub next_key: Option<String>,
+    /// 응답 코드
+    pub code: String,
+    /// 응답 메시지
+    pub message: String,
+    /// 서버 요청 후 응답까지 소요된 시간
+    pub elapsed: Duration,
+    /// 응답의 연속 조회 키
+    pub res_next_key: Option<String>,
+    pub(crate) raw_data: Option<RawData>,
+}
+
+impl QueryRecord {
+    /// 기록된 원본 데이터를 지정된 레이아웃으로 디코딩하여 응답을
+    /// 재구성합니다.
+    pub fn replay(&self, tr_layout: &TrLayout) -> QueryResponse {
+        let data = self.raw_data.clone().map(|raw_data| {
+            let block_mode = matches!(raw_data, RawData::Block(_));
+            if tr_layout.code != self.request.tr_code || tr_layout.block_mode != block_mode {
+                return Err(DecodeError::UnknownLayout(self.request.tr_code.clone()));
+            }
+
+            data::decode(tr_layout, raw_data)
+        });
+
+        QueryResponse {
+            code: self.code.clone(),
+            message: self.message.clone(),
+            elapsed: self.elapsed,
+            next_key: self.res_next_key.clone(),
+            data: data.map(Into::into),
+        }
+    }
+}
+
+/// 조회 TR 기록 파일을 쓰는 객체
+pub struct QueryWriter {
+    writer: BufWriter<File>,
+}
+
+impl QueryWriter {
+    /// 파일을 생성하고 헤더를 씁니다.
+    ///
+    /// 파일이 이미 존재하는 경우 덮어씁니다.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        Ok(Self { writer })
+    }
+
+    /// 레코드를 기록합니다.
+    pub fn write(&mut self, record: &QueryRecord) -> io::Result<()> {
+        let mut buf = Vec::new();
+        encode_record(&mut buf, record);
+
+        self.writer.write_all(&(buf.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&buf)
+    }
+
+    /// 버퍼에 남아있는 기록을 파일에 씁니다.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// 조회 TR 기록 파일을 읽는 객체
+///
+/// 레코드를 순서대로 반환하는 반복자입니다.
+pub struct QueryReader<R> {
+    reader: R,
+}
+
+impl QueryReader<BufReader<File>> {
+    /// 기록된 파일을 엽니다.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> QueryReader<R> {
+    /// 파일 헤더를 확인하고 객체를 생성합니다.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; 5];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(invalid_data());
+        }
+
+        Ok(Self { reader })
+    }
+
+    /// 다음 레코드를 읽습니다.
+    ///
+    /// 파일의 끝에 도달한 경우 `None`을 반환합니다.
+    pub fn read_record(&mut self) -> io::Result<Option<QueryRecord>> {
+        let mut len = [0; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        let mut buf = vec![0; u32::from_le_bytes(len) as usize];
+        self.reader.read_exact(&mut buf)?;
+
+        decode_record(&mut buf.as_slice()).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for QueryReader<R> {
+    type Item = io::Result<QueryRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// 지정된 파일에 조회 TR 기록을 시작합니다.
+///
+/// 이미 기록 중인 경우 이전 파일의 기록은 중단됩니다.
+pub fn start<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let writer = QueryWriter::create(path)?;
+    if let Some(mut prev) = session::global().set_capture(Some(writer)) {
+        prev.flush()?;
+    }
+
+    Ok(())
+}
+
+/// 조회 TR 기록을 중단하고 버퍼에 남아있는 기록을 파일에 씁니다.
+pub fn stop() -> io::Result<()> {
+    match session::global().set_capture(None) {
+        Some(mut writer) => writer.flush(),
+        None => Ok(()),
+    }
+}
+
+pub(super) fn put_opt_str(buf: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            buf.push(1);
+            put_str(buf, value);
+        }
+        None => buf.push(0),
+    }
+}
+
+fn put_bytes(buf: &mut Vec<u8>, value: &[u8]) {
+    put_u32(buf, value.len() as u32);
+    buf.extend_from_slice(value);
+}
+
+fn encode_record(buf: &mut Vec<u8>, record: &QueryRecord) {
+    put_time(buf, record.time);
+    put_str(buf, &record.request.tr_code);
+    put_blocks(buf, &record.request.blocks);
+    put_opt_str(buf, record.next_key.as_deref());
+    put_str(buf, &record.code);
+    put_str(buf, &record.message);
+    put_u32(buf, record.elapsed.as_millis() as u32);
+    put_opt_str(buf, record.res_next_key.as_deref());
+
+    match &record.raw_data {
+        None => buf.push(0),
+        Some(RawData::Block(block_tbl)) => {
+            buf.push(1);
+            put_u32(buf, block_tbl.len() as u32);
+            for (block_name, raw_block) in block_tbl {
+                put_str(buf, block_name);
+                put_bytes(buf, raw_block);
+            }
+        }
+        Some(RawData::NonBlock(raw_data)) => {
+            buf.push(2);
+            put_bytes(buf, raw_data);
+        }
+    }
+}
+
+pub(super) fn get_opt_str(buf: &mut &[u8]) -> io::Result<Option<String>> {
+    match get_u8(buf)? {
+        0 => Ok(None),
+        1 => get_str(buf).map(Some),
+        _ => Err(invalid_data()),
+    }
+}
+
+fn get_bytes(buf: &mut &[u8]) -> io::Result<Vec<u8>> {
+    let len = get_u32(buf)? as usize;
+    Ok(take(buf, len)?.to_owned())
+}
+
+fn decode_record(buf: &mut &[u8]) -> io::Result<QueryRecord> {
+    let time = get_time(buf)?;
+    let request = Data {
+        tr_code: get_str(buf)?,
+        data_type: DataType::Input,
+        blocks: get_blocks(buf)?,
+    };
+    let next_key = get_opt_str(buf)?;
+    let code = get_str(buf)?;
+    let message = get_str(buf)?;
+    let elapsed = Duration::from_millis(get_u32(buf)? as u64);
+    let res_next_key = get_opt_str(buf)?;
+
+    let raw_data = match get_u8(buf)? {
+        0 => None,
+        1 => {
+            let len = get_u32(buf)? as usize;
+            let mut block_tbl = HashMap::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                block_tbl.insert(get_str(buf)?, get_bytes(buf)?);
+            }
+            Some(RawData::Block(block_tbl))
+        }
+        2 => Some(RawData::NonBlock(get_bytes(buf)?)),
+        _ => return Err(invalid_data()),
+    };
+
+    Ok(QueryRecord {
+        time,
+        request,
+        next_key,
+        code,
+        message,
+        elapsed,
+        res_next_key,
+        raw_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryReader, QueryRecord, QueryWriter};
+    use crate::data::{Block, Data, DataType, RawData};
+    use crate::hashmap;
+    use crate::os::testdata::t1102_layout;
+    use crate::Response;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_query_capture() {
+        let tr_layout = t1102_layout();
+
+        let record = QueryRecord {
+            time: UNIX_EPOCH + Duration::from_secs(1610325000),
+            request: Data {
+                tr_code: "t1102".into(),
+                data_type: DataType::Input,
+                blocks: hashmap! {
+                    "t1102InBlock" => Block::Block(hashmap! { "shcode" => "005930" }),
+                },
+            },
+            next_key: None,
+            code: "00000".into(),
+            message: "조회완료".into(),
+            elapsed: Duration::from_millis(12),
+            res_next_key: None,
+            raw_data: Some(RawData::Block(hashmap! {
+                "t1102OutBlock" => b"91000   ".to_vec(),
+            })),
+        };
+
+        let path = std::env::temp_dir().join(format!("xingapi-capture-{}", std::process::id()));
+        let mut writer = QueryWriter::create(&path).unwrap();
+        writer.write(&record).unwrap();
+        writer.write(&record).unwrap();
+        drop(writer);
+
+        let records: Vec<_> = QueryReader::open(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records, [record.clone(), record.clone()]);
+
+        let res = records[0].replay(&tr_layout);
+        assert!(res.is_ok());
+        assert_eq!(res.elapsed(), Duration::from_millis(12));
+        assert_eq!(
+            res.data().unwrap().blocks["t1102OutBlock"],
+            Block::Block(hashmap! { "price" => "91000" })
+        );
+
+        let mut tr_layout = tr_layout;
+        tr_layout.block_mode = false;
+        assert!(records[0].replay(&tr_layout).data().is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
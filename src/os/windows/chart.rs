@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 차트 조회 모듈
+//!
+//! 주식 차트 TR인 t8411(틱), t8412(분), t8413(일주월)을 타입이 지정된 캔들
+//! 목록으로 조회할 수 있도록 감싼 함수를 제공합니다.
+//!
+//! 응답 데이터는 압축하여 요청하며, 수신한 데이터는 `ETK_Decompress` 함수로
+//! 압축을 해제한 후 디코딩됩니다.
+//!
+//! 실시간 자동 등록으로 차트 지표(ChartIndex)를 조회한 경우 갱신되는 지표
+//! 데이터는 [`ChartRealEvent`]로 수신합니다.
+
+use super::service::{ServiceEvent, ServiceKind};
+use super::{ensure_ok, remove_service, request_with_retry, set_fields, Error, RealResponse};
+use crate::data::{self, Data, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 캔들
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Candle {
+    /// `YYYYMMDD` 형식의 날짜
+    pub date: String,
+    /// `HHMMSS` 형식의 시각, 일주월 캔들인 경우 `None`
+    pub time: Option<String>,
+    /// 시가
+    pub open: f64,
+    /// 고가
+    pub high: f64,
+    /// 저가
+    pub low: f64,
+    /// 종가
+    pub close: f64,
+    /// 거래량
+    pub volume: u64,
+}
+
+impl Candle {
+    fn from_fields(
+        fields: &HashMap<Arc<str>, String>,
+        block_name: &str,
+    ) -> Result<Self, DecodeError> {
+        let parse_f64 = |field_name| data::parse_field::<f64>(fields, block_name, field_name);
+
+        Ok(Self {
+            date: data::parse_field(fields, block_name, "date")?,
+            time: fields.get("time").cloned(),
+            open: parse_f64("open")?,
+            high: parse_f64("high")?,
+            low: parse_f64("low")?,
+            close: parse_f64("close")?,
+            volume: data::parse_field(fields, block_name, "jdiff_vol")?,
+        })
+    }
+}
+
+/// 일주월 캔들의 주기
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Period {
+    /// 일
+    Daily,
+    /// 주
+    Weekly,
+    /// 월
+    Monthly,
+}
+
+impl Period {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Daily => "2",
+            Self::Weekly => "3",
+            Self::Monthly => "4",
+        }
+    }
+}
+
+// 압축 요청 시 한 번에 조회할 수 있는 최대 개수
+const MAX_QUERY_COUNT: &str = "2000";
+
+/// 틱 캔들을 조회합니다. (t8411)
+///
+/// `start`와 `end`는 `YYYYMMDD` 형식의 날짜이며, 연속 조회하여 기간 내의 모든
+/// 캔들을 오래된 순서로 반환합니다.
+pub fn tick_candles(
+    symbol: &str,
+    ticks: u32,
+    start: &str,
+    end: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<Candle>, Error> {
+    intraday_candles("t8411", symbol, ticks, start, end, tr_layout, timeout)
+}
+
+/// 분 캔들을 조회합니다. (t8412)
+///
+/// `start`와 `end`는 `YYYYMMDD` 형식의 날짜이며, 연속 조회하여 기간 내의 모든
+/// 캔들을 오래된 순서로 반환합니다.
+pub fn minute_candles(
+    symbol: &str,
+    minutes: u32,
+    start: &str,
+    end: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<Candle>, Error> {
+    intraday_candles("t8412", symbol, minutes, start, end, tr_layout, timeout)
+}
+
+/// 일주월 캔들을 조회합니다. (t8413)
+///
+/// `start`와 `end`는 `YYYYMMDD` 형식의 날짜이며, 연속 조회하여 기간 내의 모든
+/// 캔들을 오래된 순서로 반환합니다.
+pub fn period_candles(
+    symbol: &str,
+    period: Period,
+    start: &str,
+    end: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<Candle>, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "t8413InBlock",
+        &[
+            ("shcode", symbol.to_owned()),
+            ("gubun", period.code().into()),
+            ("qrycnt", MAX_QUERY_COUNT.into()),
+            ("sdate", start.to_owned()),
+            ("edate", end.to_owned()),
+            ("comp_yn", "Y".into()),
+        ],
+    )?;
+
+    request_candles(req_data, "t8413", &["cts_date"], tr_layout, timeout)
+}
+
+fn intraday_candles(
+    tr_code: &str,
+    symbol: &str,
+    unit: u32,
+    start: &str,
+    end: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<Candle>, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        &format!("{}InBlock", tr_code),
+        &[
+            ("shcode", symbol.to_owned()),
+            ("ncnt", unit.to_string()),
+            ("qrycnt", MAX_QUERY_COUNT.into()),
+            ("nday", "0".into()),
+            ("sdate", start.to_owned()),
+            ("edate", end.to_owned()),
+            ("comp_yn", "Y".into()),
+        ],
+    )?;
+
+    request_candles(
+        req_data,
+        tr_code,
+        &["cts_date", "cts_time"],
+        tr_layout,
+        timeout,
+    )
+}
+
+// 연속 조회하여 모든 캔들을 가져옵니다.
+fn request_candles(
+    mut req_data: Data,
+    tr_code: &str,
+    cts_fields: &[&str],
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<Candle>, Error> {
+    let in_block_name = format!("{}InBlock", tr_code);
+    let out_block_name = format!("{}OutBlock", tr_code);
+    let array_block_name = format!("{}OutBlock1", tr_code);
+
+    let mut candles = Vec::new();
+    let mut next_key = None;
+
+    loop {
+        let res = ensure_ok(request_with_retry(
+            &req_data,
+            tr_layout.clone(),
+            next_key.as_deref(),
+            timeout,
+        )?)?;
+
+        let res_data = res.data()?;
+        for fields in data::get_array(res_data, &array_block_name)? {
+            candles.push(Candle::from_fields(fields, &array_block_name)?);
+        }
+
+        match res.next_key() {
+            Some(key) => {
+                let out_block = data::get_block(res_data, &out_block_name)?;
+                let cts = cts_fields
+                    .iter()
+                    .map(|&field_name| {
+                        let value = data::parse_field(out_block, &out_block_name, field_name)?;
+                        Ok((field_name, value))
+                    })
+                    .collect::<Result<Vec<_>, DecodeError>>()?;
+
+                set_fields(&mut req_data, &in_block_name, &cts)?;
+                next_key = Some(key.to_owned());
+            }
+            None => break,
+        }
+    }
+
+    candles.sort_by(|a, b| (&a.date, &a.time).cmp(&(&b.date, &b.time)));
+    candles.dedup();
+
+    Ok(candles)
+}
+
+/// 차트 지표의 실시간 데이터를 수신하는 객체
+///
+/// 차트 지표 TR을 [`request_service()`](super::request_service)로 실시간
+/// 자동 등록(`IsReal` 필드가 `Y`)하여 조회하면 이후 갱신되는 지표 데이터가
+/// 이 객체로 수신됩니다. 객체는 하나만 사용할 수
+/// 있으며, 새로운 객체를 생성하면 이전 객체는 더 이상 데이터를 수신하지
+/// 않습니다.
+///
+/// 수신한 응답의 키는 빈 문자열입니다.
+pub struct ChartRealEvent {
+    event: ServiceEvent,
+}
+
+impl ChartRealEvent {
+    /// 차트 지표 TR 코드
+    pub const TR_CODE: &'static str = "ChartIndex";
+
+    /// 응답을 디코딩하기 위한 차트 지표 TR의 레이아웃으로 객체를 생성합니다.
+    pub fn new(tr_layout: TrLayout) -> Self {
+        Self {
+            event: ServiceEvent::new(ServiceKind::Chart, tr_layout),
+        }
+    }
+
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        self.event.try_recv()
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.event.recv_timeout(timeout)
+    }
+
+    /// 지정된 지표 ID의 실시간 데이터 수신을 중단합니다.
+    pub fn remove(&self, index_id: &str) -> Result<(), Error> {
+        remove_service(Self::TR_CODE, index_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Candle;
+    use crate::hashmap;
+
+    #[test]
+    fn test_candle_from_fields() {
+        let candle = Candle::from_fields(
+            &hashmap! {
+                "date" => "20210111",
+                "time" => "090100",
+                "open" => "00091000",
+                "high" => "00091300",
+                "low" => "00090700",
+                "close" => "00091100",
+                "jdiff_vol" => "000000123456",
+            },
+            "t8412OutBlock1",
+        )
+        .unwrap();
+
+        assert_eq!(candle.date, "20210111");
+        assert_eq!(candle.time.as_deref(), Some("090100"));
+        assert_eq!(candle.open, 91000.0);
+        assert_eq!(candle.close, 91100.0);
+        assert_eq!(candle.volume, 123456);
+
+        let candle = Candle::from_fields(
+            &hashmap! {
+                "date" => "20210111",
+                "open" => "91000",
+                "high" => "91300",
+                "low" => "90700",
+                "close" => "91100",
+                "jdiff_vol" => "123456",
+            },
+            "t8413OutBlock1",
+        )
+        .unwrap();
+
+        assert_eq!(candle.time, None);
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{ensure_ok, kst, request_with_retry, Error};
+use crate::data::{self, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// 서버 시각을 조회합니다. (t0167)
+///
+/// 네트워크 지연 시간은 보정하지 않으며, 보정된 시각이 필요한 경우
+/// [`ServerClock`]을 사용해야 합니다.
+pub fn server_time(tr_layout: &Arc<TrLayout>, timeout: Duration) -> Result<SystemTime, Error> {
+    let req_data = data::empty_input(tr_layout);
+    let res = ensure_ok(request_with_retry(
+        &req_data,
+        tr_layout.clone(),
+        None,
+        timeout,
+    )?)?;
+
+    Ok(parse_server_time(res.data()?)?)
+}
+
+fn parse_server_time(data: &data::Data) -> Result<SystemTime, DecodeError> {
+    const BLOCK_NAME: &str = "t0167OutBlock";
+
+    let fields = data::get_block(data, BLOCK_NAME)?;
+    let invalid_field = |field_name: &str| DecodeError::InvalidField {
+        block: BLOCK_NAME.to_owned(),
+        field: field_name.to_owned(),
+    };
+
+    let date: String = data::parse_field(fields, BLOCK_NAME, "dt")?;
+    let days = kst::parse_date(&date).ok_or_else(|| invalid_field("dt"))?;
+
+    // `HHMMSS` 뒤에 소수점 이하의 초가 이어집니다.
+    let time: String = data::parse_field(fields, BLOCK_NAME, "time")?;
+    if time.len() < 6 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid_field("time"));
+    }
+
+    let h: u32 = time[0..2].parse().unwrap();
+    let m: u32 = time[2..4].parse().unwrap();
+    let s: u32 = time[4..6].parse().unwrap();
+    let frac = &time[6..];
+    let nanos: u32 = format!("{:0<9}", &frac[..frac.len().min(9)])
+        .parse()
+        .unwrap();
+
+    let secs = (h * 60 + m) * 60 + s;
+    let server_time = kst::DateTime { days, secs }.to_system_time();
+
+    Ok(server_time + Duration::from_nanos(nanos as _))
+}
+
+/// 서버 시각 추정 객체
+///
+/// 서버 시각을 여러 번 조회하여 로컬 시각과의 차이를 추정합니다. 왕복 시간의
+/// 절반을 네트워크 지연 시간으로 간주하며, 최근 조회 결과 중 왕복 시간이 가장
+/// 짧은 결과를 사용합니다.
+#[derive(Clone, Debug, Default)]
+pub struct ServerClock {
+    samples: VecDeque<Sample>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    rtt: Duration,
+    offset_nanos: i128,
+}
+
+impl ServerClock {
+    const MAX_SAMPLES: usize = 8;
+
+    /// 조회 결과가 없는 객체를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 서버 시각을 조회하여 추정 결과를 갱신합니다.
+    pub fn sync(&mut self, tr_layout: &Arc<TrLayout>, timeout: Duration) -> Result<(), Error> {
+        let sent_at = SystemTime::now();
+        let instant = Instant::now();
+        let server_time = server_time(tr_layout, timeout)?;
+
+        self.add_sample(sent_at, instant.elapsed(), server_time);
+
+        Ok(())
+    }
+
+    fn add_sample(&mut self, sent_at: SystemTime, rtt: Duration, server_time: SystemTime) {
+        let local_time = sent_at + rtt / 2;
+        let offset_nanos = match server_time.duration_since(local_time) {
+            Ok(d) => d.as_nanos() as i128,
+            Err(err) => -(err.duration().as_nanos() as i128),
+        };
+
+        if self.samples.len() == Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample { rtt, offset_nanos });
+    }
+
+    fn best_sample(&self) -> Option<&Sample> {
+        self.samples.iter().min_by_key(|s| s.rtt)
+    }
+
+    /// 서버 시각에서 로컬 시각을 뺀 값을 밀리초 단위로 반환합니다.
+    ///
+    /// 조회 결과가 없는 경우 `None`을 반환합니다.
+    pub fn offset_millis(&self) -> Option<i64> {
+        self.best_sample()
+            .map(|s| (s.offset_nanos / 1_000_000) as i64)
+    }
+
+    /// 추정된 현재 서버 시각을 반환합니다.
+    ///
+    /// 조회 결과가 없는 경우 로컬 시각을 반환합니다.
+    pub fn now(&self) -> SystemTime {
+        self.to_server_time(SystemTime::now())
+    }
+
+    /// 로컬 시각을 추정된 서버 시각으로 변환합니다.
+    pub fn to_server_time(&self, local_time: SystemTime) -> SystemTime {
+        match self.best_sample() {
+            Some(s) if s.offset_nanos >= 0 => {
+                local_time + Duration::from_nanos(s.offset_nanos as u64)
+            }
+            Some(s) => local_time - Duration::from_nanos(s.offset_nanos.unsigned_abs() as u64),
+            None => local_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_server_time, ServerClock};
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_parse_server_time() {
+        let data = Data {
+            tr_code: "t0167".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t0167OutBlock" => Block::Block(hashmap! {
+                    "dt" => "20210111",
+                    "time" => "093000123456",
+                }),
+            },
+        };
+
+        // 2021-01-11 00:30:00 UTC
+        assert_eq!(
+            parse_server_time(&data).unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1610325000) + Duration::from_micros(123456)
+        );
+    }
+
+    #[test]
+    fn test_server_clock() {
+        let mut clock = ServerClock::new();
+        assert_eq!(clock.offset_millis(), None);
+
+        let base = UNIX_EPOCH + Duration::from_secs(1610325000);
+        clock.add_sample(
+            base,
+            Duration::from_millis(100),
+            base + Duration::from_millis(550),
+        );
+        clock.add_sample(
+            base,
+            Duration::from_millis(20),
+            base - Duration::from_millis(190),
+        );
+
+        assert_eq!(clock.offset_millis(), Some(-200));
+        assert_eq!(
+            clock.to_server_time(base),
+            base - Duration::from_millis(200)
+        );
+    }
+}
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use super::{decode_euckr, raw::XM_OFFSET, Account, DllError, Error};
+use super::{decode_text, raw::XM_OFFSET, Account, DllError, Error, TrLimits};
+use crate::data::{self, Encoding};
 
-use encoding_rs::EUC_KR;
 use libloading::os::windows::{Library, Symbol};
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::{Path, PathBuf};
@@ -52,6 +52,9 @@ type SetHeaderInfo = unsafe extern "system" fn(*const i8, *const i8);
 type SetUseApiVer = unsafe extern "system" fn(*const i8);
 type SetMode = unsafe extern "system" fn(*const i8, *const i8);
 
+// 문자열 입출력을 UTF-8로 변경하는 `ETK_SetMode`의 항목
+const MODE_UTF8: &str = "_UTF8_";
+
 // 추가 정보
 type GetProcBranchNo = unsafe extern "system" fn(*mut i8);
 type GetUseOverFuture = unsafe extern "system" fn() -> BOOL;
@@ -80,6 +83,58 @@ type UnadviseLinkFromHts = unsafe extern "system" fn(HWND);
 // 차트 관련
 type Decompress = unsafe extern "system" fn(*const i8, *const i8, i32) -> i32;
 
+// 실행기 스레드에서 호출하는 XingAPI 함수
+//
+// 실행기는 DLL을 불러온 `Entry` 대신 이 트레잇을 구현한 객체로도 동작하므로,
+// DLL 없이 세션과 실행기를 거치는 요청을 시험할 수 있습니다.
+pub(crate) trait Api {
+    fn path(&self) -> &Path;
+
+    fn connect(&self, hwnd: usize, addr: &str, port: u16, timeout: Duration) -> Result<(), Error>;
+    fn is_connected(&self) -> bool;
+    fn disconnect(&self);
+    fn login(
+        &self,
+        hwnd: usize,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<(), Error>;
+
+    // 조회 TR을 요청하고 요청 ID를 반환합니다.
+    fn request(
+        &self,
+        hwnd: usize,
+        tr_code: &str,
+        data: &[u8],
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<i32, Error>;
+    fn release_request_data(&self, req_id: i32);
+    fn release_message_data(&self, lparam: LPARAM);
+
+    fn request_link_to_hts(&self, hwnd: usize, link_name: &str, data: &str) -> bool;
+    fn advise_link_from_hts(&self, hwnd: usize);
+    fn unadvise_link_from_hts(&self, hwnd: usize);
+
+    fn request_service(&self, hwnd: usize, tr_code: &str, data: &[u8]) -> Result<i32, Error>;
+    fn remove_service(&self, hwnd: usize, tr_code: &str, data: &str) -> Result<(), Error>;
+    fn decompress(&self, src: &[u8], dest: &mut [u8]) -> usize;
+
+    fn advise_real_data(&self, hwnd: usize, tr_code: &str, keys: &[String]) -> Vec<bool>;
+    fn unadvise_real_data(&self, hwnd: usize, tr_code: &str, keys: &[String]) -> Vec<bool>;
+    fn unadvise_window(&self, hwnd: usize) -> bool;
+
+    fn accounts(&self) -> Vec<Account>;
+    fn get_comm_media(&self) -> Option<String>;
+    fn get_etk_media(&self) -> Option<String>;
+    fn get_server_name(&self) -> Option<String>;
+    fn get_use_over_future(&self) -> bool;
+    fn get_use_fx(&self) -> bool;
+    fn get_tr_limits(&self, tr_code: &str) -> Option<TrLimits>;
+}
+
 #[allow(dead_code)]
 pub struct Entry {
     _marker: PhantomData<*const ()>,
@@ -181,6 +236,17 @@ impl Entry {
     }
 
     fn load_entry(lib: Library, path: &Path) -> Result<Self, DllError> {
+        let entry = Self::load_symbols(lib, path)?;
+
+        // DLL의 출력이 크레이트에서 디코딩하는 인코딩과 같도록 지정합니다.
+        if data::lock_encoding() == Encoding::Utf8 {
+            entry.set_mode(MODE_UTF8, "TRUE");
+        }
+
+        Ok(entry)
+    }
+
+    fn load_symbols(lib: Library, path: &Path) -> Result<Self, DllError> {
         macro_rules! load_sym {
             ($sym_name:literal) => {
                 unsafe { lib.get($sym_name.as_bytes()) }.map_err(|error| DllError::Symbol {
@@ -275,21 +341,176 @@ impl Entry {
         Self::load_entry(Self::load_lib(path.as_ref())?, path.as_ref())
     }
 
-    pub fn path(&self) -> &Path {
+    pub fn get_last_error(&self) -> Error {
+        let code = unsafe { (self.get_last_error)() };
+
+        Error::XingApi {
+            code,
+            message: self.get_error_message(code),
+        }
+    }
+
+    pub fn get_error_message(&self, code: i32) -> String {
+        let mut buffer = [0; 1024];
+        let len: usize = unsafe {
+            (self.get_error_message)(code, buffer.as_mut_ptr(), buffer.len() as _)
+                .try_into()
+                .unwrap()
+        };
+        assert!(len <= buffer.len());
+
+        decode_text(&buffer[..len])
+    }
+
+    fn get_account_list(&self) -> Vec<String> {
+        let len = unsafe { (self.get_acc_list_count)() };
+
+        let mut accounts = Vec::with_capacity(len.try_into().unwrap());
+        let mut buffer = [0; 32];
+        for i in 0..len {
+            unsafe {
+                assert_eq!(
+                    (self.get_acc_list)(i, buffer.as_mut_ptr(), buffer.len() as _),
+                    TRUE
+                );
+            }
+            accounts.push(decode_text(&buffer));
+        }
+
+        accounts
+    }
+
+    fn get_account_name(&self, account: &str) -> String {
+        let mut buffer = [0; 64];
+        unsafe {
+            (self.get_acc_name)(
+                encode_text(account).as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+            );
+        }
+
+        decode_text(&buffer)
+    }
+
+    fn get_account_detail_name(&self, account: &str) -> String {
+        let mut buffer = [0; 64];
+        unsafe {
+            (self.get_acc_detail_name)(
+                encode_text(account).as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+            );
+        }
+
+        decode_text(&buffer)
+    }
+
+    fn get_account_nickname(&self, account: &str) -> String {
+        let mut buffer = [0; 64];
+        unsafe {
+            (self.get_acc_nickname)(
+                encode_text(account).as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+            );
+        }
+
+        decode_text(&buffer)
+    }
+
+    // 최신 버전에서 더 이상 유의미한 값을 반환하지 않는 것 같습니다.
+    pub fn get_client_ip(&self) -> Option<IpAddr> {
+        let mut buffer = [0; 256];
+        unsafe {
+            (self.get_client_ip)(buffer.as_mut_ptr());
+        }
+
+        match decode_text(&buffer) {
+            s if s.is_empty() => None,
+            s => {
+                // `192.168.000.100`와 같은 형식으로 반환되어 파싱이 되지 않는
+                // 경우도 있습니다.
+                if let Ok(addr) = s.parse() {
+                    Some(addr)
+                } else {
+                    let mut ipv4: [u8; 4] = [0; 4];
+                    let mut octets = s.split('.');
+
+                    ipv4[0] = octets.next().unwrap().parse().unwrap();
+                    ipv4[1] = octets.next().unwrap().parse().unwrap();
+                    ipv4[2] = octets.next().unwrap().parse().unwrap();
+                    ipv4[3] = octets.next().unwrap().parse().unwrap();
+
+                    Some(Ipv4Addr::from(ipv4).into())
+                }
+            }
+        }
+    }
+
+    // 최신 버전에서 빈 문자열만을 반환하는 것 같습니다.
+    pub fn get_proc_branch_no(&self) -> Option<String> {
+        let mut buffer = [0; 256];
+        unsafe {
+            (self.get_proc_branch_no)(buffer.as_mut_ptr());
+        }
+
+        match decode_text(&buffer) {
+            s if s.is_empty() => None,
+            s => Some(s),
+        }
+    }
+
+    pub fn set_mode(&self, key: &str, value: &str) {
+        unsafe {
+            (self.set_mode)(encode_text(key).as_ptr(), encode_text(value).as_ptr());
+        }
+    }
+
+    pub fn get_tr_count_per_sec(&self, tr_code: &str) -> Option<u32> {
+        match unsafe { (self.get_tr_count_per_sec)(encode_text(tr_code).as_ptr()) } {
+            i32::MAX => None,
+            cnt if (cnt <= 0) => None,
+            cnt => Some(cnt as u32),
+        }
+    }
+
+    pub fn get_tr_count_base_sec(&self, tr_code: &str) -> Option<u32> {
+        match unsafe { (self.get_tr_count_base_sec)(encode_text(tr_code).as_ptr()) } {
+            i32::MAX => None,
+            cnt if (cnt <= 0) => None,
+            cnt => Some(cnt as u32),
+        }
+    }
+
+    // 사용한 횟수는 0일 수 있으므로 음수인 경우에만 `None`을 반환합니다.
+    pub fn get_tr_count_request(&self, tr_code: &str) -> Option<u32> {
+        match unsafe { (self.get_tr_count_request)(encode_text(tr_code).as_ptr()) } {
+            i32::MAX => None,
+            cnt if (cnt < 0) => None,
+            cnt => Some(cnt as u32),
+        }
+    }
+
+    pub fn get_tr_count_limit(&self, tr_code: &str) -> Option<u32> {
+        match unsafe { (self.get_tr_count_limit)(encode_text(tr_code).as_ptr()) } {
+            i32::MAX => None,
+            cnt if (cnt <= 0) => None,
+            cnt => Some(cnt as u32),
+        }
+    }
+}
+
+impl Api for Entry {
+    fn path(&self) -> &Path {
         self.lib_path.as_path()
     }
 
-    pub fn connect(
-        &self,
-        hwnd: usize,
-        addr: &str,
-        port: u16,
-        timeout: Duration,
-    ) -> Result<(), Error> {
+    fn connect(&self, hwnd: usize, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
         if unsafe {
             (self.connect)(
                 hwnd as _,
-                encode_euckr(addr).as_ptr(),
+                encode_text(addr).as_ptr(),
                 port as _,
                 XM_OFFSET as _,
                 timeout.as_millis().max(1).try_into().unwrap_or(i32::MAX),
@@ -302,15 +523,15 @@ impl Entry {
         }
     }
 
-    pub fn is_connected(&self) -> bool {
+    fn is_connected(&self) -> bool {
         unsafe { (self.is_connected)() == TRUE }
     }
 
-    pub fn disconnect(&self) {
+    fn disconnect(&self) {
         unsafe { (self.disconnect)() };
     }
 
-    pub fn login(
+    fn login(
         &self,
         hwnd: usize,
         id: &str,
@@ -321,9 +542,9 @@ impl Entry {
         if unsafe {
             (self.login)(
                 hwnd as _,
-                encode_euckr(id).as_ptr(),
-                encode_euckr(pw).as_ptr(),
-                encode_euckr(cert_pw).as_ptr(),
+                encode_text(id).as_ptr(),
+                encode_text(pw).as_ptr(),
+                encode_text(cert_pw).as_ptr(),
                 0,
                 if cert_err_dialog { TRUE } else { FALSE },
             ) == TRUE
@@ -334,28 +555,7 @@ impl Entry {
         }
     }
 
-    pub fn get_last_error(&self) -> Error {
-        let code = unsafe { (self.get_last_error)() };
-
-        Error::XingApi {
-            code,
-            message: self.get_error_message(code),
-        }
-    }
-
-    pub fn get_error_message(&self, code: i32) -> String {
-        let mut buffer = [0; 1024];
-        let len: usize = unsafe {
-            (self.get_error_message)(code, buffer.as_mut_ptr(), buffer.len() as _)
-                .try_into()
-                .unwrap()
-        };
-        assert!(len <= buffer.len());
-
-        decode_euckr(&buffer[..len])
-    }
-
-    pub fn request(
+    fn request(
         &self,
         hwnd: usize,
         tr_code: &str,
@@ -366,13 +566,13 @@ impl Entry {
         let id = unsafe {
             (self.request)(
                 hwnd as _,
-                encode_euckr(tr_code).as_ptr(),
+                encode_text(tr_code).as_ptr(),
                 data.as_ptr(),
                 data.len().try_into().unwrap(),
                 if next_key.is_some() { TRUE } else { FALSE },
                 match next_key {
-                    Some(key) => encode_euckr(key).as_ptr(),
-                    None => encode_euckr("").as_ptr(),
+                    Some(key) => encode_text(key).as_ptr(),
+                    None => encode_text("").as_ptr(),
                 },
                 timeout.as_secs().max(1).try_into().unwrap_or(i32::MAX),
             )
@@ -388,256 +588,232 @@ impl Entry {
         }
     }
 
-    pub fn release_request_data(&self, req_id: i32) {
+    fn release_request_data(&self, req_id: i32) {
         unsafe { (self.release_request_data)(req_id) }
     }
 
-    pub fn release_message_data(&self, lparam: LPARAM) {
+    fn release_message_data(&self, lparam: LPARAM) {
         unsafe { (self.release_message_data)(lparam) }
     }
 
-    pub fn advise_real_data<T: AsRef<str>>(&self, hwnd: usize, tr_code: &str, keys: &[T]) {
-        for key in keys.iter().map(|k| k.as_ref()) {
-            if key.contains('\0') || key.len() >= i8::MAX as _ {
-                continue;
-            }
+    // HTS로 연동 데이터를 보냅니다.
+    fn request_link_to_hts(&self, hwnd: usize, link_name: &str, data: &str) -> bool {
+        unsafe {
+            (self.request_link_to_hts)(
+                hwnd as _,
+                encode_text(link_name).as_ptr(),
+                encode_text(data).as_ptr(),
+                encode_text("").as_ptr(),
+            ) != 0
+        }
+    }
 
-            let key = encode_euckr(key);
+    fn advise_link_from_hts(&self, hwnd: usize) {
+        unsafe { (self.advise_link_from_hts)(hwnd as _) }
+    }
 
-            // 한 번의 함수 호출로 여러 실시간 데이터를 한꺼번에 등록할 수는
-            // 있지만 특정 개수를 넘어서면 메모리 접근 위반이 발생합니다.
-            unsafe {
-                (self.advise_real_data)(
-                    hwnd as _,
-                    encode_euckr(tr_code).as_ptr(),
-                    key.as_ptr(),
-                    key.as_bytes().len() as _,
-                );
-            }
-        }
+    fn unadvise_link_from_hts(&self, hwnd: usize) {
+        unsafe { (self.unadvise_link_from_hts)(hwnd as _) }
     }
 
-    pub fn unadvise_real_data<T: AsRef<str>>(&self, hwnd: usize, tr_code: &str, keys: &[T]) {
-        for key in keys.iter().map(|k| k.as_ref()) {
-            if key.contains('\0') || key.len() >= i8::MAX as _ {
-                continue;
-            }
+    // 부가 서비스 TR을 요청하고 요청 ID를 반환합니다.
+    fn request_service(&self, hwnd: usize, tr_code: &str, data: &[u8]) -> Result<i32, Error> {
+        // 요청 데이터는 NUL 문자로 끝나는 문자열로 전달됩니다.
+        let mut data = data.to_owned();
+        data.push(0);
 
-            let key = encode_euckr(key);
+        let id = unsafe {
+            (self.request_service)(hwnd as _, encode_text(tr_code).as_ptr(), data.as_ptr() as _)
+        };
 
-            unsafe {
-                (self.unadvise_real_data)(
-                    hwnd as _,
-                    encode_euckr(tr_code).as_ptr(),
-                    key.as_ptr(),
-                    key.as_bytes().len() as _,
-                );
-            }
+        if id >= 0 {
+            Ok(id)
+        } else {
+            Err(Error::XingApi {
+                code: id,
+                message: self.get_error_message(id),
+            })
         }
     }
 
-    pub fn unadvise_window(&self, hwnd: usize) -> bool {
-        // 반환형은 BOOL이지만 에러 코드를 반환하기도 합니다.
-        unsafe { (self.unadvise_window)(hwnd as _) > 0 }
-    }
-
-    pub fn accounts(&self) -> Vec<Account> {
-        let codes = self.get_account_list();
+    // 부가 서비스 TR의 실시간 데이터 수신을 중단합니다.
+    fn remove_service(&self, hwnd: usize, tr_code: &str, data: &str) -> Result<(), Error> {
+        let code = unsafe {
+            (self.remove_service)(
+                hwnd as _,
+                encode_text(tr_code).as_ptr(),
+                encode_text(data).as_ptr(),
+            )
+        };
 
-        codes
-            .into_iter()
-            .map(|code| Account {
-                name: self.get_account_name(&code),
-                detailed_name: self.get_account_detail_name(&code),
-                nickname: self.get_account_nickname(&code),
+        if code >= 0 {
+            Ok(())
+        } else {
+            Err(Error::XingApi {
                 code,
+                message: self.get_error_message(code),
             })
-            .collect()
+        }
     }
 
-    fn get_account_list(&self) -> Vec<String> {
-        let len = unsafe { (self.get_acc_list_count)() };
-
-        let mut accounts = Vec::with_capacity(len.try_into().unwrap());
-        let mut buffer = [0; 32];
-        for i in 0..len {
-            unsafe {
-                assert_eq!(
-                    (self.get_acc_list)(i, buffer.as_mut_ptr(), buffer.len() as _),
-                    TRUE
-                );
-            }
-            accounts.push(decode_euckr(&buffer));
-        }
+    // 압축된 데이터를 해제하고 해제된 데이터의 길이를 반환합니다.
+    fn decompress(&self, src: &[u8], dest: &mut [u8]) -> usize {
+        let len = unsafe {
+            (self.decompress)(
+                src.as_ptr() as _,
+                dest.as_mut_ptr() as _,
+                src.len().try_into().unwrap(),
+            )
+        };
 
-        accounts
+        usize::try_from(len).unwrap_or(0).min(dest.len())
     }
 
-    fn get_account_name(&self, account: &str) -> String {
-        let mut buffer = [0; 64];
-        unsafe {
-            (self.get_acc_name)(
-                encode_euckr(account).as_ptr(),
-                buffer.as_mut_ptr(),
-                buffer.len() as _,
-            );
-        }
+    // 키별로 등록에 성공했는지를 반환합니다.
+    fn advise_real_data(&self, hwnd: usize, tr_code: &str, keys: &[String]) -> Vec<bool> {
+        let tr_code = encode_text(tr_code);
+
+        keys.iter()
+            .map(|k| k.as_str())
+            .map(|key| {
+                if key.contains('\0') || key.len() >= i8::MAX as _ {
+                    return false;
+                }
 
-        decode_euckr(&buffer)
+                let key = encode_text(key);
+
+                // 한 번의 함수 호출로 여러 실시간 데이터를 한꺼번에 등록할 수는
+                // 있지만 특정 개수를 넘어서면 메모리 접근 위반이 발생합니다.
+                unsafe {
+                    (self.advise_real_data)(
+                        hwnd as _,
+                        tr_code.as_ptr(),
+                        key.as_ptr(),
+                        key.as_bytes().len() as _,
+                    ) != 0
+                }
+            })
+            .collect()
     }
 
-    fn get_account_detail_name(&self, account: &str) -> String {
-        let mut buffer = [0; 64];
-        unsafe {
-            (self.get_acc_detail_name)(
-                encode_euckr(account).as_ptr(),
-                buffer.as_mut_ptr(),
-                buffer.len() as _,
-            );
-        }
+    // 키별로 등록 해제에 성공했는지를 반환합니다.
+    fn unadvise_real_data(&self, hwnd: usize, tr_code: &str, keys: &[String]) -> Vec<bool> {
+        let tr_code = encode_text(tr_code);
 
-        decode_euckr(&buffer)
-    }
+        keys.iter()
+            .map(|k| k.as_str())
+            .map(|key| {
+                if key.contains('\0') || key.len() >= i8::MAX as _ {
+                    return false;
+                }
 
-    fn get_account_nickname(&self, account: &str) -> String {
-        let mut buffer = [0; 64];
-        unsafe {
-            (self.get_acc_nickname)(
-                encode_euckr(account).as_ptr(),
-                buffer.as_mut_ptr(),
-                buffer.len() as _,
-            );
-        }
+                let key = encode_text(key);
 
-        decode_euckr(&buffer)
+                unsafe {
+                    (self.unadvise_real_data)(
+                        hwnd as _,
+                        tr_code.as_ptr(),
+                        key.as_ptr(),
+                        key.as_bytes().len() as _,
+                    ) != 0
+                }
+            })
+            .collect()
     }
 
-    pub fn get_comm_media(&self) -> Option<String> {
-        let mut buffer = [0; 256];
-        unsafe {
-            (self.get_comm_media)(buffer.as_mut_ptr());
-        }
-
-        match decode_euckr(&buffer) {
-            s if s.is_empty() => None,
-            s => Some(s),
-        }
+    fn unadvise_window(&self, hwnd: usize) -> bool {
+        // 반환형은 BOOL이지만 에러 코드를 반환하기도 합니다.
+        unsafe { (self.unadvise_window)(hwnd as _) > 0 }
     }
 
-    pub fn get_etk_media(&self) -> Option<String> {
-        let mut buffer = [0; 256];
-        unsafe {
-            (self.get_etk_media)(buffer.as_mut_ptr());
-        }
+    fn accounts(&self) -> Vec<Account> {
+        let codes = self.get_account_list();
 
-        match decode_euckr(&buffer) {
-            s if s.is_empty() => None,
-            s => Some(s),
-        }
+        codes
+            .into_iter()
+            .map(|code| Account {
+                name: self.get_account_name(&code),
+                detailed_name: self.get_account_detail_name(&code),
+                nickname: self.get_account_nickname(&code),
+                code,
+            })
+            .collect()
     }
 
-    // 최신 버전에서 더 이상 유의미한 값을 반환하지 않는 것 같습니다.
-    pub fn get_client_ip(&self) -> Option<IpAddr> {
+    fn get_comm_media(&self) -> Option<String> {
         let mut buffer = [0; 256];
         unsafe {
-            (self.get_client_ip)(buffer.as_mut_ptr());
+            (self.get_comm_media)(buffer.as_mut_ptr());
         }
 
-        match decode_euckr(&buffer) {
+        match decode_text(&buffer) {
             s if s.is_empty() => None,
-            s => {
-                // `192.168.000.100`와 같은 형식으로 반환되어 파싱이 되지 않는
-                // 경우도 있습니다.
-                if let Ok(addr) = s.parse() {
-                    Some(addr)
-                } else {
-                    let mut ipv4: [u8; 4] = [0; 4];
-                    let mut octets = s.split('.');
-
-                    ipv4[0] = octets.next().unwrap().parse().unwrap();
-                    ipv4[1] = octets.next().unwrap().parse().unwrap();
-                    ipv4[2] = octets.next().unwrap().parse().unwrap();
-                    ipv4[3] = octets.next().unwrap().parse().unwrap();
-
-                    Some(Ipv4Addr::from(ipv4).into())
-                }
-            }
+            s => Some(s),
         }
     }
 
-    pub fn get_server_name(&self) -> Option<String> {
+    fn get_etk_media(&self) -> Option<String> {
         let mut buffer = [0; 256];
         unsafe {
-            (self.get_server_name)(buffer.as_mut_ptr());
+            (self.get_etk_media)(buffer.as_mut_ptr());
         }
 
-        match decode_euckr(&buffer) {
+        match decode_text(&buffer) {
             s if s.is_empty() => None,
             s => Some(s),
         }
     }
 
-    // 최신 버전에서 빈 문자열만을 반환하는 것 같습니다.
-    pub fn get_proc_branch_no(&self) -> Option<String> {
+    fn get_server_name(&self) -> Option<String> {
         let mut buffer = [0; 256];
         unsafe {
-            (self.get_proc_branch_no)(buffer.as_mut_ptr());
+            (self.get_server_name)(buffer.as_mut_ptr());
         }
 
-        match decode_euckr(&buffer) {
+        match decode_text(&buffer) {
             s if s.is_empty() => None,
             s => Some(s),
         }
     }
 
-    pub fn get_use_over_future(&self) -> bool {
+    fn get_use_over_future(&self) -> bool {
         unsafe { (self.get_use_over_future)() == TRUE }
     }
 
-    pub fn get_use_fx(&self) -> bool {
+    fn get_use_fx(&self) -> bool {
         unsafe { (self.get_use_fx)() == TRUE }
     }
 
-    pub fn get_tr_count_per_sec(&self, tr_code: &str) -> Option<i32> {
-        match unsafe { (self.get_tr_count_per_sec)(encode_euckr(tr_code).as_ptr()) } {
-            i32::MAX => None,
-            cnt if (cnt <= 0) => None,
-            cnt => Some(cnt),
-        }
-    }
+    fn get_tr_limits(&self, tr_code: &str) -> Option<TrLimits> {
+        let per_sec = self.get_tr_count_per_sec(tr_code);
+        let base_sec = self.get_tr_count_base_sec(tr_code);
+        let used_in_ten_min = self.get_tr_count_request(tr_code);
+        let limit_per_ten_min = self.get_tr_count_limit(tr_code);
 
-    pub fn get_tr_count_base_sec(&self, tr_code: &str) -> Option<i32> {
-        match unsafe { (self.get_tr_count_base_sec)(encode_euckr(tr_code).as_ptr()) } {
-            i32::MAX => None,
-            cnt if (cnt <= 0) => None,
-            cnt => Some(cnt),
+        if per_sec.is_none()
+            && base_sec.is_none()
+            && used_in_ten_min.is_none()
+            && limit_per_ten_min.is_none()
+        {
+            return None;
         }
-    }
-
-    pub fn get_tr_count_request(&self, tr_code: &str) -> Option<i32> {
-        match unsafe { (self.get_tr_count_request)(encode_euckr(tr_code).as_ptr()) } {
-            i32::MAX => None,
-            cnt if (cnt <= 0) => None,
-            cnt => Some(cnt),
-        }
-    }
 
-    pub fn get_tr_count_limit(&self, tr_code: &str) -> Option<i32> {
-        match unsafe { (self.get_tr_count_limit)(encode_euckr(tr_code).as_ptr()) } {
-            i32::MAX => None,
-            cnt if (cnt <= 0) => None,
-            cnt => Some(cnt),
-        }
+        Some(TrLimits {
+            per_sec,
+            base_sec,
+            used_in_ten_min,
+            limit_per_ten_min,
+        })
     }
 }
 
-fn encode_euckr(string: &str) -> CString {
-    CString::new(EUC_KR.encode(string).0).unwrap()
+pub(super) fn encode_text(string: &str) -> CString {
+    CString::new(data::encoding().encode(string).0).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::DllError, Entry};
+    use super::{super::DllError, Api, Entry};
 
     #[test]
     fn test_load_entry() {
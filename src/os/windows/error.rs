@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// DLL을 불러오는 과정에서 발생하는 에러 타입
+
+use std::path::PathBuf;
+
+/// XingAPI를 불러오는데 실패하여 발생하는 에러
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// DLL 에러
+    Dll(DllError),
+    /// I/O 에러
+    Io(std::io::Error),
+}
+
+impl From<DllError> for LoadError {
+    fn from(err: DllError) -> Self {
+        Self::Dll(err)
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dll(err) => {
+                write!(f, "dll error: {}", err)
+            }
+            Self::Io(err) => {
+                write!(f, "io error: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dll(err) => Some(err),
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+/// DLL을 불러오는데 실패하여 발생하는 에러
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DllError {
+    /// 라이브러리 에러
+    Library {
+        /// DLL 경로
+        path: PathBuf,
+        /// 에러 내용
+        error: libloading::Error,
+    },
+    /// 심볼 에러
+    Symbol {
+        /// 심볼 이름
+        symbol: String,
+        /// DLL 경로
+        path: PathBuf,
+        /// 에러 내용
+        error: libloading::Error,
+    },
+    /// DLL이 현재 프로세스에서 이미 사용 중임
+    LibraryInUse,
+}
+
+impl std::fmt::Display for DllError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Library { path, error } => {
+                write!(f, "could not load a library; ")?;
+                write!(f, "path: {}, error: {}", path.display(), error)
+            }
+            Self::Symbol {
+                symbol,
+                path,
+                error,
+            } => {
+                write!(f, "could not load a symbol: {}; ", symbol)?;
+                write!(f, "path: {}, error: {}", path.display(), error)
+            }
+            Self::LibraryInUse => {
+                write!(f, "a library is already in use in current process")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DllError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Library { error, .. } | Self::Symbol { error, .. } => Some(error),
+            Self::LibraryInUse => None,
+        }
+    }
+}
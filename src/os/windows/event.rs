@@ -1,16 +1,21 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::data::{self, DataType, DecodeError};
-use crate::layout::TrLayout;
+use crate::layout::{LayoutRegistry, TrLayout};
 
 use super::executor::{self, Executor, Window};
+use super::metrics::LatencyStats;
 use super::raw::{RECV_REAL_PACKET, XM_RECEIVE_REAL_DATA};
-use super::{decode_euckr, RealResponse};
+use super::{decode_text, RealResponse};
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
 use lazy_static::lazy_static;
-use std::sync::{atomic::AtomicPtr, RwLock};
-use std::{collections::HashMap, ffi::CString, time::Duration};
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::CString;
+use std::sync::{atomic::AtomicPtr, Arc, Mutex, RwLock};
+use std::task::{Context, Poll, Waker};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
 
 use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WPARAM};
 use winapi::shared::windef::HWND;
@@ -43,27 +48,179 @@ struct IncompleteRealResponse {
     tr_code: String,
     key: String,
     data: Vec<u8>,
+    received_at: SystemTime,
+    received: Instant,
 }
 
 impl IncompleteRealResponse {
-    fn decode(self, layout_tbl: &HashMap<String, TrLayout>) -> RealResponse {
+    fn decode(
+        self,
+        registry: &LayoutRegistry,
+        latency: &Mutex<Option<LatencyStats>>,
+    ) -> RealResponse {
+        let started = Instant::now();
+        let data = (|| -> Result<_, DecodeError> {
+            let tr_layout = registry
+                .get(&self.tr_code)
+                .ok_or_else(|| DecodeError::UnknownLayout(self.tr_code.clone()))?;
+            data::decode_non_block(&tr_layout, DataType::Output, &self.data)
+        })();
+
+        if let Some(stats) = &mut *latency.lock().unwrap() {
+            stats.queue.record(started.duration_since(self.received));
+            stats.decode.record(started.elapsed());
+        }
+
         RealResponse {
+            tr_code: self.tr_code,
             key: self.key,
-            data: (|| -> Result<_, DecodeError> {
-                data::decode_non_block(
-                    layout_tbl
-                        .get(&self.tr_code)
-                        .ok_or_else(|| DecodeError::UnknownLayout(self.tr_code.clone()))?,
-                    DataType::Output,
-                    &self.data,
-                )
-            })(),
+            data,
+            received_at: self.received_at,
+        }
+    }
+}
+
+type RealCallback = Box<dyn FnMut(&RealResponse) + Send>;
+type RouteTable = HashMap<(String, String), Sender<IncompleteRealResponse>>;
+type RealFilter = Box<dyn Fn(&str, &str) -> bool + Send + Sync>;
+
+// 콜백을 호출하는 스레드
+//
+// `tx_stop`을 해제하면 스레드가 종료됩니다.
+struct Dispatcher {
+    tx_stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Dispatcher {
+    fn drop(&mut self) {
+        drop(self.tx_stop.take());
+
+        if let Some(thread) = self.thread.take() {
+            // 콜백 내에서 객체를 해제한 경우에는 기다리지 않습니다.
+            if thread.thread().id() != std::thread::current().id() {
+                let _ = thread.join();
+            }
         }
     }
 }
 
 struct RealEventWindowData {
     tx_res: Sender<IncompleteRealResponse>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    route_tbl: Arc<Mutex<RouteTable>>,
+    filter: Arc<RwLock<Option<RealFilter>>>,
+}
+
+/// 실시간 TR을 나누어 등록하기 위한 설정
+///
+/// 한 번에 너무 많은 키를 등록하면 DLL 내부에서 메모리 접근 위반이 발생할
+/// 수 있으므로 지정된 개수씩 나누어 등록합니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubscribeBatch {
+    /// 한 번에 등록할 키의 개수
+    pub size: usize,
+    /// 나누어 등록할 때마다 기다리는 시간
+    pub delay: Duration,
+}
+
+impl Default for SubscribeBatch {
+    fn default() -> Self {
+        Self {
+            size: 100,
+            delay: Duration::from_millis(50),
+        }
+    }
+}
+
+/// 등록된 실시간 TR 목록
+///
+/// TR 코드별로 등록된 키들을 정렬된 순서로 유지합니다.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Subscriptions {
+    tbl: HashMap<String, BTreeSet<String>>,
+}
+
+impl Subscriptions {
+    /// 지정된 TR 코드로 등록된 키들을 반환합니다.
+    pub fn get(&self, tr_code: &str) -> Option<&BTreeSet<String>> {
+        self.tbl.get(tr_code)
+    }
+
+    /// 실시간 TR이 지정된 키로 등록되어 있는지 확인합니다.
+    pub fn contains(&self, tr_code: &str, key: &str) -> bool {
+        self.tbl.get(tr_code).is_some_and(|keys| keys.contains(key))
+    }
+
+    /// 등록된 실시간 TR이 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.tbl.is_empty()
+    }
+
+    /// 등록된 TR 코드와 키들을 반환합니다.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BTreeSet<String>)> {
+        self.tbl
+            .iter()
+            .map(|(tr_code, keys)| (tr_code.as_str(), keys))
+    }
+
+    // 새로 추가된 키들을 반환합니다.
+    pub(super) fn insert<T: AsRef<str>>(&mut self, tr_code: &str, keys: &[T]) -> Vec<String> {
+        let registered = self.tbl.entry(tr_code.to_owned()).or_default();
+        let added = keys
+            .iter()
+            .map(|k| k.as_ref())
+            .filter(|k| registered.insert((*k).to_owned()))
+            .map(|k| k.to_owned())
+            .collect();
+
+        if registered.is_empty() {
+            self.tbl.remove(tr_code);
+        }
+
+        added
+    }
+
+    // 실제로 삭제된 키들을 반환합니다.
+    pub(super) fn remove<T: AsRef<str>>(&mut self, tr_code: &str, keys: &[T]) -> Vec<String> {
+        let registered = match self.tbl.get_mut(tr_code) {
+            Some(registered) => registered,
+            None => return Vec::new(),
+        };
+
+        let removed = keys
+            .iter()
+            .map(|k| k.as_ref())
+            .filter(|k| registered.remove(*k))
+            .map(|k| k.to_owned())
+            .collect();
+
+        if registered.is_empty() {
+            self.tbl.remove(tr_code);
+        }
+
+        removed
+    }
+
+    // 지정된 키들만 등록되도록 추가할 키들과 삭제할 키들을 반환합니다.
+    fn diff<T: AsRef<str>>(&self, tr_code: &str, desired: &[T]) -> (Vec<String>, Vec<String>) {
+        let desired: BTreeSet<&str> = desired.iter().map(|k| k.as_ref()).collect();
+        let registered: BTreeSet<&str> = match self.tbl.get(tr_code) {
+            Some(keys) => keys.iter().map(|k| k.as_str()).collect(),
+            None => BTreeSet::new(),
+        };
+
+        let added = desired
+            .difference(&registered)
+            .map(|k| (*k).to_owned())
+            .collect();
+        let removed = registered
+            .difference(&desired)
+            .map(|k| (*k).to_owned())
+            .collect();
+
+        (added, removed)
+    }
 }
 
 /// 실시간 TR을 등록하고 수신하는 객체
@@ -71,23 +228,52 @@ struct RealEventWindowData {
 /// 실시간 TR을 등록한 이후에는 수신한 응답을 `try_recv()`나 `recv_timeout()`
 /// 함수를 호출하여 지속적으로 큐에서 가져와야 합니다. 그렇지 않을 경우 메모리
 /// 누수로 이어질 수 있습니다.
+///
+/// 비동기 환경에서는 `recv_async()`나 `poll_recv()`를 사용합니다.
+/// `futures-core` 기능을 켠 경우 `into_stream()`으로 `Stream`을 구현한
+/// [`RealStream`]으로 변환할 수 있습니다.
+///
+/// 직접 큐에서 가져오는 대신 `on()` 함수로 TR 코드별 콜백을 등록할 수도
+/// 있습니다.
 pub struct RealEvent {
     window: Window,
     _window_data: AtomicPtr<RealEventWindowData>,
-    layout_tbl: RwLock<HashMap<String, TrLayout>>,
+    registry: LayoutRegistry,
     rx_res: Receiver<IncompleteRealResponse>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    callback_tbl: Arc<Mutex<HashMap<String, Vec<RealCallback>>>>,
+    dispatcher: Mutex<Option<Dispatcher>>,
+    route_tbl: Arc<Mutex<RouteTable>>,
+    subscriptions: Mutex<Subscriptions>,
+    filter: Arc<RwLock<Option<RealFilter>>>,
+    latency: Arc<Mutex<Option<LatencyStats>>>,
 }
 
 impl RealEvent {
     /// 객체를 생성합니다.
     pub fn new() -> Result<Self, std::io::Error> {
+        Self::with_registry(LayoutRegistry::new())
+    }
+
+    /// 지정된 레이아웃 테이블을 사용하는 객체를 생성합니다.
+    ///
+    /// 테이블을 다른 객체와 공유하는 경우 한 곳에서 추가한 레이아웃으로 모든
+    /// 객체의 응답을 디코딩합니다.
+    pub fn with_registry(registry: LayoutRegistry) -> Result<Self, std::io::Error> {
         let window = Window::new(REAL_EVENT_WNDCLASS.clone())?;
 
-        let layout_tbl = RwLock::new(HashMap::new());
         let (tx_res, rx_res) = crossbeam_channel::unbounded();
+        let waker = Arc::new(Mutex::new(None));
+        let route_tbl = Arc::new(Mutex::new(HashMap::new()));
+        let filter = Arc::new(RwLock::new(None));
+        let latency = Arc::new(Mutex::new(None));
 
-        let mut _window_data =
-            AtomicPtr::new(Box::into_raw(Box::new(RealEventWindowData { tx_res })));
+        let mut _window_data = AtomicPtr::new(Box::into_raw(Box::new(RealEventWindowData {
+            tx_res,
+            waker: waker.clone(),
+            route_tbl: route_tbl.clone(),
+            filter: filter.clone(),
+        })));
 
         unsafe {
             SetWindowLongPtrA(*window as _, GWLP_USERDATA, *_window_data.get_mut() as _);
@@ -96,51 +282,217 @@ impl RealEvent {
         Ok(Self {
             window,
             _window_data,
-            layout_tbl,
+            registry,
             rx_res,
+            waker,
+            callback_tbl: Arc::new(Mutex::new(HashMap::new())),
+            dispatcher: Mutex::new(None),
+            route_tbl,
+            subscriptions: Mutex::new(Subscriptions::default()),
+            filter,
+            latency,
         })
     }
 
+    /// 응답을 디코딩하기 위한 레이아웃 테이블을 반환합니다.
+    pub fn registry(&self) -> &LayoutRegistry {
+        &self.registry
+    }
+
     /// 응답을 디코딩하기 위한 레이아웃을 추가합니다.
-    pub fn insert_layout(&self, tr_layout: TrLayout) {
-        self.layout_tbl
-            .write()
-            .unwrap()
-            .insert(tr_layout.code.clone(), tr_layout);
+    pub fn insert_layout<T: Into<Arc<TrLayout>>>(&self, tr_layout: T) {
+        self.registry.insert(tr_layout);
     }
 
     /// 응답을 디코딩하기 위한 레이아웃을 삭제합니다.
     pub fn remove_layout(&self, tr_code: &str) {
-        self.layout_tbl.write().unwrap().remove(tr_code);
+        self.registry.remove(tr_code);
+    }
+
+    /// 수신한 응답을 TR 코드와 키로 걸러내는 함수를 지정합니다.
+    ///
+    /// 함수가 `false`를 반환한 응답은 디코딩하거나 큐에 추가하지 않고
+    /// 버립니다. 함수는 실시간 데이터를 수신하는 내부 스레드에서 호출되므로
+    /// 오래 걸리는 작업을 해서는 안 됩니다.
+    pub fn set_filter<F>(&self, filter: F)
+    where
+        F: Fn(&str, &str) -> bool + Send + Sync + 'static,
+    {
+        *self.filter.write().unwrap() = Some(Box::new(filter));
+    }
+
+    /// 지정된 함수를 삭제하여 모든 응답을 수신합니다.
+    pub fn clear_filter(&self) {
+        *self.filter.write().unwrap() = None;
+    }
+
+    /// 응답의 지연 시간 측정을 시작합니다.
+    ///
+    /// 이미 측정 중인 경우 기록을 모두 삭제합니다.
+    pub fn enable_latency_stats(&self) {
+        *self.latency.lock().unwrap() = Some(LatencyStats::default());
+    }
+
+    /// 응답의 지연 시간 측정을 중단합니다.
+    pub fn disable_latency_stats(&self) {
+        *self.latency.lock().unwrap() = None;
+    }
+
+    /// 측정한 지연 시간 통계를 반환합니다.
+    ///
+    /// 측정 중이 아닌 경우 `None`을 반환합니다.
+    pub fn latency_stats(&self) -> Option<LatencyStats> {
+        self.latency.lock().unwrap().clone()
+    }
+
+    /// 등록된 실시간 TR 목록을 반환합니다.
+    pub fn current(&self) -> Subscriptions {
+        self.subscriptions.lock().unwrap().clone()
     }
 
     /// 실시간 TR을 지정된 키들로 등록합니다.
+    ///
+    /// 이미 등록된 키는 다시 등록하지 않습니다.
     pub fn subscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) {
-        executor::global().handle().advise_real_data(
-            *self.window,
-            tr_code,
-            keys.iter().map(|k| k.as_ref().to_owned()).collect(),
-        );
+        let added = self.subscriptions.lock().unwrap().insert(tr_code, keys);
+        self.advise(tr_code, added);
+    }
+
+    /// 실시간 TR을 지정된 키들로 나누어 등록하고 키별 등록 결과를 반환합니다.
+    ///
+    /// 이미 등록된 키는 다시 등록하지 않고 성공으로 간주하며, 등록에 실패한
+    /// 키는 등록된 목록에 추가되지 않습니다.
+    pub fn subscribe_batched<T: AsRef<str>>(
+        &self,
+        tr_code: &str,
+        keys: &[T],
+        batch: SubscribeBatch,
+    ) -> Vec<(String, bool)> {
+        let added = self.subscriptions.lock().unwrap().insert(tr_code, keys);
+
+        let mut failed = Vec::new();
+        for (i, chunk) in added.chunks(batch.size.max(1)).enumerate() {
+            if i > 0 && !batch.delay.is_zero() {
+                std::thread::sleep(batch.delay);
+            }
+
+            let results =
+                executor::global()
+                    .handle()
+                    .advise_real_data(*self.window, tr_code, chunk.to_vec());
+            failed.extend(
+                chunk
+                    .iter()
+                    .zip(results)
+                    .filter(|(_, ok)| !ok)
+                    .map(|(key, _)| key.clone()),
+            );
+        }
+
+        self.subscriptions.lock().unwrap().remove(tr_code, &failed);
+
+        keys.iter()
+            .map(|k| k.as_ref())
+            .map(|key| (key.to_owned(), !failed.iter().any(|k| k == key)))
+            .collect()
+    }
+
+    /// 실시간 TR이 지정된 키들로만 등록되도록 등록된 키들과의 차이만큼 등록
+    /// 및 등록 해제합니다.
+    pub fn set_subscriptions<T: AsRef<str>>(&self, tr_code: &str, desired: &[T]) {
+        let (added, removed) = {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            let (added, removed) = subscriptions.diff(tr_code, desired);
+            subscriptions.insert(tr_code, &added);
+            subscriptions.remove(tr_code, &removed);
+            (added, removed)
+        };
+
+        self.remove_routes(tr_code, &removed);
+        self.unadvise(tr_code, removed);
+        self.advise(tr_code, added);
+    }
+
+    fn advise(&self, tr_code: &str, keys: Vec<String>) {
+        if !keys.is_empty() {
+            executor::global()
+                .handle()
+                .advise_real_data(*self.window, tr_code, keys);
+        }
+    }
+
+    fn unadvise(&self, tr_code: &str, keys: Vec<String>) {
+        if !keys.is_empty() {
+            executor::global()
+                .handle()
+                .unadvise_real_data(*self.window, tr_code, keys);
+        }
+    }
+
+    fn remove_routes<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) {
+        let mut route_tbl = self.route_tbl.lock().unwrap();
+        for key in keys.iter().map(|k| k.as_ref()) {
+            route_tbl.remove(&(tr_code.to_owned(), key.to_owned()));
+        }
+    }
+
+    /// 실시간 TR을 지정된 키들로 등록하고 키별로 응답을 수신하는 채널을
+    /// 반환합니다.
+    ///
+    /// 반환된 채널로 수신되는 응답은 큐에 추가되지 않으며, 채널을 해제하면
+    /// 이후의 응답은 다시 큐에 추가됩니다. 응답은 채널에서 가져올 때
+    /// 디코딩됩니다.
+    pub fn subscribe_routed<T: AsRef<str>>(
+        &self,
+        tr_code: &str,
+        keys: &[T],
+    ) -> HashMap<String, RoutedReceiver> {
+        let mut rx_tbl = HashMap::with_capacity(keys.len());
+
+        {
+            let mut route_tbl = self.route_tbl.lock().unwrap();
+            for key in keys.iter().map(|k| k.as_ref()) {
+                let (tx, rx) = crossbeam_channel::unbounded();
+                route_tbl.insert((tr_code.to_owned(), key.to_owned()), tx);
+                rx_tbl.insert(
+                    key.to_owned(),
+                    RoutedReceiver {
+                        rx_res: rx,
+                        registry: self.registry.clone(),
+                        latency: self.latency.clone(),
+                    },
+                );
+            }
+        }
+
+        self.subscribe(tr_code, keys);
+
+        rx_tbl
     }
 
     /// 실시간 TR을 지정된 키들로 등록 해제합니다.
+    ///
+    /// 키별로 응답을 수신하는 채널도 함께 닫힙니다. 등록되지 않은 키는
+    /// 무시합니다.
     pub fn unsubscribe<T: AsRef<str>>(&self, tr_code: &str, keys: &[T]) {
-        executor::global().handle().unadvise_real_data(
-            *self.window,
-            tr_code,
-            keys.iter().map(|k| k.as_ref().to_owned()).collect(),
-        );
+        self.remove_routes(tr_code, keys);
+
+        let removed = self.subscriptions.lock().unwrap().remove(tr_code, keys);
+        self.unadvise(tr_code, removed);
     }
 
     /// 실시간 TR을 모두 등록 해제합니다.
     pub fn unsubscribe_all(&self) {
+        self.route_tbl.lock().unwrap().clear();
+        *self.subscriptions.lock().unwrap() = Subscriptions::default();
+
         executor::global().unadvise_window(*self.window);
     }
 
     /// 수신한 응답이 큐에 있는 경우 가져옵니다.
     pub fn try_recv(&self) -> Option<RealResponse> {
         if let Ok(res) = self.rx_res.try_recv() {
-            Some(res.decode(&self.layout_tbl.read().unwrap()))
+            Some(res.decode(&self.registry, &self.latency))
         } else {
             None
         }
@@ -149,12 +501,115 @@ impl RealEvent {
     /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
     pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
         if let Ok(res) = self.rx_res.recv_timeout(timeout) {
-            Some(res.decode(&self.layout_tbl.read().unwrap()))
+            Some(res.decode(&self.registry, &self.latency))
         } else {
             None
         }
     }
 
+    /// 수신한 응답을 비동기로 가져옵니다.
+    ///
+    /// 응답이 큐에 없는 경우 `Poll::Pending`을 반환하고 응답을 수신했을 때
+    /// 태스크를 깨웁니다.
+    ///
+    /// 태스크는 하나만 등록되므로 마지막으로 호출한 태스크만 깨어나며, 여러
+    /// 태스크에서 동시에 호출하면 나머지 태스크는 깨어나지 않습니다. 또한
+    /// `on()`으로 콜백을 등록한 이후의 응답과 `subscribe_routed()`로 등록한
+    /// 키의 응답은 큐에 추가되지 않으므로 이 함수로 수신되지 않습니다.
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<RealResponse>> {
+        let decode = |res: IncompleteRealResponse| {
+            Poll::Ready(Some(res.decode(&self.registry, &self.latency)))
+        };
+
+        match self.rx_res.try_recv() {
+            Ok(res) => return decode(res),
+            Err(TryRecvError::Disconnected) => return Poll::Ready(None),
+            Err(TryRecvError::Empty) => {}
+        }
+
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // 태스크를 등록하는 동안 수신한 응답을 놓치지 않도록 다시 확인합니다.
+        match self.rx_res.try_recv() {
+            Ok(res) => decode(res),
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 비동기로 기다립니다.
+    ///
+    /// `poll_recv()`와 같은 제약이 있습니다.
+    pub async fn recv_async(&self) -> Option<RealResponse> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// 객체를 `Stream`으로 변환합니다.
+    #[cfg(feature = "futures-core")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "futures-core")))]
+    pub fn into_stream(self) -> RealStream {
+        RealStream { event: self }
+    }
+
+    /// 지정된 TR 코드의 응답을 수신했을 때 호출할 콜백을 등록합니다.
+    ///
+    /// 처음 콜백을 등록하면 내부 스레드가 생성되어 큐에서 응답을 가져오고 TR
+    /// 코드에 해당하는 콜백을 호출합니다. 이후에는 큐에서 응답을 직접
+    /// 가져와서는 안 되며, 콜백이 등록되지 않은 TR 코드의 응답은
+    /// 버려집니다.
+    ///
+    /// 콜백 내에서 `on()`이나 `off()` 함수를 호출해서는 안 됩니다.
+    pub fn on<F>(&self, tr_code: &str, callback: F)
+    where
+        F: FnMut(&RealResponse) + Send + 'static,
+    {
+        self.callback_tbl
+            .lock()
+            .unwrap()
+            .entry(tr_code.to_owned())
+            .or_default()
+            .push(Box::new(callback));
+
+        let mut dispatcher = self.dispatcher.lock().unwrap();
+        if dispatcher.is_some() {
+            return;
+        }
+
+        let (tx_stop, rx_stop) = crossbeam_channel::bounded::<()>(0);
+        let rx_res = self.rx_res.clone();
+        let registry = self.registry.clone();
+        let callback_tbl = self.callback_tbl.clone();
+        let latency = self.latency.clone();
+
+        let thread = std::thread::spawn(move || loop {
+            crossbeam_channel::select! {
+                recv(rx_res) -> res => {
+                    let res = match res {
+                        Ok(res) => res.decode(&registry, &latency),
+                        Err(_) => break,
+                    };
+
+                    if let Some(callbacks) = callback_tbl.lock().unwrap().get_mut(res.tr_code()) {
+                        for callback in callbacks {
+                            callback(&res);
+                        }
+                    }
+                }
+                recv(rx_stop) -> _ => break,
+            }
+        });
+
+        *dispatcher = Some(Dispatcher {
+            tx_stop: Some(tx_stop),
+            thread: Some(thread),
+        });
+    }
+
+    /// 지정된 TR 코드에 등록된 콜백을 모두 삭제합니다.
+    pub fn off(&self, tr_code: &str) {
+        self.callback_tbl.lock().unwrap().remove(tr_code);
+    }
+
     unsafe extern "system" fn window_proc(
         hwnd: HWND,
         msg: UINT,
@@ -183,15 +638,61 @@ impl RealEvent {
                 assert!(!packet.data.is_null());
                 assert!(packet.data_len >= 0);
 
-                let _ = window_data.tx_res.send(IncompleteRealResponse {
-                    tr_code: decode_euckr(&packet.tr_code),
-                    key: decode_euckr(&packet.key),
+                let received_at = SystemTime::now();
+                let received = Instant::now();
+                let tr_code = decode_text(&packet.tr_code);
+                let key = decode_text(&packet.key);
+
+                if let Some(filter) = &*window_data.filter.read().unwrap() {
+                    if !filter(&tr_code, &key) {
+                        return 0;
+                    }
+                }
+
+                let res = IncompleteRealResponse {
+                    tr_code,
+                    key,
                     data: std::slice::from_raw_parts(
                         packet.data,
                         packet.data_len.try_into().unwrap(),
                     )
                     .to_owned(),
-                });
+                    received_at,
+                    received,
+                };
+
+                // 메시지 처리가 늦어지지 않도록 디코딩은 수신하는 쪽에서 합니다.
+                let route_key = (res.tr_code.clone(), res.key.clone());
+                let tx_route = window_data
+                    .route_tbl
+                    .lock()
+                    .unwrap()
+                    .get(&route_key)
+                    .cloned();
+
+                let res = match tx_route {
+                    Some(tx) => match tx.send(res) {
+                        Ok(()) => return 0,
+                        Err(err) => {
+                            // 채널이 해제된 경우 이후의 응답은 큐에 추가합니다.
+                            let mut route_tbl = window_data.route_tbl.lock().unwrap();
+                            if route_tbl
+                                .get(&route_key)
+                                .is_some_and(|cur| cur.same_channel(&tx))
+                            {
+                                route_tbl.remove(&route_key);
+                            }
+                            err.into_inner()
+                        }
+                    },
+                    None => res,
+                };
+
+                let _ = window_data.tx_res.send(res);
+
+                if let Some(waker) = window_data.waker.lock().unwrap().take() {
+                    waker.wake();
+                }
 
                 0
             }
@@ -200,10 +701,111 @@ impl RealEvent {
     }
 }
 
+/// [`RealEvent::subscribe_routed()`]로 등록한 키의 응답을 수신하는 채널
+///
+/// 응답은 채널에서 가져올 때 디코딩되며, 객체를 해제하면 이후의 응답은
+/// [`RealEvent`]의 큐에 추가됩니다.
+pub struct RoutedReceiver {
+    rx_res: Receiver<IncompleteRealResponse>,
+    registry: LayoutRegistry,
+    latency: Arc<Mutex<Option<LatencyStats>>>,
+}
+
+impl RoutedReceiver {
+    /// 수신한 응답이 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        let res = self.rx_res.try_recv().ok()?;
+        Some(res.decode(&self.registry, &self.latency))
+    }
+
+    /// 응답을 수신할 때까지 기다립니다.
+    ///
+    /// 등록을 해제하여 채널이 닫힌 경우 `None`을 반환합니다.
+    pub fn recv(&self) -> Option<RealResponse> {
+        let res = self.rx_res.recv().ok()?;
+        Some(res.decode(&self.registry, &self.latency))
+    }
+
+    /// 응답을 수신할 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        let res = self.rx_res.recv_timeout(timeout).ok()?;
+        Some(res.decode(&self.registry, &self.latency))
+    }
+}
+
+/// 수신한 응답을 `Stream`으로 가져오는 객체
+///
+/// [`RealEvent::into_stream()`]으로 생성합니다. 객체를 소유하므로 스트림이
+/// 유일한 소비자이며, 등록과 해제는 [`get_ref()`](Self::get_ref)로 합니다.
+///
+/// `on()`으로 콜백을 등록한 이후의 응답과 `subscribe_routed()`로 등록한 키의
+/// 응답은 스트림으로 수신되지 않습니다.
+#[cfg(feature = "futures-core")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "futures-core")))]
+pub struct RealStream {
+    event: RealEvent,
+}
+
+#[cfg(feature = "futures-core")]
+impl RealStream {
+    /// 실시간 TR을 등록하고 해제하기 위한 객체를 반환합니다.
+    pub fn get_ref(&self) -> &RealEvent {
+        &self.event
+    }
+
+    /// 스트림을 다시 객체로 변환합니다.
+    pub fn into_inner(self) -> RealEvent {
+        self.event
+    }
+}
+
+#[cfg(feature = "futures-core")]
+impl futures_core::Stream for RealStream {
+    type Item = RealResponse;
+
+    fn poll_next(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.event.poll_recv(cx)
+    }
+}
+
 impl Drop for RealEvent {
     fn drop(&mut self) {
         if let Some(executor) = &*executor::GLOBAL_EXECUTOR.read().unwrap() {
             executor.unadvise_window(*self.window);
         }
+
+        drop(self.dispatcher.lock().unwrap().take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Subscriptions;
+
+    #[test]
+    fn test_subscriptions() {
+        let mut subscriptions = Subscriptions::default();
+
+        assert_eq!(
+            subscriptions.insert("S3_", &["005930", "000660"]),
+            ["005930", "000660"]
+        );
+        assert_eq!(
+            subscriptions.insert("S3_", &["005930", "035420"]),
+            ["035420"]
+        );
+        assert!(subscriptions.contains("S3_", "000660"));
+        assert!(!subscriptions.contains("H1_", "000660"));
+
+        let (added, removed) = subscriptions.diff("S3_", &["005930", "051910"]);
+        assert_eq!(added, ["051910"]);
+        assert_eq!(removed, ["000660", "035420"]);
+
+        assert_eq!(
+            subscriptions.remove("S3_", &["000660", "999999"]),
+            ["000660"]
+        );
+        assert_eq!(subscriptions.remove("S3_", &["005930", "035420"]).len(), 2);
+        assert!(subscriptions.is_empty());
     }
 }
@@ -0,0 +1,382 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 주문 체결 모듈
+//!
+//! 실시간 TR인 SC0(접수), SC1(체결), SC2(정정), SC3(취소), SC4(거부)를 타입이
+//! 지정된 이벤트로 변환하고, 이를 주문과 연관지어 주문 상태를 추적합니다.
+//!
+//! 해당 실시간 TR은 키 없이 등록하며 로그인한 계정의 모든 주문에 대해
+//! 수신됩니다.
+
+use super::order::Side;
+use super::RealResponse;
+use crate::data::{self, Data, DecodeError};
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 주문 체결 이벤트 종류
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExecutionKind {
+    /// 주문 접수 (SC0)
+    Accepted,
+    /// 주문 체결 (SC1)
+    Filled,
+    /// 정정 확인 (SC2)
+    Modified,
+    /// 취소 확인 (SC3)
+    Cancelled,
+    /// 주문 거부 (SC4)
+    Rejected,
+}
+
+impl ExecutionKind {
+    /// TR 코드에 해당하는 이벤트 종류를 반환합니다.
+    pub fn from_tr_code(tr_code: &str) -> Option<Self> {
+        match tr_code {
+            "SC0" => Some(Self::Accepted),
+            "SC1" => Some(Self::Filled),
+            "SC2" => Some(Self::Modified),
+            "SC3" => Some(Self::Cancelled),
+            "SC4" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// 주문 체결 이벤트
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExecutionEvent {
+    /// 이벤트 종류
+    pub kind: ExecutionKind,
+    /// 주문번호
+    pub order_no: u64,
+    /// 원주문번호
+    ///
+    /// 정정 및 취소 주문인 경우에만 존재합니다.
+    pub orig_order_no: Option<u64>,
+    /// 종목번호
+    pub symbol: String,
+    /// 매매 구분
+    pub side: Side,
+    /// 이벤트에 해당하는 수량
+    ///
+    /// 접수는 주문 수량, 체결은 체결 수량, 정정은 정정 확인 수량, 취소는 취소
+    /// 확인 수량, 거부는 거부 수량입니다.
+    pub qty: u64,
+    /// 이벤트에 해당하는 가격
+    ///
+    /// 접수 및 정정은 주문 가격, 체결은 체결 가격입니다. 그 외에는 0입니다.
+    pub price: f64,
+    /// 미체결 수량
+    ///
+    /// 접수 이벤트에는 존재하지 않습니다.
+    pub remaining_qty: Option<u64>,
+}
+
+impl ExecutionEvent {
+    /// 수신한 실시간 응답을 이벤트로 변환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Self, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 이벤트로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        let kind = ExecutionKind::from_tr_code(&data.tr_code)
+            .ok_or_else(|| DecodeError::UnknownLayout(data.tr_code.clone()))?;
+
+        let block_name = "OutBlock";
+        let block = data::get_block(data, block_name)?;
+        let parse_u64 = |field| data::parse_field::<u64>(block, block_name, field);
+        let parse_f64 = |field| data::parse_field::<f64>(block, block_name, field);
+
+        let orig_order_no = Some(parse_u64("orgordno")?).filter(|&no| no != 0);
+        let side = block
+            .get("bnstp")
+            .and_then(|s| Side::from_text(s))
+            .ok_or_else(|| DecodeError::InvalidField {
+                block: block_name.to_owned(),
+                field: "bnstp".to_owned(),
+            })?;
+
+        if kind == ExecutionKind::Accepted {
+            return Ok(Self {
+                kind,
+                order_no: parse_u64("ordno")?,
+                orig_order_no,
+                symbol: data::parse_field(block, block_name, "shtcode")?,
+                side,
+                qty: parse_u64("ordqty")?,
+                price: parse_f64("ordprice")?,
+                remaining_qty: None,
+            });
+        }
+
+        let (qty, price) = match kind {
+            ExecutionKind::Filled => (parse_u64("execqty")?, parse_f64("execprc")?),
+            ExecutionKind::Modified => (parse_u64("mdfycnfqty")?, parse_f64("ordprc")?),
+            ExecutionKind::Cancelled => (parse_u64("canccnfqty")?, 0.0),
+            ExecutionKind::Rejected => (parse_u64("rjtqty")?, 0.0),
+            ExecutionKind::Accepted => unreachable!(),
+        };
+
+        Ok(Self {
+            kind,
+            order_no: parse_u64("ordno")?,
+            orig_order_no,
+            symbol: data::parse_field(block, block_name, "shtnIsuno")?,
+            side,
+            qty,
+            price,
+            remaining_qty: Some(parse_u64("unercqty")?),
+        })
+    }
+}
+
+/// 주문 상태
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OrderState {
+    /// 접수됨
+    Accepted,
+    /// 일부 체결됨
+    PartiallyFilled,
+    /// 모두 체결됨
+    Filled,
+    /// 정정되어 새로운 주문으로 대체됨
+    Modified,
+    /// 취소됨
+    Cancelled,
+    /// 거부됨
+    Rejected,
+}
+
+impl OrderState {
+    /// 더 이상 상태가 변하지 않는지 여부를 반환합니다.
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Modified | Self::Cancelled | Self::Rejected
+        )
+    }
+}
+
+/// 추적 중인 주문
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrackedOrder {
+    /// 주문번호
+    pub order_no: u64,
+    /// 종목번호
+    pub symbol: String,
+    /// 주문 수량
+    pub qty: u64,
+    /// 누적 체결 수량
+    pub filled_qty: u64,
+    /// 미체결 수량
+    pub remaining_qty: u64,
+    /// 주문 상태
+    pub state: OrderState,
+}
+
+/// 주문 상태 추적 객체
+///
+/// [`track()`](Self::track)으로 제출한 주문을 등록하거나 접수 이벤트를 통해
+/// 자동으로 등록된 주문에 대해 체결 이벤트를 적용하여 주문 상태를 갱신합니다.
+#[derive(Clone, Debug, Default)]
+pub struct OrderTracker {
+    orders: HashMap<u64, TrackedOrder>,
+}
+
+impl OrderTracker {
+    /// 객체를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 제출한 주문을 추적 대상으로 등록합니다.
+    ///
+    /// 이미 등록된 주문인 경우 아무런 동작을 하지 않습니다.
+    pub fn track(&mut self, order_no: u64, symbol: &str, qty: u64) {
+        self.orders.entry(order_no).or_insert_with(|| TrackedOrder {
+            order_no,
+            symbol: symbol.to_owned(),
+            qty,
+            filled_qty: 0,
+            remaining_qty: qty,
+            state: OrderState::Accepted,
+        });
+    }
+
+    /// 이벤트를 적용하고 상태가 갱신된 주문을 반환합니다.
+    ///
+    /// 정정 및 취소 확인 이벤트는 원주문의 상태를 갱신하며, 정정된 경우 새로운
+    /// 주문을 추적 대상으로 등록합니다.
+    pub fn apply(&mut self, event: &ExecutionEvent) -> Option<&TrackedOrder> {
+        match event.kind {
+            ExecutionKind::Accepted => {
+                // 정정 및 취소 주문의 접수는 확인 이벤트에서 처리합니다.
+                if event.orig_order_no.is_some() {
+                    return None;
+                }
+
+                self.track(event.order_no, &event.symbol, event.qty);
+                self.orders.get(&event.order_no)
+            }
+            ExecutionKind::Filled => {
+                let order = self.orders.get_mut(&event.order_no)?;
+
+                order.filled_qty += event.qty;
+                order.remaining_qty = event
+                    .remaining_qty
+                    .unwrap_or_else(|| order.qty.saturating_sub(order.filled_qty));
+                order.state = if order.remaining_qty == 0 {
+                    OrderState::Filled
+                } else {
+                    OrderState::PartiallyFilled
+                };
+
+                Some(order)
+            }
+            ExecutionKind::Modified => {
+                let orig_order_no = event.orig_order_no?;
+
+                if let Some(orig) = self.orders.get_mut(&orig_order_no) {
+                    orig.remaining_qty = orig.remaining_qty.saturating_sub(event.qty);
+                    if orig.remaining_qty == 0 {
+                        orig.state = OrderState::Modified;
+                    }
+                }
+
+                self.track(event.order_no, &event.symbol, event.qty);
+                self.orders.get(&event.order_no)
+            }
+            ExecutionKind::Cancelled => {
+                let order = self.orders.get_mut(&event.orig_order_no?)?;
+
+                order.remaining_qty = order.remaining_qty.saturating_sub(event.qty);
+                if order.remaining_qty == 0 {
+                    order.state = OrderState::Cancelled;
+                }
+
+                Some(order)
+            }
+            ExecutionKind::Rejected => {
+                let order = self.orders.get_mut(&event.order_no)?;
+                order.state = OrderState::Rejected;
+
+                Some(order)
+            }
+        }
+    }
+
+    /// 주문번호에 해당하는 주문을 반환합니다.
+    pub fn get(&self, order_no: u64) -> Option<&TrackedOrder> {
+        self.orders.get(&order_no)
+    }
+
+    /// 추적 중인 주문 목록을 반환합니다.
+    pub fn orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values()
+    }
+
+    /// 미체결 주문 목록을 반환합니다.
+    pub fn open_orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values().filter(|o| !o.state.is_closed())
+    }
+
+    /// 더 이상 상태가 변하지 않는 주문을 추적 대상에서 제외합니다.
+    pub fn remove_closed(&mut self) {
+        self.orders.retain(|_, o| !o.state.is_closed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecutionEvent, ExecutionKind, OrderState, OrderTracker, Side};
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+
+    fn event(
+        kind: ExecutionKind,
+        order_no: u64,
+        orig_order_no: Option<u64>,
+        qty: u64,
+        remaining_qty: Option<u64>,
+    ) -> ExecutionEvent {
+        ExecutionEvent {
+            kind,
+            order_no,
+            orig_order_no,
+            symbol: "005930".into(),
+            side: Side::Buy,
+            qty,
+            price: 0.0,
+            remaining_qty,
+        }
+    }
+
+    #[test]
+    fn test_execution_event_from_data() {
+        let data = Data {
+            tr_code: "SC1".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "OutBlock" => Block::Block(hashmap! {
+                    "ordno" => "0000012345",
+                    "orgordno" => "0000000000",
+                    "shtnIsuno" => "A005930",
+                    "bnstp" => "2",
+                    "execqty" => "0000000003",
+                    "execprc" => "00090700",
+                    "unercqty" => "0000000007",
+                }),
+            },
+        };
+
+        assert_eq!(
+            ExecutionEvent::from_data(&data).unwrap(),
+            ExecutionEvent {
+                kind: ExecutionKind::Filled,
+                order_no: 12345,
+                orig_order_no: None,
+                symbol: "A005930".into(),
+                side: Side::Buy,
+                qty: 3,
+                price: 90700.0,
+                remaining_qty: Some(7),
+            }
+        );
+    }
+
+    #[test]
+    fn test_order_tracker() {
+        let mut tracker = OrderTracker::new();
+
+        tracker.apply(&event(ExecutionKind::Accepted, 1, None, 10, None));
+        assert_eq!(tracker.get(1).unwrap().state, OrderState::Accepted);
+
+        tracker.apply(&event(ExecutionKind::Filled, 1, None, 4, Some(6)));
+        assert_eq!(tracker.get(1).unwrap().state, OrderState::PartiallyFilled);
+        assert_eq!(tracker.get(1).unwrap().filled_qty, 4);
+
+        tracker.apply(&event(ExecutionKind::Modified, 2, Some(1), 6, Some(6)));
+        assert_eq!(tracker.get(1).unwrap().state, OrderState::Modified);
+        assert_eq!(tracker.get(2).unwrap().state, OrderState::Accepted);
+
+        tracker.apply(&event(ExecutionKind::Filled, 2, None, 6, Some(0)));
+        assert_eq!(tracker.get(2).unwrap().state, OrderState::Filled);
+
+        tracker.track(3, "005930", 5);
+        tracker.apply(&event(ExecutionKind::Cancelled, 4, Some(3), 5, Some(0)));
+        assert_eq!(tracker.get(3).unwrap().state, OrderState::Cancelled);
+        assert_eq!(tracker.open_orders().count(), 0);
+
+        tracker.remove_closed();
+        assert_eq!(tracker.orders().count(), 0);
+    }
+}
@@ -1,12 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use super::entry::Entry;
-use super::{Account, Error, LoadError};
+use super::entry::{Api, Entry};
+use super::mock::MockEntry;
+use super::{backend::MockBackend, Account, Error, LoadError, TrLimits};
 
 use lazy_static::lazy_static;
 use std::sync::atomic::{AtomicPtr, Ordering};
-use std::sync::{mpsc, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use std::{ffi::CString, ops::Deref, path::PathBuf, pin::Pin, thread::JoinHandle, time::Duration};
+use std::sync::{mpsc, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::{ffi::CString, ops::Deref, path::PathBuf, thread::JoinHandle, time::Duration};
 
 use winapi::shared::minwindef::{LPARAM, LRESULT, TRUE, UINT, WPARAM};
 use winapi::shared::windef::HWND;
@@ -48,6 +49,17 @@ pub(crate) fn load(path: Option<PathBuf>) -> Result<(), LoadError> {
     Ok(())
 }
 
+pub(crate) fn load_mock(backend: Arc<MockBackend>) -> Result<(), LoadError> {
+    let mut executor = GLOBAL_EXECUTOR.write().unwrap();
+    if executor.is_none() {
+        *executor = Some(Executor::with_api(move || {
+            Ok(Box::new(MockEntry::new(backend)))
+        })?);
+    }
+
+    Ok(())
+}
+
 pub(crate) fn unload() {
     *GLOBAL_EXECUTOR.write().unwrap() = None;
 }
@@ -84,8 +96,8 @@ define_req! {
 
     Request(usize, String, Vec<u8>, Option<String>, Duration) -> Result<i32, Error>
 
-    AdviseRealData(usize, String, Vec<String>) -> ()
-    UnadviseRealData(usize, String, Vec<String>) -> ()
+    AdviseRealData(usize, String, Vec<String>) -> Vec<bool>
+    UnadviseRealData(usize, String, Vec<String>) -> Vec<bool>
     UnadviseWindow(usize) -> bool
 
     Accounts() -> Vec<Account>
@@ -95,10 +107,14 @@ define_req! {
     GetUseOverFuture() -> bool
     GetUseFx() -> bool
 
-    GetTrCountPerSec(String) -> Option<i32>
-    GetTrCountBaseSec(String) -> Option<i32>
-    GetTrCountRequest(String) -> Option<i32>
-    GetTrCountLimit(String) -> Option<i32>
+    GetTrLimits(String) -> Option<TrLimits>
+
+    RequestLinkToHts(usize, String, String) -> bool
+    AdviseLinkFromHts(usize) -> ()
+    UnadviseLinkFromHts(usize) -> ()
+
+    RequestService(usize, String, Vec<u8>) -> Result<i32, Error>
+    RemoveService(usize, String, String) -> Result<(), Error>
 }
 
 // 호출 요청을 보내는 매크로입니다.
@@ -168,11 +184,11 @@ impl ExecutorHandle {
         req!(self, Request(hwnd, tr_code, data, next_key, timeout))
     }
 
-    pub fn advise_real_data(&self, hwnd: usize, tr_code: &str, keys: Vec<String>) {
+    pub fn advise_real_data(&self, hwnd: usize, tr_code: &str, keys: Vec<String>) -> Vec<bool> {
         req!(self, AdviseRealData(hwnd, tr_code, keys))
     }
 
-    pub fn unadvise_real_data(&self, hwnd: usize, tr_code: &str, keys: Vec<String>) {
+    pub fn unadvise_real_data(&self, hwnd: usize, tr_code: &str, keys: Vec<String>) -> Vec<bool> {
         req!(self, UnadviseRealData(hwnd, tr_code, keys))
     }
 
@@ -195,17 +211,28 @@ impl ExecutorHandle {
         req!(self, GetUseFx())
     }
 
-    pub fn get_tr_count_per_sec(&self, tr_code: &str) -> Option<i32> {
-        req!(self, GetTrCountPerSec(tr_code))
+    pub fn get_tr_limits(&self, tr_code: &str) -> Option<TrLimits> {
+        req!(self, GetTrLimits(tr_code))
+    }
+
+    pub fn request_link_to_hts(&self, hwnd: usize, link_name: &str, data: &str) -> bool {
+        req!(self, RequestLinkToHts(hwnd, link_name, data))
     }
-    pub fn get_tr_count_base_sec(&self, tr_code: &str) -> Option<i32> {
-        req!(self, GetTrCountBaseSec(tr_code))
+
+    pub fn advise_link_from_hts(&self, hwnd: usize) {
+        req!(self, AdviseLinkFromHts(hwnd))
     }
-    pub fn get_tr_count_request(&self, tr_code: &str) -> Option<i32> {
-        req!(self, GetTrCountRequest(tr_code))
+
+    pub fn unadvise_link_from_hts(&self, hwnd: usize) {
+        req!(self, UnadviseLinkFromHts(hwnd))
+    }
+
+    pub fn request_service(&self, hwnd: usize, tr_code: &str, data: Vec<u8>) -> Result<i32, Error> {
+        req!(self, RequestService(hwnd, tr_code, data))
     }
-    pub fn get_tr_count_limit(&self, tr_code: &str) -> Option<i32> {
-        req!(self, GetTrCountLimit(tr_code))
+
+    pub fn remove_service(&self, hwnd: usize, tr_code: &str, data: &str) -> Result<(), Error> {
+        req!(self, RemoveService(hwnd, tr_code, data))
     }
 }
 
@@ -229,7 +256,7 @@ lazy_static! {
 }
 
 struct ExecutorWindowData {
-    entry: Pin<Box<Entry>>,
+    entry: Box<dyn Api>,
 }
 
 pub(crate) struct Executor {
@@ -245,9 +272,9 @@ impl Executor {
     }
 
     #[allow(clippy::needless_lifetimes)]
-    pub fn entry<'a>(&'a self) -> &'a Entry {
+    pub fn entry<'a>(&'a self) -> &'a dyn Api {
         debug_assert!(Self::is_executor_thread());
-        unsafe { &(*self.window_data.load(Ordering::Relaxed)).entry }
+        unsafe { &*(*self.window_data.load(Ordering::Relaxed)).entry }
     }
 
     pub fn handle(&self) -> RwLockReadGuard<ExecutorHandle> {
@@ -271,15 +298,25 @@ impl Executor {
     }
 
     pub fn new(path: Option<PathBuf>) -> Result<Self, LoadError> {
+        Self::with_api(move || -> Result<Box<dyn Api>, LoadError> {
+            Ok(Box::new(if let Some(path) = path.as_deref() {
+                Entry::new_with_path(path)?
+            } else {
+                Entry::new()?
+            }))
+        })
+    }
+
+    // 실행기 스레드에서 호출할 함수를 불러오는 클로저로 객체를 생성합니다.
+    pub fn with_api<F>(load_api: F) -> Result<Self, LoadError>
+    where
+        F: FnOnce() -> Result<Box<dyn Api>, LoadError> + Send + 'static,
+    {
         let (tx_result, rx_result) = mpsc::sync_channel(1);
 
         let thread_main = move || {
             let load = || -> Result<_, LoadError> {
-                let entry = Pin::new(Box::new(if let Some(path) = path.as_deref() {
-                    Entry::new_with_path(path)?
-                } else {
-                    Entry::new()?
-                }));
+                let entry = load_api()?;
 
                 let window_data = Box::new(ExecutorWindowData { entry });
 
@@ -380,7 +417,7 @@ impl Executor {
                 assert_ne!(lparam, 0);
 
                 let req = Box::from_raw(lparam as *mut CallReq);
-                Self::on_request(&window_data.entry, *req);
+                Self::on_request(&*window_data.entry, *req);
 
                 0
             }
@@ -388,7 +425,7 @@ impl Executor {
         }
     }
 
-    fn on_request(entry: &Entry, req: CallReq) {
+    fn on_request(entry: &dyn Api, req: CallReq) {
         macro_rules! match_req {
             ($($func:ident($($arg:ident),*) => $code:expr$(,)?)*) => {
                 match req {
@@ -452,17 +489,19 @@ impl Executor {
             GetServerName() => entry.get_server_name(),
             GetUseOverFuture() => entry.get_use_over_future(),
             GetUseFx() => entry.get_use_fx(),
-            GetTrCountPerSec(tr_code) => {
-                entry.get_tr_count_per_sec(&tr_code)
+            GetTrLimits(tr_code) => {
+                entry.get_tr_limits(&tr_code)
             }
-            GetTrCountBaseSec(tr_code) => {
-                entry.get_tr_count_base_sec(&tr_code)
+            RequestLinkToHts(hwnd, link_name, data) => {
+                entry.request_link_to_hts(hwnd, &link_name, &data)
             }
-            GetTrCountRequest(tr_code) => {
-                entry.get_tr_count_request(&tr_code)
+            AdviseLinkFromHts(hwnd) => entry.advise_link_from_hts(hwnd),
+            UnadviseLinkFromHts(hwnd) => entry.unadvise_link_from_hts(hwnd),
+            RequestService(hwnd, tr_code, data) => {
+                entry.request_service(hwnd, &tr_code, &data)
             }
-            GetTrCountLimit(tr_code) => {
-                entry.get_tr_count_limit(&tr_code)
+            RemoveService(hwnd, tr_code, data) => {
+                entry.remove_service(hwnd, &tr_code, &data)
             }
         }
     }
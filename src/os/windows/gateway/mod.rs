@@ -0,0 +1,560 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 웹소켓 게이트웨이 모듈
+//!
+//! 다른 호스트에서 실행 중인 전략 프로세스가 웹소켓으로 조회 TR을 요청하고
+//! 실시간 TR을 수신할 수 있도록 중계합니다. 웹소켓 프로토콜은 `tungstenite`
+//! 크레이트로 처리하며, 모든 메시지는 JSON 텍스트 프레임입니다.
+//!
+//! ## 요청
+//! ```text
+//! {"id":1,"type":"request","tr_code":"t1102","blocks":{"t1102InBlock":{"shcode":"005930"}}}
+//! {"type":"subscribe","tr_code":"S3_","keys":["005930"]}
+//! {"type":"unsubscribe","tr_code":"S3_","keys":["005930"]}
+//! ```
+//!
+//! 조회 요청의 `blocks`에 지정하지 않은 단일 블록의 필드는 빈 문자열로
+//! 요청하며, `next_key`로 연속 조회 키를 지정할 수 있습니다.
+//!
+//! ## 응답
+//! ```text
+//! {"id":1,"type":"response","code":"00000","message":"...","elapsed_ms":12,"next_key":null,"data":{...}}
+//! {"id":1,"type":"error","message":"..."}
+//! {"type":"real","tr_code":"S3_","key":"005930","data":{...}}
+//! ```
+//!
+//! 요청에 지정한 `id`는 응답에 그대로 포함되며, 지정하지 않은 경우
+//! `null`입니다.
+//!
+//! 기본적으로 헤더 타입이 B인 계좌 및 주문 TR은 요청할 수 없습니다.
+//! [`Gateway::with_allowed_trs()`]로 요청 및 구독할 수 있는 TR을 지정하고,
+//! [`Gateway::with_auth()`]로 핸드셰이크의 `Authorization` 헤더를 검사할 수
+//! 있습니다.
+
+pub use super::access::AuthHook;
+
+use super::access::{Access, DeadlineStream};
+use super::backend::Backend;
+use super::{RealEvent, RealResponse};
+use crate::data::json::{self, Value};
+use crate::layout::TrLayout;
+
+use crossbeam_channel::{Receiver, Sender};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::protocol::WebSocketConfig;
+use tungstenite::{http, Message, WebSocket};
+
+use std::collections::HashMap;
+use std::io::{self, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+// 수신한 메시지가 없을 때 보낼 실시간 응답을 확인하는 간격
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+// 수신하는 메시지의 최대 크기
+const MAX_MESSAGE_LEN: usize = 1024 * 1024;
+
+/// 웹소켓 게이트웨이 서버
+pub struct Gateway {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    backend: Arc<dyn Backend>,
+    layout_tbl: HashMap<String, Arc<TrLayout>>,
+    timeout: Duration,
+    access: Access,
+}
+
+impl Gateway {
+    /// 지정된 주소에서 연결을 받는 서버를 생성합니다.
+    ///
+    /// 조회 TR은 `backend`로 요청하며, 요청 및 실시간 TR에 사용할 레이아웃을
+    /// 모두 지정해야 합니다. 조회 TR의 응답을 기다리는 시간은 30초입니다.
+    pub fn bind<A: ToSocketAddrs>(
+        addr: A,
+        backend: Arc<dyn Backend>,
+        layout_tbl: HashMap<String, Arc<TrLayout>>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            shared: Arc::new(Shared {
+                backend,
+                layout_tbl,
+                timeout: Duration::from_secs(30),
+                access: Access::default(),
+            }),
+        })
+    }
+
+    fn shared_mut(&mut self) -> &mut Shared {
+        Arc::get_mut(&mut self.shared).unwrap()
+    }
+
+    /// 조회 TR의 응답을 기다리는 시간을 변경합니다.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.shared_mut().timeout = timeout;
+        self
+    }
+
+    /// 핸드셰이크를 끝내고 메시지를 보내는 데 허용하는 시간을 변경합니다.
+    ///
+    /// 기본값은 10초이며, 핸드셰이크를 끝내지 않거나 메시지를 받지 않는
+    /// 연결은 이 시간이 지나면 종료됩니다.
+    pub fn with_io_timeout(mut self, io_timeout: Duration) -> Self {
+        self.shared_mut().access.io_timeout = io_timeout;
+        self
+    }
+
+    /// 동시에 처리하는 최대 연결 개수를 변경합니다.
+    ///
+    /// 기본값은 64개이며, 초과한 연결에는 503 상태 코드로 응답합니다.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.shared_mut().access.max_connections = max_connections;
+        self
+    }
+
+    /// 요청 및 구독할 수 있는 TR 코드를 지정합니다.
+    ///
+    /// 지정하지 않은 경우 헤더 타입이 B인 계좌 및 주문 TR을 제외한 모든
+    /// 레이아웃의 TR을 사용할 수 있습니다. 지정한 경우 실시간 TR도 포함해야
+    /// 합니다.
+    pub fn with_allowed_trs<I, S>(mut self, tr_codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.shared_mut().access.allowed_trs = Some(tr_codes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// 핸드셰이크 요청의 `Authorization` 헤더를 검사할 함수를 지정합니다.
+    ///
+    /// 함수가 거짓을 반환한 연결은 401 상태 코드로 거부합니다.
+    pub fn with_auth(mut self, auth: AuthHook) -> Self {
+        self.shared_mut().access.auth = Some(auth);
+        self
+    }
+
+    /// 서버의 주소를 반환합니다.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// 연결을 받아 처리합니다.
+    ///
+    /// 연결마다 스레드를 생성하며, 연결을 받는 데 실패하기 전까지 반환하지
+    /// 않습니다.
+    pub fn serve(&self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+
+            let slot = match self.shared.access.try_connect() {
+                Some(slot) => slot,
+                None => {
+                    let _ = reject_connection(stream, &self.shared);
+                    continue;
+                }
+            };
+            let shared = self.shared.clone();
+
+            std::thread::spawn(move || {
+                if let Ok(mut conn) = Connection::new(stream, shared) {
+                    let _ = conn.run();
+                }
+                drop(slot);
+            });
+        }
+    }
+}
+
+struct Connection {
+    socket: WebSocket<DeadlineStream>,
+    shared: Arc<Shared>,
+    real: Option<RealEvent>,
+    // 실시간 응답은 연결을 처리하는 스레드에서 보냅니다.
+    tx_real: Sender<String>,
+    rx_real: Receiver<String>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream, shared: Arc<Shared>) -> Result<Self, tungstenite::Error> {
+        // 핸드셰이크를 끝내지 않는 연결은 시간이 지나면 종료합니다.
+        stream.set_write_timeout(Some(shared.access.io_timeout))?;
+        let stream = DeadlineStream::new(stream, shared.access.io_timeout);
+        // 에러 응답의 타입은 `tungstenite`에서 정해집니다.
+        #[allow(clippy::result_large_err)]
+        let authorize = |request: &Request, response: Response| {
+            let authorization = request
+                .headers()
+                .get(http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+
+            if shared.access.is_authorized(authorization) {
+                Ok(response)
+            } else {
+                Err(error_response(http::StatusCode::UNAUTHORIZED))
+            }
+        };
+        let config = WebSocketConfig::default()
+            .max_message_size(Some(MAX_MESSAGE_LEN))
+            .max_frame_size(Some(MAX_MESSAGE_LEN));
+
+        let mut socket = tungstenite::accept_hdr_with_config(stream, authorize, Some(config))
+            .map_err(|err| match err {
+                tungstenite::HandshakeError::Failure(err) => err,
+                tungstenite::HandshakeError::Interrupted(_) => {
+                    tungstenite::Error::Io(ErrorKind::TimedOut.into())
+                }
+            })?;
+        socket.get_mut().clear_deadline(Some(POLL_INTERVAL))?;
+
+        let (tx_real, rx_real) = crossbeam_channel::unbounded();
+
+        Ok(Self {
+            socket,
+            shared,
+            real: None,
+            tx_real,
+            rx_real,
+        })
+    }
+
+    fn run(&mut self) -> Result<(), tungstenite::Error> {
+        loop {
+            while let Ok(text) = self.rx_real.try_recv() {
+                self.socket.send(Message::text(text))?;
+            }
+
+            // 핑과 종료 메시지에 대한 응답은 자동으로 보내집니다.
+            match self.socket.read() {
+                Ok(Message::Text(text)) => {
+                    if let Some(reply) = self.handle(text.as_str()) {
+                        self.socket.send(Message::text(reply))?;
+                    }
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    // 요청을 처리하고 응답할 메시지를 반환합니다.
+    fn handle(&mut self, text: &str) -> Option<String> {
+        let msg = match json::parse(text) {
+            Ok(msg) => msg,
+            Err(err) => return Some(error_message(None, &err.to_string())),
+        };
+        let id = msg.get("id");
+
+        let result = match msg.get("type").and_then(Value::as_str) {
+            Some("request") => self.request(&msg).map(Some),
+            Some("subscribe") => self.subscribe(&msg, true).map(|_| None),
+            Some("unsubscribe") => self.subscribe(&msg, false).map(|_| None),
+            _ => Err("unknown message type".into()),
+        };
+
+        match result {
+            Ok(Some(body)) => {
+                let mut reply = String::from("{\"id\":");
+                write_id(&mut reply, id);
+                reply.push_str(",\"type\":\"response\",");
+                reply.push_str(&body);
+                reply.push('}');
+                Some(reply)
+            }
+            Ok(None) => None,
+            Err(err) => Some(error_message(id, &err)),
+        }
+    }
+
+    fn request(&self, msg: &Value) -> Result<String, String> {
+        let tr_layout = self.layout(msg)?;
+        if !self.shared.access.is_allowed(tr_layout) {
+            return Err(format!("tr code not allowed: {}", tr_layout.code));
+        }
+
+        let data = json::to_input(
+            tr_layout,
+            msg.get("blocks").unwrap_or(&Value::Object(vec![])),
+        )
+        .map_err(|err| err.to_string())?;
+        let next_key = msg.get("next_key").and_then(Value::as_str);
+
+        let res = self
+            .shared
+            .backend
+            .request(&data, tr_layout, next_key, self.shared.timeout)
+            .map_err(|err| err.to_string())?;
+
+        let mut body = String::new();
+        res.write_json_members(&mut body)
+            .map_err(|err| err.to_string())?;
+
+        Ok(body)
+    }
+
+    fn subscribe(&mut self, msg: &Value, subscribe: bool) -> Result<(), String> {
+        let tr_layout = self.layout(msg)?.clone();
+        if !self.shared.access.is_allowed(&tr_layout) {
+            return Err(format!("tr code not allowed: {}", tr_layout.code));
+        }
+        let keys: Vec<&str> = msg
+            .get("keys")
+            .and_then(Value::as_array)
+            .and_then(|keys| keys.iter().map(Value::as_str).collect())
+            .ok_or("keys must be an array of strings")?;
+
+        if !subscribe {
+            if let Some(real) = &self.real {
+                real.unsubscribe(&tr_layout.code, &keys);
+            }
+            return Ok(());
+        }
+
+        if self.real.is_none() {
+            self.real = Some(RealEvent::new().map_err(|err| err.to_string())?);
+        }
+        let real = self.real.as_ref().unwrap();
+
+        if real.current().get(&tr_layout.code).is_none() {
+            let tr_code = tr_layout.code.clone();
+            real.insert_layout(tr_layout);
+            real.off(&tr_code);
+
+            let tx_real = self.tx_real.clone();
+            real.on(&tr_code, move |res| {
+                if let Some(text) = real_message(res) {
+                    let _ = tx_real.send(text);
+                }
+            });
+
+            real.subscribe(&tr_code, &keys);
+        } else {
+            real.subscribe(&tr_layout.code, &keys);
+        }
+
+        Ok(())
+    }
+
+    fn layout(&self, msg: &Value) -> Result<&Arc<TrLayout>, String> {
+        let tr_code = msg
+            .get("tr_code")
+            .and_then(Value::as_str)
+            .ok_or("missing tr_code")?;
+
+        self.shared
+            .layout_tbl
+            .get(tr_code)
+            .ok_or_else(|| format!("unknown tr code: {}", tr_code))
+    }
+}
+
+fn error_response(status: http::StatusCode) -> ErrorResponse {
+    let mut response = ErrorResponse::new(None);
+    *response.status_mut() = status;
+    response
+}
+
+// 처리할 수 있는 연결 개수를 초과한 연결을 거부합니다.
+fn reject_connection(mut stream: TcpStream, shared: &Shared) -> io::Result<()> {
+    stream.set_write_timeout(Some(shared.access.io_timeout))?;
+    stream.write_all(
+        b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    )
+}
+
+// 요청의 `id`를 그대로 씁니다.
+fn write_id(out: &mut String, id: Option<&Value>) {
+    match id {
+        Some(Value::Number(id)) => out.push_str(id),
+        Some(Value::String(id)) => json::write_str(out, id),
+        _ => out.push_str("null"),
+    }
+}
+
+fn error_message(id: Option<&Value>, message: &str) -> String {
+    let mut out = String::from("{\"id\":");
+    write_id(&mut out, id);
+    out.push_str(",\"type\":\"error\",\"message\":");
+    json::write_str(&mut out, message);
+    out.push('}');
+    out
+}
+
+// 디코딩에 실패한 실시간 응답은 보내지 않습니다.
+fn real_message(res: &RealResponse) -> Option<String> {
+    let data = res.data().ok()?;
+
+    let mut out = String::from("{\"type\":\"real\",\"tr_code\":");
+    json::write_str(&mut out, res.tr_code());
+    out.push_str(",\"key\":");
+    json::write_str(&mut out, res.key());
+    out.push_str(",\"data\":");
+    json::write_data(&mut out, data);
+    out.push('}');
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gateway;
+    use crate::backend::MockBackend;
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+    use crate::layout::TrLayout;
+    use crate::os::testdata::t1102_layout;
+
+    use tungstenite::client::IntoClientRequest;
+    use tungstenite::Message;
+
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_gateway() {
+        let tr_layout = t1102_layout();
+        let layout_tbl: HashMap<_, _> = [("t1102".to_owned(), Arc::new(tr_layout))].into();
+
+        let backend = Arc::new(MockBackend::new(layout_tbl.clone()));
+        backend
+            .push_response(
+                &Data {
+                    tr_code: "t1102".into(),
+                    data_type: DataType::Output,
+                    blocks: hashmap! {
+                        "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+                    },
+                },
+                None,
+            )
+            .unwrap();
+
+        let gateway = Gateway::bind("127.0.0.1:0", backend.clone(), layout_tbl).unwrap();
+        let addr = gateway.local_addr().unwrap();
+        std::thread::spawn(move || gateway.serve());
+
+        let stream = TcpStream::connect(addr).unwrap();
+        let (mut socket, _) = tungstenite::client(format!("ws://{}/", addr), stream).unwrap();
+        let mut request = |text: &str| {
+            socket.send(Message::text(text)).unwrap();
+            socket.read().unwrap().into_text().unwrap().to_string()
+        };
+
+        assert_eq!(
+            request(
+                r#"{"id":7,"type":"request","tr_code":"t1102","blocks":{"t1102InBlock":{"shcode":"005930"}}}"#
+            ),
+            concat!(
+                r#"{"id":7,"type":"response","code":"00000","message":"조회완료","elapsed_ms":0,"#,
+                r#""next_key":null,"data":{"tr_code":"t1102","data_type":"output","#,
+                r#""blocks":{"t1102OutBlock":{"price":"91000"}}}}"#,
+            )
+        );
+        assert_eq!(
+            backend.requests()[0].blocks["t1102InBlock"],
+            Block::Block(hashmap! { "shcode" => "005930" })
+        );
+
+        assert_eq!(
+            request(r#"{"id":"a","type":"request","tr_code":"t9999"}"#),
+            r#"{"id":"a","type":"error","message":"unknown tr code: t9999"}"#
+        );
+
+        assert!(request("{").contains(r#""type":"error""#));
+
+        socket.close(None).unwrap();
+    }
+
+    #[test]
+    fn test_gateway_access() {
+        let order_layout: TrLayout = "
+            BEGIN_FUNCTION_MAP
+                .Func,현물정상주문,CSPAT00600,block,headtype=B;
+                BEGIN_DATA_MAP
+                CSPAT00600InBlock1,In(*EMPTY*),input;
+                begin
+                    종목번호,IsuNo,IsuNo,char,12;
+                end
+                END_DATA_MAP
+            END_FUNCTION_MAP
+        "
+        .parse()
+        .unwrap();
+        let layout_tbl: HashMap<_, _> = [
+            ("t1102".to_owned(), Arc::new(t1102_layout())),
+            ("CSPAT00600".to_owned(), Arc::new(order_layout)),
+        ]
+        .into();
+        let backend = Arc::new(MockBackend::new(layout_tbl.clone()));
+
+        let gateway = Gateway::bind("127.0.0.1:0", backend.clone(), layout_tbl.clone())
+            .unwrap()
+            .with_auth(|auth| auth == Some("Bearer secret"))
+            .with_io_timeout(Duration::from_millis(100));
+        let addr = gateway.local_addr().unwrap();
+        std::thread::spawn(move || gateway.serve());
+
+        let stream = TcpStream::connect(addr).unwrap();
+        assert!(tungstenite::client(format!("ws://{}/", addr), stream).is_err());
+
+        // 핸드셰이크를 끝내지 않는 연결은 시간이 지나면 종료됩니다.
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.is_empty());
+
+        // 주문 TR은 기본적으로 요청할 수 없습니다.
+        let mut request = format!("ws://{}/", addr).into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("Authorization", "Bearer secret".parse().unwrap());
+        let stream = TcpStream::connect(addr).unwrap();
+        let (mut socket, _) = tungstenite::client(request, stream).unwrap();
+        socket
+            .send(Message::text(
+                r#"{"type":"request","tr_code":"CSPAT00600"}"#,
+            ))
+            .unwrap();
+        assert_eq!(
+            socket.read().unwrap().into_text().unwrap().as_str(),
+            r#"{"id":null,"type":"error","message":"tr code not allowed: CSPAT00600"}"#
+        );
+        assert!(backend.requests().is_empty());
+        socket.close(None).unwrap();
+
+        let gateway = Gateway::bind("127.0.0.1:0", backend.clone(), layout_tbl)
+            .unwrap()
+            .with_allowed_trs(["CSPAT00600"])
+            .with_max_connections(1);
+        let addr = gateway.local_addr().unwrap();
+        std::thread::spawn(move || gateway.serve());
+
+        let stream = TcpStream::connect(addr).unwrap();
+        let (mut socket, _) = tungstenite::client(format!("ws://{}/", addr), stream).unwrap();
+
+        // 최대 연결 개수를 초과한 연결은 거부됩니다.
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        socket
+            .send(Message::text(r#"{"type":"request","tr_code":"t1102"}"#))
+            .unwrap();
+        assert_eq!(
+            socket.read().unwrap().into_text().unwrap().as_str(),
+            r#"{"id":null,"type":"error","message":"tr code not allowed: t1102"}"#
+        );
+        socket.close(None).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! HTS 연동 모듈
+//!
+//! 실행 중인 HTS 화면으로 종목번호 등을 보내거나, HTS에서 선택한 종목을
+//! [`HtsLink`]로 수신합니다.
+
+use super::session;
+
+use crossbeam_channel::{Receiver, Sender};
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 종목 연동 이름
+pub const STOCK_CODE: &str = "&STOCK_CODE";
+
+/// HTS에서 수신한 연동 데이터
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkEvent {
+    /// 연동 이름
+    pub link_name: String,
+    /// 연동 데이터
+    pub data: String,
+    /// 필터
+    pub filter: String,
+}
+
+impl LinkEvent {
+    /// 종목 연동인 경우 종목번호를 반환합니다.
+    pub fn stock_code(&self) -> Option<&str> {
+        if self.link_name == STOCK_CODE {
+            Some(&self.data)
+        } else {
+            None
+        }
+    }
+}
+
+/// HTS 연동 데이터를 수신하는 객체
+///
+/// 객체는 하나만 사용할 수 있으며, 새로운 객체를 생성하면 이전 객체는 더
+/// 이상 데이터를 수신하지 않습니다.
+pub struct HtsLink {
+    tx_event: Sender<LinkEvent>,
+    rx_event: Receiver<LinkEvent>,
+}
+
+impl HtsLink {
+    /// 객체를 생성하고 HTS 연동 데이터 수신을 시작합니다.
+    pub fn new() -> Self {
+        let (tx_event, rx_event) = crossbeam_channel::unbounded();
+        session::global().set_link_sender(tx_event.clone());
+
+        Self { tx_event, rx_event }
+    }
+
+    /// 수신한 연동 데이터가 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<LinkEvent> {
+        self.rx_event.try_recv().ok()
+    }
+
+    /// 수신한 연동 데이터를 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<LinkEvent> {
+        self.rx_event.recv_timeout(timeout).ok()
+    }
+}
+
+impl Default for HtsLink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HtsLink {
+    fn drop(&mut self) {
+        if session::is_loaded() {
+            session::global().remove_link_sender(&self.tx_event);
+        }
+    }
+}
+
+/// HTS로 연동 데이터를 보냅니다.
+///
+/// HTS가 실행 중이지 않은 경우 `false`를 반환합니다.
+pub fn request_link(link_name: &str, data: &str) -> bool {
+    session::global().request_link_to_hts(link_name, data)
+}
+
+/// HTS 화면에 종목번호를 연동합니다.
+pub fn link_stock(symbol: &str) -> bool {
+    request_link(STOCK_CODE, symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkEvent, STOCK_CODE};
+
+    #[test]
+    fn test_link_event() {
+        let mut event = LinkEvent {
+            link_name: STOCK_CODE.into(),
+            data: "005930".into(),
+            filter: String::new(),
+        };
+        assert_eq!(event.stock_code(), Some("005930"));
+
+        event.link_name = "&FUTURE_CODE".into();
+        assert_eq!(event.stock_code(), None);
+    }
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MPL-2.0
+
+// 한국 표준시(UTC+9) 기준의 날짜 계산을 위한 모듈입니다.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const OFFSET_SECS: i64 = 9 * 60 * 60;
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+// 1970년 1월 1일로부터 경과한 일수와 자정으로부터 경과한 초로 표현되는 시각
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DateTime {
+    pub days: i64,
+    pub secs: u32,
+}
+
+impl DateTime {
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let secs = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs() as i64),
+        } + OFFSET_SECS;
+
+        Self {
+            days: secs.div_euclid(DAY_SECS),
+            secs: secs.rem_euclid(DAY_SECS) as u32,
+        }
+    }
+
+    pub fn to_system_time(self) -> SystemTime {
+        let secs = self.days * DAY_SECS + self.secs as i64 - OFFSET_SECS;
+        if secs >= 0 {
+            UNIX_EPOCH + Duration::from_secs(secs as u64)
+        } else {
+            UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+        }
+    }
+}
+
+// `YYYYMMDD` 형식의 오늘 날짜를 반환합니다.
+pub(crate) fn today() -> String {
+    date_string(DateTime::now().days)
+}
+
+// 1970년 1월 1일로부터 경과한 일수를 `YYYYMMDD` 형식으로 변환합니다.
+pub(crate) fn date_string(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+// `YYYYMMDD` 형식의 날짜를 1970년 1월 1일로부터 경과한 일수로 변환합니다.
+pub(crate) fn parse_date(date: &str) -> Option<i64> {
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let year = date[0..4].parse().ok()?;
+    let month = date[4..6].parse().ok()?;
+    let day = date[6..8].parse().ok()?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+// 월요일을 0으로 하는 요일을 반환합니다.
+pub(crate) fn weekday(days: i64) -> u32 {
+    // 1970년 1월 1일은 목요일입니다.
+    (days + 3).rem_euclid(7) as u32
+}
+
+// 1970년 1월 1일로부터 경과한 일수를 그레고리력 날짜로 변환합니다.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+// 그레고리력 날짜를 1970년 1월 1일로부터 경과한 일수로 변환합니다.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, weekday, DateTime};
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(18628), (2021, 1, 1));
+        assert_eq!(civil_from_days(19417), (2023, 3, 1));
+        assert_eq!(weekday(18628), 4);
+
+        for days in [-1, 0, 59, 18628, 19417, 40000] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_date_time() {
+        // 2021-01-11 00:30:00 UTC
+        let time = UNIX_EPOCH + Duration::from_secs(1610325000);
+        let dt = DateTime::from_system_time(time);
+
+        assert_eq!(dt.secs, 9 * 3600 + 30 * 60);
+        assert_eq!(civil_from_days(dt.days), (2021, 1, 11));
+        assert_eq!(dt.to_system_time(), time);
+    }
+}
@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 시세 조회 모듈
+//!
+//! 주식 시세 TR을 타입이 지정된 구조체로 조회할 수 있도록 감싼 함수와 장 운영
+//! 시간을 확인하기 위한 [`Calendar`]를 제공합니다.
+
+use super::{ensure_ok, kst, request_with_retry, set_fields, Error};
+use crate::data::{self, Data, DecodeError};
+use crate::layout::TrLayout;
+
+use array_init::try_array_init;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 호가 단계의 개수
+pub const ORDERBOOK_DEPTH: usize = 10;
+
+/// 주식 현재가 호가 (t1101)
+///
+/// 각 호가 단계는 `(가격, 잔량)`으로 표현되며, 첫 번째 원소가 최우선
+/// 호가입니다.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrderBook {
+    /// 단축코드
+    pub symbol: String,
+    /// 현재가
+    pub price: f64,
+    /// 매도 호가
+    pub asks: [(f64, u64); ORDERBOOK_DEPTH],
+    /// 매수 호가
+    pub bids: [(f64, u64); ORDERBOOK_DEPTH],
+    /// 매도 호가 총잔량
+    pub total_ask_qty: u64,
+    /// 매수 호가 총잔량
+    pub total_bid_qty: u64,
+    /// `HHMMSSmm` 형식의 호가 수신 시각
+    pub time: String,
+}
+
+impl OrderBook {
+    /// t1101 TR의 응답 데이터를 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        const BLOCK_NAME: &str = "t1101OutBlock";
+
+        let fields = data::get_block(data, BLOCK_NAME)?;
+
+        Ok(Self {
+            symbol: data::parse_field(fields, BLOCK_NAME, "shcode")?,
+            price: data::parse_field(fields, BLOCK_NAME, "price")?,
+            asks: parse_levels(fields, BLOCK_NAME, "offerho", "offerrem")?,
+            bids: parse_levels(fields, BLOCK_NAME, "bidho", "bidrem")?,
+            total_ask_qty: data::parse_field(fields, BLOCK_NAME, "offer")?,
+            total_bid_qty: data::parse_field(fields, BLOCK_NAME, "bid")?,
+            time: data::parse_field(fields, BLOCK_NAME, "hotime")?,
+        })
+    }
+
+    /// 최우선 매도 호가를 반환합니다.
+    pub fn best_ask(&self) -> (f64, u64) {
+        self.asks[0]
+    }
+
+    /// 최우선 매수 호가를 반환합니다.
+    pub fn best_bid(&self) -> (f64, u64) {
+        self.bids[0]
+    }
+
+    /// 최우선 매도 호가와 최우선 매수 호가의 차이를 반환합니다.
+    pub fn spread(&self) -> f64 {
+        self.asks[0].0 - self.bids[0].0
+    }
+}
+
+// `offerho1`부터 `offerho10`까지와 같이 번호가 붙은 필드들을 호가 단계로
+// 변환합니다.
+pub(super) fn parse_levels(
+    fields: &HashMap<Arc<str>, String>,
+    block_name: &str,
+    price_prefix: &str,
+    qty_prefix: &str,
+) -> Result<[(f64, u64); ORDERBOOK_DEPTH], DecodeError> {
+    try_array_init(|i| {
+        Ok((
+            data::parse_field(fields, block_name, &format!("{}{}", price_prefix, i + 1))?,
+            data::parse_field(fields, block_name, &format!("{}{}", qty_prefix, i + 1))?,
+        ))
+    })
+}
+
+/// 종목의 현재가 호가를 조회합니다.
+pub fn orderbook(
+    symbol: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<OrderBook, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "t1101InBlock",
+        &[("shcode", symbol.to_owned())],
+    )?;
+
+    let res = ensure_ok(request_with_retry(
+        &req_data,
+        tr_layout.clone(),
+        None,
+        timeout,
+    )?)?;
+    Ok(OrderBook::from_data(res.data()?)?)
+}
+
+/// 장 운영 구간
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SessionPhase {
+    /// 장 운영 시간이 아님
+    Closed,
+    /// 장 시작 동시호가 (08:30 - 09:00)
+    PreOpenAuction,
+    /// 정규장 (09:00 - 15:20)
+    Regular,
+    /// 장 마감 동시호가 (15:20 - 15:30)
+    ClosingAuction,
+    /// 장후 시간외 (15:40 - 18:00)
+    AfterHours,
+}
+
+const fn hms(h: u32, m: u32) -> u32 {
+    (h * 60 + m) * 60
+}
+
+const PRE_OPEN_AUCTION: u32 = hms(8, 30);
+const REGULAR_OPEN: u32 = hms(9, 0);
+const CLOSING_AUCTION: u32 = hms(15, 20);
+const REGULAR_CLOSE: u32 = hms(15, 30);
+const AFTER_HOURS_OPEN: u32 = hms(15, 40);
+const AFTER_HOURS_CLOSE: u32 = hms(18, 0);
+
+/// 주식 시장 운영 일정
+///
+/// 주말과 연말 휴장일(12월 31일)은 항상 휴장일로 간주하며, 공휴일 등의 그 외
+/// 휴장일은 직접 추가해야 합니다. 모든 시각은 한국 표준시를 기준으로
+/// 계산합니다.
+#[derive(Clone, Debug, Default)]
+pub struct Calendar {
+    holidays: HashSet<String>,
+}
+
+impl Calendar {
+    /// 휴장일이 추가되지 않은 일정을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `YYYYMMDD` 형식의 휴장일 목록으로 일정을 생성합니다.
+    pub fn with_holidays<I, S>(holidays: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            holidays: holidays.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+
+    /// `YYYYMMDD` 형식의 휴장일을 추가합니다.
+    pub fn insert_holiday<S: Into<String>>(&mut self, date: S) {
+        self.holidays.insert(date.into());
+    }
+
+    /// 현재 장 운영 구간을 반환합니다.
+    pub fn phase(&self) -> SessionPhase {
+        self.phase_at(SystemTime::now())
+    }
+
+    /// 주어진 시각의 장 운영 구간을 반환합니다.
+    pub fn phase_at(&self, time: SystemTime) -> SessionPhase {
+        let dt = kst::DateTime::from_system_time(time);
+        if !self.is_trading_day(dt.days) {
+            return SessionPhase::Closed;
+        }
+
+        match dt.secs {
+            s if (PRE_OPEN_AUCTION..REGULAR_OPEN).contains(&s) => SessionPhase::PreOpenAuction,
+            s if (REGULAR_OPEN..CLOSING_AUCTION).contains(&s) => SessionPhase::Regular,
+            s if (CLOSING_AUCTION..REGULAR_CLOSE).contains(&s) => SessionPhase::ClosingAuction,
+            s if (AFTER_HOURS_OPEN..AFTER_HOURS_CLOSE).contains(&s) => SessionPhase::AfterHours,
+            _ => SessionPhase::Closed,
+        }
+    }
+
+    /// 현재 정규장이 열려 있는지 여부를 반환합니다.
+    ///
+    /// 장 마감 동시호가 구간을 포함합니다.
+    pub fn is_open(&self) -> bool {
+        self.is_open_at(SystemTime::now())
+    }
+
+    /// 주어진 시각에 정규장이 열려 있는지 여부를 반환합니다.
+    pub fn is_open_at(&self, time: SystemTime) -> bool {
+        matches!(
+            self.phase_at(time),
+            SessionPhase::Regular | SessionPhase::ClosingAuction
+        )
+    }
+
+    /// 주어진 시각 이후 정규장이 처음 열리는 시각을 반환합니다.
+    pub fn next_open(&self, time: SystemTime) -> SystemTime {
+        self.next_event(time, REGULAR_OPEN)
+    }
+
+    /// 주어진 시각 이후 정규장이 처음 닫히는 시각을 반환합니다.
+    pub fn next_close(&self, time: SystemTime) -> SystemTime {
+        self.next_event(time, REGULAR_CLOSE)
+    }
+
+    fn next_event(&self, time: SystemTime, secs: u32) -> SystemTime {
+        let dt = kst::DateTime::from_system_time(time);
+        let mut days = if dt.secs < secs { dt.days } else { dt.days + 1 };
+
+        while !self.is_trading_day(days) {
+            days += 1;
+        }
+
+        kst::DateTime { days, secs }.to_system_time()
+    }
+
+    fn is_trading_day(&self, days: i64) -> bool {
+        let (_, month, day) = kst::civil_from_days(days);
+
+        kst::weekday(days) < 5
+            && !(month == 12 && day == 31)
+            && !self.holidays.contains(&kst::date_string(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calendar, OrderBook, SessionPhase};
+    use crate::data::{Block, Data, DataType};
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    // 한국 표준시 기준의 2021년 1월 11일(월) 시각
+    fn kst(h: u64, m: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1610290800 + (h * 60 + m) * 60)
+    }
+
+    #[test]
+    fn test_orderbook_from_data() {
+        let mut fields: HashMap<Arc<str>, String> = HashMap::new();
+        for i in 1..=10 {
+            fields.insert(
+                format!("offerho{}", i).into(),
+                format!("{:08}", 5990 + i * 10),
+            );
+            fields.insert(format!("offerrem{}", i).into(), format!("{:012}", i * 100));
+            fields.insert(
+                format!("bidho{}", i).into(),
+                format!("{:08}", 6000 - i * 10),
+            );
+            fields.insert(format!("bidrem{}", i).into(), format!("{:012}", i * 200));
+        }
+        fields.insert("shcode".into(), "078020".into());
+        fields.insert("price".into(), "00006000".into());
+        fields.insert("offer".into(), "000000010283".into());
+        fields.insert("bid".into(), "000000034598".into());
+        fields.insert("hotime".into(), "16000112".into());
+
+        let data = Data {
+            tr_code: "t1101".into(),
+            data_type: DataType::Output,
+            blocks: [("t1101OutBlock".to_owned(), Block::Block(fields))]
+                .into_iter()
+                .collect(),
+        };
+
+        let orderbook = OrderBook::from_data(&data).unwrap();
+        assert_eq!(orderbook.symbol, "078020");
+        assert_eq!(orderbook.best_ask(), (6000.0, 100));
+        assert_eq!(orderbook.best_bid(), (5990.0, 200));
+        assert_eq!(orderbook.asks[9], (6090.0, 1000));
+        assert_eq!(orderbook.bids[9], (5900.0, 2000));
+        assert_eq!(orderbook.spread(), 10.0);
+        assert_eq!(orderbook.total_bid_qty, 34598);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = Calendar::with_holidays(["20210112"]);
+
+        assert_eq!(calendar.phase_at(kst(8, 0)), SessionPhase::Closed);
+        assert_eq!(calendar.phase_at(kst(8, 45)), SessionPhase::PreOpenAuction);
+        assert_eq!(calendar.phase_at(kst(9, 0)), SessionPhase::Regular);
+        assert_eq!(calendar.phase_at(kst(15, 25)), SessionPhase::ClosingAuction);
+        assert_eq!(calendar.phase_at(kst(15, 35)), SessionPhase::Closed);
+        assert_eq!(calendar.phase_at(kst(17, 0)), SessionPhase::AfterHours);
+        assert!(calendar.is_open_at(kst(10, 0)));
+        assert!(!calendar.is_open_at(kst(24 + 10, 0)));
+
+        assert_eq!(calendar.next_open(kst(8, 0)), kst(9, 0));
+        assert_eq!(calendar.next_open(kst(9, 0)), kst(48 + 9, 0));
+        assert_eq!(calendar.next_close(kst(10, 0)), kst(15, 30));
+
+        // 금요일 장 마감 후에는 다음 주 월요일에 개장합니다.
+        assert_eq!(calendar.next_open(kst(4 * 24 + 16, 0)), kst(7 * 24 + 9, 0));
+    }
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 지연 시간 측정 모듈
+
+use std::time::Duration;
+
+const BUCKET_COUNT: usize = 32;
+
+/// 지연 시간 히스토그램
+///
+/// 마이크로초 단위로 2의 거듭제곱 간격의 구간에 기록하므로, 백분위수는
+/// 해당 구간의 상한으로 근사됩니다.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Histogram {
+    buckets: [u64; BUCKET_COUNT],
+    count: u64,
+    sum: Duration,
+    max: Duration,
+}
+
+impl Histogram {
+    /// 기록이 없는 히스토그램을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 지연 시간을 기록합니다.
+    pub fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros().max(1);
+        let index = (127 - micros.leading_zeros()) as usize;
+
+        self.buckets[index.min(BUCKET_COUNT - 1)] += 1;
+        self.count += 1;
+        self.sum += latency;
+        self.max = self.max.max(latency);
+    }
+
+    /// 기록된 개수를 반환합니다.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 평균 지연 시간을 반환합니다.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(self.sum / self.count as u32)
+    }
+
+    /// 최대 지연 시간을 반환합니다.
+    pub fn max(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(self.max)
+    }
+
+    /// 지정된 백분위수(0 ~ 100)의 지연 시간을 반환합니다.
+    pub fn percentile(&self, percent: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((percent.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil() as u64;
+        let mut seen = 0;
+        for (index, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank.max(1) {
+                let upper = Duration::from_micros(2u64 << index);
+                return Some(upper.min(self.max));
+            }
+        }
+
+        Some(self.max)
+    }
+
+    /// 기록을 모두 삭제합니다.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 실시간 응답의 지연 시간 통계
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// 수신한 후 큐에서 가져오기까지 걸린 시간
+    pub queue: Histogram,
+    /// 디코딩하는데 걸린 시간
+    pub decode: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Histogram;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_histogram() {
+        let mut histogram = Histogram::new();
+        assert_eq!(histogram.mean(), None);
+
+        for micros in [1, 3, 10, 100, 1000] {
+            histogram.record(Duration::from_micros(micros));
+        }
+
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.mean(), Some(Duration::from_nanos(222800)));
+        assert_eq!(histogram.max(), Some(Duration::from_micros(1000)));
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_micros(16)));
+        assert_eq!(
+            histogram.percentile(100.0),
+            Some(Duration::from_micros(1000))
+        );
+    }
+}
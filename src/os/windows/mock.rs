@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::backend::{Backend, MockBackend};
+use super::entry::{encode_text, Api};
+use super::raw::{MSG_PACKET, RECV_PACKET, XM_LOGIN, XM_RECEIVE_DATA, XM_TIMEOUT};
+use super::{Account, Error, TrLimits};
+use crate::data::RawData;
+use crate::os::backend::CannedResponse;
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{convert::TryInto, time::Duration};
+
+use winapi::shared::basetsd::UINT_PTR;
+use winapi::shared::minwindef::{DWORD, LPARAM, UINT};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{KillTimer, SendMessageA, SetTimer};
+
+// 요청 ID를 반환한 후 응답을 보내기까지 기다리는 시간 (밀리초)
+//
+// 세션은 요청 ID를 받은 후에 응답을 받을 준비를 합니다.
+const DELIVERY_DELAY: UINT = 10;
+
+// 동시에 사용할 수 있는 요청 ID의 개수
+const REQUEST_IDS: i32 = 256;
+
+struct Delivery {
+    hwnd: usize,
+    req_id: i32,
+    res: Option<(CannedResponse, Option<RawData>)>,
+}
+
+thread_local! {
+    // 타이머 ID별로 보낼 응답
+    static DELIVERIES: RefCell<HashMap<UINT_PTR, Delivery>> = RefCell::new(HashMap::new());
+
+    // 사용 중인 요청 ID
+    static REQ_IDS: RefCell<BTreeSet<i32>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+// DLL 대신 `MockBackend`로 응답하는 객체
+//
+// 응답은 DLL과 같은 형식의 메시지로 세션 창에 보내므로 세션과 실행기를
+// 그대로 거칩니다. 실행기 스레드에서만 사용됩니다.
+pub(crate) struct MockEntry {
+    backend: Arc<MockBackend>,
+    path: PathBuf,
+}
+
+impl MockEntry {
+    pub fn new(backend: Arc<MockBackend>) -> Self {
+        Self {
+            backend,
+            path: PathBuf::new(),
+        }
+    }
+
+    fn schedule(&self, delivery: Delivery) {
+        let timer_id = unsafe { SetTimer(std::ptr::null_mut(), 0, DELIVERY_DELAY, Some(deliver)) };
+        assert_ne!(timer_id, 0);
+
+        DELIVERIES.with(|tbl| tbl.borrow_mut().insert(timer_id, delivery));
+    }
+}
+
+impl Api for MockEntry {
+    fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn connect(&self, _hwnd: usize, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
+        self.backend.connect(addr, port, timeout)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.backend.is_connected()
+    }
+
+    fn disconnect(&self) {
+        self.backend.disconnect()
+    }
+
+    fn login(
+        &self,
+        hwnd: usize,
+        id: &str,
+        pw: &str,
+        cert_pw: &str,
+        cert_err_dialog: bool,
+    ) -> Result<(), Error> {
+        let res = self.backend.login(id, pw, cert_pw, cert_err_dialog)?;
+        let code = encode_text(&res.code);
+        let message = encode_text(&res.message);
+
+        // 세션은 로그인을 요청하기 전에 응답을 받을 준비를 합니다.
+        unsafe {
+            SendMessageA(
+                hwnd as _,
+                XM_LOGIN,
+                code.as_ptr() as _,
+                message.as_ptr() as _,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn request(
+        &self,
+        hwnd: usize,
+        tr_code: &str,
+        _data: &[u8],
+        _next_key: Option<&str>,
+        _timeout: Duration,
+    ) -> Result<i32, Error> {
+        let req_id = REQ_IDS
+            .with(|ids| {
+                let mut ids = ids.borrow_mut();
+                let req_id = (0..REQUEST_IDS).find(|req_id| !ids.contains(req_id))?;
+                ids.insert(req_id);
+                Some(req_id)
+            })
+            .ok_or(Error::Busy)?;
+
+        // 인코딩되지 않은 응답 데이터는 요청에 사용한 레이아웃 대신 백엔드의
+        // 레이아웃으로 인코딩하며, 인코딩할 수 없는 경우 응답하지 않습니다.
+        let res = self.backend.pop(tr_code).and_then(|mut res| {
+            let data = match res.data.take() {
+                Some(data) => Some(data.into_raw(self.backend.layout(tr_code).ok()?).ok()?),
+                None => None,
+            };
+            Some((res, data))
+        });
+
+        self.schedule(Delivery { hwnd, req_id, res });
+
+        Ok(req_id)
+    }
+
+    fn release_request_data(&self, req_id: i32) {
+        REQ_IDS.with(|ids| ids.borrow_mut().remove(&req_id));
+    }
+
+    // 메시지는 세션 창으로 보낸 후 바로 해제됩니다.
+    fn release_message_data(&self, _lparam: LPARAM) {}
+
+    fn request_link_to_hts(&self, _hwnd: usize, _link_name: &str, _data: &str) -> bool {
+        false
+    }
+
+    fn advise_link_from_hts(&self, _hwnd: usize) {}
+
+    fn unadvise_link_from_hts(&self, _hwnd: usize) {}
+
+    fn request_service(&self, _hwnd: usize, _tr_code: &str, _data: &[u8]) -> Result<i32, Error> {
+        Err(Error::Internal(
+            "service tr is not supported by mock backend",
+        ))
+    }
+
+    fn remove_service(&self, _hwnd: usize, _tr_code: &str, _data: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // 응답 데이터는 압축되지 않은 상태로 보냅니다.
+    fn decompress(&self, src: &[u8], dest: &mut [u8]) -> usize {
+        let len = src.len().min(dest.len());
+        dest[..len].copy_from_slice(&src[..len]);
+        len
+    }
+
+    fn advise_real_data(&self, _hwnd: usize, _tr_code: &str, keys: &[String]) -> Vec<bool> {
+        vec![true; keys.len()]
+    }
+
+    fn unadvise_real_data(&self, _hwnd: usize, _tr_code: &str, keys: &[String]) -> Vec<bool> {
+        vec![true; keys.len()]
+    }
+
+    fn unadvise_window(&self, _hwnd: usize) -> bool {
+        true
+    }
+
+    fn accounts(&self) -> Vec<Account> {
+        self.backend.accounts()
+    }
+
+    fn get_comm_media(&self) -> Option<String> {
+        None
+    }
+
+    fn get_etk_media(&self) -> Option<String> {
+        None
+    }
+
+    fn get_server_name(&self) -> Option<String> {
+        None
+    }
+
+    fn get_use_over_future(&self) -> bool {
+        false
+    }
+
+    fn get_use_fx(&self) -> bool {
+        false
+    }
+
+    fn get_tr_limits(&self, _tr_code: &str) -> Option<TrLimits> {
+        None
+    }
+}
+
+// 예약된 응답을 DLL과 같은 순서의 메시지로 세션 창에 보냅니다.
+unsafe extern "system" fn deliver(_hwnd: HWND, _msg: UINT, timer_id: UINT_PTR, _time: DWORD) {
+    KillTimer(std::ptr::null_mut(), timer_id);
+
+    let Some(Delivery { hwnd, req_id, res }) =
+        DELIVERIES.with(|tbl| tbl.borrow_mut().remove(&timer_id))
+    else {
+        return;
+    };
+    let hwnd = hwnd as HWND;
+
+    // 남은 응답이 없는 경우 DLL의 요청 시간 초과와 같이 처리합니다.
+    let Some((res, data)) = res else {
+        REQ_IDS.with(|ids| ids.borrow_mut().remove(&req_id));
+        SendMessageA(hwnd, XM_TIMEOUT, 0, req_id as _);
+        return;
+    };
+
+    let blocks: Vec<(&str, &[u8])> = match &data {
+        Some(RawData::Block(block_tbl)) => block_tbl
+            .iter()
+            .map(|(block_name, data)| (block_name.as_str(), data.as_slice()))
+            .collect(),
+        Some(RawData::NonBlock(data)) => vec![("", data.as_slice())],
+        None => Vec::new(),
+    };
+
+    for (block_name, data) in blocks {
+        let mut packet: RECV_PACKET = std::mem::zeroed();
+        packet.req_id = req_id;
+        packet.data_len = data.len().try_into().unwrap();
+        packet.data_buffer_len = packet.data_len;
+        copy_text(&mut packet.next_key, res.next_key.as_deref().unwrap_or(""));
+        copy_text(&mut packet.block_name, block_name);
+        packet.data = data.as_ptr();
+
+        SendMessageA(hwnd, XM_RECEIVE_DATA, 1, &packet as *const _ as _);
+    }
+
+    let message = encode_text(&res.message);
+
+    let mut packet: MSG_PACKET = std::mem::zeroed();
+    packet.req_id = req_id;
+    copy_text(&mut packet.msg_code, &res.code);
+    packet.msg_data_len = message.as_bytes().len().try_into().unwrap();
+    packet.msg_data = message.as_ptr();
+
+    SendMessageA(hwnd, XM_RECEIVE_DATA, 2, &packet as *const _ as _);
+    SendMessageA(hwnd, XM_RECEIVE_DATA, 4, req_id as _);
+}
+
+// NUL 문자로 끝나도록 문자열을 고정 길이 버퍼에 복사합니다.
+fn copy_text(dest: &mut [i8], text: &str) {
+    let text = encode_text(text);
+    let len = text.as_bytes().len().min(dest.len() - 1);
+
+    for (dest, &ch) in dest.iter_mut().zip(&text.as_bytes()[..len]) {
+        *dest = ch as _;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{backend::MockBackend, loader};
+    use crate::data::{self, Block, Data, DataType};
+    use crate::hashmap;
+    use crate::os::testdata::t1102_layout;
+    use crate::{Error, Response};
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_mock_entry() {
+        let tr_layout = Arc::new(t1102_layout());
+
+        let backend = Arc::new(MockBackend::new(hashmap! {
+            "t1102" => tr_layout.clone(),
+        }));
+        backend.set_login_response("0000", "로그인 성공");
+
+        let out_data = Data {
+            tr_code: "t1102".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+            },
+        };
+        backend.push_response(&out_data, Some("005930")).unwrap();
+        backend.push_message("t1102", "02714", "조회할 자료가 없습니다.");
+
+        loader::load_mock(backend.clone()).unwrap();
+
+        let timeout = Duration::from_secs(1);
+        crate::connect("127.0.0.1", 20001, timeout).unwrap();
+        assert!(crate::is_connected());
+
+        let res = crate::login("id", "pw", "", false).unwrap();
+        assert!(res.is_ok());
+        assert_eq!(res.message(), "로그인 성공");
+
+        let mut in_data = data::empty_input(&tr_layout);
+        data::set_field(&mut in_data, "t1102InBlock", "shcode", "005930").unwrap();
+
+        let res = crate::request(&in_data, tr_layout.clone(), None, timeout).unwrap();
+        assert!(res.is_ok());
+        assert_eq!(res.next_key(), Some("005930"));
+        assert_eq!(res.data().unwrap(), &out_data);
+
+        let res = crate::request(&in_data, tr_layout.clone(), None, timeout).unwrap();
+        assert!(res.is_err());
+        assert_eq!(res.code(), "02714");
+
+        assert!(matches!(
+            crate::request(&in_data, tr_layout.clone(), None, timeout),
+            Err(Error::TimedOut)
+        ));
+
+        loader::unload();
+    }
+}
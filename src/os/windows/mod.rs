@@ -1,20 +1,66 @@
 // SPDX-License-Identifier: MPL-2.0
 
+#[cfg(any(feature = "bridge", feature = "gateway"))]
+mod access;
+mod clock;
 mod entry;
+mod error;
 mod event;
 mod executor;
+mod kst;
+mod mock;
 mod raw;
 mod session;
 
-pub use self::event::RealEvent;
-
-use crate::data::{Data, DecodeError, EncodeError};
-use crate::layout::TrLayout;
-
-use std::{path::PathBuf, time::Duration};
-
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+pub mod account;
+pub mod backend;
+pub mod book;
+#[cfg(feature = "bridge")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bridge")))]
+pub mod bridge;
+#[cfg(feature = "broker")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "broker")))]
+pub mod broker;
+#[cfg(feature = "capi")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "capi")))]
+pub mod capi;
+pub mod capture;
+pub mod chart;
+pub mod execution;
+#[cfg(feature = "gateway")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "gateway")))]
+pub mod gateway;
+pub mod hts_link;
+pub mod market;
+pub mod metrics;
+pub mod order;
+pub mod portfolio;
+pub mod realtime;
+pub mod recorder;
+pub mod replay;
+pub mod scheduler;
+pub mod search;
+pub mod service;
+pub mod stats;
+pub mod symbols;
+pub mod watchdog;
+
+pub use self::clock::{server_time, ServerClock};
+pub use self::error::{DllError, LoadError};
+#[cfg(feature = "futures-core")]
+pub use self::event::RealStream;
+pub use self::event::{RealEvent, RoutedReceiver, SubscribeBatch, Subscriptions};
+pub use super::common::{
+    Account, Error, ErrorKind, LoginCode, LoginResponse, QueryResponse, RealResponse, Response,
+};
+pub use super::jsonl::JsonLinesWriter;
+
+use super::common::LazyData;
+use crate::data::{self, Data, DecodeError};
+use crate::layout::{LayoutRegistry, TrLayout};
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// DLL 로더 모듈
 ///
@@ -23,9 +69,10 @@ use serde::{Deserialize, Serialize};
 /// XingAPI 구버전의 경우 DLL을 불러온 후 언로드하지 않으면 버그로 인해
 /// 프로그램이 정상적으로 종료되지 않을 수도 있습니다.
 pub mod loader {
-    use super::{executor, session, LoadError};
+    use super::{backend::MockBackend, executor, session, LoadError};
 
     use std::path::{Path, PathBuf};
+    use std::sync::Arc;
 
     /// XingAPI SDK의 기본 설치 경로에서 DLL을 불러옵니다.
     ///
@@ -57,6 +104,25 @@ pub mod loader {
         Ok(())
     }
 
+    /// DLL 대신 지정한 [`MockBackend`]로 응답하도록 불러옵니다.
+    ///
+    /// 세션과 실행기는 DLL을 불러온 경우와 같이 동작하므로
+    /// [`request()`](super::request)와 같은 함수를 DLL 없이 시험할 수
+    /// 있습니다. 요청 데이터는 인코딩된 상태로 전달되므로
+    /// [`MockBackend::requests()`]에 기록되지 않으며, 실시간 TR과 부가 서비스
+    /// TR의 응답은 보내지 않습니다.
+    ///
+    /// DLL을 이미 불러온 경우 아무런 동작을 하지 않습니다.
+    pub fn load_mock(backend: Arc<MockBackend>) -> Result<(), LoadError> {
+        executor::load_mock(backend)?;
+        if let Err(err) = session::load() {
+            executor::unload();
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
     /// 불러온 XingAPI DLL이 존재하는 경우 언로드합니다.
     pub fn unload() {
         session::unload();
@@ -107,13 +173,58 @@ pub fn login(
 }
 
 /// 서버에 조회 TR 요청을 합니다.
-pub fn request(
+///
+/// 응답을 기다리는 요청이 너무 많은 경우 요청을 보내지 않고 `Error::Busy`를
+/// 반환합니다. `Error::Busy`는 요청이 서버로 전송되지 않았음을 의미하므로
+/// 다시 요청해도 안전합니다.
+///
+/// 레이아웃은 응답을 디코딩할 때까지 보관됩니다. `&TrLayout`을 전달하면
+/// 요청마다 레이아웃을 복제하므로, 같은 레이아웃으로 반복해서 요청하는 경우
+/// [`LayoutRegistry`] 등에서 얻은 `Arc<TrLayout>`을 전달합니다.
+pub fn request<L: Into<Arc<TrLayout>>>(
+    data: &Data,
+    tr_layout: L,
+    next_key: Option<&str>,
+    timeout: Duration,
+) -> Result<QueryResponse, Error> {
+    let res = session::global().request(data, tr_layout.into(), next_key, timeout);
+    stats::record(&data.tr_code, next_key.is_some(), &res);
+    res
+}
+
+/// 레이아웃 테이블에서 TR 코드에 해당하는 레이아웃을 찾아 조회 TR 요청을
+/// 합니다.
+///
+/// 레이아웃이 없는 경우 `DecodeError::UnknownLayout`을 반환합니다.
+pub fn request_registered(
     data: &Data,
-    tr_layout: &TrLayout,
+    registry: &LayoutRegistry,
     next_key: Option<&str>,
     timeout: Duration,
 ) -> Result<QueryResponse, Error> {
-    session::global().request(data, tr_layout, next_key, timeout)
+    let tr_layout = registry
+        .get(&data.tr_code)
+        .ok_or_else(|| DecodeError::UnknownLayout(data.tr_code.clone()))?;
+    request(data, tr_layout, next_key, timeout)
+}
+
+/// 서버에 부가 서비스 TR(ChartIndex, t1857 등) 요청을 합니다.
+pub fn request_service<L: Into<Arc<TrLayout>>>(
+    data: &Data,
+    tr_layout: L,
+    timeout: Duration,
+) -> Result<QueryResponse, Error> {
+    let res = session::global().request_service(data, tr_layout.into(), timeout);
+    stats::record(&data.tr_code, false, &res);
+    res
+}
+
+/// 실시간으로 등록한 부가 서비스 TR의 등록을 해제합니다.
+///
+/// `data`는 서비스마다 다르며, 차트 지표는 지표 ID, 종목 검색은
+/// 실시간 키(AlertNum)입니다.
+pub fn remove_service(tr_code: &str, data: &str) -> Result<(), Error> {
+    session::global().remove_service(tr_code, data)
 }
 
 /// 계좌 목록을 반환합니다.
@@ -146,308 +257,103 @@ pub fn is_fx_allowed() -> bool {
     executor::global().handle().get_use_fx()
 }
 
+/// TR의 요청 제한 정보
+///
+/// [`tr_limits()`]가 반환합니다. DLL이 값을 반환하지 않은 항목은
+/// `None`입니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrLimits {
+    /// 기준 시간 동안 요청할 수 있는 횟수
+    pub per_sec: Option<u32>,
+    /// 초당 요청 제한의 기준 시간 (초)
+    pub base_sec: Option<u32>,
+    /// 10분 내 요청한 횟수
+    pub used_in_ten_min: Option<u32>,
+    /// 10분 내 제한 횟수
+    ///
+    /// 10분 내 요청 횟수를 제한하지 않는 TR은 `None`입니다.
+    pub limit_per_ten_min: Option<u32>,
+}
+
+/// TR의 요청 제한 정보를 한 번에 조회합니다.
+///
+/// 실행자 스레드에 한 번만 요청하므로 여러 항목을 조회하는 경우 개별
+/// 함수보다 빠르며, 각 항목이 같은 시점의 값입니다. 모든 항목의 값이 없는
+/// 경우 `None`을 반환합니다.
+pub fn tr_limits(tr_code: &str) -> Option<TrLimits> {
+    executor::global().handle().get_tr_limits(tr_code)
+}
+
 /// TR의 초당 요청 제한 횟수를 반환합니다.
 pub fn tr_limit_per_sec(tr_code: &str) -> Option<i32> {
-    executor::global().handle().get_tr_count_per_sec(tr_code)
+    Some(tr_limits(tr_code)?.per_sec? as i32)
 }
 
 /// TR의 요청당 대기 초를 반환합니다.
 pub fn tr_limit_wait_sec(tr_code: &str) -> Option<i32> {
-    executor::global().handle().get_tr_count_base_sec(tr_code)
+    Some(tr_limits(tr_code)?.base_sec? as i32)
 }
 
 /// TR의 10분 내 요청한 횟수를 반환합니다.
 pub fn tr_count_in_ten_min(tr_code: &str) -> Option<i32> {
-    executor::global().handle().get_tr_count_request(tr_code)
+    Some(tr_limits(tr_code)?.used_in_ten_min? as i32)
 }
 
 /// TR의 10분 내 제한 횟수를 반환합니다.
 pub fn tr_limit_per_ten_min(tr_code: &str) -> Option<i32> {
-    executor::global().handle().get_tr_count_limit(tr_code)
-}
-
-/// 이베스트투자증권 계좌 정보
-#[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Account {
-    /// 계좌번호
-    pub code: String,
-    /// 계좌명
-    pub name: String,
-    /// 계좌 상세명
-    pub detailed_name: String,
-    /// 계좌 별명
-    pub nickname: String,
-}
-
-/// XingAPI 함수가 실패하여 발생하는 에러
-#[derive(Debug)]
-pub enum Error {
-    /// XingAPI 에러
-    XingApi {
-        /// 음수로 표현되는 에러 코드
-        code: i32,
-        /// 에러 메시지
-        message: String,
-    },
-    /// 인코딩 에러
-    Encode(EncodeError),
-    /// 디코딩 에러
-    Decode(DecodeError),
-    /// 시간 초과
-    TimedOut,
-}
-
-impl From<EncodeError> for Error {
-    fn from(err: EncodeError) -> Self {
-        Self::Encode(err)
-    }
-}
-
-impl From<DecodeError> for Error {
-    fn from(err: DecodeError) -> Self {
-        Self::Decode(err)
-    }
-}
-
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::XingApi { code, message } => {
-                write!(f, "xingapi error; code: {}, message: {}", code, message)
-            }
-            Self::Encode(err) => err.fmt(f),
-            Self::Decode(err) => err.fmt(f),
-            Self::TimedOut => "request timed out".fmt(f),
-        }
-    }
-}
-
-impl std::error::Error for Error {}
-
-/// XingAPI를 불러오는데 실패하여 발생하는 에러
-#[derive(Debug)]
-pub enum LoadError {
-    /// DLL 에러
-    Dll(DllError),
-    /// I/O 에러
-    Io(std::io::Error),
-}
-
-impl From<DllError> for LoadError {
-    fn from(err: DllError) -> Self {
-        Self::Dll(err)
-    }
-}
-
-impl From<std::io::Error> for LoadError {
-    fn from(err: std::io::Error) -> Self {
-        Self::Io(err)
-    }
-}
-
-impl std::fmt::Display for LoadError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Dll(err) => {
-                write!(f, "dll error: {}", err)
-            }
-            Self::Io(err) => {
-                write!(f, "io error: {}", err)
-            }
-        }
-    }
-}
-
-impl std::error::Error for LoadError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Self::Dll(err) => Some(err),
-            Self::Io(err) => Some(err),
-        }
-    }
-}
-
-/// DLL을 불러오는데 실패하여 발생하는 에러
-#[derive(Debug)]
-pub enum DllError {
-    /// 라이브러리 에러
-    Library {
-        /// DLL 경로
-        path: PathBuf,
-        /// 에러 내용
-        error: libloading::Error,
-    },
-    /// 심볼 에러
-    Symbol {
-        /// 심볼 이름
-        symbol: String,
-        /// DLL 경로
-        path: PathBuf,
-        /// 에러 내용
-        error: libloading::Error,
-    },
-    /// DLL이 현재 프로세스에서 이미 사용 중임
-    LibraryInUse,
+    Some(tr_limits(tr_code)?.limit_per_ten_min? as i32)
 }
 
-impl std::fmt::Display for DllError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Library { path, error } => {
-                write!(f, "could not load a library; ")?;
-                write!(f, "path: {}, error: {}", path.display(), error)
-            }
-            Self::Symbol {
-                symbol,
-                path,
-                error,
-            } => {
-                write!(f, "could not load a symbol: {}; ", symbol)?;
-                write!(f, "path: {}, error: {}", path.display(), error)
-            }
-            Self::LibraryInUse => {
-                write!(f, "a library is already in use in current process")
-            }
-        }
+// 요청 데이터의 단일 블록에 필드 값들을 설정합니다.
+fn set_fields(data: &mut Data, block_name: &str, fields: &[(&str, String)]) -> Result<(), Error> {
+    for (field_name, value) in fields {
+        data::set_field(data, block_name, field_name, value.as_str())?;
     }
-}
 
-impl std::error::Error for DllError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Self::Library { error, .. } | Self::Symbol { error, .. } => Some(error),
-            Self::LibraryInUse => None,
-        }
-    }
+    Ok(())
 }
 
-/// 응답에 대한 트레이트
-///
-/// 서버에서 발생하는 응답의 공통 부분인 코드와 메시지를 트레이트로 묶어서
-/// 제공합니다.
-pub trait Response {
-    /// 4자리 이상의 응답 코드를 반환합니다. 응답 메시지가 없는 경우 빈 문자열을
-    /// 반환합니다.
-    ///
-    /// | 코드        | 내용        |
-    /// | ----------- | ----------- |
-    /// | 0000 - 0999 | 정상        |
-    /// | 1000 - 7999 | 업무 오류   |
-    /// | 8000 - 9999 | 시스템 오류 |
-    fn code(&self) -> &str;
-
-    /// 응답 메시지를 반환합니다. 응답 메시지가 없는 경우 빈 문자열을
-    /// 반환합니다.
-    fn message(&self) -> &str;
-
-    /// 정상 처리 여부를 반환합니다.
-    ///
-    /// 제공되는 구현은 응답 코드가 `0 <= x < 1000`이거나 응답 메시지와 코드가
-    /// 비어 있으면 참으로 간주합니다.
-    ///
-    /// t1764 TR과 같이 정상 처리시에 응답 메시지와 코드가 발생하지 않는 경우도
-    /// 고려하였습니다.
-    fn is_ok(&self) -> bool {
-        if let Ok(code) = self.code().parse::<i32>() {
-            (0..1000).contains(&code)
-        } else {
-            self.code().is_empty() && self.message().is_empty()
+// 초당 요청 제한 등 재요청 가능한 에러가 발생한 경우 제한 시간 내에서
+// 재요청합니다.
+//
+// 서버로 전송되지 않은 요청만 재요청하므로 재요청하더라도 중복되지 않습니다.
+fn request_with_retry<L: Into<Arc<TrLayout>>>(
+    data: &Data,
+    tr_layout: L,
+    next_key: Option<&str>,
+    timeout: Duration,
+) -> Result<QueryResponse, Error> {
+    let tr_layout = tr_layout.into();
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match request(data, tr_layout.clone(), next_key, timeout) {
+            Err(err) if err.is_unsent() && Instant::now() < deadline => match retry_backoff(&err) {
+                Some(backoff) => std::thread::sleep(backoff),
+                None => break Err(err),
+            },
+            result => break result,
         }
     }
-
-    /// 처리 실패 여부를 반환합니다.
-    ///
-    /// 제공되는 구현은 `is_ok()`의 논리 부정 값을 반환합니다.
-    fn is_err(&self) -> bool {
-        !self.is_ok()
-    }
-}
-
-/// 로그인 요청에 대한 서버 응답
-#[derive(Clone, Debug)]
-pub struct LoginResponse {
-    code: String,
-    message: String,
-}
-
-impl Response for LoginResponse {
-    fn code(&self) -> &str {
-        &self.code
-    }
-    fn message(&self) -> &str {
-        &self.message
-    }
-}
-
-impl std::fmt::Display for LoginResponse {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self, f)
-    }
-}
-
-/// 조회 TR에 대한 서버 응답
-#[derive(Clone, Debug)]
-pub struct QueryResponse {
-    code: String,
-    message: String,
-    elapsed: Duration,
-    next_key: Option<String>,
-    data: Option<Result<Data, DecodeError>>,
-}
-
-impl QueryResponse {
-    /// 서버 요청 후 응답까지 소요된 시간을 밀리초 정확도로 반환합니다.
-    ///
-    /// XingAPI의 수신 이벤트에서 반환한 값을 사용합니다.
-    pub fn elapsed(&self) -> Duration {
-        self.elapsed
-    }
-
-    /// 연속 조회 키가 존재하는 경우 연속 조회 키를 반환합니다.
-    ///
-    /// 연속 조회 키는 TR당 하나입니다.
-    pub fn next_key(&self) -> Option<&str> {
-        self.next_key.as_deref()
-    }
-
-    /// 수신한 데이터에 대한 디코딩 결과를 반환합니다.
-    ///
-    /// [`Response::is_ok()`][Response::is_ok]가 거짓인 경우 패닉이 발생합니다.
-    pub fn data(&self) -> Result<&Data, DecodeError> {
-        self.data
-            .as_ref()
-            .expect("this response has no data")
-            .as_ref()
-            .map_err(|err| err.clone())
-    }
 }
 
-impl Response for QueryResponse {
-    fn code(&self) -> &str {
-        &self.code
-    }
-    fn message(&self) -> &str {
-        &self.message
+// 재요청하기 전에 기다릴 시간을 반환합니다.
+fn retry_backoff(err: &Error) -> Option<Duration> {
+    match err {
+        Error::Busy => Some(Duration::from_millis(10)),
+        err => err.kind().and_then(ErrorKind::suggested_backoff),
     }
 }
 
-/// 실시간 TR에 대한 서버의 응답
-#[derive(Clone, Debug)]
-pub struct RealResponse {
-    key: String,
-    data: Result<Data, DecodeError>,
-}
-
-impl RealResponse {
-    /// 실시간 TR을 등록하는데 사용한 키를 반환합니다.
-    pub fn key(&self) -> &str {
-        &self.key
-    }
-
-    /// 수신한 데이터에 대한 디코딩 결과를 반환합니다.
-    pub fn data(&self) -> Result<&Data, DecodeError> {
-        self.data.as_ref().map_err(|err| err.clone())
+// 서버가 요청을 정상적으로 처리하지 못한 경우 에러로 변환합니다.
+fn ensure_ok(res: QueryResponse) -> Result<QueryResponse, Error> {
+    if res.is_ok() {
+        Ok(res)
+    } else {
+        Err(Error::Rejected {
+            code: res.code,
+            message: res.message,
+        })
     }
 }
 
@@ -455,7 +361,7 @@ trait Byte: Sized {}
 impl Byte for u8 {}
 impl Byte for i8 {}
 
-fn decode_euckr<T: Byte>(data: &[T]) -> String {
+fn decode_text<T: Byte>(data: &[T]) -> String {
     let data = unsafe { std::slice::from_raw_parts(data.as_ptr().cast(), data.len()) };
 
     let len = data
@@ -464,9 +370,8 @@ fn decode_euckr<T: Byte>(data: &[T]) -> String {
         .find(|&(_, &ch)| ch == b'\0')
         .map_or_else(|| data.len(), |(i, _)| i);
 
-    encoding_rs::EUC_KR
-        .decode_without_bom_handling(&data[..len])
-        .0
+    data::encoding()
+        .decode_lossy(&data[..len])
         .trim_matches(|c| (c as u32) < 0x20 || c == ' ')
         .to_owned()
 }
@@ -0,0 +1,540 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 주식 주문 모듈
+//!
+//! 현물 주문 TR인 CSPAT00600(신규), CSPAT00700(정정), CSPAT00800(취소)을
+//! 타입이 지정된 인자로 요청할 수 있도록 감싼 함수를 제공합니다. 미체결 주문은
+//! t0425 TR로 조회합니다.
+//!
+//! 각 함수는 해당 TR의 레이아웃을 인자로 받으며, 레이아웃에 정의된 요청
+//! 블록의 필드 중 지정되지 않은 필드는 빈 문자열로 채워집니다.
+
+use super::{ensure_ok, request_with_retry, set_fields, Error, Response};
+use crate::data::{self, Data, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 매매 구분
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Side {
+    /// 매도
+    Sell,
+    /// 매수
+    Buy,
+}
+
+impl Side {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Sell => "1",
+            Self::Buy => "2",
+        }
+    }
+
+    pub(crate) fn from_text(text: &str) -> Option<Self> {
+        match text {
+            "1" | "매도" => Some(Self::Sell),
+            "2" | "매수" => Some(Self::Buy),
+            _ => None,
+        }
+    }
+}
+
+/// 호가 유형
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OrderType {
+    /// 지정가
+    Limit,
+    /// 시장가
+    Market,
+    /// 조건부지정가
+    ConditionalLimit,
+    /// 최유리지정가
+    BestLimit,
+    /// 최우선지정가
+    PriorityLimit,
+    /// 장개시전시간외종가
+    PreMarketClose,
+    /// 시간외종가
+    AfterMarketClose,
+    /// 시간외단일가
+    AfterMarketSingle,
+}
+
+impl OrderType {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Limit => "00",
+            Self::Market => "03",
+            Self::ConditionalLimit => "05",
+            Self::BestLimit => "06",
+            Self::PriorityLimit => "07",
+            Self::PreMarketClose => "61",
+            Self::AfterMarketClose => "81",
+            Self::AfterMarketSingle => "82",
+        }
+    }
+}
+
+/// 주문 조건
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OrderCondition {
+    /// 없음
+    None,
+    /// IOC (Immediate or Cancel)
+    Ioc,
+    /// FOK (Fill or Kill)
+    Fok,
+}
+
+impl OrderCondition {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::None => "0",
+            Self::Ioc => "1",
+            Self::Fok => "2",
+        }
+    }
+}
+
+/// 신규 주문 (CSPAT00600)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NewOrder {
+    /// 계좌번호
+    pub account: String,
+    /// 계좌 비밀번호
+    pub password: String,
+    /// 종목번호
+    pub symbol: String,
+    /// 매매 구분
+    pub side: Side,
+    /// 주문 수량
+    pub qty: u64,
+    /// 주문 가격
+    ///
+    /// 시장가 주문과 같이 가격을 지정하지 않는 호가 유형에서는 무시됩니다.
+    pub price: f64,
+    /// 호가 유형
+    pub order_type: OrderType,
+    /// 주문 조건
+    pub condition: OrderCondition,
+}
+
+/// 정정 주문 (CSPAT00700)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModifyOrder {
+    /// 원주문번호
+    pub order_no: u64,
+    /// 계좌번호
+    pub account: String,
+    /// 계좌 비밀번호
+    pub password: String,
+    /// 종목번호
+    pub symbol: String,
+    /// 정정 수량
+    pub qty: u64,
+    /// 정정 가격
+    pub price: f64,
+    /// 호가 유형
+    pub order_type: OrderType,
+    /// 주문 조건
+    pub condition: OrderCondition,
+}
+
+/// 취소 주문 (CSPAT00800)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CancelOrder {
+    /// 원주문번호
+    pub order_no: u64,
+    /// 계좌번호
+    pub account: String,
+    /// 계좌 비밀번호
+    pub password: String,
+    /// 종목번호
+    pub symbol: String,
+    /// 취소 수량
+    pub qty: u64,
+}
+
+/// 미체결 주문 (t0425)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OpenOrder {
+    /// 주문번호
+    pub order_no: u64,
+    /// 종목번호
+    pub symbol: String,
+    /// 매매 구분
+    pub side: Side,
+    /// 주문 수량
+    pub qty: u64,
+    /// 주문 가격
+    pub price: f64,
+    /// 미체결 잔량
+    pub remaining_qty: u64,
+}
+
+impl OpenOrder {
+    fn from_fields(
+        fields: &HashMap<Arc<str>, String>,
+        block_name: &str,
+    ) -> Result<Self, DecodeError> {
+        let side = fields
+            .get("medosu")
+            .and_then(|s| Side::from_text(s))
+            .ok_or_else(|| DecodeError::InvalidField {
+                block: block_name.to_owned(),
+                field: "medosu".to_owned(),
+            })?;
+
+        Ok(Self {
+            order_no: data::parse_field(fields, block_name, "ordno")?,
+            symbol: data::parse_field(fields, block_name, "expcode")?,
+            side,
+            qty: data::parse_field(fields, block_name, "qty")?,
+            price: data::parse_field(fields, block_name, "price")?,
+            remaining_qty: data::parse_field(fields, block_name, "ordrem")?,
+        })
+    }
+}
+
+/// 일괄 취소 결과
+#[derive(Debug, Default)]
+pub struct CancelAllReport {
+    /// 취소가 접수된 주문 목록
+    pub accepted: Vec<(OpenOrder, OrderResponse)>,
+    /// 서버가 취소를 거부한 주문 목록
+    pub rejected: Vec<(OpenOrder, OrderResponse)>,
+    /// 취소 요청 자체가 실패한 주문 목록
+    pub failed: Vec<(OpenOrder, Error)>,
+}
+
+impl CancelAllReport {
+    /// 모든 미체결 주문의 취소가 접수되었는지 여부를 반환합니다.
+    pub fn is_complete(&self) -> bool {
+        self.rejected.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// 주문 요청에 대한 서버 응답
+#[derive(Clone, Debug)]
+pub struct OrderResponse {
+    code: String,
+    message: String,
+    order_no: Option<u64>,
+    order_time: Option<String>,
+}
+
+impl OrderResponse {
+    /// 주문이 접수된 경우 주문번호를 반환합니다.
+    pub fn order_no(&self) -> Option<u64> {
+        self.order_no
+    }
+
+    /// 주문이 접수된 경우 `HHMMSSmmm` 형식의 주문 시각을 반환합니다.
+    pub fn order_time(&self) -> Option<&str> {
+        self.order_time.as_deref()
+    }
+}
+
+impl Response for OrderResponse {
+    fn code(&self) -> &str {
+        &self.code
+    }
+    fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// 중복 주문 방지 객체
+///
+/// 신규 주문의 계좌번호, 종목번호, 매매 구분, 수량, 가격이 모두 같은 주문을
+/// 지정된 시간 내에 다시 요청하는 경우 서버로 전송하지 않고
+/// [`Error::DuplicateOrder`]를 반환합니다. 시간 초과 후 재시도하는 과정에서
+/// 같은 주문이 두 번 체결되는 것을 막기 위해 사용합니다.
+///
+/// 주문이 서버로 전송되지 않은 것이 확실한 경우, 즉
+/// [`Error::is_unsent()`]가 참인 경우에는 기록이 남지 않습니다.
+pub struct OrderGuard {
+    window: Duration,
+    history: Mutex<HashMap<OrderFingerprint, Instant>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct OrderFingerprint {
+    account: String,
+    symbol: String,
+    side: &'static str,
+    qty: u64,
+    price: u64,
+}
+
+impl OrderFingerprint {
+    fn new(order: &NewOrder) -> Self {
+        Self {
+            account: order.account.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side.code(),
+            qty: order.qty,
+            price: order.price.to_bits(),
+        }
+    }
+}
+
+impl OrderGuard {
+    /// 중복 주문으로 간주할 시간 간격을 지정하여 객체를 생성합니다.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 중복 주문이 아닌 경우 신규 주문을 요청합니다.
+    pub fn place_order(
+        &self,
+        order: &NewOrder,
+        tr_layout: &Arc<TrLayout>,
+        timeout: Duration,
+    ) -> Result<OrderResponse, Error> {
+        let fingerprint = OrderFingerprint::new(order);
+
+        {
+            let mut history = self.history.lock().unwrap();
+            let now = Instant::now();
+
+            history.retain(|_, time| now.duration_since(*time) < self.window);
+            if history.contains_key(&fingerprint) {
+                return Err(Error::DuplicateOrder);
+            }
+
+            history.insert(fingerprint.clone(), now);
+        }
+
+        let result = place_order(order, tr_layout, timeout);
+
+        if result.as_ref().is_err_and(Error::is_unsent) {
+            self.history.lock().unwrap().remove(&fingerprint);
+        }
+
+        result
+    }
+
+    /// 기록된 주문을 모두 삭제합니다.
+    pub fn clear(&self) {
+        self.history.lock().unwrap().clear();
+    }
+}
+
+/// 신규 주문을 요청합니다.
+pub fn place_order(
+    order: &NewOrder,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<OrderResponse, Error> {
+    let price = match order.order_type {
+        OrderType::Market | OrderType::BestLimit | OrderType::PriorityLimit => "0".into(),
+        _ => order.price.to_string(),
+    };
+
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "CSPAT00600InBlock1",
+        &[
+            ("AcntNo", order.account.clone()),
+            ("InptPwd", order.password.clone()),
+            ("IsuNo", order.symbol.clone()),
+            ("OrdQty", order.qty.to_string()),
+            ("OrdPrc", price),
+            ("BnsTpCode", order.side.code().into()),
+            ("OrdprcPtnCode", order.order_type.code().into()),
+            ("MgntrnCode", "000".into()),
+            ("OrdCndiTpCode", order.condition.code().into()),
+        ],
+    )?;
+
+    request_order(&req_data, tr_layout, timeout)
+}
+
+/// 정정 주문을 요청합니다.
+pub fn modify_order(
+    order: &ModifyOrder,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<OrderResponse, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "CSPAT00700InBlock1",
+        &[
+            ("OrgOrdNo", order.order_no.to_string()),
+            ("AcntNo", order.account.clone()),
+            ("InptPwd", order.password.clone()),
+            ("IsuNo", order.symbol.clone()),
+            ("OrdQty", order.qty.to_string()),
+            ("OrdprcPtnCode", order.order_type.code().into()),
+            ("OrdCndiTpCode", order.condition.code().into()),
+            ("OrdPrc", order.price.to_string()),
+        ],
+    )?;
+
+    request_order(&req_data, tr_layout, timeout)
+}
+
+/// 취소 주문을 요청합니다.
+pub fn cancel_order(
+    order: &CancelOrder,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<OrderResponse, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "CSPAT00800InBlock1",
+        &[
+            ("OrgOrdNo", order.order_no.to_string()),
+            ("AcntNo", order.account.clone()),
+            ("InptPwd", order.password.clone()),
+            ("IsuNo", order.symbol.clone()),
+            ("OrdQty", order.qty.to_string()),
+        ],
+    )?;
+
+    request_order(&req_data, tr_layout, timeout)
+}
+
+/// 계좌의 미체결 주문을 모두 조회합니다.
+///
+/// 연속 조회가 필요한 경우 모든 미체결 주문을 가져올 때까지 반복하여
+/// 요청합니다.
+pub fn open_orders(
+    account: &str,
+    password: &str,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<Vec<OpenOrder>, Error> {
+    let mut req_data = data::empty_input(tr_layout);
+    set_fields(
+        &mut req_data,
+        "t0425InBlock",
+        &[
+            ("accno", account.to_owned()),
+            ("passwd", password.to_owned()),
+            ("chegb", "2".into()),
+            ("medosu", "0".into()),
+            ("sortgb", "2".into()),
+        ],
+    )?;
+
+    let mut orders = Vec::new();
+    let mut next_key = None;
+
+    loop {
+        let res = ensure_ok(request_with_retry(
+            &req_data,
+            tr_layout.clone(),
+            next_key.as_deref(),
+            timeout,
+        )?)?;
+
+        let res_data = res.data()?;
+        for fields in data::get_array(res_data, "t0425OutBlock1")? {
+            orders.push(OpenOrder::from_fields(fields, "t0425OutBlock1")?);
+        }
+
+        match res.next_key() {
+            Some(key) => {
+                let cts_ordno: String = data::parse_field(
+                    data::get_block(res_data, "t0425OutBlock")?,
+                    "t0425OutBlock",
+                    "cts_ordno",
+                )?;
+                set_fields(&mut req_data, "t0425InBlock", &[("cts_ordno", cts_ordno)])?;
+                next_key = Some(key.to_owned());
+            }
+            None => break,
+        }
+    }
+
+    Ok(orders)
+}
+
+/// 계좌의 미체결 주문을 모두 취소합니다.
+///
+/// 자동 매매 전략을 긴급하게 중단하기 위한 용도로 사용할 수 있습니다.
+/// 미체결 주문은 t0425 TR로 조회하며, 각 주문의 미체결 잔량에 대해 CSPAT00800
+/// TR로 취소를 요청합니다. 초당 요청 제한에 걸린 경우 제한 시간 내에서
+/// 재요청합니다.
+///
+/// 미체결 주문 조회에 실패한 경우에만 에러를 반환하며, 개별 주문의 취소 결과는
+/// [`CancelAllReport`]에 기록됩니다.
+pub fn cancel_all(
+    account: &str,
+    password: &str,
+    t0425_layout: &Arc<TrLayout>,
+    cspat00800_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<CancelAllReport, Error> {
+    let mut report = CancelAllReport::default();
+
+    for open_order in open_orders(account, password, t0425_layout, timeout)? {
+        if open_order.remaining_qty == 0 {
+            continue;
+        }
+
+        let order = CancelOrder {
+            order_no: open_order.order_no,
+            account: account.to_owned(),
+            password: password.to_owned(),
+            symbol: open_order.symbol.clone(),
+            qty: open_order.remaining_qty,
+        };
+
+        match cancel_order(&order, cspat00800_layout, timeout) {
+            Ok(res) if res.is_ok() => report.accepted.push((open_order, res)),
+            Ok(res) => report.rejected.push((open_order, res)),
+            Err(err) => report.failed.push((open_order, err)),
+        }
+    }
+
+    Ok(report)
+}
+
+// 주문 TR을 요청하고 응답 블록에서 주문번호와 주문 시각을 가져옵니다.
+fn request_order(
+    req_data: &Data,
+    tr_layout: &Arc<TrLayout>,
+    timeout: Duration,
+) -> Result<OrderResponse, Error> {
+    let res = request_with_retry(req_data, tr_layout.clone(), None, timeout)?;
+
+    let mut order_res = OrderResponse {
+        code: res.code().to_owned(),
+        message: res.message().to_owned(),
+        order_no: None,
+        order_time: None,
+    };
+
+    if res.is_ok() {
+        let block_name = format!("{}OutBlock2", tr_layout.code);
+        let block = data::get_block(res.data()?, &block_name)?;
+
+        order_res.order_no = Some(data::parse_field(block, &block_name, "OrdNo")?);
+        order_res.order_time = Some(data::parse_field(block, &block_name, "OrdTime")?);
+    }
+
+    Ok(order_res)
+}
@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 포트폴리오 모듈
+//!
+//! t0424 TR로 조회한 주식 잔고를 기준으로 실시간 체결 이벤트(SC1)를 반영하여
+//! 종목별 보유 수량, 평균 단가, 실현 및 평가 손익을 갱신합니다.
+//!
+//! 손익은 수수료와 제세금을 제외하고 계산하므로 서버에서 조회한 값과 다를 수
+//! 있습니다. 주기적으로 [`Portfolio::reconcile()`]을 호출하여 서버의 잔고와
+//! 맞추는 것을 권장합니다.
+
+use super::account::Balance;
+use super::execution::{ExecutionEvent, ExecutionKind};
+use super::order::Side;
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 포트폴리오의 보유 종목
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Holding {
+    /// 종목번호
+    pub symbol: String,
+    /// 보유 수량
+    pub qty: u64,
+    /// 평균 단가
+    pub avg_price: f64,
+    /// 현재가
+    pub price: f64,
+    /// 실현 손익
+    pub realized_pnl: f64,
+}
+
+impl Holding {
+    fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_owned(),
+            qty: 0,
+            avg_price: 0.0,
+            price: 0.0,
+            realized_pnl: 0.0,
+        }
+    }
+
+    /// 현재가 기준의 평가 손익을 반환합니다.
+    pub fn unrealized_pnl(&self) -> f64 {
+        (self.price - self.avg_price) * self.qty as f64
+    }
+
+    fn fill(&mut self, side: Side, qty: u64, price: f64) {
+        match side {
+            Side::Buy => {
+                let total_qty = self.qty + qty;
+                self.avg_price =
+                    (self.avg_price * self.qty as f64 + price * qty as f64) / total_qty as f64;
+                self.qty = total_qty;
+            }
+            Side::Sell => {
+                // 잔고 조회 이전에 매수한 수량은 알 수 없으므로 보유 수량까지만
+                // 손익을 계산합니다.
+                let qty = qty.min(self.qty);
+                self.realized_pnl += (price - self.avg_price) * qty as f64;
+                self.qty -= qty;
+                if self.qty == 0 {
+                    self.avg_price = 0.0;
+                }
+            }
+        }
+
+        self.price = price;
+    }
+}
+
+/// 실시간으로 갱신되는 포트폴리오
+#[derive(Clone, Debug, Default)]
+pub struct Portfolio {
+    holdings: HashMap<String, Holding>,
+    applied: HashSet<(u64, u64)>,
+}
+
+impl Portfolio {
+    /// 보유 종목이 없는 포트폴리오를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 주식 잔고로 포트폴리오를 생성합니다.
+    pub fn from_balance(balance: &Balance) -> Self {
+        let mut portfolio = Self::new();
+        portfolio.reconcile(balance);
+        portfolio
+    }
+
+    /// 주식 잔고를 기준으로 보유 수량, 평균 단가, 현재가를 맞춥니다.
+    ///
+    /// 종목별 실현 손익은 유지되며, 잔고에 없는 종목은 보유 수량이 0이 됩니다.
+    pub fn reconcile(&mut self, balance: &Balance) {
+        for holding in self.holdings.values_mut() {
+            holding.qty = 0;
+            holding.avg_price = 0.0;
+        }
+
+        for position in &balance.positions {
+            let holding = self
+                .holdings
+                .entry(position.symbol.clone())
+                .or_insert_with(|| Holding::new(&position.symbol));
+
+            holding.qty = position.qty;
+            holding.avg_price = position.avg_price;
+            holding.price = position.price;
+        }
+
+        self.holdings
+            .retain(|_, h| h.qty != 0 || h.realized_pnl != 0.0);
+    }
+
+    /// 체결 이벤트를 적용하고 갱신된 보유 종목을 반환합니다.
+    ///
+    /// 체결 이벤트가 아니거나 이미 적용한 체결인 경우 `None`을 반환합니다.
+    /// 같은 체결은 주문번호와 미체결 수량으로 구분합니다.
+    pub fn apply(&mut self, event: &ExecutionEvent) -> Option<&Holding> {
+        if event.kind != ExecutionKind::Filled || event.qty == 0 {
+            return None;
+        }
+
+        if let Some(remaining_qty) = event.remaining_qty {
+            if !self.applied.insert((event.order_no, remaining_qty)) {
+                return None;
+            }
+        }
+
+        let symbol = normalize_symbol(&event.symbol);
+        let holding = self
+            .holdings
+            .entry(symbol.to_owned())
+            .or_insert_with(|| Holding::new(symbol));
+
+        holding.fill(event.side, event.qty, event.price);
+
+        Some(holding)
+    }
+
+    /// 종목의 현재가를 갱신합니다.
+    pub fn update_price(&mut self, symbol: &str, price: f64) {
+        if let Some(holding) = self.holdings.get_mut(normalize_symbol(symbol)) {
+            holding.price = price;
+        }
+    }
+
+    /// 종목번호에 해당하는 보유 종목을 반환합니다.
+    pub fn get(&self, symbol: &str) -> Option<&Holding> {
+        self.holdings.get(normalize_symbol(symbol))
+    }
+
+    /// 보유 종목 목록을 반환합니다.
+    ///
+    /// 모두 매도하여 보유 수량이 0인 종목도 포함됩니다.
+    pub fn holdings(&self) -> impl Iterator<Item = &Holding> {
+        self.holdings.values()
+    }
+
+    /// 전체 실현 손익을 반환합니다.
+    pub fn realized_pnl(&self) -> f64 {
+        self.holdings.values().map(|h| h.realized_pnl).sum()
+    }
+
+    /// 전체 평가 손익을 반환합니다.
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.holdings.values().map(|h| h.unrealized_pnl()).sum()
+    }
+}
+
+// 실시간 체결 TR의 종목번호는 `A005930`과 같이 앞에 `A`가 붙습니다.
+fn normalize_symbol(symbol: &str) -> &str {
+    match symbol.strip_prefix('A') {
+        Some(code) if code.len() == 6 => code,
+        _ => symbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Portfolio;
+    use crate::account::{Balance, Position};
+    use crate::execution::{ExecutionEvent, ExecutionKind};
+    use crate::order::Side;
+
+    fn fill(order_no: u64, side: Side, qty: u64, price: f64, remaining_qty: u64) -> ExecutionEvent {
+        ExecutionEvent {
+            kind: ExecutionKind::Filled,
+            order_no,
+            orig_order_no: None,
+            symbol: "A005930".into(),
+            side,
+            qty,
+            price,
+            remaining_qty: Some(remaining_qty),
+        }
+    }
+
+    #[test]
+    fn test_portfolio() {
+        let balance = Balance {
+            estimated_net_assets: 0,
+            realized_pnl: 0,
+            purchase_amount: 1814000,
+            estimated_d2_deposit: 0,
+            evaluation_amount: 1820000,
+            unrealized_pnl: 6000,
+            positions: vec![Position {
+                symbol: "005930".into(),
+                name: "삼성전자".into(),
+                qty: 20,
+                sellable_qty: 20,
+                avg_price: 90700.0,
+                price: 91000.0,
+                purchase_amount: 1814000,
+                evaluation_amount: 1820000,
+                unrealized_pnl: 6000,
+                fee: 0,
+                tax: 0,
+            }],
+        };
+
+        let mut portfolio = Portfolio::from_balance(&balance);
+        assert_eq!(portfolio.unrealized_pnl(), 6000.0);
+
+        portfolio
+            .apply(&fill(1, Side::Buy, 10, 91300.0, 0))
+            .unwrap();
+        let holding = portfolio.get("005930").unwrap();
+        assert_eq!(holding.qty, 30);
+        assert_eq!(holding.avg_price, 90900.0);
+
+        // 같은 체결은 한 번만 적용됩니다.
+        assert!(portfolio
+            .apply(&fill(1, Side::Buy, 10, 91300.0, 0))
+            .is_none());
+
+        portfolio
+            .apply(&fill(2, Side::Sell, 10, 91900.0, 5))
+            .unwrap();
+        let holding = portfolio.get("A005930").unwrap();
+        assert_eq!(holding.qty, 20);
+        assert_eq!(holding.realized_pnl, 10000.0);
+
+        portfolio.update_price("005930", 92000.0);
+        assert_eq!(portfolio.unrealized_pnl(), 22000.0);
+
+        portfolio.reconcile(&Balance {
+            positions: Vec::new(),
+            ..balance
+        });
+        assert_eq!(portfolio.get("005930").unwrap().qty, 0);
+        assert_eq!(portfolio.realized_pnl(), 10000.0);
+    }
+}
@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 실시간 데이터 모듈
+//!
+//! 자주 사용하는 실시간 TR의 응답을 문자열 대신 숫자 필드를 갖는 타입으로
+//! 변환합니다.
+//!
+//! | TR 코드        | 타입                 |
+//! |----------------|----------------------|
+//! | S3_, K3_       | [`TradeTick`]        |
+//! | H1_, HA_       | [`QuoteUpdate`]      |
+//! | SC0 ~ SC4      | [`ExecutionEvent`]   |
+//! | VI_            | [`ViEvent`]          |
+
+use super::execution::{ExecutionEvent, ExecutionKind};
+use super::market::{parse_levels, ORDERBOOK_DEPTH};
+use super::order::Side;
+use super::RealResponse;
+use crate::data::{self, Data, DecodeError, Sign};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const BLOCK_NAME: &str = "OutBlock";
+
+/// 주식 체결 (S3_, K3_)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TradeTick {
+    /// 종목번호
+    pub symbol: String,
+    /// 체결 시간 (`HHMMSS`)
+    pub time: String,
+    /// 현재가
+    pub price: f64,
+    /// 전일 대비
+    pub change: f64,
+    /// 시가
+    pub open: f64,
+    /// 고가
+    pub high: f64,
+    /// 저가
+    pub low: f64,
+    /// 체결 수량
+    pub qty: u64,
+    /// 누적 거래량
+    pub volume: u64,
+    /// 체결 구분
+    ///
+    /// 매수 체결이면 [`Side::Buy`], 매도 체결이면 [`Side::Sell`]입니다.
+    pub side: Side,
+}
+
+impl TradeTick {
+    /// 주식 체결 TR 코드
+    pub const TR_CODES: [&'static str; 2] = ["S3_", "K3_"];
+
+    /// 수신한 실시간 응답을 주식 체결로 변환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Self, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 주식 체결로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        if !Self::TR_CODES.contains(&data.tr_code.as_str()) {
+            return Err(DecodeError::UnknownLayout(data.tr_code.clone()));
+        }
+
+        let fields = data::get_block(data, BLOCK_NAME)?;
+        let parse_f64 = |field| data::parse_field::<f64>(fields, BLOCK_NAME, field);
+
+        // 전일 대비는 부호 없이 전송되며 대비 구분이 하한(4) 또는 하락(5)인
+        // 경우 음수입니다.
+        let sign: String = data::parse_field(fields, BLOCK_NAME, "sign")?;
+        let change = parse_f64("change")?.abs();
+        let change = match Sign::from_code(&sign) {
+            Some(sign) if sign.is_negative() => -change,
+            _ => change,
+        };
+
+        let side = match fields.get("cgubun").map(|s| s.trim()) {
+            Some("+") => Side::Buy,
+            Some("-") => Side::Sell,
+            _ => return Err(invalid_field("cgubun")),
+        };
+
+        Ok(Self {
+            symbol: data::parse_field(fields, BLOCK_NAME, "shcode")?,
+            time: data::parse_field(fields, BLOCK_NAME, "chetime")?,
+            price: parse_f64("price")?,
+            change,
+            open: parse_f64("open")?,
+            high: parse_f64("high")?,
+            low: parse_f64("low")?,
+            qty: data::parse_field(fields, BLOCK_NAME, "cvolume")?,
+            volume: data::parse_field(fields, BLOCK_NAME, "volume")?,
+            side,
+        })
+    }
+}
+
+/// 주식 호가 잔량 (H1_, HA_)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteUpdate {
+    /// 종목번호
+    pub symbol: String,
+    /// 호가 시간 (`HHMMSS`)
+    pub time: String,
+    /// 매도 호가와 잔량 (1호가부터 10호가까지)
+    pub asks: [(f64, u64); ORDERBOOK_DEPTH],
+    /// 매수 호가와 잔량 (1호가부터 10호가까지)
+    pub bids: [(f64, u64); ORDERBOOK_DEPTH],
+    /// 총 매도 호가 잔량
+    pub total_ask_qty: u64,
+    /// 총 매수 호가 잔량
+    pub total_bid_qty: u64,
+}
+
+impl QuoteUpdate {
+    /// 주식 호가 잔량 TR 코드
+    pub const TR_CODES: [&'static str; 2] = ["H1_", "HA_"];
+
+    /// 수신한 실시간 응답을 호가 잔량으로 변환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Self, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 호가 잔량으로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        if !Self::TR_CODES.contains(&data.tr_code.as_str()) {
+            return Err(DecodeError::UnknownLayout(data.tr_code.clone()));
+        }
+
+        let fields = data::get_block(data, BLOCK_NAME)?;
+
+        Ok(Self {
+            symbol: data::parse_field(fields, BLOCK_NAME, "shcode")?,
+            time: data::parse_field(fields, BLOCK_NAME, "hotime")?,
+            asks: parse_levels(fields, BLOCK_NAME, "offerho", "offerrem")?,
+            bids: parse_levels(fields, BLOCK_NAME, "bidho", "bidrem")?,
+            total_ask_qty: data::parse_field(fields, BLOCK_NAME, "totofferrem")?,
+            total_bid_qty: data::parse_field(fields, BLOCK_NAME, "totbidrem")?,
+        })
+    }
+}
+
+/// 변동성 완화장치 발동 구분
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ViKind {
+    /// 해제
+    Released,
+    /// 정적 VI 발동
+    Static,
+    /// 동적 VI 발동
+    Dynamic,
+    /// 정적 및 동적 VI 발동
+    StaticAndDynamic,
+}
+
+/// 변동성 완화장치 발동 및 해제 (VI_)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ViEvent {
+    /// 종목번호
+    pub symbol: String,
+    /// 발동 구분
+    pub kind: ViKind,
+    /// 정적 VI 발동 기준 가격
+    pub static_base_price: f64,
+    /// 동적 VI 발동 기준 가격
+    pub dynamic_base_price: f64,
+    /// VI 발동 가격
+    pub trigger_price: f64,
+    /// 시간 (`HHMMSS`)
+    pub time: String,
+}
+
+impl ViEvent {
+    /// 변동성 완화장치 TR 코드
+    pub const TR_CODE: &'static str = "VI_";
+
+    /// 수신한 실시간 응답을 변동성 완화장치 이벤트로 변환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Self, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 변동성 완화장치 이벤트로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        if data.tr_code != Self::TR_CODE {
+            return Err(DecodeError::UnknownLayout(data.tr_code.clone()));
+        }
+
+        let fields = data::get_block(data, BLOCK_NAME)?;
+        let parse_f64 = |field| data::parse_field::<f64>(fields, BLOCK_NAME, field);
+
+        let kind = match fields.get("vi_gubun").map(|s| s.trim()) {
+            Some("0") => ViKind::Released,
+            Some("1") => ViKind::Static,
+            Some("2") => ViKind::Dynamic,
+            Some("3") => ViKind::StaticAndDynamic,
+            _ => return Err(invalid_field("vi_gubun")),
+        };
+
+        Ok(Self {
+            symbol: data::parse_field(fields, BLOCK_NAME, "shcode")?,
+            kind,
+            static_base_price: parse_f64("svi_recprice")?,
+            dynamic_base_price: parse_f64("dvi_recprice")?,
+            trigger_price: parse_f64("vi_trgprice")?,
+            time: data::parse_field(fields, BLOCK_NAME, "time")?,
+        })
+    }
+}
+
+/// 타입이 지정된 실시간 데이터
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RealData {
+    /// 주식 체결
+    Trade(TradeTick),
+    /// 주식 호가 잔량
+    Quote(Box<QuoteUpdate>),
+    /// 주문 체결
+    Execution(ExecutionEvent),
+    /// 변동성 완화장치
+    Vi(ViEvent),
+}
+
+impl RealData {
+    /// 수신한 실시간 응답을 TR 코드에 해당하는 타입으로 변환합니다.
+    ///
+    /// 지원하지 않는 TR 코드인 경우 `DecodeError::UnknownLayout`을
+    /// 반환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Self, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 TR 코드에 해당하는 타입으로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        let tr_code = data.tr_code.as_str();
+
+        if TradeTick::TR_CODES.contains(&tr_code) {
+            TradeTick::from_data(data).map(Self::Trade)
+        } else if QuoteUpdate::TR_CODES.contains(&tr_code) {
+            QuoteUpdate::from_data(data).map(|q| Self::Quote(Box::new(q)))
+        } else if ExecutionKind::from_tr_code(tr_code).is_some() {
+            ExecutionEvent::from_data(data).map(Self::Execution)
+        } else if tr_code == ViEvent::TR_CODE {
+            ViEvent::from_data(data).map(Self::Vi)
+        } else {
+            Err(DecodeError::UnknownLayout(data.tr_code.clone()))
+        }
+    }
+}
+
+fn invalid_field(field_name: &str) -> DecodeError {
+    DecodeError::InvalidField {
+        block: BLOCK_NAME.to_owned(),
+        field: field_name.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RealData, TradeTick, ViEvent, ViKind};
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+    use crate::order::Side;
+
+    fn real_data(
+        tr_code: &str,
+        fields: std::collections::HashMap<std::sync::Arc<str>, String>,
+    ) -> Data {
+        Data {
+            tr_code: tr_code.into(),
+            data_type: DataType::Output,
+            blocks: hashmap! { "OutBlock" => Block::Block(fields) },
+        }
+    }
+
+    #[test]
+    fn test_trade_tick() {
+        let data = real_data(
+            "S3_",
+            hashmap! {
+                "shcode" => "005930",
+                "chetime" => "093000",
+                "sign" => "5",
+                "change" => "500",
+                "price" => "90500",
+                "open" => "91000",
+                "high" => "91200",
+                "low" => "90400",
+                "cgubun" => "-",
+                "cvolume" => "12",
+                "volume" => "1234567",
+            },
+        );
+
+        let tick = TradeTick::from_data(&data).unwrap();
+        assert_eq!(tick.change, -500.0);
+        assert_eq!(tick.side, Side::Sell);
+        assert_eq!(tick.qty, 12);
+
+        assert!(matches!(
+            RealData::from_data(&data).unwrap(),
+            RealData::Trade(t) if t == tick
+        ));
+    }
+
+    #[test]
+    fn test_vi_event() {
+        let data = real_data(
+            "VI_",
+            hashmap! {
+                "shcode" => "005930",
+                "vi_gubun" => "2",
+                "svi_recprice" => "0",
+                "dvi_recprice" => "90000",
+                "vi_trgprice" => "92700",
+                "time" => "100000",
+            },
+        );
+
+        let event = ViEvent::from_data(&data).unwrap();
+        assert_eq!(event.kind, ViKind::Dynamic);
+        assert_eq!(event.trigger_price, 92700.0);
+
+        let mut data = data;
+        data.tr_code = "XX_".into();
+        assert!(RealData::from_data(&data).is_err());
+    }
+}
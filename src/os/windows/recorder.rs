@@ -0,0 +1,439 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 실시간 데이터 기록 모듈
+//!
+//! 수신한 실시간 응답을 수신 시각과 함께 디스크에 추가 전용 파일로 기록하여
+//! 장 중의 데이터를 연구용으로 보관할 수 있도록 합니다.
+//!
+//! 파일은 `YYYYMMDD-NNN.xrec` 형식의 이름으로 생성되며, 한국 표준시 기준으로
+//! 날짜가 바뀌거나 지정된 크기를 넘으면 새로운 파일로 교체됩니다.
+//!
+//! ## 파일 형식
+//! 파일은 `XREC`와 버전(1바이트)으로 시작하며, 이후 각 레코드는 리틀 엔디언
+//! `u32` 길이 다음에 레코드 내용이 이어집니다. 문자열은 `u32` 길이와 UTF-8
+//! 바이트로 저장됩니다.
+
+use super::{kst, RealResponse};
+use crate::codec::{get_str, get_u32, get_u8, invalid_data, put_str, put_u32, take};
+use crate::data::{Block, Data, DataType, DecodeError};
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MAGIC: &[u8; 4] = b"XREC";
+const VERSION: u8 = 1;
+const EXTENSION: &str = "xrec";
+
+/// 기록된 실시간 응답
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// 수신 시각
+    pub time: SystemTime,
+    /// 실시간 응답
+    pub response: RealResponse,
+}
+
+/// 실시간 응답 기록 객체
+pub struct Recorder {
+    dir: PathBuf,
+    max_size: u64,
+    date: String,
+    seq: u32,
+    size: u64,
+    writer: Option<BufWriter<File>>,
+}
+
+impl Recorder {
+    /// 기본 파일 크기 제한 (1 GiB)
+    pub const DEFAULT_MAX_SIZE: u64 = 1 << 30;
+
+    /// 지정된 디렉터리에 기록하는 객체를 생성합니다.
+    ///
+    /// 디렉터리가 없는 경우 생성합니다.
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        Self::with_max_size(dir, Self::DEFAULT_MAX_SIZE)
+    }
+
+    /// 파일 크기 제한을 지정하여 객체를 생성합니다.
+    pub fn with_max_size<P: AsRef<Path>>(dir: P, max_size: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir: dir.as_ref().to_owned(),
+            max_size,
+            date: String::new(),
+            seq: 0,
+            size: 0,
+            writer: None,
+        })
+    }
+
+    /// 현재 기록 중인 파일의 경로를 반환합니다.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.writer.as_ref().map(|_| self.file_path(self.seq))
+    }
+
+    /// 실시간 응답을 수신 시각과 함께 기록합니다.
+    pub fn record(&mut self, res: &RealResponse) -> io::Result<()> {
+        self.record_at(res, res.received_at)
+    }
+
+    /// 실시간 응답을 지정된 수신 시각과 함께 기록합니다.
+    pub fn record_at(&mut self, res: &RealResponse, time: SystemTime) -> io::Result<()> {
+        let mut buf = Vec::new();
+        encode_record(&mut buf, res, time);
+
+        let date = kst::date_string(kst::DateTime::from_system_time(time).days);
+        if self.writer.is_none() || date != self.date || self.size >= self.max_size {
+            self.rotate(date)?;
+        }
+
+        let writer = self.writer.as_mut().unwrap();
+        writer.write_all(&(buf.len() as u32).to_le_bytes())?;
+        writer.write_all(&buf)?;
+        self.size += 4 + buf.len() as u64;
+
+        Ok(())
+    }
+
+    /// 버퍼에 남아있는 기록을 파일에 씁니다.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn file_path(&self, seq: u32) -> PathBuf {
+        self.dir
+            .join(format!("{}-{:03}.{}", self.date, seq, EXTENSION))
+    }
+
+    fn rotate(&mut self, date: String) -> io::Result<()> {
+        self.flush()?;
+        self.writer = None;
+
+        self.seq = if date == self.date { self.seq + 1 } else { 0 };
+        self.date = date;
+
+        // 이전에 기록한 파일이 있는 경우 덮어쓰지 않고 다음 번호를 사용합니다.
+        let file = loop {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.file_path(self.seq))
+            {
+                Ok(file) => break file,
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => self.seq += 1,
+                Err(err) => return Err(err),
+            }
+        };
+
+        let mut writer = BufWriter::new(file);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        self.writer = Some(writer);
+        self.size = (MAGIC.len() + 1) as u64;
+
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// 기록된 파일을 읽는 객체
+///
+/// 레코드를 순서대로 반환하는 반복자입니다.
+pub struct RecordReader<R> {
+    reader: R,
+}
+
+impl RecordReader<BufReader<File>> {
+    /// 기록된 파일을 엽니다.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> RecordReader<R> {
+    /// 파일 헤더를 확인하고 객체를 생성합니다.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; 5];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(invalid_data());
+        }
+
+        Ok(Self { reader })
+    }
+
+    /// 다음 레코드를 읽습니다.
+    ///
+    /// 파일의 끝에 도달한 경우 `None`을 반환합니다. 기록 중에 중단되어
+    /// 마지막 레코드가 불완전한 경우도 파일의 끝으로 간주합니다.
+    pub fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut len = [0; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        let mut buf = vec![0; u32::from_le_bytes(len) as usize];
+        match self.reader.read_exact(&mut buf) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        decode_record(&mut buf.as_slice()).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+fn put_fields(buf: &mut Vec<u8>, fields: &HashMap<Arc<str>, String>) {
+    put_u32(buf, fields.len() as u32);
+    for (name, value) in fields {
+        put_str(buf, name);
+        put_str(buf, value);
+    }
+}
+
+pub(super) fn put_blocks(buf: &mut Vec<u8>, blocks: &HashMap<String, Block>) {
+    put_u32(buf, blocks.len() as u32);
+    for (block_name, block) in blocks {
+        put_str(buf, block_name);
+        match block {
+            Block::Block(fields) => {
+                buf.push(0);
+                put_fields(buf, fields);
+            }
+            Block::Array(array) => {
+                buf.push(1);
+                put_u32(buf, array.len() as u32);
+                for fields in array {
+                    put_fields(buf, fields);
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn put_time(buf: &mut Vec<u8>, time: SystemTime) {
+    let nanos = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i64,
+        Err(err) => -(err.duration().as_nanos() as i64),
+    };
+    buf.extend_from_slice(&nanos.to_le_bytes());
+}
+
+fn encode_record(buf: &mut Vec<u8>, res: &RealResponse, time: SystemTime) {
+    put_time(buf, time);
+
+    put_str(buf, &res.tr_code);
+    put_str(buf, &res.key);
+    put_data(buf, &res.data);
+}
+
+// 디코딩 결과를 씁니다. 디코딩에 실패한 경우 에러를 대신 씁니다.
+pub(super) fn put_data(buf: &mut Vec<u8>, data: &Result<Data, DecodeError>) {
+    match data {
+        Ok(data) => {
+            buf.push(0);
+            put_blocks(buf, &data.blocks);
+        }
+        Err(err) => {
+            buf.push(1);
+            let (tag, args) = match err {
+                DecodeError::UnknownLayout(name) => (0, vec![name]),
+                DecodeError::UnknownBlock(name) => (1, vec![name]),
+                DecodeError::MismatchDataLength => (2, vec![]),
+                DecodeError::InvalidArrayLength => (3, vec![]),
+                DecodeError::MalformedString => (4, vec![]),
+                DecodeError::MissingBlock(name) => (5, vec![name]),
+                DecodeError::MissingField { block, field } => (6, vec![block, field]),
+                DecodeError::InvalidField { block, field } => (7, vec![block, field]),
+            };
+            buf.push(tag);
+            for arg in args {
+                put_str(buf, arg);
+            }
+        }
+    }
+}
+
+fn get_fields(buf: &mut &[u8]) -> io::Result<HashMap<Arc<str>, String>> {
+    let len = get_u32(buf)? as usize;
+    let mut fields = HashMap::with_capacity(len.min(buf.len()));
+    for _ in 0..len {
+        fields.insert(get_str(buf)?.into(), get_str(buf)?);
+    }
+
+    Ok(fields)
+}
+
+pub(super) fn get_blocks(buf: &mut &[u8]) -> io::Result<HashMap<String, Block>> {
+    let len = get_u32(buf)? as usize;
+    let mut blocks = HashMap::with_capacity(len.min(buf.len()));
+    for _ in 0..len {
+        let block_name = get_str(buf)?;
+        let block = match get_u8(buf)? {
+            0 => Block::Block(get_fields(buf)?),
+            1 => {
+                let len = get_u32(buf)? as usize;
+                let mut array = Vec::with_capacity(len.min(buf.len()));
+                for _ in 0..len {
+                    array.push(get_fields(buf)?);
+                }
+                Block::Array(array)
+            }
+            _ => return Err(invalid_data()),
+        };
+        blocks.insert(block_name, block);
+    }
+
+    Ok(blocks)
+}
+
+pub(super) fn get_time(buf: &mut &[u8]) -> io::Result<SystemTime> {
+    let nanos = i64::from_le_bytes(take(buf, 8)?.try_into().unwrap());
+    Ok(if nanos >= 0 {
+        UNIX_EPOCH + Duration::from_nanos(nanos as u64)
+    } else {
+        UNIX_EPOCH - Duration::from_nanos(nanos.unsigned_abs())
+    })
+}
+
+fn decode_record(buf: &mut &[u8]) -> io::Result<Record> {
+    let time = get_time(buf)?;
+
+    let tr_code = get_str(buf)?;
+    let key = get_str(buf)?;
+    let data = get_data(buf, &tr_code, DataType::Output)?;
+
+    Ok(Record {
+        time,
+        response: RealResponse {
+            tr_code,
+            key,
+            data,
+            received_at: time,
+        },
+    })
+}
+
+pub(super) fn get_data(
+    buf: &mut &[u8],
+    tr_code: &str,
+    data_type: DataType,
+) -> io::Result<Result<Data, DecodeError>> {
+    Ok(match get_u8(buf)? {
+        0 => Ok(Data {
+            tr_code: tr_code.to_owned(),
+            data_type,
+            blocks: get_blocks(buf)?,
+        }),
+        1 => Err(match get_u8(buf)? {
+            0 => DecodeError::UnknownLayout(get_str(buf)?),
+            1 => DecodeError::UnknownBlock(get_str(buf)?),
+            2 => DecodeError::MismatchDataLength,
+            3 => DecodeError::InvalidArrayLength,
+            4 => DecodeError::MalformedString,
+            5 => DecodeError::MissingBlock(get_str(buf)?),
+            6 => DecodeError::MissingField {
+                block: get_str(buf)?,
+                field: get_str(buf)?,
+            },
+            7 => DecodeError::InvalidField {
+                block: get_str(buf)?,
+                field: get_str(buf)?,
+            },
+            _ => return Err(invalid_data()),
+        }),
+        _ => return Err(invalid_data()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordReader, Recorder};
+    use crate::data::{Block, Data, DataType, DecodeError};
+    use crate::hashmap;
+    use crate::RealResponse;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_recorder() {
+        let dir = std::env::temp_dir().join(format!("xingapi-recorder-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // 2021-01-11 09:30:00 KST
+        let time = UNIX_EPOCH + Duration::from_secs(1610325000);
+
+        let ok = RealResponse {
+            tr_code: "S3_".into(),
+            key: "005930".into(),
+            data: Ok(Data {
+                tr_code: "S3_".into(),
+                data_type: DataType::Output,
+                blocks: hashmap! {
+                    "OutBlock" => Block::Block(hashmap! {
+                        "shcode" => "005930",
+                        "price" => "91000",
+                    }),
+                },
+            }),
+            received_at: time,
+        };
+        let err = RealResponse {
+            tr_code: "H1_".into(),
+            key: "005930".into(),
+            data: Err(DecodeError::UnknownLayout("H1_".into())),
+            received_at: time,
+        };
+
+        let mut recorder = Recorder::with_max_size(&dir, 64).unwrap();
+        recorder.record_at(&ok, time).unwrap();
+        recorder.record_at(&err, time).unwrap();
+        recorder.flush().unwrap();
+        assert_eq!(recorder.path().unwrap(), dir.join("20210111-001.xrec"));
+
+        let records: Vec<_> = RecordReader::open(dir.join("20210111-000.xrec"))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].time, time);
+        assert_eq!(records[0].response.key(), "005930");
+        assert_eq!(records[0].response.data().unwrap(), ok.data().unwrap());
+
+        let mut reader = RecordReader::open(dir.join("20210111-001.xrec")).unwrap();
+        let record = reader.read_record().unwrap().unwrap();
+        assert!(matches!(
+            record.response.data(),
+            Err(DecodeError::UnknownLayout(name)) if name == "H1_"
+        ));
+        assert!(reader.read_record().unwrap().is_none());
+
+        drop(recorder);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 기록된 실시간 데이터 재생 모듈
+//!
+//! [`recorder`](super::recorder) 모듈로 기록한 파일을 읽어 [`RealEvent`]와
+//! 같은 방식으로 실시간 응답을 수신할 수 있도록 합니다. DLL 없이 전략을
+//! 시험하는 데 사용할 수 있습니다.
+
+use super::recorder::{Record, RecordReader};
+use super::{RealEvent, RealResponse};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+/// 실시간 응답을 수신하는 객체
+///
+/// [`RealEvent`]와 [`Player`]를 구분하지 않고 사용하기 위한 트레잇입니다.
+pub trait RealSource {
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    fn try_recv(&self) -> Option<RealResponse>;
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse>;
+}
+
+impl RealSource for RealEvent {
+    fn try_recv(&self) -> Option<RealResponse> {
+        RealEvent::try_recv(self)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        RealEvent::recv_timeout(self, timeout)
+    }
+}
+
+/// 재생 속도
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pace {
+    /// 기다리지 않고 가능한 빠르게 재생합니다.
+    Fast,
+    /// 기록된 수신 시각의 간격대로 재생합니다.
+    Realtime,
+    /// 기록된 수신 시각의 간격을 지정된 배율로 나누어 재생합니다.
+    ///
+    /// 예를 들어 `Scaled(10.0)`은 10배속입니다.
+    Scaled(f64),
+}
+
+/// 기록된 실시간 데이터 재생 객체
+///
+/// 객체를 생성하면 내부 스레드에서 파일을 읽으며, 읽은 응답은
+/// [`try_recv()`](Self::try_recv)나 [`recv_timeout()`](Self::recv_timeout)
+/// 함수로 가져옵니다. 객체를 해제하면 재생이 중단됩니다.
+pub struct Player {
+    rx_res: Option<Receiver<RealResponse>>,
+    tx_stop: Option<Sender<()>>,
+    error: Arc<Mutex<Option<io::Error>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Player {
+    const QUEUE_SIZE: usize = 1024;
+
+    /// 기록된 파일들을 순서대로 재생합니다.
+    pub fn open<P: AsRef<Path>>(paths: &[P], pace: Pace) -> io::Result<Self> {
+        let mut readers = Vec::with_capacity(paths.len());
+        for path in paths {
+            readers.push(RecordReader::open(path)?);
+        }
+
+        Ok(Self::spawn(readers, pace))
+    }
+
+    /// 디렉터리에 기록된 파일들을 이름 순서대로 재생합니다.
+    pub fn open_dir<P: AsRef<Path>>(dir: P, pace: Pace) -> io::Result<Self> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "xrec"));
+        paths.sort();
+
+        Self::open(&paths, pace)
+    }
+
+    /// 파일 헤더를 확인한 리더들을 순서대로 재생합니다.
+    pub fn from_readers<R>(readers: Vec<RecordReader<R>>, pace: Pace) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        Self::spawn(readers, pace)
+    }
+
+    fn spawn<R>(readers: Vec<RecordReader<R>>, pace: Pace) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        let (tx_res, rx_res) = crossbeam_channel::bounded(Self::QUEUE_SIZE);
+        let (tx_stop, rx_stop) = crossbeam_channel::bounded::<()>(0);
+        let error = Arc::new(Mutex::new(None));
+
+        let thread = std::thread::spawn({
+            let error = error.clone();
+            move || {
+                let mut clock = PaceClock::new(pace);
+
+                for record in readers.into_iter().flatten() {
+                    let Record { time, response } = match record {
+                        Ok(record) => record,
+                        Err(err) => {
+                            *error.lock().unwrap() = Some(err);
+                            return;
+                        }
+                    };
+
+                    if let Some(deadline) = clock.deadline(time) {
+                        match rx_stop.recv_deadline(deadline) {
+                            Err(RecvTimeoutError::Timeout) => {}
+                            _ => return,
+                        }
+                    }
+
+                    // 객체가 해제된 경우 재생을 중단합니다.
+                    if tx_res.send(response).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self {
+            rx_res: Some(rx_res),
+            tx_stop: Some(tx_stop),
+            error,
+            thread: Some(thread),
+        }
+    }
+
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        self.rx_res.as_ref().unwrap().try_recv().ok()
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.rx_res.as_ref().unwrap().recv_timeout(timeout).ok()
+    }
+
+    /// 다음 응답을 가져올 때까지 기다립니다.
+    ///
+    /// 재생이 끝난 경우 `None`을 반환합니다.
+    pub fn recv(&self) -> Option<RealResponse> {
+        self.rx_res.as_ref().unwrap().recv().ok()
+    }
+
+    /// 재생이 끝났고 큐에 남은 응답이 없는지 확인합니다.
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().unwrap().is_finished() && self.rx_res.as_ref().unwrap().is_empty()
+    }
+
+    /// 파일을 읽는 도중 발생한 에러를 가져옵니다.
+    ///
+    /// 에러가 발생한 경우 해당 파일 이후의 재생은 중단됩니다.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error.lock().unwrap().take()
+    }
+}
+
+impl RealSource for Player {
+    fn try_recv(&self) -> Option<RealResponse> {
+        Player::try_recv(self)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        Player::recv_timeout(self, timeout)
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        // 재생 시점을 기다리거나 큐에 보내기 위해 대기 중인 스레드를 깨웁니다.
+        drop(self.tx_stop.take());
+        drop(self.rx_res.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// 기록된 수신 시각에 맞춰 재생하기 위한 시계
+struct PaceClock {
+    scale: Option<f64>,
+    origin: Option<(SystemTime, Instant)>,
+}
+
+impl PaceClock {
+    fn new(pace: Pace) -> Self {
+        let scale = match pace {
+            Pace::Fast => None,
+            Pace::Realtime => Some(1.0),
+            Pace::Scaled(speed) if speed > 0.0 => Some(1.0 / speed),
+            Pace::Scaled(_) => None,
+        };
+
+        Self {
+            scale,
+            origin: None,
+        }
+    }
+
+    // 지정된 수신 시각의 응답을 재생할 시점을 반환합니다.
+    fn deadline(&mut self, time: SystemTime) -> Option<Instant> {
+        let scale = self.scale?;
+
+        let (first_time, started) = *self.origin.get_or_insert((time, Instant::now()));
+        let offset = time.duration_since(first_time).unwrap_or_default();
+
+        Some(started + offset.mul_f64(scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pace, Player};
+    use crate::data::DecodeError;
+    use crate::recorder::{RecordReader, Recorder};
+    use crate::RealResponse;
+
+    use std::time::{Duration, Instant, UNIX_EPOCH};
+
+    #[test]
+    fn test_player() {
+        let dir = std::env::temp_dir().join(format!("xingapi-replay-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let base = UNIX_EPOCH + Duration::from_secs(1610325000);
+        let mut recorder = Recorder::new(&dir).unwrap();
+        for i in 0..3 {
+            let res = RealResponse {
+                tr_code: "S3_".into(),
+                key: format!("{:06}", i),
+                data: Err(DecodeError::UnknownLayout("S3_".into())),
+                received_at: base + Duration::from_millis(i * 50),
+            };
+            recorder.record(&res).unwrap();
+        }
+        drop(recorder);
+
+        let player = Player::open_dir(&dir, Pace::Fast).unwrap();
+        let keys: Vec<_> = std::iter::from_fn(|| player.recv())
+            .map(|res| res.key().to_owned())
+            .collect();
+        assert_eq!(keys, ["000000", "000001", "000002"]);
+        assert!(player.is_finished());
+        assert!(player.take_error().is_none());
+
+        let reader = RecordReader::open(dir.join("20210111-000.xrec")).unwrap();
+        let started = Instant::now();
+        let player = Player::from_readers(vec![reader], Pace::Realtime);
+        assert_eq!(std::iter::from_fn(|| player.recv()).count(), 3);
+        assert!(started.elapsed() >= Duration::from_millis(100));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
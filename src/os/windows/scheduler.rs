@@ -0,0 +1,419 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! TR 요청 제한 스케줄러 모듈
+//!
+//! XingAPI는 TR마다 초당 요청 횟수와 10분 내 요청 횟수를 제한하며, 제한을
+//! 넘은 요청은 서버로 전송되지 않고 실패합니다. [`Scheduler`]는 TR 코드별
+//! 대기열에서 요청한 순서대로 허용량을 나누어 주므로 여러 스레드가 각자
+//! 대기하며 재요청하지 않아도 됩니다.
+//!
+//! ## 예제
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use xingapi::scheduler::Scheduler;
+//!
+//! let layout_tbl = xingapi::layout::load().unwrap();
+//! let scheduler = Arc::new(Scheduler::new());
+//!
+//! let handles: Vec<_> = ["005930", "000660"]
+//!     .into_iter()
+//!     .map(|shcode| {
+//!         let scheduler = scheduler.clone();
+//!         let tr_layout = layout_tbl["t1102"].clone();
+//!
+//!         std::thread::spawn(move || {
+//!             let data = xingapi::data!("t1102", input {
+//!                 t1102InBlock { shcode: shcode },
+//!             });
+//!             scheduler.request(&data, &tr_layout, None, Duration::from_secs(10))
+//!         })
+//!     })
+//!     .collect();
+//!
+//! for handle in handles {
+//!     println!("{:?}", handle.join().unwrap().map(|res| res.code));
+//! }
+//! ```
+
+use super::backend::{Backend, DllBackend};
+use super::{Error, ErrorKind, QueryResponse};
+use crate::data::Data;
+use crate::layout::TrLayout;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// 10분 내 요청 제한을 계산하는 구간
+const TEN_MINUTES: Duration = Duration::from_secs(600);
+
+/// TR의 요청 제한
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quota {
+    /// 기준 시간 동안 요청할 수 있는 횟수
+    ///
+    /// 0인 경우 제한하지 않습니다.
+    pub per_sec: u32,
+    /// 초당 요청 제한의 기준 시간 (초)
+    pub base_sec: u32,
+    /// 10분 내 요청할 수 있는 횟수
+    ///
+    /// 0인 경우 요청하지 않고 `Error::TimedOut`을 반환합니다.
+    pub per_ten_min: Option<u32>,
+}
+
+impl Quota {
+    /// 불러온 DLL에서 TR의 요청 제한을 조회합니다.
+    ///
+    /// 제한이 없거나 알 수 없는 경우 `None`을 반환합니다.
+    pub fn from_dll(tr_code: &str) -> Option<Self> {
+        let limits = super::tr_limits(tr_code)?;
+
+        if limits.per_sec.is_none() && limits.limit_per_ten_min.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            per_sec: limits.per_sec.unwrap_or(0),
+            base_sec: limits.base_sec.unwrap_or(1),
+            per_ten_min: limits.limit_per_ten_min,
+        })
+    }
+
+    // 연속한 두 요청 사이의 최소 간격
+    fn interval(&self) -> Duration {
+        if self.per_sec == 0 {
+            return Duration::ZERO;
+        }
+
+        Duration::from_secs(self.base_sec.max(1).into()) / self.per_sec
+    }
+}
+
+// TR 코드 하나의 대기열
+struct Lane {
+    quota: Option<Quota>,
+    // 차례를 기다리는 요청의 번호
+    queue: VecDeque<u64>,
+    // 다음 요청을 보낼 수 있는 시각
+    next_at: Instant,
+    // 최근 10분 내에 보낸 요청의 시각
+    sent: VecDeque<Instant>,
+}
+
+impl Lane {
+    fn new(quota: Option<Quota>) -> Self {
+        Self {
+            quota,
+            queue: VecDeque::new(),
+            next_at: Instant::now(),
+            sent: VecDeque::new(),
+        }
+    }
+
+    // 대기열의 맨 앞 요청을 보낼 수 있는 시각을 반환합니다.
+    //
+    // 10분 내 제한 횟수가 0이면 보낼 수 없으므로 `None`을 반환합니다.
+    fn ready_at(&mut self, now: Instant) -> Option<Instant> {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= TEN_MINUTES)
+        {
+            self.sent.pop_front();
+        }
+
+        match self.quota.and_then(|quota| quota.per_ten_min) {
+            Some(0) => None,
+            Some(limit) if self.sent.len() >= limit as usize => {
+                let oldest = self.sent[self.sent.len() - limit as usize];
+                Some(self.next_at.max(oldest + TEN_MINUTES))
+            }
+            _ => Some(self.next_at),
+        }
+    }
+
+    fn grant(&mut self, now: Instant) {
+        if let Some(quota) = self.quota {
+            self.next_at = now + quota.interval();
+
+            if quota.per_ten_min.is_some() {
+                self.sent.push_back(now);
+            }
+        }
+    }
+}
+
+struct State {
+    lanes: HashMap<String, Lane>,
+    next_ticket: u64,
+}
+
+/// TR 요청 제한을 지키며 요청하는 스케줄러
+///
+/// TR 코드별로 요청을 받은 순서대로 대기열에 넣고, 요청 제한이 허용하는
+/// 시점에 하나씩 백엔드로 요청합니다. 서로 다른 TR 코드의 요청은 서로
+/// 기다리지 않습니다. 여러 스레드에서 공유하려면 [`Arc`](std::sync::Arc)로
+/// 감싸서 사용합니다.
+///
+/// 요청 제한은 TR 코드별로 처음 요청할 때 조회하며, 스케줄러를 통해 보낸
+/// 요청만 횟수에 포함합니다.
+pub struct Scheduler<B = DllBackend> {
+    backend: B,
+    quota: fn(&str) -> Option<Quota>,
+    state: Mutex<State>,
+    cvar: Condvar,
+}
+
+impl Scheduler<DllBackend> {
+    /// 불러온 DLL로 요청하고 DLL에서 요청 제한을 조회하는 스케줄러를
+    /// 생성합니다.
+    pub fn new() -> Self {
+        Self::with_backend(DllBackend, Quota::from_dll)
+    }
+}
+
+impl Default for Scheduler<DllBackend> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> Scheduler<B> {
+    /// 지정된 백엔드로 요청하고 `quota`로 요청 제한을 조회하는 스케줄러를
+    /// 생성합니다.
+    pub fn with_backend(backend: B, quota: fn(&str) -> Option<Quota>) -> Self {
+        Self {
+            backend,
+            quota,
+            state: Mutex::new(State {
+                lanes: HashMap::new(),
+                next_ticket: 0,
+            }),
+            cvar: Condvar::new(),
+        }
+    }
+
+    /// 백엔드를 반환합니다.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// TR의 요청 제한을 지정합니다.
+    ///
+    /// 조회한 요청 제한 대신 사용하며, `None`인 경우 제한하지 않습니다.
+    pub fn set_quota(&self, tr_code: &str, quota: Option<Quota>) {
+        let mut state = self.state.lock().unwrap();
+
+        match state.lanes.get_mut(tr_code) {
+            Some(lane) => lane.quota = quota,
+            None => {
+                state.lanes.insert(tr_code.to_owned(), Lane::new(quota));
+            }
+        }
+
+        self.cvar.notify_all();
+    }
+
+    /// 차례를 기다린 후 조회 TR 요청을 합니다.
+    ///
+    /// `timeout`은 대기열에서 기다리는 시간을 포함합니다. 제한 시간 내에
+    /// 차례가 오지 않는 경우 요청하지 않고 `Error::TimedOut`을 반환합니다.
+    /// 요청 제한으로 실패한 요청은 서버로 전송되지 않으므로 제한 시간 내에서
+    /// 다시 요청합니다.
+    pub fn request(
+        &self,
+        data: &Data,
+        tr_layout: &Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            self.acquire(&data.tr_code, deadline)?;
+
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let result = self.backend.request(data, tr_layout, next_key, timeout);
+
+            let backoff = match &result {
+                Err(err) if Instant::now() < deadline => {
+                    err.kind().and_then(ErrorKind::suggested_backoff)
+                }
+                _ => None,
+            };
+
+            match backoff {
+                Some(backoff) => self.delay(&data.tr_code, backoff),
+                None => return result,
+            }
+        }
+    }
+
+    // 대기열에서 차례가 오고 요청 제한이 허용할 때까지 기다립니다.
+    fn acquire(&self, tr_code: &str, deadline: Instant) -> Result<(), Error> {
+        // 요청 제한 조회는 DLL을 거치므로 잠그기 전에 합니다.
+        let quota = if self.state.lock().unwrap().lanes.contains_key(tr_code) {
+            None
+        } else {
+            Some((self.quota)(tr_code))
+        };
+
+        let mut state = self.state.lock().unwrap();
+
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+
+        state
+            .lanes
+            .entry(tr_code.to_owned())
+            .or_insert_with(|| Lane::new(quota.flatten()))
+            .queue
+            .push_back(ticket);
+
+        loop {
+            let now = Instant::now();
+            let lane = state.lanes.get_mut(tr_code).unwrap();
+
+            let is_front = lane.queue.front() == Some(&ticket);
+            let ready_at = if is_front { lane.ready_at(now) } else { None };
+
+            if ready_at.is_some_and(|ready_at| ready_at <= now) {
+                lane.queue.pop_front();
+                lane.grant(now);
+                self.cvar.notify_all();
+
+                return Ok(());
+            }
+
+            // 기한 내에 보낼 수 없는 경우 다음 요청에게 차례를 넘깁니다.
+            let unreachable = is_front && ready_at.is_none_or(|ready_at| ready_at > deadline);
+            if now >= deadline || unreachable {
+                lane.queue.retain(|t| *t != ticket);
+                self.cvar.notify_all();
+
+                return Err(Error::TimedOut);
+            }
+
+            let until = ready_at.unwrap_or(deadline);
+            state = self.cvar.wait_timeout(state, until - now).unwrap().0;
+        }
+    }
+
+    // 요청 제한으로 실패한 경우 대기열의 모든 요청을 늦춥니다.
+    fn delay(&self, tr_code: &str, backoff: Duration) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(lane) = state.lanes.get_mut(tr_code) {
+            lane.next_at = lane.next_at.max(Instant::now() + backoff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Quota, Scheduler};
+    use crate::backend::MockBackend;
+    use crate::data::{self, Block, Data, DataType};
+    use crate::hashmap;
+    use crate::os::testdata::t1102_layout;
+    use crate::Error;
+
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_scheduler() {
+        let tr_layout = Arc::new(t1102_layout());
+        let backend = MockBackend::new(hashmap! { "t1102" => tr_layout.clone() });
+
+        let out_data = Data {
+            tr_code: "t1102".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1102OutBlock" => Block::Block(hashmap! { "price" => "91000" }),
+            },
+        };
+        for _ in 0..5 {
+            backend.push_response(&out_data, None).unwrap();
+        }
+
+        let scheduler = Arc::new(Scheduler::with_backend(backend, |_| {
+            Some(Quota {
+                per_sec: 20,
+                base_sec: 1,
+                per_ten_min: Some(4),
+            })
+        }));
+
+        let mut in_data = data::empty_input(&tr_layout);
+        data::set_field(&mut in_data, "t1102InBlock", "shcode", "005930").unwrap();
+
+        // 초당 20회로 제한되므로 4회 요청하는 동안 최소 3번의 간격이 있습니다.
+        let started = Instant::now();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let scheduler = scheduler.clone();
+                let (in_data, tr_layout) = (in_data.clone(), tr_layout.clone());
+
+                std::thread::spawn(move || {
+                    scheduler
+                        .request(&in_data, &tr_layout, None, Duration::from_secs(5))
+                        .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().data().unwrap(), &out_data);
+        }
+        assert!(started.elapsed() >= Duration::from_millis(150));
+
+        // 10분 내 제한을 넘은 요청은 기다리지 않고 실패합니다.
+        let started = Instant::now();
+        assert!(matches!(
+            scheduler.request(&in_data, &tr_layout, None, Duration::from_secs(5)),
+            Err(Error::TimedOut)
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(scheduler.backend().requests().len(), 4);
+
+        scheduler.set_quota("t1102", None);
+        assert!(scheduler
+            .request(&in_data, &tr_layout, None, Duration::from_secs(5))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_zero_quota() {
+        let tr_layout = Arc::new(t1102_layout());
+        let backend = MockBackend::new(hashmap! { "t1102" => tr_layout.clone() });
+        backend.push_message("t1102", "00000", "조회완료");
+
+        let scheduler = Scheduler::with_backend(backend, |_| None);
+        scheduler.set_quota(
+            "t1102",
+            Some(Quota {
+                per_sec: 0,
+                base_sec: 1,
+                per_ten_min: Some(0),
+            }),
+        );
+
+        let mut in_data = data::empty_input(&tr_layout);
+        data::set_field(&mut in_data, "t1102InBlock", "shcode", "005930").unwrap();
+
+        let started = Instant::now();
+        assert!(matches!(
+            scheduler.request(&in_data, &tr_layout, None, Duration::from_secs(5)),
+            Err(Error::TimedOut)
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(scheduler.backend().requests().is_empty());
+
+        scheduler.set_quota("t1102", None);
+        assert!(scheduler
+            .request(&in_data, &tr_layout, None, Duration::from_secs(5))
+            .is_ok());
+    }
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 종목 검색 모듈
+//!
+//! HTS에서 서버에 저장한 검색 조건으로 종목을 검색하는 t1857 TR을 감싸며,
+//! 실시간 검색을 시작한 경우 조건에 새로 편입되거나 이탈한 종목을
+//! [`SearchEvent`]로 수신합니다.
+
+use super::service::{ServiceEvent, ServiceKind};
+use super::{ensure_ok, remove_service, request_service, set_fields, Error, RealResponse};
+use crate::data::{self, Data, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 검색 결과의 변동 구분
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SearchJob {
+    /// 신규 편입
+    New,
+    /// 재편입
+    Reentered,
+    /// 이탈
+    Exited,
+}
+
+impl SearchJob {
+    fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "N" => Some(Self::New),
+            "R" => Some(Self::Reentered),
+            "O" => Some(Self::Exited),
+            _ => None,
+        }
+    }
+}
+
+/// 검색된 종목
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchHit {
+    /// 종목번호
+    pub symbol: String,
+    /// 종목명
+    pub name: String,
+    /// 현재가
+    pub price: f64,
+    /// 거래량
+    pub volume: u64,
+    /// 변동 구분
+    ///
+    /// 처음 조회한 결과에는 존재하지 않습니다.
+    pub job: Option<SearchJob>,
+}
+
+impl SearchHit {
+    const BLOCK_NAME: &'static str = "t1857OutBlock1";
+
+    /// 수신한 실시간 응답을 검색된 종목 목록으로 변환합니다.
+    pub fn from_response(res: &RealResponse) -> Result<Vec<Self>, DecodeError> {
+        Self::from_data(res.data()?)
+    }
+
+    /// 디코딩된 데이터를 검색된 종목 목록으로 변환합니다.
+    pub fn from_data(data: &Data) -> Result<Vec<Self>, DecodeError> {
+        // 실시간 데이터는 한 종목씩 단일 블록으로 수신될 수도 있습니다.
+        match data::get_array(data, Self::BLOCK_NAME) {
+            Ok(array) => array.iter().map(Self::from_fields).collect(),
+            Err(_) => Ok(vec![Self::from_fields(data::get_block(
+                data,
+                Self::BLOCK_NAME,
+            )?)?]),
+        }
+    }
+
+    fn from_fields(fields: &HashMap<Arc<str>, String>) -> Result<Self, DecodeError> {
+        let block_name = Self::BLOCK_NAME;
+
+        Ok(Self {
+            symbol: data::parse_field(fields, block_name, "shcode")?,
+            name: data::parse_field(fields, block_name, "hname")?,
+            price: data::parse_field(fields, block_name, "price")?,
+            volume: data::parse_field(fields, block_name, "volume")?,
+            job: fields
+                .get("JobFlag")
+                .and_then(|flag| SearchJob::from_flag(flag.trim())),
+        })
+    }
+}
+
+/// 검색 결과
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchResult {
+    /// 실시간 검색을 중단하기 위한 키
+    ///
+    /// 실시간 검색을 시작하지 않은 경우 `None`입니다.
+    pub alert_num: Option<String>,
+    /// 검색된 종목 목록
+    pub hits: Vec<SearchHit>,
+}
+
+impl SearchResult {
+    fn from_data(data: &Data) -> Result<Self, DecodeError> {
+        let block_name = "t1857OutBlock";
+        let fields = data::get_block(data, block_name)?;
+        let alert_num: String = data::parse_field(fields, block_name, "AlertNum")?;
+
+        Ok(Self {
+            alert_num: Some(alert_num).filter(|num| !num.is_empty()),
+            hits: SearchHit::from_data(data)?,
+        })
+    }
+}
+
+/// 실시간 종목 검색 결과를 수신하는 객체
+///
+/// 객체는 하나만 사용할 수 있으며, 새로운 객체를 생성하면 이전 객체는 더
+/// 이상 데이터를 수신하지 않습니다. 수신한 응답의 키는 빈 문자열입니다.
+pub struct SearchEvent {
+    event: ServiceEvent,
+}
+
+impl SearchEvent {
+    /// 종목 검색 TR 코드
+    pub const TR_CODE: &'static str = "t1857";
+
+    /// 응답을 디코딩하기 위한 t1857 레이아웃으로 객체를 생성합니다.
+    pub fn new(tr_layout: TrLayout) -> Self {
+        Self {
+            event: ServiceEvent::new(ServiceKind::Search, tr_layout),
+        }
+    }
+
+    /// 서버에 저장된 검색 조건으로 종목을 검색합니다.
+    ///
+    /// `real`이 `true`인 경우 이후 검색 결과가 변동될 때마다 실시간으로
+    /// 수신합니다.
+    pub fn search(
+        &self,
+        query_index: &str,
+        real: bool,
+        timeout: Duration,
+    ) -> Result<SearchResult, Error> {
+        let tr_layout = self.event.tr_layout();
+        let mut req_data = data::empty_input(tr_layout);
+        set_fields(
+            &mut req_data,
+            "t1857InBlock",
+            &[
+                ("sRealFlag", if real { "1" } else { "0" }.to_owned()),
+                ("sSearchFlag", "S".to_owned()),
+                ("query_index", query_index.to_owned()),
+            ],
+        )?;
+
+        let res = ensure_ok(request_service(&req_data, tr_layout.clone(), timeout)?)?;
+
+        Ok(SearchResult::from_data(res.data()?)?)
+    }
+
+    /// 실시간 검색을 중단합니다.
+    pub fn stop(&self, alert_num: &str) -> Result<(), Error> {
+        remove_service(Self::TR_CODE, alert_num)
+    }
+
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        self.event.try_recv()
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        self.event.recv_timeout(timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchHit, SearchJob, SearchResult};
+    use crate::data::{Block, Data, DataType};
+    use crate::hashmap;
+
+    #[test]
+    fn test_search_result() {
+        let mut data = Data {
+            tr_code: "t1857".into(),
+            data_type: DataType::Output,
+            blocks: hashmap! {
+                "t1857OutBlock" => Block::Block(hashmap! {
+                    "result_count" => "1",
+                    "AlertNum" => "12345",
+                }),
+                "t1857OutBlock1" => Block::Array(vec![hashmap! {
+                    "shcode" => "005930",
+                    "hname" => "삼성전자",
+                    "price" => "91000",
+                    "volume" => "1000",
+                    "JobFlag" => "",
+                }]),
+            },
+        };
+
+        let result = SearchResult::from_data(&data).unwrap();
+        assert_eq!(result.alert_num.as_deref(), Some("12345"));
+        assert_eq!(result.hits[0].symbol, "005930");
+        assert_eq!(result.hits[0].job, None);
+
+        data.blocks.insert(
+            "t1857OutBlock1".into(),
+            Block::Block(hashmap! {
+                "shcode" => "000660",
+                "hname" => "SK하이닉스",
+                "price" => "130000",
+                "volume" => "500",
+                "JobFlag" => "O",
+            }),
+        );
+
+        let hits = SearchHit::from_data(&data).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].job, Some(SearchJob::Exited));
+    }
+}
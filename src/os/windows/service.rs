@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 부가 서비스 TR 모듈
+//!
+//! 부가 서비스 TR은 [`request_service()`](super::request_service)로 요청하며,
+//! 실시간으로 등록한 경우 이후 수신되는 데이터는 서비스 종류별로
+//! [`ServiceEvent`]로 수신합니다. 등록을 해제할 때는
+//! [`remove_service()`](super::remove_service)를 사용합니다.
+
+use super::raw::{XM_RECEIVE_REAL_DATA_CHART, XM_RECEIVE_REAL_DATA_SEARCH};
+use super::session::{self, ServicePacket};
+use super::RealResponse;
+use crate::layout::TrLayout;
+
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use winapi::shared::minwindef::UINT;
+
+/// 부가 서비스 종류
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ServiceKind {
+    /// 차트 지표 (ChartIndex 등)
+    Chart,
+    /// 종목 검색 (t1857 등)
+    Search,
+}
+
+impl ServiceKind {
+    fn msg(self) -> UINT {
+        match self {
+            Self::Chart => XM_RECEIVE_REAL_DATA_CHART,
+            Self::Search => XM_RECEIVE_REAL_DATA_SEARCH,
+        }
+    }
+}
+
+/// 부가 서비스 TR의 실시간 데이터를 수신하는 객체
+///
+/// 종류별로 객체는 하나만 사용할 수 있으며, 같은 종류의 객체를 새로
+/// 생성하면 이전 객체는 더 이상 데이터를 수신하지 않습니다. 수신한 응답의
+/// 키는 빈 문자열입니다.
+pub struct ServiceEvent {
+    kind: ServiceKind,
+    tr_layout: Arc<TrLayout>,
+    tx_res: Sender<ServicePacket>,
+    rx_res: Receiver<ServicePacket>,
+}
+
+impl ServiceEvent {
+    /// 응답을 디코딩하기 위한 레이아웃으로 객체를 생성합니다.
+    pub fn new<T: Into<Arc<TrLayout>>>(kind: ServiceKind, tr_layout: T) -> Self {
+        let (tx_res, rx_res) = crossbeam_channel::unbounded();
+        session::global().set_service_sender(kind.msg(), tx_res.clone());
+
+        Self {
+            kind,
+            tr_layout: tr_layout.into(),
+            tx_res,
+            rx_res,
+        }
+    }
+
+    /// 서비스 종류를 반환합니다.
+    pub fn kind(&self) -> ServiceKind {
+        self.kind
+    }
+
+    /// 응답을 디코딩하는 레이아웃을 반환합니다.
+    pub fn tr_layout(&self) -> &Arc<TrLayout> {
+        &self.tr_layout
+    }
+
+    /// 수신한 응답이 큐에 있는 경우 가져옵니다.
+    pub fn try_recv(&self) -> Option<RealResponse> {
+        let packet = self.rx_res.try_recv().ok()?;
+        Some(packet.decode(&self.tr_layout))
+    }
+
+    /// 수신한 응답을 큐에서 가져올 때까지 지정된 시간 동안 기다립니다.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<RealResponse> {
+        let packet = self.rx_res.recv_timeout(timeout).ok()?;
+        Some(packet.decode(&self.tr_layout))
+    }
+}
+
+impl Drop for ServiceEvent {
+    fn drop(&mut self) {
+        if session::is_loaded() {
+            session::global().remove_service_sender(self.kind.msg(), &self.tx_res);
+        }
+    }
+}
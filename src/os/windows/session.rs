@@ -3,19 +3,23 @@
 use crate::data::{self, Data, RawData};
 use crate::layout::TrLayout;
 
-use super::executor::{self, Executor, Window};
-use super::raw::{MSG_PACKET, RECV_PACKET};
+use super::capture::{QueryRecord, QueryWriter};
+use super::executor::{self, Executor, ExecutorHandle, Window};
+use super::hts_link::LinkEvent;
+use super::raw::{LINKDATA_RECV_MSG, MSG_PACKET, RECV_PACKET, XM_RECEIVE_LINK_DATA};
 use super::raw::{XM_DISCONNECT, XM_LOGIN, XM_LOGOUT, XM_RECEIVE_DATA, XM_TIMEOUT};
-use super::{decode_euckr, Error, LoginResponse, QueryResponse};
+use super::raw::{XM_RECEIVE_REAL_DATA_CHART, XM_RECEIVE_REAL_DATA_SEARCH};
+use super::{decode_text, Error, LazyData, LoginResponse, QueryResponse, RealResponse};
 
 use array_init::array_init;
 use lazy_static::lazy_static;
 
 use std::ffi::{CStr, CString};
-use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
-use std::sync::{Mutex, RwLock, RwLockReadGuard};
-use std::{cmp::Ord, collections::HashMap, time::Duration};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::time::{Duration, SystemTime};
+use std::{cmp::Ord, collections::HashMap};
 
 use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WPARAM};
 use winapi::shared::windef::HWND;
@@ -103,33 +107,72 @@ impl IncompleteQueryResponse {
     }
 }
 
+// 압축 요청 시 한 번에 수신할 수 있는 최대 레코드 개수
+const MAX_COMPRESSED_RECORDS: usize = 2000;
+
 struct QueryState {
-    tr_layout: TrLayout,
+    tr_layout: Arc<TrLayout>,
+    compressed: bool,
     tx_res: SyncSender<IncompleteQueryResponse>,
     res: Option<IncompleteQueryResponse>,
 }
 
+// 부가 서비스 TR로 수신한 실시간 데이터
+pub(crate) struct ServicePacket {
+    pub tr_code: String,
+    pub block_name: String,
+    pub data: Vec<u8>,
+    pub received_at: SystemTime,
+}
+
+impl ServicePacket {
+    pub fn decode(self, tr_layout: &TrLayout) -> RealResponse {
+        let raw_data = if tr_layout.block_mode {
+            RawData::Block([(self.block_name, self.data)].into_iter().collect())
+        } else {
+            RawData::NonBlock(self.data)
+        };
+
+        RealResponse {
+            data: data::decode(tr_layout, raw_data),
+            tr_code: self.tr_code,
+            key: String::new(),
+            received_at: self.received_at,
+        }
+    }
+}
+
+// 창 하나에서 동시에 응답을 기다릴 수 있는 요청 개수
+const REQUEST_SLOTS: usize = 256;
+
+// 조회 TR을 요청하는 창의 최대 개수
+const MAX_QUERY_WINDOWS: usize = 16;
+
 struct SessionWindowData {
     tx_login_res: Mutex<Option<SyncSender<LoginResponse>>>,
-    state_tbl: [Mutex<Option<QueryState>>; 256],
+    state_tbl: [Mutex<Option<QueryState>>; REQUEST_SLOTS],
+    // 메시지별로 실시간 데이터를 보낼 채널
+    service_tbl: Mutex<HashMap<UINT, crossbeam_channel::Sender<ServicePacket>>>,
+    // HTS 연동 데이터를 보낼 채널
+    tx_link: Mutex<Option<crossbeam_channel::Sender<LinkEvent>>>,
 }
 
-pub(crate) struct Session {
+struct SessionWindow {
     window: Window,
     window_data: AtomicPtr<SessionWindowData>,
+    // 응답을 기다리는 요청 개수
+    pending: AtomicUsize,
 }
 
-impl Session {
-    fn window_data(&self) -> &SessionWindowData {
-        unsafe { &mut *self.window_data.load(Ordering::Relaxed) }
-    }
-
-    pub fn new() -> Result<Self, std::io::Error> {
+impl SessionWindow {
+    fn new() -> Result<Self, std::io::Error> {
         let window = Window::new(SESSION_WNDCLASS.clone())?;
 
         let mut window_data = AtomicPtr::new(Box::into_raw(Box::new(SessionWindowData {
             tx_login_res: Mutex::new(None),
             state_tbl: array_init(|_| Mutex::new(None)),
+            service_tbl: Mutex::new(HashMap::new()),
+            tx_link: Mutex::new(None),
         })));
 
         unsafe {
@@ -139,14 +182,106 @@ impl Session {
         Ok(Self {
             window,
             window_data,
+            pending: AtomicUsize::new(0),
         })
     }
 
+    fn hwnd(&self) -> usize {
+        *self.window
+    }
+
+    fn data(&self) -> &SessionWindowData {
+        unsafe { &*self.window_data.load(Ordering::Relaxed) }
+    }
+}
+
+// 요청 슬롯 하나를 차지한 창
+//
+// 응답을 받거나 시간이 초과되어 요청이 끝나면 슬롯을 반환합니다.
+struct WindowLease {
+    window: Arc<SessionWindow>,
+}
+
+impl WindowLease {
+    fn try_new(window: &Arc<SessionWindow>) -> Option<Self> {
+        window
+            .pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                (pending < REQUEST_SLOTS).then_some(pending + 1)
+            })
+            .ok()?;
+
+        Some(Self {
+            window: window.clone(),
+        })
+    }
+}
+
+impl std::ops::Deref for WindowLease {
+    type Target = SessionWindow;
+    fn deref(&self) -> &Self::Target {
+        &self.window
+    }
+}
+
+impl Drop for WindowLease {
+    fn drop(&mut self) {
+        self.window.pending.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+pub(crate) struct Session {
+    // 로그인, 부가 서비스, HTS 연동에 사용하는 창
+    window: Arc<SessionWindow>,
+    // 조회 TR을 요청하는 창 목록
+    //
+    // 창마다 요청 ID 테이블의 크기가 정해져 있으므로 모든 창의 슬롯이 가득
+    // 찬 경우 최대 개수까지 창을 추가합니다. 응답은 요청한 창으로
+    // 수신됩니다.
+    query_windows: Mutex<Vec<Arc<SessionWindow>>>,
+    capture: Mutex<Option<QueryWriter>>,
+}
+
+impl Session {
+    fn window_data(&self) -> &SessionWindowData {
+        self.window.data()
+    }
+
+    pub fn new() -> Result<Self, std::io::Error> {
+        Ok(Self {
+            window: Arc::new(SessionWindow::new()?),
+            query_windows: Mutex::new(Vec::new()),
+            capture: Mutex::new(None),
+        })
+    }
+
+    // 빈 슬롯이 있는 창을 찾고, 없는 경우 새 창을 만듭니다.
+    //
+    // 모든 창의 슬롯이 가득 차고 더 이상 창을 만들 수 없는 경우
+    // `Error::Busy`를 반환합니다.
+    fn acquire_window(&self) -> Result<WindowLease, Error> {
+        let mut query_windows = self.query_windows.lock().unwrap();
+
+        if let Some(lease) = query_windows.iter().find_map(WindowLease::try_new) {
+            return Ok(lease);
+        }
+
+        if query_windows.len() >= MAX_QUERY_WINDOWS {
+            return Err(Error::Busy);
+        }
+
+        let window = Arc::new(SessionWindow::new().map_err(|err| Error::Load(err.into()))?);
+        let lease = WindowLease::try_new(&window).unwrap();
+        query_windows.push(window);
+
+        Ok(lease)
+    }
+
     pub fn connect(&self, addr: &str, port: u16, timeout: Duration) -> Result<(), Error> {
         let executor = executor::global();
         let mut handle = executor.lock_handle();
 
-        handle.connect(*self.window, addr, port, timeout)
+        handle.connect(self.window.hwnd(), addr, port, timeout)
     }
 
     pub fn disconnect(&self) {
@@ -168,7 +303,7 @@ impl Session {
 
         *window_data.tx_login_res.lock().unwrap() = Some(tx_res);
 
-        if let Err(err) = handle.login(*self.window, id, pw, cert_pw, cert_err_dialog) {
+        if let Err(err) = handle.login(self.window.hwnd(), id, pw, cert_pw, cert_err_dialog) {
             *window_data.tx_login_res.lock().unwrap() = None;
             return Err(err);
         }
@@ -186,44 +321,128 @@ impl Session {
     pub fn request(
         &self,
         data: &Data,
-        tr_layout: &TrLayout,
+        tr_layout: Arc<TrLayout>,
         next_key: Option<&str>,
         timeout: Duration,
     ) -> Result<QueryResponse, Error> {
+        let window = self.acquire_window()?;
+
+        self.query(
+            &window,
+            data,
+            tr_layout,
+            next_key,
+            timeout,
+            |handle, hwnd, enc_data| {
+                handle.request(hwnd, &data.tr_code, enc_data, next_key, timeout)
+            },
+        )
+    }
+
+    // 부가 서비스 TR을 요청합니다.
+    pub fn request_service(
+        &self,
+        data: &Data,
+        tr_layout: Arc<TrLayout>,
+        timeout: Duration,
+    ) -> Result<QueryResponse, Error> {
+        // 부가 서비스의 실시간 데이터는 요청한 창으로 수신됩니다.
+        let window = WindowLease::try_new(&self.window).ok_or(Error::Busy)?;
+
+        self.query(
+            &window,
+            data,
+            tr_layout,
+            None,
+            timeout,
+            |handle, hwnd, enc_data| handle.request_service(hwnd, &data.tr_code, enc_data),
+        )
+    }
+
+    // 인코딩된 데이터로 요청하고 요청 ID에 해당하는 응답을 기다립니다.
+    fn query<F>(
+        &self,
+        window: &SessionWindow,
+        data: &Data,
+        tr_layout: Arc<TrLayout>,
+        next_key: Option<&str>,
+        timeout: Duration,
+        send: F,
+    ) -> Result<QueryResponse, Error>
+    where
+        F: FnOnce(&ExecutorHandle, usize, Vec<u8>) -> Result<i32, Error>,
+    {
         let executor = executor::global();
         let handle = executor.handle();
 
-        let tr_code = &data.tr_code;
-        let enc_data = data::encode(data, tr_layout)?;
+        let enc_data = data::encode(data, &tr_layout)?;
 
-        let req_id: usize = handle
-            .request(*self.window, tr_code, enc_data, next_key, timeout)?
-            .try_into()
-            .unwrap();
+        // 차트 TR은 `comp_yn` 필드로 응답 데이터의 압축 여부를 지정합니다.
+        let compressed = data
+            .blocks
+            .values()
+            .filter_map(|b| b.as_block())
+            .any(|b| b.get("comp_yn").map(|v| v.as_str()) == Some("Y"));
 
         let (tx_res, rx_res) = mpsc::sync_channel(1);
 
-        {
-            let mut state = self.window_data().state_tbl[req_id].lock().unwrap();
-            assert!(state.is_none());
+        // 요청을 보낸 후에는 `Error::Busy`를 반환하지 않습니다. 요청 슬롯은
+        // 임대한 창에서 미리 확보되어 있습니다.
+        let req_id = send(&handle, window.hwnd(), enc_data)?;
+
+        let req_id = {
+            // DLL이 범위를 벗어나거나 사용 중인 요청 ID를 반환한 경우 응답을
+            // 구분할 수 없습니다. 사용 중이던 요청은 더 이상 응답을 받을 수
+            // 없으므로 슬롯을 비우며, 비어 있는 슬롯의 요청 ID는 응답을 수신할
+            // 때 해제됩니다.
+            let slot = usize::try_from(req_id)
+                .ok()
+                .and_then(|req_id| Some((req_id, window.data().state_tbl.get(req_id)?)));
+            let Some((req_id, slot)) = slot else {
+                return Err(Error::Internal("request id out of range"));
+            };
+
+            let mut state = slot.lock().unwrap();
+            if state.take().is_some() {
+                return Err(Error::Internal("request id already in use"));
+            }
 
             *state = Some(QueryState {
                 tr_layout: tr_layout.clone(),
+                compressed,
                 tx_res,
                 res: None,
             });
+
+            req_id
+        };
+
+        let result = rx_res.recv_timeout(timeout + Duration::from_millis(100));
+
+        if let (Ok(res), Some(writer)) = (&result, &mut *self.capture.lock().unwrap()) {
+            // 기록에 실패하더라도 응답은 정상적으로 반환합니다.
+            let _ = writer.write(&QueryRecord {
+                time: SystemTime::now(),
+                request: data.clone(),
+                next_key: next_key.map(|key| key.to_owned()),
+                code: res.code.clone(),
+                message: res.message.clone(),
+                elapsed: res.elapsed_time,
+                res_next_key: res.next_key.clone(),
+                raw_data: res.data.clone(),
+            });
         }
 
-        match rx_res.recv_timeout(timeout + Duration::from_millis(100)) {
+        match result {
             Ok(res) => Ok(QueryResponse {
                 code: res.code,
                 message: res.message,
                 elapsed: res.elapsed_time,
                 next_key: res.next_key,
-                data: res.data.map(|d| data::decode(tr_layout, d)),
+                data: res.data.map(|d| LazyData::new(d, tr_layout)),
             }),
             Err(RecvTimeoutError::Timeout) => {
-                *self.window_data().state_tbl[req_id].lock().unwrap() = None;
+                *window.data().state_tbl[req_id].lock().unwrap() = None;
 
                 Err(Error::TimedOut)
             }
@@ -231,6 +450,70 @@ impl Session {
         }
     }
 
+    // 조회 TR을 기록할 객체를 지정하고 이전 객체를 반환합니다.
+    pub fn set_capture(&self, writer: Option<QueryWriter>) -> Option<QueryWriter> {
+        std::mem::replace(&mut *self.capture.lock().unwrap(), writer)
+    }
+
+    // 지정된 메시지로 수신한 실시간 데이터를 보낼 채널을 지정합니다.
+    //
+    // 이전에 지정된 채널은 더 이상 데이터를 수신하지 않습니다.
+    pub fn set_service_sender(&self, msg: UINT, tx: crossbeam_channel::Sender<ServicePacket>) {
+        self.window_data()
+            .service_tbl
+            .lock()
+            .unwrap()
+            .insert(msg, tx);
+    }
+
+    // 지정된 채널이 현재 채널인 경우에만 삭제합니다.
+    pub fn remove_service_sender(&self, msg: UINT, tx: &crossbeam_channel::Sender<ServicePacket>) {
+        let mut service_tbl = self.window_data().service_tbl.lock().unwrap();
+        if service_tbl
+            .get(&msg)
+            .is_some_and(|cur| cur.same_channel(tx))
+        {
+            service_tbl.remove(&msg);
+        }
+    }
+
+    pub fn remove_service(&self, tr_code: &str, data: &str) -> Result<(), Error> {
+        executor::global()
+            .handle()
+            .remove_service(self.window.hwnd(), tr_code, data)
+    }
+
+    // HTS 연동 데이터를 보낼 채널을 지정하고 수신을 시작합니다.
+    //
+    // 이전에 지정된 채널은 더 이상 데이터를 수신하지 않습니다.
+    pub fn set_link_sender(&self, tx: crossbeam_channel::Sender<LinkEvent>) {
+        *self.window_data().tx_link.lock().unwrap() = Some(tx);
+        executor::global()
+            .handle()
+            .advise_link_from_hts(self.window.hwnd());
+    }
+
+    // 지정된 채널이 현재 채널인 경우에만 삭제하고 수신을 중단합니다.
+    pub fn remove_link_sender(&self, tx: &crossbeam_channel::Sender<LinkEvent>) {
+        let removed = {
+            let mut tx_link = self.window_data().tx_link.lock().unwrap();
+            tx_link.take_if(|cur| cur.same_channel(tx)).is_some()
+        };
+
+        // 실행자 스레드가 잠금을 기다리지 않도록 잠금을 해제한 후 요청합니다.
+        if removed {
+            executor::global()
+                .handle()
+                .unadvise_link_from_hts(self.window.hwnd());
+        }
+    }
+
+    pub fn request_link_to_hts(&self, link_name: &str, data: &str) -> bool {
+        executor::global()
+            .handle()
+            .request_link_to_hts(self.window.hwnd(), link_name, data)
+    }
+
     unsafe extern "system" fn window_proc(
         hwnd: HWND,
         msg: UINT,
@@ -261,30 +544,38 @@ impl Session {
             XM_LOGIN => {
                 if let Some(tx) = load_window_data().tx_login_res.lock().unwrap().take() {
                     let _ = tx.try_send(LoginResponse {
-                        code: decode_euckr(CStr::from_ptr(wparam as _).to_bytes()),
-                        message: decode_euckr(CStr::from_ptr(lparam as _).to_bytes()),
+                        code: decode_text(CStr::from_ptr(wparam as _).to_bytes()),
+                        message: decode_text(CStr::from_ptr(lparam as _).to_bytes()),
                     });
                 }
 
                 0
             }
             XM_RECEIVE_DATA => {
-                let req_id: usize = match wparam {
+                let req_id: i32 = match wparam {
                     1 => { &*(lparam as *const RECV_PACKET) }.req_id,
                     2 | 3 => { &*(lparam as *const MSG_PACKET) }.req_id,
-                    4 => lparam.try_into().unwrap(),
+                    4 => lparam as _,
                     _ => unreachable!(),
-                }
-                .try_into()
-                .unwrap();
+                };
+
+                // 알 수 없는 요청 ID의 응답은 무시합니다.
+                let state_slot = usize::try_from(req_id)
+                    .ok()
+                    .and_then(|req_id| load_window_data().state_tbl.get(req_id));
 
                 // RECV_PACKET보다 MSG_PACKET이 먼저 수신될 수도 있습니다.
                 match wparam {
                     1 => {
                         let recv_packet = &*(lparam as *const RECV_PACKET);
 
-                        let mut state_guard = load_window_data().state_tbl[req_id].lock().unwrap();
-                        let state = state_guard.as_mut().unwrap();
+                        let mut state_guard = match state_slot {
+                            Some(state) => state.lock().unwrap(),
+                            None => return 0,
+                        };
+                        let Some(state) = state_guard.as_mut() else {
+                            return 0;
+                        };
                         let res = state.res.get_or_insert(IncompleteQueryResponse::empty());
 
                         res.elapsed_time = Ord::max(
@@ -292,7 +583,7 @@ impl Session {
                             Duration::from_millis(recv_packet.elapsed_time.try_into().unwrap()),
                         );
 
-                        match decode_euckr(&recv_packet.next_key) {
+                        match decode_text(&recv_packet.next_key) {
                             key if key.is_empty() => {}
                             key => res.next_key = Some(key),
                         }
@@ -307,11 +598,28 @@ impl Session {
 
                         // 블록 모드 여부는 레이아웃에서 확인해야 정확합니다.
                         if state.tr_layout.block_mode {
+                            let block_name = decode_text(&recv_packet.block_name);
+
+                            // 압축 요청 시 배열 블록만 압축되어 수신됩니다.
+                            let raw_data =
+                                match state.tr_layout.out_block(&block_name).filter(|b| b.occurs) {
+                                    Some(block_layout) if state.compressed => {
+                                        let mut buffer =
+                                            vec![0; block_layout.len * MAX_COMPRESSED_RECORDS];
+                                        let len = executor::global()
+                                            .entry()
+                                            .decompress(&raw_data, &mut buffer);
+                                        buffer.truncate(len);
+                                        buffer
+                                    }
+                                    _ => raw_data,
+                                };
+
                             if let RawData::Block(block_tbl) = res
                                 .data
                                 .get_or_insert_with(|| RawData::Block(HashMap::new()))
                             {
-                                block_tbl.insert(decode_euckr(&recv_packet.block_name), raw_data);
+                                block_tbl.insert(block_name, raw_data);
                             } else {
                                 unreachable!();
                             }
@@ -322,15 +630,17 @@ impl Session {
                     2 => {
                         let msg_packet = &*(lparam as *const MSG_PACKET);
 
-                        let mut state_guard = load_window_data().state_tbl[req_id].lock().unwrap();
-                        let state = state_guard.as_mut().unwrap();
-                        let res = state.res.get_or_insert(IncompleteQueryResponse::empty());
+                        if let Some(state) = state_slot {
+                            if let Some(state) = state.lock().unwrap().as_mut() {
+                                let res = state.res.get_or_insert(IncompleteQueryResponse::empty());
 
-                        res.code = decode_euckr(&msg_packet.msg_code);
-                        res.message = decode_euckr(std::slice::from_raw_parts(
-                            msg_packet.msg_data,
-                            msg_packet.msg_data_len.try_into().unwrap(),
-                        ));
+                                res.code = decode_text(&msg_packet.msg_code);
+                                res.message = decode_text(std::slice::from_raw_parts(
+                                    msg_packet.msg_data,
+                                    msg_packet.msg_data_len.try_into().unwrap(),
+                                ));
+                            }
+                        }
 
                         executor::global().entry().release_message_data(lparam);
                     }
@@ -338,22 +648,62 @@ impl Session {
                         executor::global().entry().release_message_data(lparam);
                     }
                     4 => {
-                        let mut state_guard = load_window_data().state_tbl[req_id].lock().unwrap();
-                        let state = state_guard.as_mut().unwrap();
-
-                        let _ = state.tx_res.send(state.res.take().unwrap());
-                        *state_guard = None;
+                        if let Some(state) =
+                            state_slot.and_then(|state| state.lock().unwrap().take())
+                        {
+                            let res = state.res.unwrap_or(IncompleteQueryResponse::empty());
+                            let _ = state.tx_res.send(res);
+                        }
 
-                        executor::global().entry().release_request_data(req_id as _);
+                        // 알 수 없는 요청 ID도 해제해야 DLL에서 다시 사용할 수 있습니다.
+                        executor::global().entry().release_request_data(req_id);
                     }
                     _ => unreachable!(),
                 }
 
                 0
             }
+            XM_RECEIVE_REAL_DATA_CHART | XM_RECEIVE_REAL_DATA_SEARCH => {
+                let recv_packet = &*(lparam as *const RECV_PACKET);
+                assert!(!recv_packet.data.is_null());
+
+                if let Some(tx) = load_window_data().service_tbl.lock().unwrap().get(&msg) {
+                    let _ = tx.send(ServicePacket {
+                        tr_code: decode_text(&recv_packet.tr_code),
+                        block_name: decode_text(&recv_packet.block_name),
+                        data: std::slice::from_raw_parts(
+                            recv_packet.data,
+                            recv_packet.data_len.try_into().unwrap(),
+                        )
+                        .to_owned(),
+                        received_at: SystemTime::now(),
+                    });
+                }
+
+                0
+            }
+            XM_RECEIVE_LINK_DATA => {
+                let link_msg = &*(lparam as *const LINKDATA_RECV_MSG);
+
+                if let Some(tx) = &*load_window_data().tx_link.lock().unwrap() {
+                    let _ = tx.send(LinkEvent {
+                        link_name: decode_text(&link_msg.link_name),
+                        data: decode_text(&link_msg.link_data),
+                        filter: decode_text(&link_msg.filter),
+                    });
+                }
+
+                executor::global().entry().release_message_data(lparam);
+
+                0
+            }
             XM_TIMEOUT => {
-                let req_id: usize = lparam.try_into().unwrap();
-                *load_window_data().state_tbl[req_id].lock().unwrap() = None;
+                let state_slot = usize::try_from(lparam)
+                    .ok()
+                    .and_then(|req_id| load_window_data().state_tbl.get(req_id));
+                if let Some(state) = state_slot {
+                    *state.lock().unwrap() = None;
+                }
 
                 0
             }
@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 조회 TR 요청 통계 모듈
+//!
+//! [`enable()`]을 호출한 후 [`request()`](super::request)와
+//! [`request_service()`](super::request_service)로 요청할 때마다 TR 코드별로
+//! 요청 횟수, 정상 응답 횟수, 실패한 응답 코드, 시간 초과 횟수, 서버 처리
+//! 시간, 연속 조회 깊이를 기록합니다. [`snapshot()`]으로 현재까지의 통계를
+//! 가져올 수 있습니다.
+//!
+//! ## 예제
+//! ```rust,no_run
+//! xingapi::stats::enable();
+//!
+//! // 요청 후
+//! for (tr_code, stats) in xingapi::stats::snapshot() {
+//!     println!(
+//!         "{}: sent={} ok={} p99={:?}",
+//!         tr_code,
+//!         stats.sent,
+//!         stats.ok,
+//!         stats.latency.percentile(99.0)
+//!     );
+//! }
+//! ```
+
+use super::metrics::Histogram;
+use super::{Error, QueryResponse, Response};
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+static STATS: Mutex<Option<HashMap<String, TrStats>>> = Mutex::new(None);
+
+thread_local! {
+    // 스레드별로 TR 코드마다 진행 중인 연속 조회의 깊이
+    static DEPTH_TBL: RefCell<HashMap<String, u32>> = RefCell::new(HashMap::new());
+}
+
+/// TR 하나의 요청 통계
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrStats {
+    /// 요청한 횟수
+    pub sent: u64,
+    /// 정상 응답을 받은 횟수
+    pub ok: u64,
+    /// 응답 코드별 실패 횟수
+    ///
+    /// 서버가 처리하지 못한 경우 응답 코드를, XingAPI 에러인 경우 음수의
+    /// 에러 코드를 키로 사용합니다.
+    pub errors: BTreeMap<String, u64>,
+    /// 시간 초과 횟수
+    pub timeouts: u64,
+    /// 그 외의 에러로 실패한 횟수
+    pub failures: u64,
+    /// 연속 조회 요청 횟수
+    pub continued: u64,
+    /// 최대 연속 조회 깊이
+    ///
+    /// 같은 스레드에서 연속 키로 이어서 요청한 횟수입니다.
+    pub max_depth: u32,
+    /// 서버 처리 시간
+    pub latency: Histogram,
+}
+
+/// 통계 기록을 시작합니다.
+///
+/// 이미 기록 중인 경우 아무런 동작을 하지 않습니다.
+pub fn enable() {
+    STATS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get_or_insert_with(HashMap::new);
+}
+
+/// 통계 기록을 중단하고 기록된 통계를 삭제합니다.
+pub fn disable() {
+    *STATS.lock().unwrap_or_else(|err| err.into_inner()) = None;
+}
+
+/// 기록된 통계를 삭제합니다.
+pub fn reset() {
+    if let Some(stats_tbl) = &mut *STATS.lock().unwrap_or_else(|err| err.into_inner()) {
+        stats_tbl.clear();
+    }
+}
+
+/// TR 코드별로 현재까지 기록된 통계를 반환합니다.
+///
+/// 기록 중이 아닌 경우 빈 테이블을 반환합니다.
+pub fn snapshot() -> HashMap<String, TrStats> {
+    STATS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+// 요청 결과를 기록합니다.
+pub(crate) fn record(tr_code: &str, continued: bool, result: &Result<QueryResponse, Error>) {
+    let mut guard = STATS.lock().unwrap_or_else(|err| err.into_inner());
+    let Some(stats_tbl) = &mut *guard else {
+        return;
+    };
+
+    let depth = DEPTH_TBL.with(|depth_tbl| {
+        let mut depth_tbl = depth_tbl.borrow_mut();
+        let depth = depth_tbl.entry(tr_code.to_owned()).or_default();
+
+        *depth = if continued { *depth + 1 } else { 0 };
+
+        // 다음 연속 키가 없으면 연속 조회가 끝납니다.
+        let depth_now = *depth;
+        if !matches!(result, Ok(res) if res.next_key.is_some()) {
+            depth_tbl.remove(tr_code);
+        }
+        depth_now
+    });
+
+    let stats = stats_tbl.entry(tr_code.to_owned()).or_default();
+
+    stats.sent += 1;
+    if continued {
+        stats.continued += 1;
+    }
+    stats.max_depth = stats.max_depth.max(depth);
+
+    match result {
+        Ok(res) => {
+            stats.latency.record(res.elapsed);
+
+            if res.is_ok() {
+                stats.ok += 1;
+            } else {
+                *stats.errors.entry(res.code.clone()).or_default() += 1;
+            }
+        }
+        Err(Error::XingApi { code, .. }) => {
+            *stats.errors.entry(code.to_string()).or_default() += 1;
+        }
+        Err(Error::TimedOut) => stats.timeouts += 1,
+        Err(_) => stats.failures += 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disable, enable, record, reset, snapshot};
+    use crate::{Error, QueryResponse};
+
+    use std::time::Duration;
+
+    fn response(code: &str, elapsed_ms: u64, next_key: Option<&str>) -> QueryResponse {
+        QueryResponse {
+            code: code.into(),
+            message: String::new(),
+            elapsed: Duration::from_millis(elapsed_ms),
+            next_key: next_key.map(|key| key.into()),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        record("t0000", false, &Ok(response("00000", 10, None)));
+        assert!(snapshot().is_empty());
+
+        enable();
+
+        record("t0000", false, &Ok(response("00000", 10, Some("1"))));
+        record("t0000", true, &Ok(response("00000", 20, Some("2"))));
+        record("t0000", true, &Ok(response("00000", 30, None)));
+        record("t0000", false, &Ok(response("IGW40011", 5, None)));
+        record(
+            "t0000",
+            false,
+            &Err(Error::XingApi {
+                code: -21,
+                message: String::new(),
+            }),
+        );
+        record("t0000", false, &Err(Error::TimedOut));
+        record("t0001", false, &Err(Error::Busy));
+
+        let stats_tbl = snapshot();
+        let stats = &stats_tbl["t0000"];
+        assert_eq!(stats.sent, 6);
+        assert_eq!(stats.ok, 3);
+        assert_eq!(stats.errors["IGW40011"], 1);
+        assert_eq!(stats.errors["-21"], 1);
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.continued, 2);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(stats.latency.count(), 4);
+        assert_eq!(stats.latency.max(), Some(Duration::from_millis(30)));
+        assert_eq!(stats_tbl["t0001"].failures, 1);
+
+        reset();
+        assert!(snapshot().is_empty());
+
+        disable();
+        record("t0000", false, &Ok(response("00000", 10, None)));
+        assert!(snapshot().is_empty());
+    }
+}
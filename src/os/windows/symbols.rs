@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 종목 마스터 모듈
+//!
+//! 주식 종목 조회 TR인 t8430 또는 t8436의 결과를 캐시하여 종목코드나
+//! 종목명으로 종목 정보를 검색할 수 있도록 합니다.
+//!
+//! 종목 마스터는 디스크에 저장할 수 있으며, 저장한 날짜가 지나면 다시
+//! 조회하도록 합니다.
+
+use super::{ensure_ok, kst, request_with_retry, set_fields, Error};
+use crate::data::{self, DecodeError};
+use crate::layout::TrLayout;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 시장 구분
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Market {
+    /// 코스피
+    Kospi,
+    /// 코스닥
+    Kosdaq,
+}
+
+impl Market {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Kospi => "1",
+            Self::Kosdaq => "2",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "1" => Some(Self::Kospi),
+            "2" => Some(Self::Kosdaq),
+            _ => None,
+        }
+    }
+}
+
+/// 종목 정보
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SymbolInfo {
+    /// 단축코드
+    pub code: String,
+    /// 종목명
+    pub name: String,
+    /// 시장 구분
+    pub market: Market,
+    /// ETF 여부
+    pub etf: bool,
+    /// 전일가
+    pub prev_close: f64,
+}
+
+impl SymbolInfo {
+    /// 주어진 가격에서의 호가 단위를 반환합니다.
+    ///
+    /// 2023년 1월 25일부터 시행된 유가증권시장과 코스닥시장의 통합 호가 단위를
+    /// 기준으로 합니다.
+    pub fn tick_size(&self, price: f64) -> f64 {
+        if self.etf {
+            return if price < 2000.0 { 1.0 } else { 5.0 };
+        }
+
+        match price {
+            p if p < 2000.0 => 1.0,
+            p if p < 5000.0 => 5.0,
+            p if p < 20000.0 => 10.0,
+            p if p < 50000.0 => 50.0,
+            p if p < 200000.0 => 100.0,
+            p if p < 500000.0 => 500.0,
+            _ => 1000.0,
+        }
+    }
+
+    fn from_fields(
+        fields: &HashMap<Arc<str>, String>,
+        block_name: &str,
+    ) -> Result<Self, DecodeError> {
+        let market = fields
+            .get("gubun")
+            .and_then(|s| Market::from_code(s))
+            .ok_or_else(|| DecodeError::InvalidField {
+                block: block_name.to_owned(),
+                field: "gubun".to_owned(),
+            })?;
+
+        Ok(Self {
+            code: data::parse_field(fields, block_name, "shcode")?,
+            name: data::parse_field(fields, block_name, "hname")?,
+            market,
+            etf: fields.get("etfgubun").map(|s| s.as_str()) == Some("1"),
+            prev_close: data::parse_field(fields, block_name, "jnilclose")?,
+        })
+    }
+}
+
+/// 종목 마스터
+#[derive(Clone, Debug)]
+pub struct SymbolMaster {
+    date: String,
+    symbols: Vec<SymbolInfo>,
+    code_tbl: HashMap<String, usize>,
+    name_tbl: HashMap<String, usize>,
+}
+
+impl SymbolMaster {
+    fn new(date: String, symbols: Vec<SymbolInfo>) -> Self {
+        let code_tbl = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.code.clone(), i))
+            .collect();
+        let name_tbl = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.clone(), i))
+            .collect();
+
+        Self {
+            date,
+            symbols,
+            code_tbl,
+            name_tbl,
+        }
+    }
+
+    /// 서버에 전체 종목을 조회합니다.
+    ///
+    /// t8430 또는 t8436 TR의 레이아웃을 사용할 수 있습니다.
+    pub fn request(tr_layout: &Arc<TrLayout>, timeout: Duration) -> Result<Self, Error> {
+        let in_block_name = format!("{}InBlock", tr_layout.code);
+        let out_block_name = format!("{}OutBlock", tr_layout.code);
+
+        let mut req_data = data::empty_input(tr_layout);
+        set_fields(&mut req_data, &in_block_name, &[("gubun", "0".into())])?;
+
+        let res = ensure_ok(request_with_retry(
+            &req_data,
+            tr_layout.clone(),
+            None,
+            timeout,
+        )?)?;
+        let symbols = data::get_array(res.data()?, &out_block_name)?
+            .iter()
+            .map(|fields| SymbolInfo::from_fields(fields, &out_block_name))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::new(kst::today(), symbols))
+    }
+
+    /// 디스크에 저장된 종목 마스터를 불러옵니다.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let invalid_data = || io::Error::new(io::ErrorKind::InvalidData, "invalid symbol master");
+
+        let mut lines = BufReader::new(fs::File::open(path)?).lines();
+        let date = lines.next().ok_or_else(invalid_data)??;
+
+        let mut symbols = Vec::new();
+        for line in lines {
+            let line = line?;
+            let mut columns = line.split('\t');
+            let mut next = || columns.next().ok_or_else(invalid_data);
+
+            symbols.push(SymbolInfo {
+                code: next()?.to_owned(),
+                name: next()?.to_owned(),
+                market: Market::from_code(next()?).ok_or_else(invalid_data)?,
+                etf: next()? == "1",
+                prev_close: next()?.parse().map_err(|_| invalid_data())?,
+            });
+        }
+
+        Ok(Self::new(date, symbols))
+    }
+
+    /// 종목 마스터를 디스크에 저장합니다.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+
+        writeln!(writer, "{}", self.date)?;
+        for symbol in &self.symbols {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                symbol.code,
+                symbol.name,
+                symbol.market.code(),
+                if symbol.etf { "1" } else { "0" },
+                symbol.prev_close,
+            )?;
+        }
+
+        writer.flush()
+    }
+
+    /// 디스크에 저장된 종목 마스터를 불러오거나 서버에 조회합니다.
+    ///
+    /// 저장된 종목 마스터가 없거나 오늘 조회한 것이 아닌 경우 서버에 다시
+    /// 조회하여 저장합니다. 디스크에 저장하지 못한 경우는 무시합니다.
+    pub fn cached<P: AsRef<Path>>(
+        path: P,
+        tr_layout: &Arc<TrLayout>,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        if let Ok(master) = Self::load(&path) {
+            if !master.is_stale() {
+                return Ok(master);
+            }
+        }
+
+        let master = Self::request(tr_layout, timeout)?;
+        let _ = master.save(&path);
+
+        Ok(master)
+    }
+
+    /// `YYYYMMDD` 형식의 조회 날짜를 반환합니다.
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    /// 오늘 조회한 종목 마스터가 아닌지 여부를 반환합니다.
+    pub fn is_stale(&self) -> bool {
+        self.date != kst::today()
+    }
+
+    /// 종목코드로 종목 정보를 검색합니다.
+    pub fn get(&self, code: &str) -> Option<&SymbolInfo> {
+        self.code_tbl.get(code).map(|&i| &self.symbols[i])
+    }
+
+    /// 종목명으로 종목 정보를 검색합니다.
+    pub fn find_by_name(&self, name: &str) -> Option<&SymbolInfo> {
+        self.name_tbl.get(name).map(|&i| &self.symbols[i])
+    }
+
+    /// 종목의 상장 여부를 반환합니다.
+    pub fn contains(&self, code: &str) -> bool {
+        self.code_tbl.contains_key(code)
+    }
+
+    /// 전체 종목을 반환합니다.
+    pub fn symbols(&self) -> &[SymbolInfo] {
+        &self.symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Market, SymbolInfo, SymbolMaster};
+
+    fn symbol(code: &str, name: &str, etf: bool) -> SymbolInfo {
+        SymbolInfo {
+            code: code.into(),
+            name: name.into(),
+            market: Market::Kospi,
+            etf,
+            prev_close: 10000.0,
+        }
+    }
+
+    #[test]
+    fn test_symbol_master() {
+        let master = SymbolMaster::new(
+            "20210111".into(),
+            vec![
+                symbol("005930", "삼성전자", false),
+                symbol("069500", "KODEX 200", true),
+            ],
+        );
+
+        assert!(master.is_stale());
+        assert!(master.contains("005930"));
+        assert_eq!(master.find_by_name("KODEX 200").unwrap().code, "069500");
+
+        let path = std::env::temp_dir().join("xingapi_test_symbol_master.txt");
+        master.save(&path).unwrap();
+        let loaded = SymbolMaster::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.date(), "20210111");
+        assert_eq!(loaded.symbols(), master.symbols());
+
+        let samsung = loaded.get("005930").unwrap();
+        assert_eq!(samsung.tick_size(91000.0), 100.0);
+        assert_eq!(samsung.tick_size(4995.0), 5.0);
+        assert_eq!(loaded.get("069500").unwrap().tick_size(40000.0), 5.0);
+    }
+}
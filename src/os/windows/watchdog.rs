@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 실시간 데이터 감시 모듈
+//!
+//! 서버에 다시 연결한 후 실시간 TR의 등록이 해제되는 등의 이유로 실시간
+//! 데이터가 수신되지 않는 경우를 감지합니다. 또한 주식 체결의 누적 거래량을
+//! 확인하여 일부 데이터가 누락된 경우를 감지합니다.
+
+use super::market::Calendar;
+use super::realtime::TradeTick;
+use super::RealResponse;
+use crate::data::DecodeError;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// 일정 시간 동안 데이터가 수신되지 않은 실시간 TR
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleFeed {
+    /// TR 코드
+    pub tr_code: String,
+    /// 등록한 키
+    pub key: String,
+    /// 마지막으로 수신한 후 경과한 시간
+    pub elapsed: Duration,
+}
+
+struct FeedState {
+    last_recv: Instant,
+    stale: bool,
+}
+
+/// 실시간 데이터 감시 객체
+///
+/// 감시 대상으로 등록한 TR 코드와 키별로 마지막 수신 시각을 기록하며,
+/// [`check()`](Self::check)를 호출했을 때 지정된 시간 동안 수신되지 않은
+/// 대상을 반환합니다. 같은 대상은 다시 수신되기 전까지 한 번만 반환됩니다.
+///
+/// 일정이 지정된 경우 정규장 시간에만 감시하며, 장 운영 시간이 아닌 동안의
+/// 시간은 경과 시간에 포함하지 않습니다.
+pub struct FeedWatchdog {
+    window: Duration,
+    calendar: Option<Calendar>,
+    feeds: HashMap<(String, String), FeedState>,
+}
+
+impl FeedWatchdog {
+    /// 항상 감시하는 객체를 생성합니다.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            calendar: None,
+            feeds: HashMap::new(),
+        }
+    }
+
+    /// 정규장 시간에만 감시하는 객체를 생성합니다.
+    pub fn with_calendar(window: Duration, calendar: Calendar) -> Self {
+        Self {
+            window,
+            calendar: Some(calendar),
+            feeds: HashMap::new(),
+        }
+    }
+
+    /// 실시간 TR을 지정된 키들로 감시 대상에 등록합니다.
+    pub fn watch<T: AsRef<str>>(&mut self, tr_code: &str, keys: &[T]) {
+        let now = Instant::now();
+        for key in keys {
+            self.feeds
+                .entry((tr_code.to_owned(), key.as_ref().to_owned()))
+                .or_insert(FeedState {
+                    last_recv: now,
+                    stale: false,
+                });
+        }
+    }
+
+    /// 실시간 TR을 지정된 키들로 감시 대상에서 제외합니다.
+    pub fn unwatch<T: AsRef<str>>(&mut self, tr_code: &str, keys: &[T]) {
+        for key in keys {
+            self.feeds
+                .remove(&(tr_code.to_owned(), key.as_ref().to_owned()));
+        }
+    }
+
+    /// 수신한 실시간 응답을 기록합니다.
+    pub fn record(&mut self, res: &RealResponse) {
+        self.record_at(res.tr_code(), res.key(), Instant::now());
+    }
+
+    fn record_at(&mut self, tr_code: &str, key: &str, now: Instant) {
+        if let Some(state) = self.feeds.get_mut(&(tr_code.to_owned(), key.to_owned())) {
+            state.last_recv = now;
+            state.stale = false;
+        }
+    }
+
+    /// 새롭게 수신이 중단된 것으로 판단된 대상을 반환합니다.
+    pub fn check(&mut self) -> Vec<StaleFeed> {
+        let is_open = match &self.calendar {
+            Some(calendar) => calendar.is_open(),
+            None => true,
+        };
+        self.check_at(Instant::now(), is_open)
+    }
+
+    fn check_at(&mut self, now: Instant, is_open: bool) -> Vec<StaleFeed> {
+        let mut stale_feeds = Vec::new();
+
+        for ((tr_code, key), state) in &mut self.feeds {
+            // 장 운영 시간이 아닌 경우 장이 열린 시점부터 다시 감시합니다.
+            if !is_open {
+                state.last_recv = now;
+                state.stale = false;
+                continue;
+            }
+
+            let elapsed = now.saturating_duration_since(state.last_recv);
+            if !state.stale && elapsed >= self.window {
+                state.stale = true;
+                stale_feeds.push(StaleFeed {
+                    tr_code: tr_code.clone(),
+                    key: key.clone(),
+                    elapsed,
+                });
+            }
+        }
+
+        stale_feeds
+    }
+}
+
+/// 주식 체결 데이터의 누락
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GapDetected {
+    /// 종목번호
+    pub symbol: String,
+    /// 이전 체결까지의 누적 거래량에 체결 수량을 더한 값
+    pub expected_volume: u64,
+    /// 수신한 누적 거래량
+    pub volume: u64,
+}
+
+impl GapDetected {
+    /// 누락된 것으로 추정되는 체결 수량을 반환합니다.
+    ///
+    /// 음수인 경우 체결이 중복되거나 순서가 바뀌어 수신된 것입니다.
+    pub fn missing_qty(&self) -> i64 {
+        self.volume as i64 - self.expected_volume as i64
+    }
+}
+
+/// 주식 체결 누락 감지 객체
+///
+/// 종목별로 마지막으로 수신한 누적 거래량을 기록하며, 다음 체결의 누적
+/// 거래량이 체결 수량만큼 증가하지 않은 경우 누락으로 판단합니다. 누락이
+/// 감지된 경우 조회 TR로 데이터를 다시 조회하는 것을 권장합니다.
+#[derive(Clone, Debug, Default)]
+pub struct GapMonitor {
+    volumes: HashMap<String, u64>,
+}
+
+impl GapMonitor {
+    /// 기록이 없는 객체를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 수신한 실시간 응답을 확인합니다.
+    ///
+    /// 주식 체결 TR이 아닌 응답은 무시합니다.
+    pub fn check(&mut self, res: &RealResponse) -> Result<Option<GapDetected>, DecodeError> {
+        if !TradeTick::TR_CODES.contains(&res.tr_code()) {
+            return Ok(None);
+        }
+
+        Ok(self.check_tick(&TradeTick::from_response(res)?))
+    }
+
+    /// 주식 체결을 확인합니다.
+    ///
+    /// 종목의 첫 체결인 경우 누적 거래량을 기록하기만 합니다.
+    pub fn check_tick(&mut self, tick: &TradeTick) -> Option<GapDetected> {
+        let prev_volume = self.volumes.insert(tick.symbol.clone(), tick.volume)?;
+        let expected_volume = prev_volume + tick.qty;
+
+        if tick.volume == expected_volume {
+            return None;
+        }
+
+        Some(GapDetected {
+            symbol: tick.symbol.clone(),
+            expected_volume,
+            volume: tick.volume,
+        })
+    }
+
+    /// 종목의 기록을 삭제합니다.
+    ///
+    /// 데이터를 다시 조회한 후 호출하면 다음 체결부터 다시 확인합니다.
+    pub fn reset(&mut self, symbol: &str) {
+        self.volumes.remove(symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeedWatchdog, GapMonitor};
+    use crate::order::Side;
+    use crate::realtime::TradeTick;
+
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_feed_watchdog() {
+        let mut watchdog = FeedWatchdog::new(Duration::from_secs(10));
+        watchdog.watch("S3_", &["005930", "000660"]);
+
+        let start = Instant::now();
+        let after = |secs| start + Duration::from_secs(secs);
+
+        watchdog.record_at("S3_", "005930", after(5));
+        assert!(watchdog.check_at(after(8), true).is_empty());
+
+        let stale = watchdog.check_at(after(12), true);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].key, "000660");
+
+        // 다시 수신되기 전까지 한 번만 반환합니다.
+        let stale = watchdog.check_at(after(16), true);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].key, "005930");
+        assert!(watchdog.check_at(after(20), true).is_empty());
+
+        watchdog.record_at("S3_", "000660", after(21));
+        assert!(watchdog.check_at(after(25), true).is_empty());
+
+        // 장 운영 시간이 아닌 동안은 감시하지 않습니다.
+        assert!(watchdog.check_at(after(100), false).is_empty());
+        assert!(watchdog.check_at(after(105), true).is_empty());
+        assert_eq!(watchdog.check_at(after(110), true).len(), 2);
+    }
+
+    #[test]
+    fn test_gap_monitor() {
+        let tick = |qty, volume| TradeTick {
+            symbol: "005930".into(),
+            time: "090000".into(),
+            price: 91000.0,
+            change: 0.0,
+            open: 91000.0,
+            high: 91000.0,
+            low: 91000.0,
+            qty,
+            volume,
+            side: Side::Buy,
+        };
+
+        let mut monitor = GapMonitor::new();
+        assert!(monitor.check_tick(&tick(10, 100)).is_none());
+        assert!(monitor.check_tick(&tick(5, 105)).is_none());
+
+        let gap = monitor.check_tick(&tick(5, 130)).unwrap();
+        assert_eq!(gap.expected_volume, 110);
+        assert_eq!(gap.missing_qty(), 20);
+
+        assert!(monitor.check_tick(&tick(1, 131)).is_none());
+        assert_eq!(
+            monitor.check_tick(&tick(1, 120)).unwrap().missing_qty(),
+            -12
+        );
+
+        monitor.reset("005930");
+        assert!(monitor.check_tick(&tick(1, 500)).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! 모의투자 서버에 대한 통합 테스트
+//!
+//! 서버에 접속해야 하므로 모든 테스트는 기본적으로 무시되며, `--ignored`로
+//! 실행합니다. 실행하려면 `XINGAPI_ID`와 `XINGAPI_PW` 환경 변수를 지정해야
+//! 하며, 지정되지 않은 경우 실패합니다. 서버 주소는 `XINGAPI_ADDR`로 변경할
+//! 수 있습니다.
+//!
+//! ```text
+//! XINGAPI_ID=... XINGAPI_PW=... cargo test --test it -- --ignored
+//! ```
+
+#![cfg(windows)]
+
+mod query;
+mod real;
+mod session;
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use xingapi::layout::TrLayout;
+use xingapi::Response;
+
+const DEFAULT_ADDR: &str = "demo.ebestsec.co.kr";
+
+/// 로그인된 세션과 TR 레이아웃 목록
+pub struct Context {
+    pub layout_tbl: HashMap<String, Arc<TrLayout>>,
+}
+
+impl Context {
+    pub fn layout(&self, tr_code: &str) -> &Arc<TrLayout> {
+        self.layout_tbl.get(tr_code).unwrap()
+    }
+}
+
+/// 서버에 한 번만 로그인하고 공유되는 객체를 반환합니다.
+///
+/// `XINGAPI_ID` 또는 `XINGAPI_PW`가 지정되지 않은 경우 패닉합니다.
+pub fn context() -> &'static Context {
+    static CONTEXT: OnceLock<Context> = OnceLock::new();
+
+    CONTEXT.get_or_init(|| {
+        let (Ok(id), Ok(pw)) = (std::env::var("XINGAPI_ID"), std::env::var("XINGAPI_PW")) else {
+            panic!("XINGAPI_ID and XINGAPI_PW must be set to run integration tests");
+        };
+        let addr = std::env::var("XINGAPI_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.into());
+
+        let layout_tbl = xingapi::layout::load().unwrap();
+        xingapi::loader::load().unwrap();
+        xingapi::connect(&addr, 20001, Duration::from_secs(10)).unwrap();
+
+        let res = xingapi::login(&id, &pw, "", false).unwrap();
+        assert!(res.is_ok(), "login failed: {:?}", res);
+
+        Context { layout_tbl }
+    })
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::context;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use xingapi::data::{Block, Data, DataType};
+use xingapi::layout::TrLayout;
+use xingapi::{hashmap, ErrorKind, QueryResponse, Response};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// 요청 제한에 도달한 경우 권장 대기 시간 후에 다시 요청합니다.
+fn request(data: &Data, tr_layout: &Arc<TrLayout>) -> QueryResponse {
+    loop {
+        let result = xingapi::request(data, tr_layout.clone(), None, TIMEOUT);
+        let backoff = match &result {
+            Err(err) => err.kind().and_then(ErrorKind::suggested_backoff),
+            Ok(_) => None,
+        };
+
+        match backoff {
+            Some(backoff) => std::thread::sleep(backoff),
+            None => break result.unwrap(),
+        }
+    }
+}
+
+#[test]
+#[ignore = "requires XINGAPI_ID and XINGAPI_PW"]
+fn test_request() {
+    let ctx = context();
+
+    let data = Data {
+        tr_code: "t1102".into(),
+        data_type: DataType::Input,
+        blocks: hashmap! {
+            "t1102InBlock" => Block::Block(hashmap! { "shcode" => "005930" }),
+        },
+    };
+
+    let res = request(&data, ctx.layout("t1102"));
+    assert!(res.is_ok(), "{:?}", res);
+    assert!(res.elapsed() < TIMEOUT);
+
+    let out_data = res.data().unwrap();
+    assert_eq!(out_data.tr_code, "t1102");
+    assert!(out_data.blocks.contains_key("t1102OutBlock"));
+}
+
+#[test]
+#[ignore = "requires XINGAPI_ID and XINGAPI_PW"]
+fn test_rate_limit() {
+    let ctx = context();
+
+    let limit_per_sec = xingapi::tr_limit_per_sec("t1101").unwrap();
+    assert!(limit_per_sec > 0);
+    assert!(xingapi::tr_limit_per_ten_min("t1101").is_some());
+
+    let data = Data {
+        tr_code: "t1101".into(),
+        data_type: DataType::Input,
+        blocks: hashmap! {
+            "t1101InBlock" => Block::Block(hashmap! { "shcode" => "078020" }),
+        },
+    };
+    let tr_layout = ctx.layout("t1101");
+
+    // 초당 제한 횟수에 맞춰 요청하는 경우 모두 정상 처리되어야 합니다.
+    let count = 3 * limit_per_sec;
+    let interval = Duration::from_secs_f32(1.0 / limit_per_sec as f32);
+    let start = Instant::now();
+
+    for _ in 0..count {
+        let res = request(&data, tr_layout);
+        assert!(res.is_ok(), "{:?}", res);
+
+        let elapsed = res.elapsed();
+        if interval > elapsed {
+            std::thread::sleep(interval - elapsed);
+        }
+    }
+
+    assert!(start.elapsed() >= interval * (count - 1) as u32);
+    assert!(xingapi::tr_count_in_ten_min("t1101").unwrap_or(0) >= count);
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::context;
+
+use std::time::Duration;
+
+use xingapi::RealEvent;
+
+#[test]
+#[ignore = "requires XINGAPI_ID and XINGAPI_PW"]
+fn test_real_subscribe() {
+    let ctx = context();
+
+    let real = RealEvent::new().unwrap();
+    real.insert_layout(ctx.layout("S3_").clone());
+    real.subscribe("S3_", &["005930"]);
+    assert!(real.current().contains("S3_", "005930"));
+
+    // 장 운영 시간이 아닌 경우 응답이 없을 수 있습니다.
+    if let Some(res) = real.recv_timeout(Duration::from_secs(5)) {
+        assert_eq!(res.tr_code(), "S3_");
+        assert_eq!(res.key(), "005930");
+        assert!(res.data().is_ok());
+    }
+
+    real.unsubscribe("S3_", &["005930"]);
+    assert!(real.current().is_empty());
+}
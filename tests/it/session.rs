@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::context;
+
+#[test]
+#[ignore = "requires XINGAPI_ID and XINGAPI_PW"]
+fn test_login() {
+    context();
+
+    assert!(xingapi::loader::is_loaded());
+    assert!(xingapi::is_connected());
+    assert!(!xingapi::accounts().is_empty());
+    assert!(xingapi::server_name().is_some());
+}